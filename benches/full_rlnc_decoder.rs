@@ -1,7 +1,7 @@
 use rand::Rng;
 use rlnc::{
     RLNCError,
-    full::{Decoder, Encoder},
+    full::{Decoder, Encoder, PayloadLayout},
 };
 use std::{fmt::Debug, time::Duration};
 
@@ -105,6 +105,15 @@ const ARGS: &[RLNCConfig] = &[
 
 #[divan::bench(args = ARGS, max_time = Duration::from_secs(100), skip_ext_time = true)]
 fn decode(bencher: divan::Bencher, rlnc_config: &RLNCConfig) {
+    decode_with_layout(bencher, rlnc_config, PayloadLayout::RowMajor);
+}
+
+#[divan::bench(args = ARGS, max_time = Duration::from_secs(100), skip_ext_time = true)]
+fn decode_with_column_major_payload(bencher: divan::Bencher, rlnc_config: &RLNCConfig) {
+    decode_with_layout(bencher, rlnc_config, PayloadLayout::ColumnMajor);
+}
+
+fn decode_with_layout(bencher: divan::Bencher, rlnc_config: &RLNCConfig, layout: PayloadLayout) {
     let mut rng = rand::rng();
 
     let data = (0..rlnc_config.data_byte_len).map(|_| rng.random()).collect::<Vec<u8>>();
@@ -114,7 +123,7 @@ fn decode(bencher: divan::Bencher, rlnc_config: &RLNCConfig) {
     let coded_pieces = (0..num_pieces_to_produce).flat_map(|_| encoder.code(&mut rng)).collect::<Vec<u8>>();
 
     bencher
-        .with_inputs(|| Decoder::new(encoder.get_piece_byte_len(), encoder.get_piece_count()).expect("Failed to create RLNC decoder"))
+        .with_inputs(|| Decoder::new_with_layout(encoder.get_piece_byte_len(), encoder.get_piece_count(), layout).expect("Failed to create RLNC decoder"))
         .input_counter(|decoder| divan::counter::BytesCount::new(decoder.get_full_coded_piece_byte_len() * decoder.get_num_pieces_coded_together()))
         .bench_refs(|mut decoder| {
             let mut piece_index = 0;
@@ -131,3 +131,25 @@ fn encode_zero_alloc(bencher: divan::Bencher, rlnc_config: &RLNCConfig) {
         .with_inputs(|| (rand::rng(), vec![0u8; encoder.get_full_coded_piece_byte_len()]))
         .bench_refs(|(rng, coded_piece)| divan::black_box(&encoder).code_with_buf(divan::black_box(rng), divan::black_box(coded_piece)));
 }
+
+/// Number of coded pieces, produced per pass over source data, by batched encoding.
+const BATCH_SIZE: usize = 16;
+
+#[divan::bench(args = ARGS, max_time = Duration::from_secs(100), skip_ext_time = true)]
+fn encode_batch_zero_alloc(bencher: divan::Bencher, rlnc_config: &RLNCConfig) {
+    let mut rng = rand::rng();
+    let data = (0..rlnc_config.data_byte_len).map(|_| rng.random()).collect::<Vec<u8>>();
+
+    let encoder = Encoder::new(data, rlnc_config.piece_count).expect("Failed to create RLNC encoder");
+
+    bencher
+        .counter(divan::counter::BytesCount::new(
+            encoder.get_piece_byte_len() * encoder.get_piece_count() +  // Number of bytes used as input to encoder
+            BATCH_SIZE * encoder.get_full_coded_piece_byte_len(), // Number of bytes for all coded pieces of the batch
+        ))
+        .with_inputs(|| (rand::rng(), vec![vec![0u8; encoder.get_full_coded_piece_byte_len()]; BATCH_SIZE]))
+        .bench_refs(|(rng, coded_pieces)| {
+            let mut bufs = coded_pieces.iter_mut().map(|coded_piece| coded_piece.as_mut_slice()).collect::<Vec<_>>();
+            divan::black_box(&encoder).code_batch_with_bufs(divan::black_box(rng), divan::black_box(&mut bufs))
+        });
+}
@@ -0,0 +1,189 @@
+use crate::{
+    RLNCError,
+    full::{Decoder, Params},
+};
+
+/// State of a single generation, held by `GenerationDecoder`.
+#[derive(Clone, Debug)]
+enum GenerationState {
+    /// Generation is being decoded.
+    Decoding(Box<Decoder>),
+    /// Generation is decoded, only its original data is kept.
+    Decoded(Vec<u8>),
+}
+
+/// Decoder-side companion of `chunked::Encoder`, owning one `full::Decoder` per generation.
+///
+/// Tagged pieces are routed to the decoder of their generation. As soon as a generation is decoded, its decoder, holding the
+/// coefficient matrix and `k` coded pieces, is dropped, keeping only the original data of the generation. Once all generations
+/// are decoded, they are concatenated, in order, into the original object.
+#[derive(Clone, Debug)]
+pub struct GenerationDecoder {
+    params: Params,
+    generations: Vec<GenerationState>,
+    decoded_generation_count: usize,
+}
+
+impl GenerationDecoder {
+    /// Sets up a decoder for each of `generation_count` generations, sharing given RLNC configuration.
+    ///
+    /// # Returns
+    /// * Returns `Ok(GenerationDecoder)` on success.
+    /// * Returns `Err(RLNCError::GenerationCountZero)` if `generation_count` is zero.
+    pub fn new(params: Params, generation_count: usize) -> Result<GenerationDecoder, RLNCError> {
+        if generation_count == 0 {
+            return Err(RLNCError::GenerationCountZero);
+        }
+
+        Ok(GenerationDecoder {
+            params,
+            generations: vec![GenerationState::Decoding(Box::new(Decoder::new_with(params))); generation_count],
+            decoded_generation_count: 0,
+        })
+    }
+
+    /// RLNC configuration shared by all generations.
+    pub fn get_params(&self) -> Params {
+        self.params
+    }
+
+    /// Number of generations, the object got split into.
+    pub fn get_generation_count(&self) -> usize {
+        self.generations.len()
+    }
+
+    /// Number of generations decoded so far.
+    pub fn get_decoded_generation_count(&self) -> usize {
+        self.decoded_generation_count
+    }
+
+    /// Returns `true` if all generations are decoded.
+    pub fn is_already_decoded(&self) -> bool {
+        self.decoded_generation_count == self.generations.len()
+    }
+
+    /// Returns `true` if given generation is decoded, `false` if it's not or if it doesn't exist.
+    pub fn is_generation_decoded(&self, generation: usize) -> bool {
+        matches!(self.generations.get(generation), Some(GenerationState::Decoded(_)))
+    }
+
+    /// Number of useful pieces, still required to decode given generation, which is zero, once it's decoded.
+    /// Returns `None` if the generation doesn't exist.
+    pub fn get_remaining_piece_count(&self, generation: usize) -> Option<usize> {
+        self.generations.get(generation).map(|state| match state {
+            GenerationState::Decoding(decoder) => decoder.get_remaining_piece_count(),
+            GenerationState::Decoded(_) => 0,
+        })
+    }
+
+    /// Number of useful pieces, still required to decode all generations.
+    pub fn get_total_remaining_piece_count(&self) -> usize {
+        (0..self.generations.len())
+            .filter_map(|generation| self.get_remaining_piece_count(generation))
+            .sum()
+    }
+
+    /// Routes a full coded piece to the decoder of given generation. Decoder of the generation is dropped, as soon as it's decoded.
+    ///
+    /// # Arguments
+    /// * `generation` - Generation, the piece belongs to, as tagged by `chunked::Encoder::code`.
+    /// * `full_coded_piece` - Coding vector followed by coded data.
+    ///
+    /// # Returns
+    /// * Returns `Ok(())` if the piece was useful.
+    /// * Returns `Err(RLNCError::InvalidGenerationIndex)` if `generation` doesn't exist.
+    /// * Returns `Err(RLNCError::ReceivedAllPieces)` if the generation is already decoded.
+    /// * Returns `Err(RLNCError::InvalidDecodedDataFormat(_))` if the piece completed the generation, but decoded data is malformed, in
+    ///   which case the generation starts over, with a fresh decoder.
+    /// * Otherwise, returns same as `Decoder::decode`.
+    pub fn decode(&mut self, generation: usize, full_coded_piece: &[u8]) -> Result<(), RLNCError> {
+        let state = self.generations.get_mut(generation).ok_or(RLNCError::InvalidGenerationIndex)?;
+        let GenerationState::Decoding(decoder) = state else {
+            return Err(RLNCError::ReceivedAllPieces);
+        };
+
+        decoder.decode(full_coded_piece)?;
+        if !decoder.is_already_decoded() {
+            return Ok(());
+        }
+
+        let decoder = std::mem::replace(decoder.as_mut(), Decoder::new_with(self.params));
+        *state = GenerationState::Decoded(decoder.get_decoded_data()?);
+        self.decoded_generation_count += 1;
+
+        Ok(())
+    }
+
+    /// Reassembles the original object, concatenating decoded data of all generations, in order.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Vec<u8>)` holding the original object, if all generations are decoded.
+    /// * Returns `Err(RLNCError::NotAllPiecesReceivedYet)` if any generation is not yet decoded.
+    pub fn get_decoded_data(self) -> Result<Vec<u8>, RLNCError> {
+        if !self.is_already_decoded() {
+            return Err(RLNCError::NotAllPiecesReceivedYet);
+        }
+
+        Ok(self
+            .generations
+            .into_iter()
+            .flat_map(|state| match state {
+                GenerationState::Decoded(data) => data,
+                GenerationState::Decoding(_) => Vec::new(),
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GenerationDecoder;
+    use crate::{
+        RLNCError,
+        chunked::{Encoder, SchedulingPolicy},
+        full::Params,
+    };
+    use rand::Rng;
+
+    #[test]
+    fn test_generation_decoder_reassembles_object() {
+        let mut rng = rand::rng();
+
+        assert!(matches!(
+            GenerationDecoder::new(Params::new(4, 4).unwrap(), 0),
+            Err(RLNCError::GenerationCountZero)
+        ));
+
+        let data = (0..10_000).map(|_| rng.random()).collect::<Vec<u8>>();
+        let policy = SchedulingPolicy::Sequential { pieces_per_generation: 20 };
+        let mut encoder = Encoder::new_with_policy(&data, 1024, 16, policy).expect("Failed to create Encoder");
+
+        let mut decoder = GenerationDecoder::new(encoder.get_params(), encoder.get_generation_count()).expect("Failed to create GenerationDecoder");
+        assert_eq!(decoder.get_total_remaining_piece_count(), 16 * 10);
+        assert_eq!(decoder.decode(10, &encoder.code(&mut rng).1), Err(RLNCError::InvalidGenerationIndex));
+
+        while decoder.get_decoded_generation_count() == 0 {
+            let (generation, piece) = encoder.code(&mut rng);
+            let _ = decoder.decode(generation, &piece);
+        }
+
+        // First generation gets decoded first, while the rest are still waiting for pieces.
+        assert!(decoder.is_generation_decoded(0));
+        assert_eq!(decoder.get_remaining_piece_count(0), Some(0));
+        assert_eq!(decoder.get_remaining_piece_count(9), Some(16));
+        assert_eq!(decoder.get_remaining_piece_count(10), None);
+        assert_eq!(
+            decoder.decode(0, &encoder.get_encoder(0).unwrap().code(&mut rng)),
+            Err(RLNCError::ReceivedAllPieces)
+        );
+        assert_eq!(decoder.clone().get_decoded_data(), Err(RLNCError::NotAllPiecesReceivedYet));
+
+        while !decoder.is_already_decoded() {
+            let (generation, piece) = encoder.code(&mut rng);
+            let _ = decoder.decode(generation, &piece);
+        }
+
+        assert_eq!(decoder.get_total_remaining_piece_count(), 0);
+        assert_eq!(decoder.get_decoded_data(), Ok(data));
+    }
+}
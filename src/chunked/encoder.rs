@@ -0,0 +1,160 @@
+use crate::{
+    RLNCError,
+    full::{self, Params},
+};
+use rand::Rng;
+
+/// Policy deciding which generation the next coded piece of a `chunked::Encoder` comes from.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SchedulingPolicy {
+    /// Cycles over all generations, one piece each, so that all of them progress evenly. This is the default.
+    #[default]
+    RoundRobin,
+    /// Emits given number of pieces of a generation, before moving on to the next one, cycling back to the first one after the last
+    /// one. Suits receivers, which consume the object in order.
+    Sequential { pieces_per_generation: usize },
+    /// Picks a uniformly random generation for each piece, so that no receiver, joining at any time, is favoured.
+    Random,
+}
+
+/// Random Linear Network Coding (RLNC) Encoder, for objects split into many generations.
+///
+/// Consecutive blocks of `generation_byte_len` bytes of the object make consecutive generations, the last one can be shorter.
+/// All generations share the same `Params`, shorter ones are padded like any other, so that the receiver can set up a decoder per
+/// generation, using `full::Decoder::new_with(encoder.get_params())`.
+#[derive(Clone, Debug)]
+pub struct Encoder {
+    encoders: Vec<full::Encoder>,
+    params: Params,
+    policy: SchedulingPolicy,
+    /// Number of pieces, coded by `Self::code`, deciding the generation of the next one, under deterministic policies.
+    coded_piece_count: usize,
+}
+
+impl Encoder {
+    /// Splits the object into generations, coding each of them independently, with pieces scheduled in round-robin fashion.
+    ///
+    /// # Arguments
+    /// * `data` - Original data, to be split into generations of `generation_byte_len` bytes, except the last one, which can be shorter.
+    /// * `generation_byte_len` - Byte length of original data in each generation.
+    /// * `piece_count` - Number of pieces each generation gets split into.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Encoder)` on success.
+    /// * Returns `Err(RLNCError::DataLengthZero)` if `data` is empty or `generation_byte_len` is zero.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `piece_count` is zero.
+    pub fn new(data: &[u8], generation_byte_len: usize, piece_count: usize) -> Result<Encoder, RLNCError> {
+        Self::new_with_policy(data, generation_byte_len, piece_count, SchedulingPolicy::default())
+    }
+
+    /// Same as `Self::new`, but pieces are scheduled using given policy.
+    ///
+    /// # Returns
+    /// * Returns `Err(RLNCError::InvalidSendPlanParameter)` if `policy` is sequential, with zero pieces per generation.
+    /// * Otherwise, returns same as `Self::new`.
+    pub fn new_with_policy(data: &[u8], generation_byte_len: usize, piece_count: usize, policy: SchedulingPolicy) -> Result<Encoder, RLNCError> {
+        if data.is_empty() || generation_byte_len == 0 {
+            return Err(RLNCError::DataLengthZero);
+        }
+        if policy == (SchedulingPolicy::Sequential { pieces_per_generation: 0 }) {
+            return Err(RLNCError::InvalidSendPlanParameter);
+        }
+
+        let params = Params::from_data_len(generation_byte_len.min(data.len()), piece_count)?;
+        let encoders = data
+            .chunks(generation_byte_len)
+            .map(|generation_data| full::Encoder::new_with(generation_data.to_vec(), params))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Encoder {
+            encoders,
+            params,
+            policy,
+            coded_piece_count: 0,
+        })
+    }
+
+    /// RLNC configuration shared by all generations.
+    pub fn get_params(&self) -> Params {
+        self.params
+    }
+
+    /// Number of generations, original data got split into.
+    pub fn get_generation_count(&self) -> usize {
+        self.encoders.len()
+    }
+
+    /// Policy deciding which generation the next coded piece comes from.
+    pub fn get_policy(&self) -> SchedulingPolicy {
+        self.policy
+    }
+
+    /// Encoder of given generation, say for coding extra pieces of it, on receiver's request.
+    pub fn get_encoder(&self, generation: usize) -> Option<&full::Encoder> {
+        self.encoders.get(generation)
+    }
+
+    /// Produces a coded piece of the generation, picked by the scheduling policy.
+    ///
+    /// # Arguments
+    /// * `rng` - Used to sample the coding vector, and the generation, under random scheduling policy.
+    ///
+    /// # Returns
+    /// Returns the generation index along with the full coded piece of `self.get_params().get_full_coded_piece_byte_len()` bytes.
+    pub fn code<R: Rng + ?Sized>(&mut self, rng: &mut R) -> (usize, Vec<u8>) {
+        let generation_count = self.get_generation_count();
+        let generation = match self.policy {
+            SchedulingPolicy::RoundRobin => self.coded_piece_count % generation_count,
+            SchedulingPolicy::Sequential { pieces_per_generation } => (self.coded_piece_count / pieces_per_generation) % generation_count,
+            SchedulingPolicy::Random => rng.random_range(0..generation_count),
+        };
+        self.coded_piece_count += 1;
+
+        (generation, self.encoders[generation].code(rng))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Encoder, SchedulingPolicy};
+    use crate::{RLNCError, full::Decoder};
+    use rand::Rng;
+
+    #[test]
+    fn test_chunked_encoder_scheduling_policies() {
+        let mut rng = rand::rng();
+
+        assert!(matches!(Encoder::new(&[], 10, 4), Err(RLNCError::DataLengthZero)));
+        assert!(matches!(Encoder::new(&[1], 0, 4), Err(RLNCError::DataLengthZero)));
+        assert!(matches!(Encoder::new(&[1], 10, 0), Err(RLNCError::PieceCountZero)));
+        assert!(matches!(
+            Encoder::new_with_policy(&[1], 10, 4, SchedulingPolicy::Sequential { pieces_per_generation: 0 }),
+            Err(RLNCError::InvalidSendPlanParameter)
+        ));
+
+        let data = (0..1000).map(|_| rng.random()).collect::<Vec<u8>>();
+
+        let mut encoder = Encoder::new(&data, 300, 8).expect("Failed to create Encoder");
+        assert_eq!(encoder.get_generation_count(), 4);
+        assert_eq!((0..6).map(|_| encoder.code(&mut rng).0).collect::<Vec<_>>(), [0, 1, 2, 3, 0, 1]);
+
+        let policy = SchedulingPolicy::Sequential { pieces_per_generation: 2 };
+        let mut encoder = Encoder::new_with_policy(&data, 300, 8, policy).expect("Failed to create Encoder");
+        assert_eq!((0..10).map(|_| encoder.code(&mut rng).0).collect::<Vec<_>>(), [0, 0, 1, 1, 2, 2, 3, 3, 0, 0]);
+
+        // Each generation decodes independently, concatenation of all of them being the object.
+        let mut encoder = Encoder::new_with_policy(&data, 300, 8, SchedulingPolicy::Random).expect("Failed to create Encoder");
+        let mut decoders = vec![Decoder::new_with(encoder.get_params()); encoder.get_generation_count()];
+
+        while !decoders.iter().all(|decoder| decoder.is_already_decoded()) {
+            let (generation, piece) = encoder.code(&mut rng);
+            let _ = decoders[generation].decode(&piece);
+        }
+
+        let decoded = decoders
+            .into_iter()
+            .flat_map(|decoder| decoder.get_decoded_data().unwrap())
+            .collect::<Vec<u8>>();
+        assert_eq!(decoded, data);
+    }
+}
@@ -0,0 +1,10 @@
+//! Random Linear Network Coding of objects too large for one generation. Object is split into fixed-size generations, each coded
+//! independently by a `full::Encoder`, sharing the same RLNC configuration, and pieces are tagged with the index of the generation,
+//! they belong to. Which generation the next piece comes from is decided by a `SchedulingPolicy`. On the receiving end, a
+//! `GenerationDecoder` routes tagged pieces to per-generation decoders and reassembles the object.
+
+mod decoder;
+mod encoder;
+
+pub use decoder::GenerationDecoder;
+pub use encoder::{Encoder, SchedulingPolicy};
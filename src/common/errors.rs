@@ -1,3 +1,62 @@
+/// Maximum number of trailing bytes of decoded data, kept in `DecodedDataDiagnostics`.
+const MAX_TRAILING_BYTE_COUNT: usize = 32;
+
+/// Context of decoded data, which doesn't end with the boundary marker, followed by zero padding, as `Encoder` lays it out.
+///
+/// * Marker missing, with zero trailing bytes, hints at a sender, which doesn't pad data the way `Encoder` does.
+/// * Marker found, but followed by non-zero bytes, hints at piece count or piece length not matching the sender's, or at
+///   pieces corrupted in transit, say by a relay mixing pieces of another generation, unless every piece is validated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodedDataDiagnostics {
+    /// Byte length of padded data i.e. all decoded source pieces, concatenated.
+    pub padded_data_byte_len: usize,
+    /// Number of source pieces, decoded data is split into.
+    pub piece_count: usize,
+    /// Number of pieces received by the decoder, useful or not.
+    pub received_piece_count: usize,
+    /// Index of the last boundary marker byte, if any.
+    pub boundary_marker_index: Option<usize>,
+    /// Index of the first non-zero byte after the boundary marker, if any, which should have been padding.
+    pub nonzero_padding_index: Option<usize>,
+    /// Up to 32 trailing bytes of padded data, which hold the boundary marker and padding, if the data is well-formed.
+    pub trailing_bytes: Vec<u8>,
+}
+
+impl DecodedDataDiagnostics {
+    /// Inspects padded data, which failed boundary marker validation.
+    pub(crate) fn new(padded_data: &[u8], piece_count: usize, received_piece_count: usize, boundary_marker: u8) -> DecodedDataDiagnostics {
+        let boundary_marker_index = padded_data.iter().rposition(|&byte| byte == boundary_marker);
+        let nonzero_padding_index =
+            boundary_marker_index.and_then(|index| padded_data[index + 1..].iter().position(|&byte| byte != 0).map(|offset| index + 1 + offset));
+
+        DecodedDataDiagnostics {
+            padded_data_byte_len: padded_data.len(),
+            piece_count,
+            received_piece_count,
+            boundary_marker_index,
+            nonzero_padding_index,
+            trailing_bytes: padded_data[padded_data.len().saturating_sub(MAX_TRAILING_BYTE_COUNT)..].to_vec(),
+        }
+    }
+}
+
+impl std::fmt::Display for DecodedDataDiagnostics {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match (self.boundary_marker_index, self.nonzero_padding_index) {
+            (None, _) => write!(f, "no boundary marker")?,
+            (Some(marker_index), None) => write!(f, "boundary marker at byte {marker_index}")?,
+            (Some(marker_index), Some(padding_index)) => write!(f, "boundary marker at byte {marker_index}, non-zero padding at byte {padding_index}")?,
+        }
+        write!(
+            f,
+            " of {} bytes, decoded from {} pieces, after receiving {}, ending with",
+            self.padded_data_byte_len, self.piece_count, self.received_piece_count
+        )?;
+
+        self.trailing_bytes.iter().try_for_each(|byte| write!(f, " {byte:02x}"))
+    }
+}
+
 /// Errors that can occur during RLNC (Random Linear Network Coding) encoding/ recoding/ decoding.
 #[derive(Debug, PartialEq)]
 pub enum RLNCError {
@@ -23,12 +82,69 @@ pub enum RLNCError {
     ReceivedAllPieces,
     /// When an attempt is made to retrieve decoded data, but not all required pieces have arrived yet.
     NotAllPiecesReceivedYet,
-    /// When the format or structure of the decoded data is not as expected.
-    InvalidDecodedDataFormat,
+    /// When the format or structure of the decoded data is not as expected i.e. it doesn't end with the boundary marker, followed
+    /// by zero padding. Carries diagnostics, telling apart sender bugs, parameter mismatch and corruption.
+    InvalidDecodedDataFormat(Box<DecodedDataDiagnostics>),
     /// When the length of a received piece does not match the expected length.
     InvalidPieceLength,
+    /// When the generation count is zero.
+    GenerationCountZero,
+    /// When estimated loss rate is not in `[0, 1)` or overhead factor is less than 1, while computing a send plan.
+    InvalidSendPlanParameter,
+    /// When a source piece index is not less than the number of pieces coded together.
+    InvalidPieceIndex,
+    /// When an operation requires retained pieces, but the decoder was created without piece retention.
+    PieceRetentionDisabled,
     /// The user provided an invalid output buffer.
     InvalidOutputBuffer,
+    /// When the storage backing decoder or recoder can't grow, say because of failing to remap a file.
+    PieceStorageFailure,
+    /// When a decoder snapshot is inconsistent with its own configuration.
+    InvalidSnapshot,
+    /// When a message can't be serialized into the given buffer, or given bytes don't hold a valid message.
+    SerializationFailure,
+    /// When reading coded pieces, or original data, off the underlying reader or file fails.
+    ReadFailure,
+    /// When a rank watermark is zero or exceeds the number of pieces coded together.
+    InvalidRankWatermark,
+    /// When a generation index doesn't refer to any generation.
+    InvalidGenerationIndex,
+    /// When a simulated network topology is malformed, say a link refers to an unknown node or starts at a sink.
+    InvalidTopology,
+    /// When coded pieces can't be placed on distinct storage targets, say because there are fewer targets than pieces per generation.
+    InvalidPlacement,
+    /// When writing coded pieces to a storage target, or decoded data to a sink, fails.
+    WriteFailure,
+    /// When a piece is past its expiry timestamp, or a relay gets a piece with no hops left, so it must neither be decoded nor forwarded.
+    PieceExpired,
+    /// When strict validation is enabled and a piece carries an all-zero coding vector, which can never be useful.
+    ZeroCodingVector,
+    /// When a simulated channel is misconfigured, say its loss or duplication probability is not in `[0, 1]`.
+    InvalidChannel,
+    /// When a worker thread pool is misconfigured, say with zero workers or an empty core set, or its workers can't be pinned to their cores.
+    InvalidWorkerPool,
+    /// When a coding coefficient doesn't belong to the GF(2^4) subfield of GF(2^8), so it can't be nibble-packed.
+    CoefficientNotInSubfield,
+    /// When a product code is asked for more row generations than it can tell apart, which is 255.
+    TooManyGenerations,
+    /// When a lazily loading encoder is asked to cache no source pieces at all.
+    InvalidCacheCapacity,
+    /// When a chunk provider fails to read source data, a lazily loading encoder needs for coding.
+    ChunkFetchFailure,
+    /// When a decoder manager has no room for a new decoder, even after evicting all generations not yet decoded.
+    MemoryBudgetExceeded,
+    /// When two recoders or decoders, being merged, don't share RLNC configuration.
+    ParamsMismatch,
+    /// When sparse coding vectors are asked for a density, which is not in `(0, 1]`.
+    InvalidDensity,
+    /// When a piece length is not a multiple of the byte length of a GF(2^16) symbol, which is 2.
+    OddPieceLength,
+    /// When a framed piece carries a wire format version, this library doesn't understand.
+    UnsupportedWireVersion,
+    /// When a sliding-window encoder is asked for a coded piece, but its window holds no source symbol.
+    EmptyWindow,
+    /// When a bounded recoder is asked to keep no pool rows at all.
+    InvalidPoolSize,
 }
 
 impl std::fmt::Display for RLNCError {
@@ -49,10 +165,38 @@ impl std::fmt::Display for RLNCError {
             RLNCError::PieceNotUseful => write!(f, "Received piece is not useful"),
             RLNCError::ReceivedAllPieces => write!(f, "Received all pieces"),
             RLNCError::NotAllPiecesReceivedYet => write!(f, "Not all pieces are received yet"),
-            RLNCError::InvalidDecodedDataFormat => write!(f, "Invalid decoded data format"),
+            RLNCError::InvalidDecodedDataFormat(diagnostics) => write!(f, "Invalid decoded data format: {diagnostics}"),
             RLNCError::InvalidPieceLength => write!(f, "Invalid piece length"),
+            RLNCError::GenerationCountZero => write!(f, "Generation count is zero"),
+            RLNCError::InvalidSendPlanParameter => write!(f, "Invalid send plan parameter"),
+            RLNCError::InvalidPieceIndex => write!(f, "Invalid piece index"),
+            RLNCError::PieceRetentionDisabled => write!(f, "Piece retention is disabled"),
 
             RLNCError::InvalidOutputBuffer => write!(f, "Invalid output buffer"),
+            RLNCError::PieceStorageFailure => write!(f, "Piece storage failed to grow"),
+            RLNCError::InvalidSnapshot => write!(f, "Invalid decoder snapshot"),
+            RLNCError::SerializationFailure => write!(f, "Serialization failure"),
+            RLNCError::ReadFailure => write!(f, "Failed to read coded pieces or original data"),
+            RLNCError::InvalidRankWatermark => write!(f, "Invalid rank watermark"),
+            RLNCError::InvalidGenerationIndex => write!(f, "Invalid generation index"),
+            RLNCError::InvalidTopology => write!(f, "Invalid network topology"),
+            RLNCError::InvalidPlacement => write!(f, "Invalid piece placement"),
+            RLNCError::WriteFailure => write!(f, "Failed to write coded pieces or decoded data"),
+            RLNCError::PieceExpired => write!(f, "Piece expired"),
+            RLNCError::ZeroCodingVector => write!(f, "Coding vector is all-zero"),
+            RLNCError::InvalidChannel => write!(f, "Invalid channel configuration"),
+            RLNCError::InvalidWorkerPool => write!(f, "Invalid worker thread pool configuration"),
+            RLNCError::CoefficientNotInSubfield => write!(f, "Coding coefficient is not in GF(2^4) subfield"),
+            RLNCError::TooManyGenerations => write!(f, "Too many generations"),
+            RLNCError::InvalidCacheCapacity => write!(f, "Invalid cache capacity"),
+            RLNCError::ChunkFetchFailure => write!(f, "Failed to fetch source data chunk"),
+            RLNCError::MemoryBudgetExceeded => write!(f, "Memory budget exceeded"),
+            RLNCError::ParamsMismatch => write!(f, "RLNC configuration mismatch"),
+            RLNCError::InvalidDensity => write!(f, "Invalid coding vector density"),
+            RLNCError::OddPieceLength => write!(f, "Piece length is odd"),
+            RLNCError::UnsupportedWireVersion => write!(f, "Unsupported wire format version"),
+            RLNCError::EmptyWindow => write!(f, "Sliding window is empty"),
+            RLNCError::InvalidPoolSize => write!(f, "Invalid recoding pool size"),
         }
     }
 }
@@ -0,0 +1,305 @@
+//! GF(2**16) logarithm and exponentiation tables are too large to be embedded, so they are generated on first use,
+//! by repeated multiplication with the primitive element.
+
+use rand::Rng;
+use rand::distr::{Distribution, StandardUniform};
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub};
+use std::sync::LazyLock;
+
+pub const GF65536_ORDER: usize = u16::MAX as usize + 1;
+
+/// Byte length of a GF(2^16) symbol, which is serialized in little-endian byte order.
+pub const GF65536_SYMBOL_BYTE_LEN: usize = size_of::<u16>();
+
+/// Irreducible polynomial x^16 + x^12 + x^3 + x + 1, for which x is a primitive element.
+const GF65536_IRREDUCIBLE_POLYNOMIAL: u32 = 0x1100b;
+
+/// Logarithm table, indexed by non-zero field element, and exponentiation table, of doubled length, so that sum of two
+/// logarithms can be looked up without reduction modulo `GF65536_ORDER - 1`.
+struct Gf65536Tables {
+    log: Box<[u16]>,
+    exp: Box<[u16]>,
+}
+
+static GF65536_TABLES: LazyLock<Gf65536Tables> = LazyLock::new(|| {
+    let mut log = vec![0u16; GF65536_ORDER].into_boxed_slice();
+    let mut exp = vec![0u16; 2 * GF65536_ORDER - 2].into_boxed_slice();
+
+    let mut elem = 1u32;
+    for power in 0..(GF65536_ORDER - 1) {
+        exp[power] = elem as u16;
+        exp[power + GF65536_ORDER - 1] = elem as u16;
+        log[elem as usize] = power as u16;
+
+        elem <<= 1;
+        if elem >= GF65536_ORDER as u32 {
+            elem ^= GF65536_IRREDUCIBLE_POLYNOMIAL;
+        }
+    }
+
+    Gf65536Tables { log, exp }
+});
+
+/// Galois Field GF(2^16) wrapper type.
+///
+/// Large field for coding generations of thousands of pieces, where a random coding vector is linearly dependent on the ones
+/// received before with probability 1/65536 at most, instead of 1/256 of GF(2^8). Operations are defined over the finite field
+/// GF(2^16) with the irreducible polynomial x^16 + x^12 + x^3 + x + 1 and the primitive element x = 2.
+#[repr(transparent)]
+#[derive(Default, Clone, Copy, Debug)]
+pub struct Gf65536 {
+    val: u16,
+}
+
+impl Gf65536 {
+    /// Creates a new Gf65536 element from a u16 value.
+    pub const fn new(val: u16) -> Self {
+        Gf65536 { val }
+    }
+
+    /// Returns the raw u16 value of the Gf65536 element.
+    pub const fn get(&self) -> u16 {
+        self.val
+    }
+
+    /// Returns the additive identity element (0).
+    pub const fn zero() -> Self {
+        Gf65536::new(0)
+    }
+
+    /// Returns the multiplicative identity element (1).
+    pub const fn one() -> Self {
+        Gf65536::new(1)
+    }
+
+    /// Returns primitive element x, for GF(2^16) field with irreducible polynomial x^16 + x^12 + x^3 + x + 1.
+    pub const fn primitive_element() -> Self {
+        Gf65536::new(2)
+    }
+
+    /// Multiplication of two 16-bit words, over GF(2^16).
+    pub fn mul_raw(a: u16, b: u16) -> u16 {
+        if a == 0 || b == 0 {
+            return 0;
+        }
+
+        let tables = &*GF65536_TABLES;
+        let l = tables.log[a as usize] as usize;
+        let r = tables.log[b as usize] as usize;
+
+        tables.exp[l + r]
+    }
+
+    /// Computes the multiplicative inverse of the element. Returns `None` for the zero element.
+    pub fn inv(self) -> Option<Self> {
+        if self.val == 0 {
+            return None;
+        }
+
+        let tables = &*GF65536_TABLES;
+        Some(Gf65536 {
+            val: tables.exp[(GF65536_ORDER - 1) - tables.log[self.val as usize] as usize],
+        })
+    }
+}
+
+impl Add for Gf65536 {
+    type Output = Self;
+
+    /// Performs addition (XOR) of two Gf65536 elements.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn add(self, rhs: Self) -> Self::Output {
+        Gf65536 { val: self.val ^ rhs.val }
+    }
+}
+
+impl AddAssign for Gf65536 {
+    /// Performs in-place addition i.e. compound addition operation (XOR) of two Gf65536 elements.
+    #[allow(clippy::suspicious_op_assign_impl)]
+    fn add_assign(&mut self, rhs: Self) {
+        self.val ^= rhs.val;
+    }
+}
+
+impl Neg for Gf65536 {
+    type Output = Self;
+
+    /// Computes the additive inverse (itself, as XOR is self-inverse).
+    fn neg(self) -> Self::Output {
+        Gf65536 { val: self.val }
+    }
+}
+
+impl Sub for Gf65536 {
+    type Output = Self;
+
+    /// Performs subtraction (XOR) of two Gf65536 elements.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, rhs: Self) -> Self::Output {
+        Gf65536 { val: self.val ^ rhs.val }
+    }
+}
+
+impl Mul for Gf65536 {
+    type Output = Self;
+
+    /// Performs multiplication of two Gf65536 elements using logarithm and exponentiation tables.
+    fn mul(self, rhs: Self) -> Self::Output {
+        Gf65536 {
+            val: Self::mul_raw(self.val, rhs.val),
+        }
+    }
+}
+
+impl Div for Gf65536 {
+    type Output = Option<Self>;
+
+    /// Performs division of two Gf65536 elements using multiplicative inverse. Returns `None` if dividing by zero.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        rhs.inv().map(|rhs_inv| self * rhs_inv)
+    }
+}
+
+impl PartialEq for Gf65536 {
+    /// Checks for equality between two Gf65536 elements.
+    fn eq(&self, other: &Self) -> bool {
+        self.val == other.val
+    }
+}
+
+impl Distribution<Gf65536> for StandardUniform {
+    /// Samples a random Gf65536 element.
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Gf65536 {
+        Gf65536 { val: rng.random() }
+    }
+}
+
+/// Products of the scalar with each value of each nibble of a 16-bit word, so that product of the scalar and any word is XOR of
+/// four table lookups, one per nibble, multiplication being linear over GF(2).
+fn nibble_product_tables(scalar: u16) -> [[u16; 16]; 4] {
+    let mut tables = [[0u16; 16]; 4];
+
+    tables.iter_mut().enumerate().for_each(|(nibble_idx, table)| {
+        table
+            .iter_mut()
+            .enumerate()
+            .for_each(|(nibble, product)| *product = Gf65536::mul_raw(scalar, (nibble as u16) << (4 * nibble_idx)));
+    });
+
+    tables
+}
+
+/// Multiplies a 16-bit word with the scalar, whose nibble product tables are given.
+#[inline(always)]
+fn mul_by_nibble_product_tables(tables: &[[u16; 16]; 4], word: u16) -> u16 {
+    tables[0][(word & 0xf) as usize] ^ tables[1][((word >> 4) & 0xf) as usize] ^ tables[2][((word >> 8) & 0xf) as usize] ^ tables[3][(word >> 12) as usize]
+}
+
+/// Given a byte slice of little-endian serialized GF(2^16) symbols, multiplies each of them by the scalar, in-place.
+/// Slice length is expected to be even.
+pub fn gf65536_inplace_mul_vec_by_scalar(vec: &mut [u8], scalar: u16) {
+    if scalar == 0 {
+        vec.fill(0);
+        return;
+    }
+    if scalar == 1 {
+        return;
+    }
+
+    let tables = nibble_product_tables(scalar);
+    vec.chunks_exact_mut(GF65536_SYMBOL_BYTE_LEN).for_each(|symbol| {
+        let product = mul_by_nibble_product_tables(&tables, u16::from_le_bytes([symbol[0], symbol[1]]));
+        symbol.copy_from_slice(&product.to_le_bytes());
+    });
+}
+
+/// Given two equal length byte slices of little-endian serialized GF(2^16) symbols, multiplies each symbol of `src` by
+/// the scalar and adds the product into the corresponding symbol of `dst`, in-place. Slice length is expected to be even.
+pub fn gf65536_mul_vec_by_scalar_then_add_into_vec(dst: &mut [u8], src: &[u8], scalar: u16) {
+    if scalar == 0 {
+        return;
+    }
+    if scalar == 1 {
+        dst.iter_mut().zip(src).for_each(|(dst_byte, &src_byte)| *dst_byte ^= src_byte);
+        return;
+    }
+
+    let tables = nibble_product_tables(scalar);
+    dst.chunks_exact_mut(GF65536_SYMBOL_BYTE_LEN)
+        .zip(src.chunks_exact(GF65536_SYMBOL_BYTE_LEN))
+        .for_each(|(dst_symbol, src_symbol)| {
+            let product = mul_by_nibble_product_tables(&tables, u16::from_le_bytes([src_symbol[0], src_symbol[1]]));
+            let sum = u16::from_le_bytes([dst_symbol[0], dst_symbol[1]]) ^ product;
+            dst_symbol.copy_from_slice(&sum.to_le_bytes());
+        });
+}
+
+#[cfg(test)]
+mod test {
+    use super::{GF65536_ORDER, Gf65536, gf65536_inplace_mul_vec_by_scalar, gf65536_mul_vec_by_scalar_then_add_into_vec};
+    use rand::Rng;
+
+    #[test]
+    fn prop_test_gf65536_operations() {
+        const NUM_TEST_ITERATIONS: usize = 100_000;
+
+        let mut rng = rand::rng();
+
+        (0..NUM_TEST_ITERATIONS).for_each(|_| {
+            let a: Gf65536 = rng.random();
+            let b: Gf65536 = rng.random();
+            let c: Gf65536 = rng.random();
+
+            // Addition, Subtraction, Negation
+            assert_eq!((a + b) - b, a);
+            assert_eq!(-a, a);
+
+            // Multiplication is commutative and distributes over addition
+            assert_eq!(a * b, b * a);
+            assert_eq!(a * (b + c), a * b + a * c);
+
+            // Division, Inversion
+            match b.inv() {
+                Some(b_inv) => {
+                    assert_eq!(b * b_inv, Gf65536::one());
+                    assert_eq!((a * b) / b, Some(a));
+                }
+                None => assert_eq!(b, Gf65536::zero()),
+            }
+        });
+    }
+
+    #[test]
+    fn test_gf65536_primitive_element_and_vector_kernels() {
+        // Primitive element generates the whole multiplicative group, before cycling back to one.
+        let mut elem = Gf65536::one();
+        for power in 1..GF65536_ORDER {
+            elem = elem * Gf65536::primitive_element();
+            assert_eq!(elem == Gf65536::one(), power == GF65536_ORDER - 1);
+        }
+
+        let mut rng = rand::rng();
+
+        let src = (0..1000).map(|_| rng.random()).collect::<Vec<u8>>();
+        let mut dst = (0..1000).map(|_| rng.random()).collect::<Vec<u8>>();
+        let scalar = rng.random_range(2..=u16::MAX);
+
+        let expected = dst
+            .chunks_exact(2)
+            .zip(src.chunks_exact(2))
+            .flat_map(|(d, s)| {
+                let product = Gf65536::new(scalar) * Gf65536::new(u16::from_le_bytes([s[0], s[1]]));
+                (Gf65536::new(u16::from_le_bytes([d[0], d[1]])) + product).get().to_le_bytes()
+            })
+            .collect::<Vec<u8>>();
+
+        gf65536_mul_vec_by_scalar_then_add_into_vec(&mut dst, &src, scalar);
+        assert_eq!(dst, expected);
+
+        let mut scaled = src.clone();
+        gf65536_inplace_mul_vec_by_scalar(&mut scaled, scalar);
+        gf65536_inplace_mul_vec_by_scalar(&mut scaled, Gf65536::new(scalar).inv().unwrap().get());
+        assert_eq!(scaled, src);
+    }
+}
@@ -1,6 +1,7 @@
 pub mod errors;
 pub mod gf256;
+pub mod gf65536;
 pub mod simd;
 
-#[cfg(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"))]
+#[cfg(all(feature = "simd", any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64", target_arch = "arm")))]
 mod simd_mul_table;
@@ -1,8 +1,30 @@
 mod neon;
+#[cfg(not(feature = "compile-time-dispatch"))]
 use std::arch::is_aarch64_feature_detected;
 
+/// Checks whether all given CPU features are available. With `compile-time-dispatch` feature, it's decided purely by target
+/// features enabled at compile time, so that there is no runtime detection and unused kernels get compiled away.
+macro_rules! is_feature_available {
+    ($($feature:tt),+) => {{
+        #[cfg(feature = "compile-time-dispatch")]
+        {
+            cfg!(all($(target_feature = $feature),+))
+        }
+
+        #[cfg(not(feature = "compile-time-dispatch"))]
+        {
+            $(is_aarch64_feature_detected!($feature))&&+
+        }
+    }};
+}
+
+/// Backend, vector multiply-accumulate kernels run on, following same order of preference as the dispatchers below.
+pub(super) fn active_backend() -> Option<super::Backend> {
+    is_feature_available!("neon").then_some(super::Backend::Neon)
+}
+
 pub(super) fn gf256_inplace_mul_vec_by_scalar(vec: &mut [u8], scalar: u8) -> bool {
-    if is_aarch64_feature_detected!("neon") {
+    if is_feature_available!("neon") {
         unsafe { neon::mul_vec_by_scalar(vec, scalar) };
         return true;
     }
@@ -11,7 +33,7 @@ pub(super) fn gf256_inplace_mul_vec_by_scalar(vec: &mut [u8], scalar: u8) -> boo
 }
 
 pub(super) fn gf256_inplace_add_vectors(vec_dst: &mut [u8], vec_src: &[u8]) -> bool {
-    if is_aarch64_feature_detected!("neon") {
+    if is_feature_available!("neon") {
         unsafe { neon::add_vec_into(vec_dst, vec_src) };
         return true;
     }
@@ -20,10 +42,19 @@ pub(super) fn gf256_inplace_add_vectors(vec_dst: &mut [u8], vec_src: &[u8]) -> b
 }
 
 pub(super) fn gf256_mul_vec_by_scalar_then_add_into_vec(add_into_vec: &mut [u8], mul_vec: &[u8], scalar: u8) -> bool {
-    if is_aarch64_feature_detected!("neon") {
+    if is_feature_available!("neon") {
         unsafe { neon::mul_vec_by_scalar_then_add_into(add_into_vec, mul_vec, scalar) };
         return true;
     }
 
     false
 }
+
+pub(super) fn gf256_mul_vecs_by_scalars_then_add_into_vec<const N: usize>(add_into_vec: &mut [u8], mul_vecs: &[&[u8]; N], scalars: &[u8; N]) -> bool {
+    if is_feature_available!("neon") {
+        unsafe { neon::mul_vecs_by_scalars_then_add_into(add_into_vec, mul_vecs, scalars) };
+        return true;
+    }
+
+    false
+}
@@ -94,3 +94,44 @@ pub unsafe fn mul_vec_by_scalar_then_add_into(add_into_vec: &mut [u8], mul_vec:
             *res ^= scaled;
         });
 }
+
+#[target_feature(enable = "neon")]
+pub unsafe fn mul_vecs_by_scalars_then_add_into<const N: usize>(add_into_vec: &mut [u8], mul_vecs: &[&[u8]; N], scalars: &[u8; N]) {
+    let simd_byte_len = add_into_vec.len() - add_into_vec.len() % GF256_HALF_ORDER;
+
+    unsafe {
+        let mut l_tbls = [vdupq_n_u8(0); N];
+        let mut h_tbls = [vdupq_n_u8(0); N];
+        for ((l_tbl, h_tbl), &scalar) in l_tbls.iter_mut().zip(h_tbls.iter_mut()).zip(scalars) {
+            *l_tbl = vld1q_u8(GF256_SIMD_MUL_TABLE_LOW[scalar as usize].as_ptr().cast());
+            *h_tbl = vld1q_u8(GF256_SIMD_MUL_TABLE_HIGH[scalar as usize].as_ptr().cast());
+        }
+        let l_mask = vdupq_n_u8(0x0f);
+
+        for offset in (0..simd_byte_len).step_by(GF256_HALF_ORDER) {
+            let mut accum_res = vld1q_u8(add_into_vec.as_ptr().add(offset).cast());
+
+            for ((mul_vec, &l_tbl), &h_tbl) in mul_vecs.iter().zip(&l_tbls).zip(&h_tbls) {
+                let mul_vec_chunk_simd = vld1q_u8(mul_vec.as_ptr().add(offset).cast());
+
+                let chunk_simd_lo = vandq_u8(mul_vec_chunk_simd, l_mask);
+                let chunk_simd_lo = vqtbl1q_u8(l_tbl, chunk_simd_lo);
+
+                let chunk_simd_hi = vshrq_n_u8(mul_vec_chunk_simd, 4);
+                let chunk_simd_hi = vandq_u8(chunk_simd_hi, l_mask);
+                let chunk_simd_hi = vqtbl1q_u8(h_tbl, chunk_simd_hi);
+
+                let scaled_res = veorq_u8(chunk_simd_lo, chunk_simd_hi);
+                accum_res = veorq_u8(accum_res, scaled_res);
+            }
+
+            vst1q_u8(add_into_vec.as_mut_ptr().add(offset).cast(), accum_res);
+        }
+    }
+
+    for (offset, res) in add_into_vec.iter_mut().enumerate().skip(simd_byte_len) {
+        for (mul_vec, &scalar) in mul_vecs.iter().zip(scalars) {
+            *res ^= Gf256::mul_const(mul_vec[offset], scalar);
+        }
+    }
+}
@@ -0,0 +1,124 @@
+mod neon;
+
+/// Checks whether all given CPU features are available. With `compile-time-dispatch` feature, it's decided purely by target
+/// features enabled at compile time, so that there is no runtime detection and unused kernels get compiled away.
+macro_rules! is_feature_available {
+    ($($feature:tt),+) => {{
+        #[cfg(feature = "compile-time-dispatch")]
+        {
+            cfg!(all($(target_feature = $feature),+))
+        }
+
+        #[cfg(not(feature = "compile-time-dispatch"))]
+        {
+            $(is_arm_feature_detected($feature))&&+
+        }
+    }};
+}
+
+/// Runtime detection of 32-bit ARM CPU features, as `std::arch::is_arm_feature_detected` is not stable yet. NEON is reported by
+/// the kernel in `AT_HWCAP` entry of the auxiliary vector, which is read once and cached. Features enabled at compile time are
+/// always available.
+#[cfg(not(feature = "compile-time-dispatch"))]
+fn is_arm_feature_detected(feature: &str) -> bool {
+    use std::sync::OnceLock;
+
+    static IS_NEON_DETECTED: OnceLock<bool> = OnceLock::new();
+
+    match feature {
+        "neon" => *IS_NEON_DETECTED.get_or_init(|| cfg!(target_feature = "neon") || read_hwcap().is_some_and(|hwcap| hwcap & HWCAP_NEON != 0)),
+        _ => false,
+    }
+}
+
+/// Type of the auxiliary vector entry, holding hardware capabilities of the CPU.
+#[cfg(not(feature = "compile-time-dispatch"))]
+const AT_HWCAP: u32 = 16;
+
+/// Bit of `AT_HWCAP`, set when the CPU has NEON i.e. Advanced SIMD.
+#[cfg(not(feature = "compile-time-dispatch"))]
+const HWCAP_NEON: u32 = 1 << 12;
+
+/// Reads `AT_HWCAP` entry of the auxiliary vector, which is a sequence of native-endian 32-bit key-value pairs, on 32-bit ARM.
+#[cfg(not(feature = "compile-time-dispatch"))]
+fn read_hwcap() -> Option<u32> {
+    let auxv = std::fs::read("/proc/self/auxv").ok()?;
+
+    auxv.chunks_exact(2 * size_of::<u32>()).find_map(|entry| {
+        let (key, value) = entry.split_at(size_of::<u32>());
+        if u32::from_ne_bytes(key.try_into().ok()?) != AT_HWCAP {
+            return None;
+        }
+
+        Some(u32::from_ne_bytes(value.try_into().ok()?))
+    })
+}
+
+/// Backend, vector multiply-accumulate kernels run on, following same order of preference as the dispatchers below.
+pub(super) fn active_backend() -> Option<super::Backend> {
+    is_feature_available!("neon").then_some(super::Backend::Neon)
+}
+
+pub(super) fn gf256_inplace_mul_vec_by_scalar(vec: &mut [u8], scalar: u8) -> bool {
+    if is_feature_available!("neon") {
+        unsafe { neon::mul_vec_by_scalar(vec, scalar) };
+        return true;
+    }
+
+    false
+}
+
+pub(super) fn gf256_inplace_add_vectors(vec_dst: &mut [u8], vec_src: &[u8]) -> bool {
+    if is_feature_available!("neon") {
+        unsafe { neon::add_vec_into(vec_dst, vec_src) };
+        return true;
+    }
+
+    false
+}
+
+pub(super) fn gf256_mul_vec_by_scalar_then_add_into_vec(add_into_vec: &mut [u8], mul_vec: &[u8], scalar: u8) -> bool {
+    if is_feature_available!("neon") {
+        unsafe { neon::mul_vec_by_scalar_then_add_into(add_into_vec, mul_vec, scalar) };
+        return true;
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::scalar, active_backend, neon};
+
+    #[test]
+    fn test_neon_kernels_match_scalar_reference() {
+        if active_backend().is_none() {
+            return;
+        }
+
+        let src = (0..=255u8).cycle().skip(17).take(1000).collect::<Vec<u8>>();
+        let dst = (0..=255u8).rev().cycle().take(1000).collect::<Vec<u8>>();
+
+        for byte_len in [0, 1, 15, 16, 17, 64, 333, 1000] {
+            for scalar in [0, 1, 2, 0x1d, 0x53, 0x8e, 0xca, 0xff] {
+                let mut expected = dst[..byte_len].to_vec();
+                let mut computed = expected.clone();
+                scalar::mul_vec_by_scalar(&mut expected, scalar);
+                unsafe { neon::mul_vec_by_scalar(&mut computed, scalar) };
+                assert_eq!(computed, expected);
+
+                let mut expected = dst[..byte_len].to_vec();
+                let mut computed = expected.clone();
+                scalar::mul_vec_by_scalar_then_add_into(&mut expected, &src[..byte_len], scalar);
+                unsafe { neon::mul_vec_by_scalar_then_add_into(&mut computed, &src[..byte_len], scalar) };
+                assert_eq!(computed, expected);
+            }
+
+            let mut expected = dst[..byte_len].to_vec();
+            let mut computed = expected.clone();
+            scalar::add_vec_into(&mut expected, &src[..byte_len]);
+            unsafe { neon::add_vec_into(&mut computed, &src[..byte_len]) };
+            assert_eq!(computed, expected);
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! NEON kernels for 32-bit ARM, written in inline assembly, as `core::arch::arm` intrinsics are not stable yet. Same lookup-table
+//! technique as the `aarch64` kernels, but ARMv7 lacks 128-bit table lookup, so each 16-byte table is looked up, as a pair of
+//! 64-bit registers, one half of the vector at a time. `#[target_feature(enable = "neon")]` is not stable for 32-bit ARM, so each
+//! block opens with `.fpu neon` directive instead, so that NEON instructions are accepted, even though the target doesn't enable
+//! `neon` feature. Assemblers offer no way to push and pop the FPU setting, but LLVM assembles every inline assembly block against
+//! a fresh copy of the enclosing function's target features, so the directive ends with its block, neither letting compiler
+//! generated code use NEON, nor marking the object file as requiring NEON. Cross-target CI job, building for an ARMv7 target
+//! without NEON, catches a kernel relying on NEON outside its block. Callers must make sure the CPU supports NEON.
+
+use crate::common::{
+    gf256::{GF256_HALF_ORDER, Gf256},
+    simd_mul_table::{GF256_SIMD_MUL_TABLE_HIGH, GF256_SIMD_MUL_TABLE_LOW},
+};
+use std::arch::asm;
+
+pub unsafe fn mul_vec_by_scalar(vec: &mut [u8], scalar: u8) {
+    let num_chunks = vec.len() / GF256_HALF_ORDER;
+    let num_simd_bytes = num_chunks * GF256_HALF_ORDER;
+
+    if num_chunks > 0 {
+        unsafe {
+            asm!(
+                ".fpu neon",
+                "vld1.8 {{d8, d9}}, [{l_tbl}]",
+                "vld1.8 {{d10, d11}}, [{h_tbl}]",
+                "vmov.i8 q6, #15",
+                "2:",
+                "vld1.8 {{d0, d1}}, [{ptr}]",
+                "vand q1, q0, q6",
+                "vshr.u8 q0, q0, #4",
+                "vtbl.8 d4, {{d8, d9}}, d2",
+                "vtbl.8 d5, {{d8, d9}}, d3",
+                "vtbl.8 d6, {{d10, d11}}, d0",
+                "vtbl.8 d7, {{d10, d11}}, d1",
+                "veor q2, q2, q3",
+                "vst1.8 {{d4, d5}}, [{ptr}]!",
+                "subs {n}, {n}, #1",
+                "bne 2b",
+                l_tbl = in(reg) GF256_SIMD_MUL_TABLE_LOW[scalar as usize].as_ptr(),
+                h_tbl = in(reg) GF256_SIMD_MUL_TABLE_HIGH[scalar as usize].as_ptr(),
+                ptr = inout(reg) vec.as_mut_ptr() => _,
+                n = inout(reg) num_chunks => _,
+                out("d0") _, out("d1") _, out("d2") _, out("d3") _, out("d4") _, out("d5") _, out("d6") _, out("d7") _,
+                out("d8") _, out("d9") _, out("d10") _, out("d11") _, out("d12") _, out("d13") _,
+                options(nostack),
+            );
+        }
+    }
+
+    vec[num_simd_bytes..].iter_mut().for_each(|symbol| {
+        *symbol = Gf256::mul_const(*symbol, scalar);
+    });
+}
+
+pub unsafe fn add_vec_into(vec_dst: &mut [u8], vec_src: &[u8]) {
+    let num_chunks = vec_dst.len().min(vec_src.len()) / GF256_HALF_ORDER;
+    let num_simd_bytes = num_chunks * GF256_HALF_ORDER;
+
+    if num_chunks > 0 {
+        unsafe {
+            asm!(
+                ".fpu neon",
+                "2:",
+                "vld1.8 {{d0, d1}}, [{dst}]",
+                "vld1.8 {{d2, d3}}, [{src}]!",
+                "veor q0, q0, q1",
+                "vst1.8 {{d0, d1}}, [{dst}]!",
+                "subs {n}, {n}, #1",
+                "bne 2b",
+                dst = inout(reg) vec_dst.as_mut_ptr() => _,
+                src = inout(reg) vec_src.as_ptr() => _,
+                n = inout(reg) num_chunks => _,
+                out("d0") _, out("d1") _, out("d2") _, out("d3") _,
+                options(nostack),
+            );
+        }
+    }
+
+    vec_dst[num_simd_bytes..].iter_mut().zip(&vec_src[num_simd_bytes..]).for_each(|(a, b)| {
+        *a ^= b;
+    });
+}
+
+pub unsafe fn mul_vec_by_scalar_then_add_into(add_into_vec: &mut [u8], mul_vec: &[u8], scalar: u8) {
+    let num_chunks = add_into_vec.len().min(mul_vec.len()) / GF256_HALF_ORDER;
+    let num_simd_bytes = num_chunks * GF256_HALF_ORDER;
+
+    if num_chunks > 0 {
+        unsafe {
+            asm!(
+                ".fpu neon",
+                "vld1.8 {{d8, d9}}, [{l_tbl}]",
+                "vld1.8 {{d10, d11}}, [{h_tbl}]",
+                "vmov.i8 q6, #15",
+                "2:",
+                "vld1.8 {{d0, d1}}, [{src}]!",
+                "vand q1, q0, q6",
+                "vshr.u8 q0, q0, #4",
+                "vtbl.8 d4, {{d8, d9}}, d2",
+                "vtbl.8 d5, {{d8, d9}}, d3",
+                "vtbl.8 d6, {{d10, d11}}, d0",
+                "vtbl.8 d7, {{d10, d11}}, d1",
+                "veor q2, q2, q3",
+                "vld1.8 {{d2, d3}}, [{dst}]",
+                "veor q2, q2, q1",
+                "vst1.8 {{d4, d5}}, [{dst}]!",
+                "subs {n}, {n}, #1",
+                "bne 2b",
+                l_tbl = in(reg) GF256_SIMD_MUL_TABLE_LOW[scalar as usize].as_ptr(),
+                h_tbl = in(reg) GF256_SIMD_MUL_TABLE_HIGH[scalar as usize].as_ptr(),
+                dst = inout(reg) add_into_vec.as_mut_ptr() => _,
+                src = inout(reg) mul_vec.as_ptr() => _,
+                n = inout(reg) num_chunks => _,
+                out("d0") _, out("d1") _, out("d2") _, out("d3") _, out("d4") _, out("d5") _, out("d6") _, out("d7") _,
+                out("d8") _, out("d9") _, out("d10") _, out("d11") _, out("d12") _, out("d13") _,
+                options(nostack),
+            );
+        }
+    }
+
+    add_into_vec[num_simd_bytes..]
+        .iter_mut()
+        .zip(mul_vec[num_simd_bytes..].iter().map(|&src_symbol| Gf256::mul_const(src_symbol, scalar)))
+        .for_each(|(res, scaled)| {
+            *res ^= scaled;
+        });
+}
@@ -0,0 +1,51 @@
+//! Shadow execution of SIMD kernels by their scalar reference implementations, enabled by `simd-cross-check` feature.
+//! It's meant for bringing up new SIMD backends and for chasing silent data corruption, not for production use.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// One in these many kernel invocations gets cross-checked.
+const SAMPLING_INTERVAL: usize = 16;
+
+static INVOCATION_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// Decides whether the current kernel invocation gets cross-checked. The very first invocation always does.
+pub(super) fn is_sampled() -> bool {
+    INVOCATION_COUNT.fetch_add(1, Ordering::Relaxed).is_multiple_of(SAMPLING_INTERVAL)
+}
+
+/// Panics with diagnostics, if output of the SIMD kernel doesn't match output of the scalar reference implementation.
+pub(super) fn assert_matches(kernel: &str, expected: &[u8], actual: &[u8]) {
+    if let Some(idx) = expected.iter().zip(actual).position(|(e, a)| e != a) {
+        let num_mismatches = expected.iter().zip(actual).filter(|(e, a)| e != a).count();
+
+        panic!(
+            "SIMD kernel `{kernel}` diverged from scalar reference on a {}-byte vector: {num_mismatches} mismatching byte(s), first at index {idx}, expected {:#04x}, got {:#04x}",
+            expected.len(),
+            expected[idx],
+            actual[idx],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::assert_matches;
+    use crate::common::simd::gf256_mul_vec_by_scalar_then_add_into_vec;
+
+    #[test]
+    fn test_simd_kernels_match_scalar_reference() {
+        let mul_vec = (0..=255u8).cycle().take(1000).collect::<Vec<u8>>();
+        let mut add_into_vec = vec![0x5au8; mul_vec.len()];
+
+        // Enough invocations, so that many of them get sampled for cross-checking.
+        for scalar in 0..=255u8 {
+            gf256_mul_vec_by_scalar_then_add_into_vec(&mut add_into_vec, &mul_vec, scalar);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "first at index 2")]
+    fn test_cross_check_reports_mismatch() {
+        assert_matches("test_kernel", &[1, 2, 3, 4], &[1, 2, 0, 4]);
+    }
+}
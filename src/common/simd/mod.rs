@@ -1,21 +1,89 @@
-use crate::common::gf256::Gf256;
+#[cfg(feature = "simd-cross-check")]
+mod cross_check;
+#[cfg(any(test, feature = "simd-cross-check"))]
+mod scalar;
+mod swar;
 
-#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+// Without `simd` feature, which is enabled by default, SIMD kernels are not compiled at all, so that every dispatcher below
+// runs the portable SWAR implementation, on every target.
+
+#[cfg(all(feature = "simd", any(target_arch = "x86", target_arch = "x86_64")))]
 mod x86;
 
-#[cfg(target_arch = "aarch64")]
+#[cfg(all(feature = "simd", target_arch = "aarch64"))]
 mod aarch64;
 
+#[cfg(all(feature = "simd", target_arch = "arm"))]
+mod arm;
+
+/// Kernel family, GF(2^8) vector arithmetic runs on. See `active_backend`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Backend {
+    /// Portable implementation, processing a 64-bit word at a time, used when no SIMD kernel is available or `simd` feature is disabled.
+    Scalar,
+    /// 128-bit lookup-table kernels, on `x86` or `x86_64` CPUs with `ssse3`.
+    Ssse3,
+    /// 256-bit lookup-table kernels, on `x86` or `x86_64` CPUs with `avx2`.
+    Avx2,
+    /// 512-bit lookup-table kernels, on `x86_64` CPUs with `avx512bw`.
+    Avx512,
+    /// 128-bit Galois field affine transformation kernels, on `x86` or `x86_64` CPUs with `gfni` and `ssse3`, but without `avx2`.
+    Gfni128,
+    /// 256-bit Galois field affine transformation kernels, on `x86` or `x86_64` CPUs with `gfni` and `avx2`.
+    Gfni256,
+    /// 512-bit Galois field affine transformation kernels, on `x86_64` CPUs with `gfni` and `avx512f`.
+    Gfni512,
+    /// 128-bit lookup-table kernels, on `aarch64` or 32-bit `arm` CPUs with `neon`.
+    Neon,
+}
+
+/// Returns the backend, GF(2^8) vector multiply-accumulate kernels, which dominate encoding, recoding and decoding, run on
+/// this host, so that deployments can log whether production hosts actually hit the fastest kernels, or silently fall back to
+/// slower ones, say after a hypervisor masked CPU features. It's detected at runtime, unless `compile-time-dispatch` feature is enabled.
+pub fn active_backend() -> Backend {
+    #[cfg(all(feature = "simd", any(target_arch = "x86", target_arch = "x86_64")))]
+    if let Some(backend) = x86::active_backend() {
+        return backend;
+    }
+
+    #[cfg(all(feature = "simd", target_arch = "aarch64"))]
+    if let Some(backend) = aarch64::active_backend() {
+        return backend;
+    }
+
+    #[cfg(all(feature = "simd", target_arch = "arm"))]
+    if let Some(backend) = arm::active_backend() {
+        return backend;
+    }
+
+    Backend::Scalar
+}
+
 /// Given a byte array of arbitrary length, this function can be used to multiply each
 /// byte element with a single specific scalar, over GF(2^8), mutating the input vector.
 ///
-/// In case this function runs on `x86_64` CPU with `avx2` or `ssse3` features or on `aarch64` CPU with `neon` features,
+/// In case this function runs on `x86_64` CPU with `gfni`, `avx512bw`, `avx2` or `ssse3` features or on `aarch64` or ARMv7 CPU with `neon` features,
 /// it can use lookup-table assisted SIMD multiplication, inspired from https://github.com/ceph/gf-complete/blob/a6862d10c9db467148f20eef2c6445ac9afd94d8/src/gf_w8.c#L1029-L1037.
 ///
 /// You have to build with `RUSTFLAGS="-C target-cpu=native"` flag to enjoy full benefits of compiler optimization.
 ///
 /// I originally discovered this technique in https://www.snia.org/sites/default/files/files2/files2/SDC2013/presentations/NewThinking/EthanMiller_Screaming_Fast_Galois_Field%20Arithmetic_SIMD%20Instructions.pdf.
 pub fn gf256_inplace_mul_vec_by_scalar(vec: &mut [u8], scalar: u8) {
+    #[cfg(feature = "simd-cross-check")]
+    if cross_check::is_sampled() {
+        let mut expected = vec.to_vec();
+        scalar::mul_vec_by_scalar(&mut expected, scalar);
+
+        dispatch_inplace_mul_vec_by_scalar(vec, scalar);
+        cross_check::assert_matches("gf256_inplace_mul_vec_by_scalar", &expected, vec);
+        return;
+    }
+
+    dispatch_inplace_mul_vec_by_scalar(vec, scalar);
+}
+
+/// Runs the fastest kernel available on the CPU, falling back to portable SWAR implementation.
+fn dispatch_inplace_mul_vec_by_scalar(vec: &mut [u8], scalar: u8) {
     if vec.is_empty() {
         return;
     }
@@ -27,59 +95,84 @@ pub fn gf256_inplace_mul_vec_by_scalar(vec: &mut [u8], scalar: u8) {
         return;
     }
 
-    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[cfg(all(feature = "simd", any(target_arch = "x86", target_arch = "x86_64")))]
     {
         if x86::gf256_inplace_mul_vec_by_scalar(vec, scalar) {
             return;
         }
     }
 
-    #[cfg(target_arch = "aarch64")]
+    #[cfg(all(feature = "simd", target_arch = "aarch64"))]
     {
         if aarch64::gf256_inplace_mul_vec_by_scalar(vec, scalar) {
             return;
         }
     }
 
-    vec.iter_mut().for_each(|src_symbol| {
-        *src_symbol = Gf256::mul_const(*src_symbol, scalar);
-    });
+    #[cfg(all(feature = "simd", target_arch = "arm"))]
+    {
+        if arm::gf256_inplace_mul_vec_by_scalar(vec, scalar) {
+            return;
+        }
+    }
+
+    swar::mul_vec_by_scalar(vec, scalar);
 }
 
 /// Given two byte arrays of equal length, this routine performs element-wise
 /// addition over GF(2^8), mutating one of the operand vectors.
 ///
 /// Note, addition over GF(2^8) is nothing but XOR-ing two operands. If this function
-/// runs on `x86_64` CPU with `avx2` or `ssse3` features or on `aarch64` CPU with `neon` features,
+/// runs on `x86_64` CPU with `avx512bw`, `avx2` or `ssse3` features or on `aarch64` or ARMv7 CPU with `neon` features,
 /// it can perform fast SIMD addition using vector intrinsics.
 ///
 /// You have to compile with `RUSTFLAGS="-C target-cpu=native` flag to hint the compiler
 /// so that it generates best code.
 pub fn gf256_inplace_add_vectors(vec_dst: &mut [u8], vec_src: &[u8]) {
-    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[cfg(feature = "simd-cross-check")]
+    if cross_check::is_sampled() {
+        let mut expected = vec_dst.to_vec();
+        scalar::add_vec_into(&mut expected, vec_src);
+
+        dispatch_inplace_add_vectors(vec_dst, vec_src);
+        cross_check::assert_matches("gf256_inplace_add_vectors", &expected, vec_dst);
+        return;
+    }
+
+    dispatch_inplace_add_vectors(vec_dst, vec_src);
+}
+
+/// Runs the fastest kernel available on the CPU, falling back to portable SWAR implementation.
+fn dispatch_inplace_add_vectors(vec_dst: &mut [u8], vec_src: &[u8]) {
+    #[cfg(all(feature = "simd", any(target_arch = "x86", target_arch = "x86_64")))]
     {
         if x86::gf256_inplace_add_vectors(vec_dst, vec_src) {
             return;
         }
     }
 
-    #[cfg(target_arch = "aarch64")]
+    #[cfg(all(feature = "simd", target_arch = "aarch64"))]
     {
         if aarch64::gf256_inplace_add_vectors(vec_dst, vec_src) {
             return;
         }
     }
 
-    vec_dst.iter_mut().zip(vec_src).for_each(|(a, b)| {
-        *a ^= b;
-    });
+    #[cfg(all(feature = "simd", target_arch = "arm"))]
+    {
+        if arm::gf256_inplace_add_vectors(vec_dst, vec_src) {
+            return;
+        }
+    }
+
+    swar::add_vec_into(vec_dst, vec_src);
 }
 
 /// Given a byte array `mul_vec` of arbitrary length, this function can be used to multiply each
 /// byte element with a single specific scalar, over GF(2^8), and then adding each scaled value
 /// to corresponding value in sink vector `add_into_vec`.
 ///
-/// In case this function runs on `x86_64` CPU with `avx2` or `ssse3` features or on `aarch64` CPU with `neon` features,
+/// In case this function runs on `x86_64` CPU with `gfni`, `avx512bw`, `avx2` or `ssse3` features or on `aarch64` or ARMv7 CPU with `neon` features,
 /// it can use lookup-table assisted SIMD multiplication, inspired from https://github.com/ceph/gf-complete/blob/a6862d10c9db467148f20eef2c6445ac9afd94d8/src/gf_w8.c#L1029-L1037.
 ///
 /// You have to build with `RUSTFLAGS="-C target-cpu=native"` flag to enjoy full benefits of compiler optimization.
@@ -87,6 +180,21 @@ pub fn gf256_inplace_add_vectors(vec_dst: &mut [u8], vec_src: &[u8]) {
 /// This function can be thought of an optimization over, first applying `gf256_inplace_mul_vec_by_scalar`
 /// and then applying `gf256_inplace_add_vectors`.
 pub fn gf256_mul_vec_by_scalar_then_add_into_vec(add_into_vec: &mut [u8], mul_vec: &[u8], scalar: u8) {
+    #[cfg(feature = "simd-cross-check")]
+    if cross_check::is_sampled() {
+        let mut expected = add_into_vec.to_vec();
+        scalar::mul_vec_by_scalar_then_add_into(&mut expected, mul_vec, scalar);
+
+        dispatch_mul_vec_by_scalar_then_add_into_vec(add_into_vec, mul_vec, scalar);
+        cross_check::assert_matches("gf256_mul_vec_by_scalar_then_add_into_vec", &expected, add_into_vec);
+        return;
+    }
+
+    dispatch_mul_vec_by_scalar_then_add_into_vec(add_into_vec, mul_vec, scalar);
+}
+
+/// Runs the fastest kernel available on the CPU, falling back to portable SWAR implementation.
+fn dispatch_mul_vec_by_scalar_then_add_into_vec(add_into_vec: &mut [u8], mul_vec: &[u8], scalar: u8) {
     if add_into_vec.is_empty() {
         return;
     }
@@ -98,22 +206,238 @@ pub fn gf256_mul_vec_by_scalar_then_add_into_vec(add_into_vec: &mut [u8], mul_ve
         return;
     }
 
-    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[cfg(all(feature = "simd", any(target_arch = "x86", target_arch = "x86_64")))]
     {
         if x86::gf256_mul_vec_by_scalar_then_add_into_vec(add_into_vec, mul_vec, scalar) {
             return;
         }
     }
 
-    #[cfg(target_arch = "aarch64")]
+    #[cfg(all(feature = "simd", target_arch = "aarch64"))]
     {
         if aarch64::gf256_mul_vec_by_scalar_then_add_into_vec(add_into_vec, mul_vec, scalar) {
             return;
         }
     }
 
-    add_into_vec
-        .iter_mut()
-        .zip(mul_vec.iter().map(|&src_symbol| Gf256::mul_const(src_symbol, scalar)))
-        .for_each(|(res, scaled)| *res ^= scaled);
+    #[cfg(all(feature = "simd", target_arch = "arm"))]
+    {
+        if arm::gf256_mul_vec_by_scalar_then_add_into_vec(add_into_vec, mul_vec, scalar) {
+            return;
+        }
+    }
+
+    swar::mul_vec_by_scalar_then_add_into(add_into_vec, mul_vec, scalar);
+}
+
+/// Maximum number of source vectors, a fused multiply-accumulate kernel adds into the sink vector, in a single pass.
+pub(crate) const MAX_FUSED_VEC_COUNT: usize = 4;
+
+/// Given byte arrays `mul_vecs`, each of same length as sink vector `add_into_vec`, this function multiplies each of them with
+/// corresponding scalar from `scalars`, over GF(2^8), adding all scaled vectors into `add_into_vec`.
+///
+/// Same as applying `gf256_mul_vec_by_scalar_then_add_into_vec` for each source vector, but up to four source vectors are
+/// accumulated per pass, so that each chunk of the sink vector is loaded and stored once for all of them, instead of once
+/// for each, which matters as the kernels are bound by memory bandwidth. Source vectors with a zero scalar are skipped.
+///
+/// Panics if `mul_vecs` and `scalars` are not of same length, or if any source vector is not of same length as `add_into_vec`.
+pub fn gf256_mul_vecs_by_scalars_then_add_into_vec(add_into_vec: &mut [u8], mul_vecs: &[&[u8]], scalars: &[u8]) {
+    assert_eq!(mul_vecs.len(), scalars.len());
+    assert!(mul_vecs.iter().all(|mul_vec| mul_vec.len() == add_into_vec.len()));
+
+    #[cfg(feature = "simd-cross-check")]
+    if cross_check::is_sampled() {
+        let mut expected = add_into_vec.to_vec();
+        scalar::mul_vecs_by_scalars_then_add_into(&mut expected, mul_vecs, scalars);
+
+        dispatch_mul_vecs_by_scalars_then_add_into_vec(add_into_vec, mul_vecs, scalars);
+        cross_check::assert_matches("gf256_mul_vecs_by_scalars_then_add_into_vec", &expected, add_into_vec);
+        return;
+    }
+
+    dispatch_mul_vecs_by_scalars_then_add_into_vec(add_into_vec, mul_vecs, scalars);
+}
+
+/// Groups source vectors with non-zero scalars, `MAX_FUSED_VEC_COUNT` at a time, running the fused kernel on each group.
+fn dispatch_mul_vecs_by_scalars_then_add_into_vec(add_into_vec: &mut [u8], mul_vecs: &[&[u8]], scalars: &[u8]) {
+    if add_into_vec.is_empty() {
+        return;
+    }
+
+    let mut group_vecs: [&[u8]; MAX_FUSED_VEC_COUNT] = [&[]; MAX_FUSED_VEC_COUNT];
+    let mut group_scalars = [0u8; MAX_FUSED_VEC_COUNT];
+    let mut group_len = 0;
+
+    for (&mul_vec, &scalar) in mul_vecs.iter().zip(scalars).filter(|&(_, &scalar)| scalar != 0) {
+        group_vecs[group_len] = mul_vec;
+        group_scalars[group_len] = scalar;
+        group_len += 1;
+
+        if group_len == MAX_FUSED_VEC_COUNT {
+            dispatch_fused_group(add_into_vec, &group_vecs, &group_scalars);
+            group_len = 0;
+        }
+    }
+
+    match group_len {
+        3 => dispatch_fused_group::<3>(add_into_vec, group_vecs.first_chunk().unwrap(), group_scalars.first_chunk().unwrap()),
+        2 => dispatch_fused_group::<2>(add_into_vec, group_vecs.first_chunk().unwrap(), group_scalars.first_chunk().unwrap()),
+        1 => dispatch_mul_vec_by_scalar_then_add_into_vec(add_into_vec, group_vecs[0], group_scalars[0]),
+        _ => {}
+    }
+}
+
+/// Runs the fastest fused kernel available on the CPU, falling back to accumulating one source vector at a time.
+fn dispatch_fused_group<const N: usize>(add_into_vec: &mut [u8], mul_vecs: &[&[u8]; N], scalars: &[u8; N]) {
+    #[cfg(all(feature = "simd", any(target_arch = "x86", target_arch = "x86_64")))]
+    {
+        if x86::gf256_mul_vecs_by_scalars_then_add_into_vec(add_into_vec, mul_vecs, scalars) {
+            return;
+        }
+    }
+
+    #[cfg(all(feature = "simd", target_arch = "aarch64"))]
+    {
+        if aarch64::gf256_mul_vecs_by_scalars_then_add_into_vec(add_into_vec, mul_vecs, scalars) {
+            return;
+        }
+    }
+
+    mul_vecs
+        .iter()
+        .zip(scalars)
+        .for_each(|(mul_vec, &scalar)| dispatch_mul_vec_by_scalar_then_add_into_vec(add_into_vec, mul_vec, scalar));
+}
+
+/// Number of strided elements, gathered into a contiguous block on stack, before running a SIMD kernel on them.
+const STRIDED_BLOCK_LEN: usize = 256;
+
+/// Same as `gf256_inplace_mul_vec_by_scalar`, but on a strided view of `count` elements, `i`-th of them being `vec[i * stride]`,
+/// say a column of a row-major matrix. Elements are gathered into a small contiguous block at a time, which is processed by the
+/// SIMD kernel and scattered back, so that column-oriented operations don't need to materialize a transposed copy first.
+///
+/// Panics if `vec` doesn't hold `count` elements, `stride` apart, or if `stride` is zero.
+pub fn gf256_strided_inplace_mul_vec_by_scalar(vec: &mut [u8], stride: usize, count: usize, scalar: u8) {
+    assert!(stride > 0 && (count == 0 || (count - 1) * stride < vec.len()));
+
+    if stride == 1 {
+        gf256_inplace_mul_vec_by_scalar(&mut vec[..count], scalar);
+        return;
+    }
+
+    let mut block = [0u8; STRIDED_BLOCK_LEN];
+    for block_begin in (0..count).step_by(STRIDED_BLOCK_LEN) {
+        let block = &mut block[..(count - block_begin).min(STRIDED_BLOCK_LEN)];
+        let strided = &mut vec[block_begin * stride..];
+
+        block.iter_mut().zip(strided.iter().step_by(stride)).for_each(|(dst, src)| *dst = *src);
+        gf256_inplace_mul_vec_by_scalar(block, scalar);
+        block.iter().zip(strided.iter_mut().step_by(stride)).for_each(|(src, dst)| *dst = *src);
+    }
+}
+
+/// Same as `gf256_mul_vec_by_scalar_then_add_into_vec`, but on strided views of `count` elements each, `i`-th of them being
+/// `add_into_vec[i * add_into_stride]` and `mul_vec[i * mul_stride]` respectively. See `gf256_strided_inplace_mul_vec_by_scalar`.
+///
+/// Panics if either vector doesn't hold `count` elements, stride apart, or if either stride is zero.
+pub fn gf256_strided_mul_vec_by_scalar_then_add_into_vec(
+    add_into_vec: &mut [u8],
+    add_into_stride: usize,
+    mul_vec: &[u8],
+    mul_stride: usize,
+    count: usize,
+    scalar: u8,
+) {
+    assert!(add_into_stride > 0 && (count == 0 || (count - 1) * add_into_stride < add_into_vec.len()));
+    assert!(mul_stride > 0 && (count == 0 || (count - 1) * mul_stride < mul_vec.len()));
+
+    if add_into_stride == 1 && mul_stride == 1 {
+        gf256_mul_vec_by_scalar_then_add_into_vec(&mut add_into_vec[..count], &mul_vec[..count], scalar);
+        return;
+    }
+
+    let mut add_into_block = [0u8; STRIDED_BLOCK_LEN];
+    let mut mul_block = [0u8; STRIDED_BLOCK_LEN];
+
+    for block_begin in (0..count).step_by(STRIDED_BLOCK_LEN) {
+        let block_len = (count - block_begin).min(STRIDED_BLOCK_LEN);
+        let mul_block = &mut mul_block[..block_len];
+        mul_block
+            .iter_mut()
+            .zip(mul_vec[block_begin * mul_stride..].iter().step_by(mul_stride))
+            .for_each(|(dst, src)| *dst = *src);
+
+        if add_into_stride == 1 {
+            gf256_mul_vec_by_scalar_then_add_into_vec(&mut add_into_vec[block_begin..block_begin + block_len], mul_block, scalar);
+            continue;
+        }
+
+        let add_into_block = &mut add_into_block[..block_len];
+        let strided = &mut add_into_vec[block_begin * add_into_stride..];
+
+        add_into_block
+            .iter_mut()
+            .zip(strided.iter().step_by(add_into_stride))
+            .for_each(|(dst, src)| *dst = *src);
+        gf256_mul_vec_by_scalar_then_add_into_vec(add_into_block, mul_block, scalar);
+        add_into_block
+            .iter()
+            .zip(strided.iter_mut().step_by(add_into_stride))
+            .for_each(|(src, dst)| *dst = *src);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        gf256_mul_vecs_by_scalars_then_add_into_vec, gf256_strided_inplace_mul_vec_by_scalar, gf256_strided_mul_vec_by_scalar_then_add_into_vec, scalar,
+    };
+
+    #[test]
+    fn test_strided_kernels_match_scalar_reference() {
+        let matrix = (0..=255u8).cycle().take(7 * 600).collect::<Vec<u8>>();
+
+        for (stride, count, scalar) in [(1, 600, 0x53), (7, 600, 0xca), (7, 37, 1), (3, 0, 9)] {
+            let column = |matrix: &[u8]| matrix.iter().step_by(stride).take(count).copied().collect::<Vec<u8>>();
+
+            let mut expected = column(&matrix);
+            scalar::mul_vec_by_scalar(&mut expected, scalar);
+
+            let mut strided = matrix.clone();
+            gf256_strided_inplace_mul_vec_by_scalar(&mut strided, stride, count, scalar);
+            assert_eq!(column(&strided), expected);
+            assert!(strided.iter().zip(&matrix).enumerate().all(|(idx, (a, b))| a == b || idx % stride == 0));
+
+            let mut expected = vec![0x5au8; count];
+            scalar::mul_vec_by_scalar_then_add_into(&mut expected, &column(&matrix), scalar);
+
+            // Column of a matrix accumulated into a contiguous vector, then into another column of the same shape.
+            let mut contiguous = vec![0x5au8; count];
+            gf256_strided_mul_vec_by_scalar_then_add_into_vec(&mut contiguous, 1, &matrix, stride, count, scalar);
+            assert_eq!(contiguous, expected);
+
+            let mut accumulated = matrix.iter().map(|_| 0x5au8).collect::<Vec<u8>>();
+            gf256_strided_mul_vec_by_scalar_then_add_into_vec(&mut accumulated, stride, &matrix, stride, count, scalar);
+            assert_eq!(column(&accumulated), expected);
+        }
+    }
+
+    #[test]
+    fn test_fused_kernel_matches_scalar_reference() {
+        let vecs = (0..9u8)
+            .map(|i| (0..=255u8).cycle().skip(i as usize * 31).take(1000).collect::<Vec<u8>>())
+            .collect::<Vec<_>>();
+        let scalars = [0x53, 0, 1, 0xca, 0xff, 0x02, 0, 0x8e, 0x1d];
+
+        for (vec_count, byte_len) in [(0, 1000), (1, 1000), (2, 999), (3, 129), (4, 64), (5, 17), (8, 1000), (9, 333), (9, 0)] {
+            let mul_vecs = vecs[..vec_count].iter().map(|vec| &vec[..byte_len]).collect::<Vec<_>>();
+
+            let mut expected = vec![0x5au8; byte_len];
+            scalar::mul_vecs_by_scalars_then_add_into(&mut expected, &mul_vecs, &scalars[..vec_count]);
+
+            let mut accumulated = vec![0x5au8; byte_len];
+            gf256_mul_vecs_by_scalars_then_add_into_vec(&mut accumulated, &mul_vecs, &scalars[..vec_count]);
+            assert_eq!(accumulated, expected);
+        }
+    }
 }
@@ -0,0 +1,30 @@
+//! Portable scalar implementations of GF(2^8) vector arithmetic, processing a byte at a time, used as the reference,
+//! SIMD and SWAR kernels are checked against.
+
+use crate::common::gf256::Gf256;
+
+pub(super) fn mul_vec_by_scalar(vec: &mut [u8], scalar: u8) {
+    vec.iter_mut().for_each(|src_symbol| {
+        *src_symbol = Gf256::mul_const(*src_symbol, scalar);
+    });
+}
+
+pub(super) fn add_vec_into(vec_dst: &mut [u8], vec_src: &[u8]) {
+    vec_dst.iter_mut().zip(vec_src).for_each(|(a, b)| {
+        *a ^= b;
+    });
+}
+
+pub(super) fn mul_vec_by_scalar_then_add_into(add_into_vec: &mut [u8], mul_vec: &[u8], scalar: u8) {
+    add_into_vec
+        .iter_mut()
+        .zip(mul_vec.iter().map(|&src_symbol| Gf256::mul_const(src_symbol, scalar)))
+        .for_each(|(res, scaled)| *res ^= scaled);
+}
+
+pub(super) fn mul_vecs_by_scalars_then_add_into(add_into_vec: &mut [u8], mul_vecs: &[&[u8]], scalars: &[u8]) {
+    mul_vecs
+        .iter()
+        .zip(scalars)
+        .for_each(|(mul_vec, &scalar)| mul_vec_by_scalar_then_add_into(add_into_vec, mul_vec, scalar));
+}
@@ -0,0 +1,112 @@
+//! Portable SWAR i.e. SIMD within a register, implementations of GF(2^8) vector arithmetic, processing eight bytes at a time,
+//! packed in a `u64` word, without any intrinsics. These back the dispatchers, when no SIMD backend is available on the target.
+//!
+//! Multiplying a byte by a scalar is XOR of the scalar multiplied by `x^i`, for each set bit `i` of the byte. So, with those eight
+//! multiples of the scalar broadcast to all bytes of a word, each bit of all eight bytes is handled at once, by masking.
+
+use crate::common::gf256::Gf256;
+
+/// Least significant bit of each byte of a word.
+const BYTE_LSB_MASK: u64 = 0x0101_0101_0101_0101;
+
+/// Scalar multiplied by `x^i`, for `i` in `0..8`, each broadcast to all bytes of a word.
+fn broadcast_scalar_multiples(scalar: u8) -> [u64; u8::BITS as usize] {
+    let mut multiples = [0u64; u8::BITS as usize];
+    let mut multiple = scalar;
+
+    for broadcast in multiples.iter_mut() {
+        *broadcast = u64::from(multiple) * BYTE_LSB_MASK;
+        multiple = Gf256::mul_const(multiple, 2);
+    }
+
+    multiples
+}
+
+/// Multiplies each byte of the word by the scalar, whose multiples are given, see `broadcast_scalar_multiples`.
+#[inline(always)]
+fn mul_word(word: u64, multiples: &[u64; u8::BITS as usize]) -> u64 {
+    multiples.iter().enumerate().fold(0, |acc, (bit, &multiple)| {
+        // Spreads `bit`-th bit of each byte to all bits of that byte, without carrying into the next one.
+        let mask = ((word >> bit) & BYTE_LSB_MASK) * u64::from(u8::MAX);
+        acc ^ (mask & multiple)
+    })
+}
+
+pub(super) fn mul_vec_by_scalar(vec: &mut [u8], scalar: u8) {
+    let multiples = broadcast_scalar_multiples(scalar);
+    let mut iter = vec.chunks_exact_mut(size_of::<u64>());
+
+    for chunk in iter.by_ref() {
+        let word = u64::from_ne_bytes(unsafe { chunk.try_into().unwrap_unchecked() });
+        chunk.copy_from_slice(&mul_word(word, &multiples).to_ne_bytes());
+    }
+
+    iter.into_remainder().iter_mut().for_each(|symbol| {
+        *symbol = Gf256::mul_const(*symbol, scalar);
+    });
+}
+
+pub(super) fn add_vec_into(vec_dst: &mut [u8], vec_src: &[u8]) {
+    let mut iter_dst = vec_dst.chunks_exact_mut(size_of::<u64>());
+    let mut iter_src = vec_src.chunks_exact(size_of::<u64>());
+
+    for (chunk_dst, chunk_src) in iter_dst.by_ref().zip(iter_src.by_ref()) {
+        let word_dst = u64::from_ne_bytes(unsafe { chunk_dst.try_into().unwrap_unchecked() });
+        let word_src = u64::from_ne_bytes(unsafe { chunk_src.try_into().unwrap_unchecked() });
+        chunk_dst.copy_from_slice(&(word_dst ^ word_src).to_ne_bytes());
+    }
+
+    iter_dst.into_remainder().iter_mut().zip(iter_src.remainder()).for_each(|(a, b)| {
+        *a ^= b;
+    });
+}
+
+pub(super) fn mul_vec_by_scalar_then_add_into(add_into_vec: &mut [u8], mul_vec: &[u8], scalar: u8) {
+    let multiples = broadcast_scalar_multiples(scalar);
+    let mut add_vec_iter = add_into_vec.chunks_exact_mut(size_of::<u64>());
+    let mut mul_vec_iter = mul_vec.chunks_exact(size_of::<u64>());
+
+    for (add_vec_chunk, mul_vec_chunk) in add_vec_iter.by_ref().zip(mul_vec_iter.by_ref()) {
+        let add_word = u64::from_ne_bytes(unsafe { add_vec_chunk.try_into().unwrap_unchecked() });
+        let src_word = u64::from_ne_bytes(unsafe { mul_vec_chunk.try_into().unwrap_unchecked() });
+        add_vec_chunk.copy_from_slice(&(add_word ^ mul_word(src_word, &multiples)).to_ne_bytes());
+    }
+
+    add_vec_iter
+        .into_remainder()
+        .iter_mut()
+        .zip(mul_vec_iter.remainder().iter().map(|&src_symbol| Gf256::mul_const(src_symbol, scalar)))
+        .for_each(|(res, scaled)| *res ^= scaled);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::simd::scalar;
+    use rand::Rng;
+
+    #[test]
+    fn test_swar_kernels_match_scalar_reference() {
+        let mut rng = rand::rng();
+
+        for len in [0, 1, 7, 8, 9, 63, 64, 1000] {
+            let scalar_value = rng.random::<u8>();
+            let src = (0..len).map(|_| rng.random()).collect::<Vec<u8>>();
+            let dst = (0..len).map(|_| rng.random()).collect::<Vec<u8>>();
+
+            let (mut expected, mut computed) = (src.clone(), src.clone());
+            scalar::mul_vec_by_scalar(&mut expected, scalar_value);
+            super::mul_vec_by_scalar(&mut computed, scalar_value);
+            assert_eq!(expected, computed);
+
+            let (mut expected, mut computed) = (dst.clone(), dst.clone());
+            scalar::add_vec_into(&mut expected, &src);
+            super::add_vec_into(&mut computed, &src);
+            assert_eq!(expected, computed);
+
+            let (mut expected, mut computed) = (dst.clone(), dst.clone());
+            scalar::mul_vec_by_scalar_then_add_into(&mut expected, &src, scalar_value);
+            super::mul_vec_by_scalar_then_add_into(&mut computed, &src, scalar_value);
+            assert_eq!(expected, computed);
+        }
+    }
+}
@@ -98,3 +98,44 @@ pub unsafe fn mul_vec_by_scalar_then_add_into(add_into_vec: &mut [u8], mul_vec:
             *res ^= scaled;
         });
 }
+
+#[target_feature(enable = "avx2")]
+pub unsafe fn mul_vecs_by_scalars_then_add_into<const N: usize>(add_into_vec: &mut [u8], mul_vecs: &[&[u8]; N], scalars: &[u8; N]) {
+    let simd_byte_len = add_into_vec.len() - add_into_vec.len() % (2 * GF256_HALF_ORDER);
+
+    unsafe {
+        let mut l_tbls = [_mm256_setzero_si256(); N];
+        let mut h_tbls = [_mm256_setzero_si256(); N];
+        for ((l_tbl, h_tbl), &scalar) in l_tbls.iter_mut().zip(h_tbls.iter_mut()).zip(scalars) {
+            *l_tbl = _mm256_broadcastsi128_si256(_mm_lddqu_si128(GF256_SIMD_MUL_TABLE_LOW[scalar as usize].as_ptr().cast()));
+            *h_tbl = _mm256_broadcastsi128_si256(_mm_lddqu_si128(GF256_SIMD_MUL_TABLE_HIGH[scalar as usize].as_ptr().cast()));
+        }
+        let l_mask = _mm256_set1_epi8(0x0f);
+
+        for offset in (0..simd_byte_len).step_by(2 * GF256_HALF_ORDER) {
+            let mut accum_res = _mm256_lddqu_si256(add_into_vec.as_ptr().add(offset).cast());
+
+            for ((mul_vec, &l_tbl), &h_tbl) in mul_vecs.iter().zip(&l_tbls).zip(&h_tbls) {
+                let mul_vec_chunk_simd = _mm256_lddqu_si256(mul_vec.as_ptr().add(offset).cast());
+
+                let chunk_simd_lo = _mm256_and_si256(mul_vec_chunk_simd, l_mask);
+                let chunk_simd_lo = _mm256_shuffle_epi8(l_tbl, chunk_simd_lo);
+
+                let chunk_simd_hi = _mm256_srli_epi64(mul_vec_chunk_simd, 4);
+                let chunk_simd_hi = _mm256_and_si256(chunk_simd_hi, l_mask);
+                let chunk_simd_hi = _mm256_shuffle_epi8(h_tbl, chunk_simd_hi);
+
+                let scaled_res = _mm256_xor_si256(chunk_simd_lo, chunk_simd_hi);
+                accum_res = _mm256_xor_si256(accum_res, scaled_res);
+            }
+
+            _mm256_storeu_si256(add_into_vec.as_mut_ptr().add(offset).cast(), accum_res);
+        }
+    }
+
+    for (offset, res) in add_into_vec.iter_mut().enumerate().skip(simd_byte_len) {
+        for (mul_vec, &scalar) in mul_vecs.iter().zip(scalars) {
+            *res ^= Gf256::mul_const(mul_vec[offset], scalar);
+        }
+    }
+}
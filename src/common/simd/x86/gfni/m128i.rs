@@ -6,9 +6,9 @@ use std::arch::x86::*;
 #[cfg(target_arch = "x86_64")]
 use std::arch::x86_64::*;
 
-#[target_feature(enable = "gfni", enable = "avx512vl")]
+#[target_feature(enable = "gfni")]
 pub unsafe fn mul_vec_by_scalar(vec: &mut [u8], scalar: u8) {
-    let mut iter = vec.chunks_exact_mut(2 * GF256_HALF_ORDER);
+    let mut iter = vec.chunks_exact_mut(GF256_HALF_ORDER);
 
     unsafe {
         let scalar_simd = _mm_set1_epi8(scalar as i8);
@@ -25,10 +25,10 @@ pub unsafe fn mul_vec_by_scalar(vec: &mut [u8], scalar: u8) {
     });
 }
 
-#[target_feature(enable = "gfni", enable = "avx512vl")]
+#[target_feature(enable = "gfni")]
 pub unsafe fn mul_vec_by_scalar_then_add_into(add_into_vec: &mut [u8], mul_vec: &[u8], scalar: u8) {
-    let mut add_vec_iter = add_into_vec.chunks_exact_mut(2 * GF256_HALF_ORDER);
-    let mut mul_vec_iter = mul_vec.chunks_exact(2 * GF256_HALF_ORDER);
+    let mut add_vec_iter = add_into_vec.chunks_exact_mut(GF256_HALF_ORDER);
+    let mut mul_vec_iter = mul_vec.chunks_exact(GF256_HALF_ORDER);
 
     unsafe {
         let scalar_simd = _mm_set1_epi8(scalar as i8);
@@ -52,3 +52,34 @@ pub unsafe fn mul_vec_by_scalar_then_add_into(add_into_vec: &mut [u8], mul_vec:
             *res ^= scaled;
         });
 }
+
+#[target_feature(enable = "gfni")]
+pub unsafe fn mul_vecs_by_scalars_then_add_into<const N: usize>(add_into_vec: &mut [u8], mul_vecs: &[&[u8]; N], scalars: &[u8; N]) {
+    let simd_byte_len = add_into_vec.len() - add_into_vec.len() % GF256_HALF_ORDER;
+
+    unsafe {
+        let mut scalars_simd = [_mm_setzero_si128(); N];
+        for (scalar_simd, &scalar) in scalars_simd.iter_mut().zip(scalars) {
+            *scalar_simd = _mm_set1_epi8(scalar as i8);
+        }
+
+        for offset in (0..simd_byte_len).step_by(GF256_HALF_ORDER) {
+            let mut accum_res = _mm_loadu_si128(add_into_vec.as_ptr().add(offset).cast());
+
+            for (mul_vec, &scalar_simd) in mul_vecs.iter().zip(&scalars_simd) {
+                let mul_vec_chunk_simd = _mm_loadu_si128(mul_vec.as_ptr().add(offset).cast());
+                let scaled_res = _mm_gf2p8mul_epi8(mul_vec_chunk_simd, scalar_simd);
+
+                accum_res = _mm_xor_si128(accum_res, scaled_res);
+            }
+
+            _mm_storeu_si128(add_into_vec.as_mut_ptr().add(offset).cast(), accum_res);
+        }
+    }
+
+    for (offset, res) in add_into_vec.iter_mut().enumerate().skip(simd_byte_len) {
+        for (mul_vec, &scalar) in mul_vecs.iter().zip(scalars) {
+            *res ^= Gf256::mul_const(mul_vec[offset], scalar);
+        }
+    }
+}
@@ -6,7 +6,7 @@ use std::arch::x86::*;
 #[cfg(target_arch = "x86_64")]
 use std::arch::x86_64::*;
 
-#[target_feature(enable = "gfni", enable = "avx512vl")]
+#[target_feature(enable = "gfni", enable = "avx2")]
 pub unsafe fn mul_vec_by_scalar(vec: &mut [u8], scalar: u8) {
     let mut iter = vec.chunks_exact_mut(2 * GF256_HALF_ORDER);
 
@@ -25,7 +25,7 @@ pub unsafe fn mul_vec_by_scalar(vec: &mut [u8], scalar: u8) {
     });
 }
 
-#[target_feature(enable = "gfni", enable = "avx512vl")]
+#[target_feature(enable = "gfni", enable = "avx2")]
 pub unsafe fn mul_vec_by_scalar_then_add_into(add_into_vec: &mut [u8], mul_vec: &[u8], scalar: u8) {
     let mut add_vec_iter = add_into_vec.chunks_exact_mut(2 * GF256_HALF_ORDER);
     let mut mul_vec_iter = mul_vec.chunks_exact(2 * GF256_HALF_ORDER);
@@ -52,3 +52,34 @@ pub unsafe fn mul_vec_by_scalar_then_add_into(add_into_vec: &mut [u8], mul_vec:
             *res ^= scaled;
         });
 }
+
+#[target_feature(enable = "gfni", enable = "avx2")]
+pub unsafe fn mul_vecs_by_scalars_then_add_into<const N: usize>(add_into_vec: &mut [u8], mul_vecs: &[&[u8]; N], scalars: &[u8; N]) {
+    let simd_byte_len = add_into_vec.len() - add_into_vec.len() % (2 * GF256_HALF_ORDER);
+
+    unsafe {
+        let mut scalars_simd = [_mm256_setzero_si256(); N];
+        for (scalar_simd, &scalar) in scalars_simd.iter_mut().zip(scalars) {
+            *scalar_simd = _mm256_set1_epi8(scalar as i8);
+        }
+
+        for offset in (0..simd_byte_len).step_by(2 * GF256_HALF_ORDER) {
+            let mut accum_res = _mm256_loadu_si256(add_into_vec.as_ptr().add(offset).cast());
+
+            for (mul_vec, &scalar_simd) in mul_vecs.iter().zip(&scalars_simd) {
+                let mul_vec_chunk_simd = _mm256_loadu_si256(mul_vec.as_ptr().add(offset).cast());
+                let scaled_res = _mm256_gf2p8mul_epi8(mul_vec_chunk_simd, scalar_simd);
+
+                accum_res = _mm256_xor_si256(accum_res, scaled_res);
+            }
+
+            _mm256_storeu_si256(add_into_vec.as_mut_ptr().add(offset).cast(), accum_res);
+        }
+    }
+
+    for (offset, res) in add_into_vec.iter_mut().enumerate().skip(simd_byte_len) {
+        for (mul_vec, &scalar) in mul_vecs.iter().zip(scalars) {
+            *res ^= Gf256::mul_const(mul_vec[offset], scalar);
+        }
+    }
+}
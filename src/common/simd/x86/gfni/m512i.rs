@@ -52,3 +52,34 @@ pub unsafe fn mul_vec_by_scalar_then_add_into(add_into_vec: &mut [u8], mul_vec:
             *res ^= scaled;
         });
 }
+
+#[target_feature(enable = "gfni", enable = "avx512f")]
+pub unsafe fn mul_vecs_by_scalars_then_add_into<const N: usize>(add_into_vec: &mut [u8], mul_vecs: &[&[u8]; N], scalars: &[u8; N]) {
+    let simd_byte_len = add_into_vec.len() - add_into_vec.len() % (4 * GF256_HALF_ORDER);
+
+    unsafe {
+        let mut scalars_simd = [_mm512_setzero_si512(); N];
+        for (scalar_simd, &scalar) in scalars_simd.iter_mut().zip(scalars) {
+            *scalar_simd = _mm512_set1_epi8(scalar as i8);
+        }
+
+        for offset in (0..simd_byte_len).step_by(4 * GF256_HALF_ORDER) {
+            let mut accum_res = _mm512_loadu_si512(add_into_vec.as_ptr().add(offset).cast());
+
+            for (mul_vec, &scalar_simd) in mul_vecs.iter().zip(&scalars_simd) {
+                let mul_vec_chunk_simd = _mm512_loadu_si512(mul_vec.as_ptr().add(offset).cast());
+                let scaled_res = _mm512_gf2p8mul_epi8(mul_vec_chunk_simd, scalar_simd);
+
+                accum_res = _mm512_xor_si512(accum_res, scaled_res);
+            }
+
+            _mm512_storeu_si512(add_into_vec.as_mut_ptr().add(offset).cast(), accum_res);
+        }
+    }
+
+    for (offset, res) in add_into_vec.iter_mut().enumerate().skip(simd_byte_len) {
+        for (mul_vec, &scalar) in mul_vecs.iter().zip(scalars) {
+            *res ^= Gf256::mul_const(mul_vec[offset], scalar);
+        }
+    }
+}
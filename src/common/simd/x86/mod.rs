@@ -3,89 +3,236 @@ mod avx512;
 mod gfni;
 mod ssse3;
 
-pub(super) fn gf256_inplace_mul_vec_by_scalar(vec: &mut [u8], scalar: u8) -> bool {
-    if is_x86_feature_detected!("gfni") && is_x86_feature_detected!("avx512f") {
-        unsafe { gfni::m512i::mul_vec_by_scalar(vec, scalar) };
-        return true;
-    }
+use super::Backend;
+
+/// Checks whether all given CPU features are available. With `compile-time-dispatch` feature, it's decided purely by target
+/// features enabled at compile time, so that there is no runtime detection and unused kernels get compiled away.
+macro_rules! is_feature_available {
+    ($($feature:tt),+) => {{
+        #[cfg(feature = "compile-time-dispatch")]
+        {
+            cfg!(all($(target_feature = $feature),+))
+        }
+
+        #[cfg(not(feature = "compile-time-dispatch"))]
+        {
+            $(is_x86_feature_detected!($feature))&&+
+        }
+    }};
+}
 
-    if is_x86_feature_detected!("gfni") && is_x86_feature_detected!("avx512vl") {
-        unsafe { gfni::m256i::mul_vec_by_scalar(vec, scalar) };
-        return true;
+/// Backend, vector multiply-accumulate kernels run on, which the dispatchers below match on. With runtime detection, CPU
+/// features are checked once, on first call, and the result is cached, so that each kernel invocation costs a single load
+/// and branch, instead of a chain of feature checks, which adds up with small pieces and many calls. With `compile-time-dispatch`
+/// feature, it's a constant.
+pub(super) fn active_backend() -> Option<Backend> {
+    #[cfg(feature = "compile-time-dispatch")]
+    {
+        detect_backend()
     }
 
-    if is_x86_feature_detected!("gfni") && is_x86_feature_detected!("avx512vl") {
-        unsafe { gfni::m128i::mul_vec_by_scalar(vec, scalar) };
-        return true;
-    }
+    #[cfg(not(feature = "compile-time-dispatch"))]
+    {
+        use std::sync::OnceLock;
 
-    if is_x86_feature_detected!("avx512bw") {
-        unsafe { avx512::mul_vec_by_scalar(vec, scalar) };
-        return true;
+        static ACTIVE_BACKEND: OnceLock<Option<Backend>> = OnceLock::new();
+        *ACTIVE_BACKEND.get_or_init(detect_backend)
     }
+}
 
-    if is_x86_feature_detected!("avx2") {
-        unsafe { avx2::mul_vec_by_scalar(vec, scalar) };
-        return true;
+/// Picks the fastest backend, the CPU supports, in order of preference.
+fn detect_backend() -> Option<Backend> {
+    if is_feature_available!("gfni", "avx512f") {
+        return Some(Backend::Gfni512);
     }
-
-    if is_x86_feature_detected!("ssse3") {
-        unsafe { ssse3::mul_vec_by_scalar(vec, scalar) };
-        return true;
+    if is_feature_available!("gfni", "avx2") {
+        return Some(Backend::Gfni256);
+    }
+    if is_feature_available!("avx512bw") {
+        return Some(Backend::Avx512);
+    }
+    if is_feature_available!("avx2") {
+        return Some(Backend::Avx2);
+    }
+    if is_feature_available!("gfni", "ssse3") {
+        return Some(Backend::Gfni128);
+    }
+    if is_feature_available!("ssse3") {
+        return Some(Backend::Ssse3);
     }
 
-    false
+    None
 }
 
-pub(super) fn gf256_inplace_add_vectors(vec_dst: &mut [u8], vec_src: &[u8]) -> bool {
-    if is_x86_feature_detected!("avx512f") {
-        unsafe { avx512::add_vec_into(vec_dst, vec_src) };
-        return true;
-    }
+// Each backend implies features, its kernels are compiled for, see `detect_backend`, which makes calling them safe.
 
-    if is_x86_feature_detected!("avx2") {
-        unsafe { avx2::add_vec_into(vec_dst, vec_src) };
-        return true;
+pub(super) fn gf256_inplace_mul_vec_by_scalar(vec: &mut [u8], scalar: u8) -> bool {
+    match active_backend() {
+        Some(Backend::Gfni512) => unsafe { gfni::m512i::mul_vec_by_scalar(vec, scalar) },
+        Some(Backend::Gfni256) => unsafe { gfni::m256i::mul_vec_by_scalar(vec, scalar) },
+        Some(Backend::Avx512) => unsafe { avx512::mul_vec_by_scalar(vec, scalar) },
+        Some(Backend::Avx2) => unsafe { avx2::mul_vec_by_scalar(vec, scalar) },
+        Some(Backend::Gfni128) => unsafe { gfni::m128i::mul_vec_by_scalar(vec, scalar) },
+        Some(Backend::Ssse3) => unsafe { ssse3::mul_vec_by_scalar(vec, scalar) },
+        _ => return false,
     }
 
-    if is_x86_feature_detected!("ssse3") {
-        unsafe { ssse3::add_vec_into(vec_dst, vec_src) };
-        return true;
+    true
+}
+
+pub(super) fn gf256_inplace_add_vectors(vec_dst: &mut [u8], vec_src: &[u8]) -> bool {
+    match active_backend() {
+        Some(Backend::Gfni512 | Backend::Avx512) => unsafe { avx512::add_vec_into(vec_dst, vec_src) },
+        Some(Backend::Gfni256 | Backend::Avx2) => unsafe { avx2::add_vec_into(vec_dst, vec_src) },
+        Some(Backend::Gfni128 | Backend::Ssse3) => unsafe { ssse3::add_vec_into(vec_dst, vec_src) },
+        _ => return false,
     }
 
-    false
+    true
 }
 
 pub(super) fn gf256_mul_vec_by_scalar_then_add_into_vec(add_into_vec: &mut [u8], mul_vec: &[u8], scalar: u8) -> bool {
-    if is_x86_feature_detected!("gfni") && is_x86_feature_detected!("avx512f") {
-        unsafe { gfni::m512i::mul_vec_by_scalar_then_add_into(add_into_vec, mul_vec, scalar) };
-        return true;
+    match active_backend() {
+        Some(Backend::Gfni512) => unsafe { gfni::m512i::mul_vec_by_scalar_then_add_into(add_into_vec, mul_vec, scalar) },
+        Some(Backend::Gfni256) => unsafe { gfni::m256i::mul_vec_by_scalar_then_add_into(add_into_vec, mul_vec, scalar) },
+        Some(Backend::Avx512) => unsafe { avx512::mul_vec_by_scalar_then_add_into(add_into_vec, mul_vec, scalar) },
+        Some(Backend::Avx2) => unsafe { avx2::mul_vec_by_scalar_then_add_into(add_into_vec, mul_vec, scalar) },
+        Some(Backend::Gfni128) => unsafe { gfni::m128i::mul_vec_by_scalar_then_add_into(add_into_vec, mul_vec, scalar) },
+        Some(Backend::Ssse3) => unsafe { ssse3::mul_vec_by_scalar_then_add_into(add_into_vec, mul_vec, scalar) },
+        _ => return false,
     }
 
-    if is_x86_feature_detected!("gfni") && is_x86_feature_detected!("avx512vl") {
-        unsafe { gfni::m256i::mul_vec_by_scalar_then_add_into(add_into_vec, mul_vec, scalar) };
-        return true;
-    }
+    true
+}
 
-    if is_x86_feature_detected!("gfni") && is_x86_feature_detected!("avx512vl") {
-        unsafe { gfni::m128i::mul_vec_by_scalar_then_add_into(add_into_vec, mul_vec, scalar) };
-        return true;
+pub(super) fn gf256_mul_vecs_by_scalars_then_add_into_vec<const N: usize>(add_into_vec: &mut [u8], mul_vecs: &[&[u8]; N], scalars: &[u8; N]) -> bool {
+    match active_backend() {
+        Some(Backend::Gfni512) => unsafe { gfni::m512i::mul_vecs_by_scalars_then_add_into(add_into_vec, mul_vecs, scalars) },
+        Some(Backend::Gfni256) => unsafe { gfni::m256i::mul_vecs_by_scalars_then_add_into(add_into_vec, mul_vecs, scalars) },
+        Some(Backend::Avx512) => unsafe { avx512::mul_vecs_by_scalars_then_add_into(add_into_vec, mul_vecs, scalars) },
+        Some(Backend::Avx2) => unsafe { avx2::mul_vecs_by_scalars_then_add_into(add_into_vec, mul_vecs, scalars) },
+        Some(Backend::Gfni128) => unsafe { gfni::m128i::mul_vecs_by_scalars_then_add_into(add_into_vec, mul_vecs, scalars) },
+        Some(Backend::Ssse3) => unsafe { ssse3::mul_vecs_by_scalars_then_add_into(add_into_vec, mul_vecs, scalars) },
+        _ => return false,
     }
 
-    if is_x86_feature_detected!("avx512bw") {
-        unsafe { avx512::mul_vec_by_scalar_then_add_into(add_into_vec, mul_vec, scalar) };
-        return true;
-    }
+    true
+}
 
-    if is_x86_feature_detected!("avx2") {
-        unsafe { avx2::mul_vec_by_scalar_then_add_into(add_into_vec, mul_vec, scalar) };
-        return true;
+#[cfg(test)]
+mod tests {
+    use super::{active_backend, avx2, avx512, detect_backend, gfni, ssse3};
+    use crate::common::simd::scalar;
+    use rand::Rng;
+
+    type AddVecInto = unsafe fn(&mut [u8], &[u8]);
+    type MulVecsByScalarsThenAddInto = unsafe fn(&mut [u8], &[&[u8]; 3], &[u8; 3]);
+
+    /// Checks kernels of a backend against the scalar reference, on vectors hitting both the SIMD loop and the scalar remainder
+    /// loop. Backends, which don't have kernels for adding vectors or fused multiply-accumulate, pass `None` for them.
+    fn assert_kernels_match_scalar_reference(
+        mul_vec_by_scalar: unsafe fn(&mut [u8], u8),
+        mul_vec_by_scalar_then_add_into: unsafe fn(&mut [u8], &[u8], u8),
+        add_vec_into: Option<AddVecInto>,
+        mul_vecs_by_scalars_then_add_into: Option<MulVecsByScalarsThenAddInto>,
+    ) {
+        let mut rng = rand::rng();
+
+        for len in [0, 1, 15, 16, 17, 63, 64, 65, 200, 1000] {
+            let scalars = [rng.random::<u8>(), rng.random(), rng.random()];
+            let srcs = [(); 3].map(|_| (0..len).map(|_| rng.random()).collect::<Vec<u8>>());
+            let dst = (0..len).map(|_| rng.random()).collect::<Vec<u8>>();
+
+            let (mut expected, mut computed) = (srcs[0].clone(), srcs[0].clone());
+            scalar::mul_vec_by_scalar(&mut expected, scalars[0]);
+            unsafe { mul_vec_by_scalar(&mut computed, scalars[0]) };
+            assert_eq!(expected, computed);
+
+            let (mut expected, mut computed) = (dst.clone(), dst.clone());
+            scalar::mul_vec_by_scalar_then_add_into(&mut expected, &srcs[0], scalars[0]);
+            unsafe { mul_vec_by_scalar_then_add_into(&mut computed, &srcs[0], scalars[0]) };
+            assert_eq!(expected, computed);
+
+            if let Some(add_vec_into) = add_vec_into {
+                let (mut expected, mut computed) = (dst.clone(), dst.clone());
+                scalar::add_vec_into(&mut expected, &srcs[0]);
+                unsafe { add_vec_into(&mut computed, &srcs[0]) };
+                assert_eq!(expected, computed);
+            }
+
+            if let Some(mul_vecs_by_scalars_then_add_into) = mul_vecs_by_scalars_then_add_into {
+                let mul_vecs = [srcs[0].as_slice(), srcs[1].as_slice(), srcs[2].as_slice()];
+
+                let (mut expected, mut computed) = (dst.clone(), dst.clone());
+                scalar::mul_vecs_by_scalars_then_add_into(&mut expected, &mul_vecs, &scalars);
+                unsafe { mul_vecs_by_scalars_then_add_into(&mut computed, &mul_vecs, &scalars) };
+                assert_eq!(expected, computed);
+            }
+        }
     }
 
-    if is_x86_feature_detected!("ssse3") {
-        unsafe { ssse3::mul_vec_by_scalar_then_add_into(add_into_vec, mul_vec, scalar) };
-        return true;
+    #[test]
+    fn test_x86_kernels_match_scalar_reference() {
+        if is_x86_feature_detected!("ssse3") {
+            assert_kernels_match_scalar_reference(
+                ssse3::mul_vec_by_scalar,
+                ssse3::mul_vec_by_scalar_then_add_into,
+                Some(ssse3::add_vec_into),
+                Some(ssse3::mul_vecs_by_scalars_then_add_into),
+            );
+        }
+
+        if is_x86_feature_detected!("avx2") {
+            assert_kernels_match_scalar_reference(
+                avx2::mul_vec_by_scalar,
+                avx2::mul_vec_by_scalar_then_add_into,
+                Some(avx2::add_vec_into),
+                Some(avx2::mul_vecs_by_scalars_then_add_into),
+            );
+        }
+
+        if is_x86_feature_detected!("avx512bw") {
+            assert_kernels_match_scalar_reference(
+                avx512::mul_vec_by_scalar,
+                avx512::mul_vec_by_scalar_then_add_into,
+                Some(avx512::add_vec_into),
+                Some(avx512::mul_vecs_by_scalars_then_add_into),
+            );
+        }
+
+        if is_x86_feature_detected!("gfni") {
+            assert_kernels_match_scalar_reference(
+                gfni::m128i::mul_vec_by_scalar,
+                gfni::m128i::mul_vec_by_scalar_then_add_into,
+                None,
+                Some(gfni::m128i::mul_vecs_by_scalars_then_add_into),
+            );
+        }
+
+        if is_x86_feature_detected!("gfni") && is_x86_feature_detected!("avx2") {
+            assert_kernels_match_scalar_reference(
+                gfni::m256i::mul_vec_by_scalar,
+                gfni::m256i::mul_vec_by_scalar_then_add_into,
+                None,
+                Some(gfni::m256i::mul_vecs_by_scalars_then_add_into),
+            );
+        }
+
+        if is_x86_feature_detected!("gfni") && is_x86_feature_detected!("avx512f") {
+            assert_kernels_match_scalar_reference(
+                gfni::m512i::mul_vec_by_scalar,
+                gfni::m512i::mul_vec_by_scalar_then_add_into,
+                None,
+                Some(gfni::m512i::mul_vecs_by_scalars_then_add_into),
+            );
+        }
     }
 
-    false
+    #[test]
+    fn test_active_backend_is_detected_once() {
+        let backend = active_backend();
+
+        assert_eq!(backend, detect_backend());
+        assert!((0..100).all(|_| active_backend() == backend));
+    }
 }
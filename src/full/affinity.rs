@@ -0,0 +1,163 @@
+use crate::RLNCError;
+use std::{io, sync::Arc};
+
+/// Restricts the calling thread to run only on given cores, say to keep the thread driving a `Decoder` off cores serving packet I/O.
+///
+/// # Returns
+/// * Returns `Ok(())` on success.
+/// * Returns `Err(io::Error)` if the kernel refuses, say because some core doesn't exist, or on platforms other than Linux.
+pub fn pin_current_thread(cores: &[usize]) -> io::Result<()> {
+    #[cfg(target_os = "linux")]
+    {
+        let mut cpu_set = unsafe { std::mem::zeroed::<libc::cpu_set_t>() };
+        for &core in cores {
+            if core >= libc::CPU_SETSIZE as usize {
+                return Err(io::Error::from(io::ErrorKind::InvalidInput));
+            }
+            unsafe { libc::CPU_SET(core, &mut cpu_set) };
+        }
+
+        let result = unsafe { libc::sched_setaffinity(0, size_of::<libc::cpu_set_t>(), &cpu_set) };
+        if result != 0 { Err(io::Error::last_os_error()) } else { Ok(()) }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = cores;
+        Err(io::Error::from(io::ErrorKind::Unsupported))
+    }
+}
+
+/// How worker threads of a `WorkerPoolConfig` are placed on cores.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum CorePlacement {
+    /// Workers float over all cores, as scheduled by the OS.
+    Any,
+    /// Each worker may run on any of these cores.
+    Subset(Vec<usize>),
+    /// Worker `i` runs only on core `i % cores.len()`.
+    PerWorker(Vec<usize>),
+}
+
+/// Configuration of the `rayon` thread pool, running data-parallel coding of an `Encoder` or a `Recoder`, controlling how many
+/// workers it has and which cores they run on, so that RLNC processing can coexist with, say, DPDK-style packet I/O threads,
+/// busy polling their own dedicated cores, without scheduler interference.
+///
+/// Build the pool once and share it, using `Encoder::with_thread_pool` and `Recoder::with_thread_pool`. Decoding runs on the
+/// calling thread, which can be confined using `pin_current_thread`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WorkerPoolConfig {
+    worker_count: Option<usize>,
+    placement: CorePlacement,
+}
+
+impl Default for WorkerPoolConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WorkerPoolConfig {
+    /// Creates configuration of a pool with as many workers as `rayon` would pick by default, floating over all cores.
+    pub fn new() -> Self {
+        WorkerPoolConfig {
+            worker_count: None,
+            placement: CorePlacement::Any,
+        }
+    }
+
+    /// Sets number of worker threads. Unless set, it's the number of configured cores, or `rayon` default, if no core is configured.
+    pub fn with_worker_count(mut self, worker_count: usize) -> Self {
+        self.worker_count = Some(worker_count);
+        self
+    }
+
+    /// Confines all workers to given subset of cores, leaving it to the OS, which of them each worker runs on.
+    pub fn with_core_subset(mut self, cores: Vec<usize>) -> Self {
+        self.placement = CorePlacement::Subset(cores);
+        self
+    }
+
+    /// Pins each worker to a single core, worker `i` running on `cores[i % cores.len()]`.
+    pub fn with_pinned_cores(mut self, cores: Vec<usize>) -> Self {
+        self.placement = CorePlacement::PerWorker(cores);
+        self
+    }
+
+    /// Builds the thread pool and confines its workers to configured cores, before returning.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Arc<rayon::ThreadPool>)` on success, ready to be passed to `Encoder::with_thread_pool`.
+    /// * Returns `Err(RLNCError::InvalidWorkerPool)` if worker count is zero, configured core set is empty, the pool can't be
+    ///   built, or any worker can't be confined to its cores.
+    pub fn build(&self) -> Result<Arc<rayon::ThreadPool>, RLNCError> {
+        let worker_count = match (&self.placement, self.worker_count) {
+            (_, Some(0)) => return Err(RLNCError::InvalidWorkerPool),
+            (CorePlacement::Subset(cores) | CorePlacement::PerWorker(cores), _) if cores.is_empty() => return Err(RLNCError::InvalidWorkerPool),
+            (_, Some(worker_count)) => worker_count,
+            (CorePlacement::Subset(cores) | CorePlacement::PerWorker(cores), None) => cores.len(),
+            (CorePlacement::Any, None) => 0,
+        };
+
+        let thread_pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(worker_count)
+            .thread_name(|index| format!("rlnc-worker-{index}"))
+            .build()
+            .map_err(|_| RLNCError::InvalidWorkerPool)?;
+
+        // Unlike a start handler, broadcasting runs on every worker before the pool is handed out, surfacing pinning failures.
+        let pinned = thread_pool.broadcast(|context| match &self.placement {
+            CorePlacement::Any => Ok(()),
+            CorePlacement::Subset(cores) => pin_current_thread(cores),
+            CorePlacement::PerWorker(cores) => pin_current_thread(&[cores[context.index() % cores.len()]]),
+        });
+        if pinned.iter().any(Result::is_err) {
+            return Err(RLNCError::InvalidWorkerPool);
+        }
+
+        Ok(Arc::new(thread_pool))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WorkerPoolConfig;
+    use crate::{RLNCError, full::Encoder};
+    use rand::Rng;
+
+    #[test]
+    fn test_worker_pool_config() {
+        assert_eq!(WorkerPoolConfig::new().with_worker_count(0).build().err(), Some(RLNCError::InvalidWorkerPool));
+        assert_eq!(
+            WorkerPoolConfig::new().with_core_subset(vec![]).build().err(),
+            Some(RLNCError::InvalidWorkerPool)
+        );
+        assert_eq!(
+            WorkerPoolConfig::new().with_pinned_cores(vec![usize::MAX]).build().err(),
+            Some(RLNCError::InvalidWorkerPool)
+        );
+
+        let thread_pool = WorkerPoolConfig::new()
+            .with_worker_count(3)
+            .with_pinned_cores(vec![0])
+            .build()
+            .expect("Pinning workers to the first core must not fail");
+        assert_eq!(thread_pool.current_num_threads(), 3);
+
+        #[cfg(target_os = "linux")]
+        assert!(thread_pool.broadcast(|_| unsafe { libc::sched_getcpu() }).iter().all(|&core| core == 0));
+
+        let mut rng = rand::rng();
+        let data = (0..10_000).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data, 32).expect("Failed to create Encoder for worker pool test");
+        let pooled_encoder = encoder.clone().with_thread_pool(thread_pool);
+
+        let coding_vector = (0..encoder.get_piece_count()).map(|_| rng.random()).collect::<Vec<u8>>();
+        let mut coded_data = vec![0u8; encoder.get_piece_byte_len()];
+        let mut pooled_coded_data = vec![0u8; encoder.get_piece_byte_len()];
+
+        encoder.code_with_coding_vector(&coding_vector, &mut coded_data).unwrap();
+        pooled_encoder.code_with_coding_vector(&coding_vector, &mut pooled_coded_data).unwrap();
+        assert_eq!(coded_data, pooled_coded_data);
+    }
+}
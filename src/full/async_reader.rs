@@ -0,0 +1,162 @@
+use super::decoder::Decoder;
+use crate::RLNCError;
+use futures::{AsyncRead, Stream};
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Notification yielded by `AsyncPieceReader`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecodeEvent {
+    /// One more piece got read off the reader and fed to the decoder.
+    Progress {
+        /// Whether the piece was linearly independent of the already received ones.
+        is_useful: bool,
+        /// Number of useful pieces received so far.
+        useful_piece_count: usize,
+        /// Number of pieces remaining to be received for successful decoding.
+        remaining_piece_count: usize,
+    },
+    /// Decoding is complete, carrying the decoded data. It's always the last event.
+    Decoded(Vec<u8>),
+}
+
+/// Reads coded pieces off any `futures::AsyncRead`, say a TCP or TLS stream or a pipe, and drives a decoder with them.
+///
+/// Pieces are expected back to back, each framed as a full coded piece of exactly `Decoder::get_full_coded_piece_byte_len`
+/// bytes, as produced by `Encoder::code` or `Recoder::recode`. It's a `Stream` of `DecodeEvent`s: one progress notification
+/// per piece, ending with decoded data. If the reader reaches its end before decoding is complete, it yields
+/// `Err(RLNCError::NotAllPiecesReceivedYet)` and ends.
+pub struct AsyncPieceReader<R> {
+    reader: R,
+    decoder: Option<Decoder>,
+    frame: Vec<u8>,
+    filled: usize,
+}
+
+impl<R: AsyncRead + Unpin> AsyncPieceReader<R> {
+    /// Wraps a reader, feeding read pieces to given decoder, which may already hold some pieces.
+    pub fn new(reader: R, decoder: Decoder) -> AsyncPieceReader<R> {
+        AsyncPieceReader {
+            reader,
+            frame: vec![0u8; decoder.get_full_coded_piece_byte_len()],
+            decoder: Some(decoder),
+            filled: 0,
+        }
+    }
+
+    /// Consumes the stream, returning the underlying reader, along with the decoder, unless decoding is complete.
+    pub fn into_parts(self) -> (R, Option<Decoder>) {
+        (self.reader, self.decoder)
+    }
+
+    /// Feeds a fully read frame to the decoder, turning the outcome into an event.
+    fn on_frame(&mut self) -> Option<Result<DecodeEvent, RLNCError>> {
+        let decoder = self.decoder.as_mut()?;
+
+        let is_useful = match decoder.decode(&self.frame) {
+            Ok(()) => true,
+            Err(RLNCError::PieceNotUseful) => false,
+            Err(e) => return Some(Err(e)),
+        };
+
+        if decoder.is_already_decoded() {
+            let decoder = self.decoder.take()?;
+            return Some(decoder.get_decoded_data().map(DecodeEvent::Decoded));
+        }
+
+        Some(Ok(DecodeEvent::Progress {
+            is_useful,
+            useful_piece_count: decoder.get_useful_piece_count(),
+            remaining_piece_count: decoder.get_remaining_piece_count(),
+        }))
+    }
+}
+
+impl<R: AsyncRead + Unpin> Stream for AsyncPieceReader<R> {
+    type Item = Result<DecodeEvent, RLNCError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+
+        if this.decoder.is_none() {
+            return Poll::Ready(None);
+        }
+
+        while this.filled < this.frame.len() {
+            match Pin::new(&mut this.reader).poll_read(cx, &mut this.frame[this.filled..]) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Ok(0)) => {
+                    this.decoder = None;
+                    return Poll::Ready(Some(Err(RLNCError::NotAllPiecesReceivedYet)));
+                }
+                Poll::Ready(Ok(n)) => this.filled += n,
+                Poll::Ready(Err(e)) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Poll::Ready(Err(_)) => {
+                    this.decoder = None;
+                    return Poll::Ready(Some(Err(RLNCError::ReadFailure)));
+                }
+            }
+        }
+
+        this.filled = 0;
+        Poll::Ready(this.on_frame())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AsyncPieceReader, DecodeEvent};
+    use crate::{
+        RLNCError,
+        full::{Decoder, Encoder},
+    };
+    use futures::{StreamExt, executor::block_on, io::Cursor};
+    use rand::Rng;
+
+    #[test]
+    fn test_async_piece_reader() {
+        let mut rng = rand::rng();
+
+        let data = (0..4096).map(|_| rng.random()).collect::<Vec<u8>>();
+        let piece_count = 16;
+        let encoder = Encoder::new(data.clone(), piece_count).expect("Failed to create Encoder");
+
+        // Enough pieces, followed by a few more, which must never be read.
+        let mut pieces = (0..piece_count * 2).flat_map(|_| encoder.code(&mut rng)).collect::<Vec<u8>>();
+        let num_trailing_bytes = pieces.len();
+        pieces.extend(std::iter::repeat_n(0xffu8, 100));
+
+        let reader = AsyncPieceReader::new(Cursor::new(pieces), Decoder::new_with(encoder.get_params()));
+        let events = block_on(reader.collect::<Vec<_>>());
+
+        let (last_event, progress_events) = events.split_last().expect("There must be events");
+        assert_eq!(last_event, &Ok(DecodeEvent::Decoded(data)));
+        assert!(progress_events.len() >= piece_count - 1);
+        assert!(progress_events.len() * encoder.get_full_coded_piece_byte_len() < num_trailing_bytes);
+
+        let num_useful_pieces = progress_events
+            .iter()
+            .filter(|event| matches!(event, Ok(DecodeEvent::Progress { is_useful: true, .. })))
+            .count();
+        assert_eq!(num_useful_pieces, piece_count - 1);
+    }
+
+    #[test]
+    fn test_async_piece_reader_truncated_input() {
+        let mut rng = rand::rng();
+
+        let encoder = Encoder::new(vec![1u8; 4096], 16).expect("Failed to create Encoder");
+
+        // Only a few pieces, with the last one cut in half.
+        let mut pieces = (0..4).flat_map(|_| encoder.code(&mut rng)).collect::<Vec<u8>>();
+        pieces.truncate(pieces.len() - encoder.get_full_coded_piece_byte_len() / 2);
+
+        let reader = AsyncPieceReader::new(Cursor::new(pieces), Decoder::new_with(encoder.get_params()));
+        let events = block_on(reader.collect::<Vec<_>>());
+
+        assert_eq!(events.len(), 4);
+        assert_eq!(events.last(), Some(&Err(RLNCError::NotAllPiecesReceivedYet)));
+    }
+}
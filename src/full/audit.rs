@@ -0,0 +1,192 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+/// Reason why a received piece was rejected by the decoder.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RejectionReason {
+    /// Coding vector is linearly dependent on the already received useful pieces.
+    Dependent,
+    /// Coding vector is exactly same as the one carried by some recently received piece. Only as many most recently seen, distinct
+    /// coding vectors are remembered, as the audit log's capacity, so an older duplicate gets reported as `Dependent`.
+    Duplicate,
+    /// Piece length doesn't match the expected full coded piece length.
+    Malformed,
+    /// Piece is past its expiry timestamp.
+    Expired,
+}
+
+/// An audit log entry, describing a single rejected piece.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RejectedPiece {
+    /// Zero-based position of this piece in the sequence of all pieces, handed over to the decoder after enabling audit log.
+    pub arrival_index: usize,
+    /// Why this piece got rejected.
+    pub reason: RejectionReason,
+    /// 64-bit FNV-1a digest of the coding vector. For malformed pieces, it is computed over as many leading bytes
+    /// of the piece, as the coding vector is expected to be long.
+    pub coding_vector_digest: u64,
+}
+
+/// Bounded log of pieces rejected by the decoder, helpful for debugging misbehaving recoders in multi-hop topologies.
+///
+/// Once the log is full, the oldest entry is dropped to make space for the newest one.
+#[derive(Clone, Debug)]
+pub struct AuditLog {
+    capacity: usize,
+    entries: VecDeque<RejectedPiece>,
+    num_dropped_entries: usize,
+    num_arrived_pieces: usize,
+    /// Most recently seen distinct coding vectors, keyed by their digest, along with arrival index of their last sighting.
+    seen_coding_vectors: HashMap<u64, (usize, Box<[u8]>)>,
+    /// Digests of remembered coding vectors, keyed by arrival index of their last sighting, least recently seen first.
+    last_sightings: BTreeMap<usize, u64>,
+}
+
+impl AuditLog {
+    /// Creates a new audit log, which holds at max `capacity` many most recent entries.
+    pub(crate) fn new(capacity: usize) -> Self {
+        AuditLog {
+            capacity,
+            entries: VecDeque::with_capacity(capacity),
+            num_dropped_entries: 0,
+            num_arrived_pieces: 0,
+            seen_coding_vectors: HashMap::with_capacity(capacity),
+            last_sightings: BTreeMap::new(),
+        }
+    }
+
+    /// Maximum number of entries this audit log can hold.
+    pub fn get_capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of entries dropped so far, because the audit log was full.
+    pub fn get_dropped_entry_count(&self) -> usize {
+        self.num_dropped_entries
+    }
+
+    /// Returns an iterator over logged rejected pieces, oldest first.
+    pub fn entries(&self) -> impl Iterator<Item = &RejectedPiece> {
+        self.entries.iter()
+    }
+
+    /// Records arrival of a well-formed piece, carrying `coding_vector`, logging it if it was not useful.
+    pub(crate) fn record(&mut self, coding_vector: &[u8], is_useful: bool) {
+        let digest = fnv1a_digest(coding_vector);
+        let is_duplicate = self.remember(digest, coding_vector);
+
+        if !is_useful {
+            let reason = if is_duplicate {
+                RejectionReason::Duplicate
+            } else {
+                RejectionReason::Dependent
+            };
+
+            self.push(reason, digest);
+        }
+
+        self.num_arrived_pieces += 1;
+    }
+
+    /// Records arrival of a malformed piece, whose coding vector is expected to be `coding_vector_len` bytes.
+    pub(crate) fn record_malformed(&mut self, piece: &[u8], coding_vector_len: usize) {
+        let digest = fnv1a_digest(&piece[..piece.len().min(coding_vector_len)]);
+
+        self.push(RejectionReason::Malformed, digest);
+        self.num_arrived_pieces += 1;
+    }
+
+    /// Records arrival of a well-formed, but expired piece, carrying `coding_vector`.
+    pub(crate) fn record_expired(&mut self, coding_vector: &[u8]) {
+        self.push(RejectionReason::Expired, fnv1a_digest(coding_vector));
+        self.num_arrived_pieces += 1;
+    }
+
+    /// Remembers sighting of `coding_vector`, forgetting the least recently seen one, if already as many as the capacity are
+    /// remembered. Returns `true` if exactly the same coding vector was remembered already, not just a colliding digest.
+    fn remember(&mut self, digest: u64, coding_vector: &[u8]) -> bool {
+        if self.capacity == 0 {
+            return false;
+        }
+
+        let arrival_index = self.num_arrived_pieces;
+
+        let is_duplicate = match self.seen_coding_vectors.get_mut(&digest) {
+            Some((last_sighting, seen_coding_vector)) => {
+                self.last_sightings.remove(last_sighting);
+                *last_sighting = arrival_index;
+
+                let is_duplicate = **seen_coding_vector == *coding_vector;
+                if !is_duplicate {
+                    *seen_coding_vector = coding_vector.into();
+                }
+                is_duplicate
+            }
+            None => {
+                if self.seen_coding_vectors.len() == self.capacity
+                    && let Some((_, oldest_digest)) = self.last_sightings.pop_first()
+                {
+                    self.seen_coding_vectors.remove(&oldest_digest);
+                }
+
+                self.seen_coding_vectors.insert(digest, (arrival_index, coding_vector.into()));
+                false
+            }
+        };
+
+        self.last_sightings.insert(arrival_index, digest);
+        is_duplicate
+    }
+
+    fn push(&mut self, reason: RejectionReason, coding_vector_digest: u64) {
+        if self.capacity == 0 {
+            self.num_dropped_entries += 1;
+            return;
+        }
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+            self.num_dropped_entries += 1;
+        }
+
+        self.entries.push_back(RejectedPiece {
+            arrival_index: self.num_arrived_pieces,
+            reason,
+            coding_vector_digest,
+        });
+    }
+}
+
+/// Computes 64-bit FNV-1a digest of a byte slice. It's not cryptographically secure, but stable across platforms and releases.
+pub(crate) fn fnv1a_digest(bytes: &[u8]) -> u64 {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x00000100000001b3;
+
+    bytes
+        .iter()
+        .fold(FNV_OFFSET_BASIS, |digest, &byte| (digest ^ byte as u64).wrapping_mul(FNV_PRIME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AuditLog, RejectionReason};
+
+    #[test]
+    fn test_audit_log_remembers_recent_coding_vectors() {
+        let mut audit_log = AuditLog::new(2);
+
+        // Same digest, but different coding vectors, are not duplicates.
+        assert!(!audit_log.remember(7, &[1, 2]));
+        assert!(!audit_log.remember(7, &[2, 1]));
+        assert!(audit_log.remember(7, &[2, 1]));
+
+        // Least recently seen coding vector is forgotten, once capacity is reached.
+        let mut audit_log = AuditLog::new(2);
+        [[3, 3], [4, 4], [3, 3], [5, 5], [3, 3], [4, 4]]
+            .iter()
+            .for_each(|coding_vector| audit_log.record(coding_vector, false));
+
+        let reasons = audit_log.entries().map(|entry| entry.reason).collect::<Vec<_>>();
+        assert_eq!(reasons, vec![RejectionReason::Duplicate, RejectionReason::Dependent]);
+        assert_eq!(audit_log.seen_coding_vectors.len(), 2);
+        assert_eq!(audit_log.last_sightings.len(), 2);
+    }
+}
@@ -0,0 +1,164 @@
+use super::params::Params;
+use crate::{RLNCError, common::simd::gf256_mul_vec_by_scalar_then_add_into_vec};
+use rand::Rng;
+
+/// Recoder with bounded memory, for relays with tight RAM budgets, which keeps a pool of at most `pool_size` rows, each a full
+/// coded piece, however many pieces pass through it.
+///
+/// First `pool_size` received pieces fill the pool verbatim. Each later one is folded into the pool, adding it, scaled by a random
+/// coefficient, into each pool row, instead of being stored. Pool rows stay valid coded pieces, as coding vector and coded data are
+/// updated together, so recoded pieces are random linear combinations of pool rows, same as `Recoder` does with received pieces.
+/// A fold can lose one dimension of the pool, with probability 1/256, which the next fold, bringing in a new piece, regains.
+#[derive(Clone, Debug)]
+pub struct BoundedRecoder {
+    params: Params,
+    pool_size: usize,
+    /// Pool rows, each of `params.get_full_coded_piece_byte_len()` bytes, concatenated.
+    pool: Vec<u8>,
+    received_piece_count: usize,
+}
+
+impl BoundedRecoder {
+    /// Creates a bounded recoder, with an empty pool, for pieces of given RLNC configuration.
+    ///
+    /// # Arguments
+    /// * `params`: RLNC configuration, shared with encoder and decoders.
+    /// * `pool_size`: Maximum number of pool rows kept, so that the pool never takes more than `pool_size` full coded pieces of memory.
+    ///
+    /// # Returns
+    /// * Returns `Ok(BoundedRecoder)` on success.
+    /// * Returns `Err(RLNCError::InvalidPoolSize)` if `pool_size` is zero.
+    pub fn new(params: Params, pool_size: usize) -> Result<BoundedRecoder, RLNCError> {
+        if pool_size == 0 {
+            return Err(RLNCError::InvalidPoolSize);
+        }
+
+        Ok(BoundedRecoder {
+            params,
+            pool_size,
+            pool: Vec::new(),
+            received_piece_count: 0,
+        })
+    }
+
+    /// RLNC configuration of this recoder, to be shared with decoders.
+    pub fn get_params(&self) -> Params {
+        self.params
+    }
+
+    /// Maximum number of pool rows kept.
+    pub fn get_pool_size(&self) -> usize {
+        self.pool_size
+    }
+
+    /// Number of pool rows filled so far, which never exceeds `self.get_pool_size()`.
+    pub fn get_pool_row_count(&self) -> usize {
+        self.pool.len() / self.params.get_full_coded_piece_byte_len()
+    }
+
+    /// Total number of pieces received so far, either stored in or folded into the pool.
+    pub fn get_received_piece_count(&self) -> usize {
+        self.received_piece_count
+    }
+
+    /// Stores a received full coded piece in the pool, if it's not yet full, otherwise folds it into the pool.
+    ///
+    /// # Arguments
+    /// * `rng`: Used to sample the coefficient, the piece gets scaled by, for each pool row, while folding it.
+    /// * `full_coded_piece`: Coding vector followed by coded data, of `self.get_params().get_full_coded_piece_byte_len()` bytes.
+    ///
+    /// # Returns
+    /// * Returns `Ok(())` when successful.
+    /// * Returns `Err(RLNCError::InvalidPieceLength)` if length of `full_coded_piece` is incorrect.
+    pub fn add_coded_piece<R: Rng + ?Sized>(&mut self, rng: &mut R, full_coded_piece: &[u8]) -> Result<(), RLNCError> {
+        if full_coded_piece.len() != self.params.get_full_coded_piece_byte_len() {
+            return Err(RLNCError::InvalidPieceLength);
+        }
+
+        if self.get_pool_row_count() < self.pool_size {
+            self.pool.extend_from_slice(full_coded_piece);
+        } else {
+            self.pool
+                .chunks_exact_mut(full_coded_piece.len())
+                .for_each(|row| gf256_mul_vec_by_scalar_then_add_into_vec(row, full_coded_piece, rng.random()));
+        }
+
+        self.received_piece_count += 1;
+        Ok(())
+    }
+
+    /// Produces a new coded piece, as a random linear combination of pool rows, writing it into the provided buffer.
+    ///
+    /// # Arguments
+    /// * `rng`: Used to sample the coefficient of each pool row.
+    /// * `full_recoded_piece`: A mutable slice of bytes, of length `self.get_params().get_full_coded_piece_byte_len()`.
+    ///
+    /// # Returns
+    /// * Returns `Ok(())` when successful.
+    /// * Returns `Err(RLNCError::NotEnoughPiecesToRecode)` if no piece has been received yet.
+    /// * Returns `Err(RLNCError::InvalidOutputBuffer)` if the length of `full_recoded_piece` is incorrect.
+    pub fn recode_with_buf<R: Rng + ?Sized>(&self, rng: &mut R, full_recoded_piece: &mut [u8]) -> Result<(), RLNCError> {
+        if self.pool.is_empty() {
+            return Err(RLNCError::NotEnoughPiecesToRecode);
+        }
+        if full_recoded_piece.len() != self.params.get_full_coded_piece_byte_len() {
+            return Err(RLNCError::InvalidOutputBuffer);
+        }
+
+        full_recoded_piece.fill(0);
+        self.pool
+            .chunks_exact(full_recoded_piece.len())
+            .for_each(|row| gf256_mul_vec_by_scalar_then_add_into_vec(full_recoded_piece, row, rng.random()));
+
+        Ok(())
+    }
+
+    /// Same as `Self::recode_with_buf`, but allocates the recoded piece.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Vec<u8>)` holding coding vector followed by recoded data.
+    /// * Returns `Err(RLNCError::NotEnoughPiecesToRecode)` if no piece has been received yet.
+    pub fn recode<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Vec<u8>, RLNCError> {
+        let mut full_recoded_piece = vec![0u8; self.params.get_full_coded_piece_byte_len()];
+        self.recode_with_buf(rng, &mut full_recoded_piece)?;
+
+        Ok(full_recoded_piece)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BoundedRecoder;
+    use crate::{
+        RLNCError,
+        full::{Decoder, Encoder},
+    };
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    #[test]
+    fn test_bounded_recoder_keeps_pool_size() {
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let data = (0..1000).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 8).expect("Failed to create Encoder for bounded recoder test");
+        let params = encoder.get_params();
+
+        assert_eq!(BoundedRecoder::new(params, 0).unwrap_err(), RLNCError::InvalidPoolSize);
+
+        let mut recoder = BoundedRecoder::new(params, 8).expect("Failed to create BoundedRecoder");
+        assert_eq!(recoder.recode(&mut rng), Err(RLNCError::NotEnoughPiecesToRecode));
+        assert_eq!(recoder.add_coded_piece(&mut rng, &[0; 3]), Err(RLNCError::InvalidPieceLength));
+
+        for _ in 0..100 {
+            let piece = encoder.code(&mut rng);
+            recoder.add_coded_piece(&mut rng, &piece).unwrap();
+        }
+        assert_eq!((recoder.get_received_piece_count(), recoder.get_pool_row_count()), (100, 8));
+
+        let mut decoder = Decoder::new_with(params);
+        while !decoder.is_already_decoded() {
+            let _ = decoder.decode(&recoder.recode(&mut rng).unwrap());
+        }
+        assert_eq!(decoder.get_decoded_data(), Ok(data));
+    }
+}
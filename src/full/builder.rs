@@ -0,0 +1,128 @@
+use super::encoder::Encoder;
+use crate::RLNCError;
+use std::io;
+
+/// Incrementally collects original data of unknown length, arriving in chunks, say from a socket or a pipe,
+/// and builds an `Encoder` out of it, once all data has arrived.
+///
+/// Chunks are appended into one growing buffer, which is handed over to the encoder as is, so finalizing
+/// doesn't copy the data again. Padding and splitting into pieces happen only at `Self::finalize`, when
+/// the total data length is known.
+#[derive(Clone, Debug, Default)]
+pub struct EncoderBuilder {
+    data: Vec<u8>,
+}
+
+impl EncoderBuilder {
+    /// Creates a new empty `EncoderBuilder`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new empty `EncoderBuilder`, with room for at least `capacity` bytes of data, before it needs to reallocate.
+    pub fn with_capacity(capacity: usize) -> Self {
+        EncoderBuilder {
+            data: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Appends a chunk of original data.
+    pub fn push(&mut self, chunk: &[u8]) {
+        self.data.extend_from_slice(chunk);
+    }
+
+    /// Number of bytes of original data collected so far.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns `true` if no data has been collected yet.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Consumes the builder, padding collected data and splitting it into `piece_count` pieces, exactly the way `Encoder::new` does.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Encoder)` on success.
+    /// * Returns `Err(RLNCError::DataLengthZero)` if no data has been collected.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `piece_count` is zero.
+    pub fn finalize(self, piece_count: usize) -> Result<Encoder, RLNCError> {
+        Encoder::new(self.data, piece_count)
+    }
+}
+
+/// Lets data be streamed into the builder, say using `std::io::copy`.
+impl io::Write for EncoderBuilder {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.push(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EncoderBuilder;
+    use crate::{
+        RLNCError,
+        full::{Decoder, Encoder},
+    };
+    use rand::Rng;
+
+    #[test]
+    fn test_encoder_builder_empty() {
+        let builder = EncoderBuilder::new();
+        assert!(builder.is_empty());
+        assert!(matches!(builder.finalize(16), Err(RLNCError::DataLengthZero)));
+
+        let mut builder = EncoderBuilder::new();
+        builder.push(&[1, 2, 3]);
+        assert!(matches!(builder.finalize(0), Err(RLNCError::PieceCountZero)));
+    }
+
+    #[test]
+    fn test_encoder_builder_matches_encoder() {
+        let mut rng = rand::rng();
+
+        let data_byte_len = 10_000usize;
+        let piece_count = 64usize;
+        let data = (0..data_byte_len).map(|_| rng.random()).collect::<Vec<u8>>();
+
+        // Push data in chunks of random length, half of them using `push`, rest by streaming through `io::Write`.
+        let mut builder = EncoderBuilder::with_capacity(data_byte_len / 2);
+        let mut offset = 0;
+        while offset < data_byte_len {
+            let chunk_len = rng.random_range(1..=1024).min(data_byte_len - offset);
+            let chunk = &data[offset..offset + chunk_len];
+
+            if offset < data_byte_len / 2 {
+                builder.push(chunk);
+            } else {
+                std::io::copy(&mut &chunk[..], &mut builder).expect("Writing into builder must not fail");
+            }
+
+            offset += chunk_len;
+        }
+        assert_eq!(builder.len(), data_byte_len);
+
+        let encoder = builder.finalize(piece_count).expect("Failed to finalize EncoderBuilder");
+        assert_eq!(
+            encoder.get_params(),
+            Encoder::new(data.clone(), piece_count).expect("Failed to create Encoder").get_params()
+        );
+
+        let mut decoder = Decoder::new_with(encoder.get_params());
+        while !decoder.is_already_decoded() {
+            match decoder.decode(&encoder.code(&mut rng)) {
+                Ok(_) | Err(RLNCError::PieceNotUseful) => {}
+                Err(e) => panic!("Unexpected error during decoding: {e:?}"),
+            }
+        }
+
+        assert_eq!(decoder.get_decoded_data(), Ok(data));
+    }
+}
@@ -0,0 +1,84 @@
+use super::{control::ControlMessage, piece::CodedPiece};
+use crate::RLNCError;
+
+impl ControlMessage {
+    /// Serializes the message into given buffer, returning the prefix of the buffer, which holds the serialized message.
+    ///
+    /// # Returns
+    /// * Returns `Ok(&mut [u8])` on success.
+    /// * Returns `Err(RLNCError::SerializationFailure)` if `buf` is too short.
+    pub fn to_postcard_slice<'a>(&self, buf: &'a mut [u8]) -> Result<&'a mut [u8], RLNCError> {
+        postcard::to_slice(self, buf).map_err(|_| RLNCError::SerializationFailure)
+    }
+
+    /// Deserializes a message, serialized using `Self::to_postcard_slice`.
+    ///
+    /// # Returns
+    /// * Returns `Ok(ControlMessage)` on success.
+    /// * Returns `Err(RLNCError::SerializationFailure)` if `bytes` don't hold a valid message.
+    pub fn from_postcard(bytes: &[u8]) -> Result<ControlMessage, RLNCError> {
+        postcard::from_bytes(bytes).map_err(|_| RLNCError::SerializationFailure)
+    }
+}
+
+impl CodedPiece {
+    /// Serializes the coded piece into given buffer, returning the prefix of the buffer, which holds the serialized piece.
+    /// Serialized piece is just two bytes longer than the piece itself, for pieces shorter than 128 bytes.
+    ///
+    /// # Returns
+    /// * Returns `Ok(&mut [u8])` on success.
+    /// * Returns `Err(RLNCError::SerializationFailure)` if `buf` is too short.
+    pub fn to_postcard_slice<'a>(&self, buf: &'a mut [u8]) -> Result<&'a mut [u8], RLNCError> {
+        postcard::to_slice(self, buf).map_err(|_| RLNCError::SerializationFailure)
+    }
+
+    /// Deserializes a coded piece, serialized using `Self::to_postcard_slice`.
+    ///
+    /// # Returns
+    /// * Returns `Ok(CodedPiece)` on success.
+    /// * Returns `Err(RLNCError::SerializationFailure)` if `bytes` don't hold a valid coded piece.
+    pub fn from_postcard(bytes: &[u8]) -> Result<CodedPiece, RLNCError> {
+        postcard::from_bytes(bytes).map_err(|_| RLNCError::SerializationFailure)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        RLNCError,
+        full::{CodedPiece, ControlMessage, Decoder, Encoder},
+    };
+
+    #[test]
+    fn test_postcard_coded_piece_and_control_messages() {
+        let mut rng = rand::rng();
+
+        // A LoRa sized generation: 8 pieces of 24 bytes each.
+        let encoder = Encoder::new(vec![42u8; 190], 8).expect("Failed to create Encoder");
+        let mut decoder = Decoder::new_with(encoder.get_params());
+
+        let mut buf = [0u8; 64];
+
+        while !decoder.is_already_decoded() {
+            let piece = CodedPiece::from_bytes(&encoder.code(&mut rng), encoder.get_piece_count()).expect("Piece must be valid");
+
+            let serialized = piece.to_postcard_slice(&mut buf).expect("Serializing must not fail");
+            assert_eq!(serialized.len(), encoder.get_full_coded_piece_byte_len() + 2);
+
+            let deserialized = CodedPiece::from_postcard(serialized).expect("Deserializing must not fail");
+            assert_eq!(deserialized, piece);
+
+            let _ = decoder.decode(&deserialized.to_bytes());
+
+            let feedback = ControlMessage::from_decoder(3, &decoder);
+            let serialized = feedback.to_postcard_slice(&mut buf).expect("Serializing must not fail");
+            assert_eq!(ControlMessage::from_postcard(serialized), Ok(feedback));
+        }
+
+        assert_eq!(ControlMessage::from_decoder(3, &decoder), ControlMessage::Ack { generation: 3 });
+
+        let piece = CodedPiece::from_bytes(&encoder.code(&mut rng), encoder.get_piece_count()).expect("Piece must be valid");
+        assert_eq!(piece.to_postcard_slice(&mut buf[..16]), Err(RLNCError::SerializationFailure));
+        assert_eq!(CodedPiece::from_postcard(&[0xff]), Err(RLNCError::SerializationFailure));
+    }
+}
@@ -0,0 +1,31 @@
+use super::decoder::Decoder;
+
+/// Feedback sent by a receiver back to the sender, which lets sender decide what and how much more to send.
+///
+/// With `postcard` feature enabled, it can be serialized compactly, for narrow links such as LoRa or serial lines, where integers are
+/// varint encoded and there are no field names, keeping each message just a few bytes long. With `protobuf` feature enabled, it
+/// can be converted to and from its protobuf counterpart.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "postcard", derive(serde::Serialize, serde::Deserialize))]
+pub enum ControlMessage {
+    /// Receiver has collected `rank` many useful pieces of given generation.
+    Progress { generation: u32, rank: u32 },
+    /// Receiver still lacks these dimensions of given generation. See `Decoder::missing_dimensions`.
+    Missing { generation: u32, dimensions: Vec<u32> },
+    /// Receiver has decoded given generation, sender can stop sending its pieces.
+    Ack { generation: u32 },
+}
+
+impl ControlMessage {
+    /// Builds the most informative feedback for given generation, from current decoder state.
+    pub fn from_decoder(generation: u32, decoder: &Decoder) -> ControlMessage {
+        if decoder.is_already_decoded() {
+            return ControlMessage::Ack { generation };
+        }
+
+        ControlMessage::Missing {
+            generation,
+            dimensions: decoder.missing_dimensions().into_iter().map(|dimension| dimension as u32).collect(),
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Future, which is pending when polled first, waking its task right away, so that an async executor gets
+/// a chance to run other tasks, before resuming the current one.
+pub(crate) struct YieldNow {
+    has_yielded: bool,
+}
+
+/// Yields to the async executor once. It doesn't depend on any particular executor.
+pub(crate) fn yield_now() -> YieldNow {
+    YieldNow { has_yielded: false }
+}
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.has_yielded {
+            return Poll::Ready(());
+        }
+
+        self.has_yielded = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
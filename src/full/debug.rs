@@ -0,0 +1,205 @@
+use crate::common::gf256::Gf256;
+use std::{
+    fmt::Debug,
+    io::{self, Write},
+    sync::{Arc, Mutex},
+};
+
+/// Writes coefficient rows of a decoder matrix, in hex, along with its rank. Pivot of each row i.e. its first non-zero
+/// coefficient is highlighted within brackets.
+pub(crate) fn write_coefficient_matrix<'a, W: Write + ?Sized>(
+    writer: &mut W,
+    rows: impl ExactSizeIterator<Item = &'a [u8]>,
+    piece_count: usize,
+) -> io::Result<()> {
+    writeln!(writer, "rank {}/{}", rows.len(), piece_count)?;
+
+    for (ridx, row) in rows.enumerate() {
+        let pivot_cidx = row.iter().position(|&coeff| coeff != 0);
+
+        write!(writer, "row {ridx:>4}:")?;
+        for (cidx, coeff) in row.iter().enumerate() {
+            if Some(cidx) == pivot_cidx {
+                write!(writer, " [{coeff:02x}]")?;
+            } else {
+                write!(writer, "  {coeff:02x} ")?;
+            }
+        }
+        writeln!(writer)?;
+    }
+
+    Ok(())
+}
+
+/// Expresses a coding vector as a linear combination of given coefficient rows, if it's in their span.
+///
+/// Returns multiplier of each row, such that sum of all rows, each multiplied by its multiplier, is the coding vector.
+pub(crate) fn express_in_rows(rows: &[&[u8]], coding_vector: &[u8]) -> Option<Vec<u8>> {
+    // Each basis vector is kept along with its combination of given rows, while reducing rows to distinct pivots.
+    let mut basis: Vec<(usize, Vec<u8>, Vec<u8>)> = Vec::with_capacity(rows.len());
+
+    let reduce = |basis: &[(usize, Vec<u8>, Vec<u8>)], vector: &mut [u8], combination: &mut [u8]| {
+        for (pivot_cidx, basis_vector, basis_combination) in basis {
+            if vector[*pivot_cidx] == 0 {
+                continue;
+            }
+
+            let quotient = unsafe { (Gf256::new(vector[*pivot_cidx]) / Gf256::new(basis_vector[*pivot_cidx])).unwrap_unchecked() };
+            vector
+                .iter_mut()
+                .zip(basis_vector)
+                .for_each(|(dst, &src)| *dst ^= (quotient * Gf256::new(src)).get());
+            combination
+                .iter_mut()
+                .zip(basis_combination)
+                .for_each(|(dst, &src)| *dst ^= (quotient * Gf256::new(src)).get());
+        }
+    };
+
+    for (ridx, row) in rows.iter().enumerate() {
+        let mut vector = row.to_vec();
+        let mut combination = vec![0u8; rows.len()];
+        combination[ridx] = 1;
+
+        reduce(&basis, &mut vector, &mut combination);
+        if let Some(pivot_cidx) = vector.iter().position(|&coeff| coeff != 0) {
+            basis.push((pivot_cidx, vector, combination));
+        }
+    }
+
+    // Reduction subtracts basis vectors off the coding vector, which is same as adding them, in GF(2^8).
+    let mut vector = coding_vector.to_vec();
+    let mut combination = vec![0u8; rows.len()];
+    reduce(&basis, &mut vector, &mut combination);
+
+    vector.iter().all(|&coeff| coeff == 0).then_some(combination)
+}
+
+/// Step-by-step elimination trace writer, attached to a decoder. Clones of the decoder share the same writer.
+#[derive(Clone)]
+pub(crate) struct EliminationTrace {
+    writer: Arc<Mutex<dyn Write + Send>>,
+    step: usize,
+}
+
+impl EliminationTrace {
+    pub(crate) fn new<W: Write + Send + 'static>(writer: W) -> Self {
+        EliminationTrace {
+            writer: Arc::new(Mutex::new(writer)),
+            step: 0,
+        }
+    }
+
+    /// Records a decoding step i.e. the received coding vector, how it changed rank, and the resulting matrix. For a linearly
+    /// dependent piece, it also records which rows of the matrix it's a combination of. Failing writes are ignored.
+    pub(crate) fn record<'a>(&mut self, coding_vector: &[u8], rank_before: usize, rows: impl ExactSizeIterator<Item = &'a [u8]> + Clone, piece_count: usize) {
+        self.step += 1;
+
+        let Ok(mut writer) = self.writer.lock() else {
+            return;
+        };
+        let _ = Self::write_step(&mut *writer, self.step, coding_vector, rank_before, rows, piece_count);
+    }
+
+    fn write_step<'a, W: Write + ?Sized>(
+        writer: &mut W,
+        step: usize,
+        coding_vector: &[u8],
+        rank_before: usize,
+        rows: impl ExactSizeIterator<Item = &'a [u8]> + Clone,
+        piece_count: usize,
+    ) -> io::Result<()> {
+        write!(writer, "step {step}: coding vector")?;
+        coding_vector.iter().try_for_each(|coeff| write!(writer, " {coeff:02x}"))?;
+        writeln!(writer)?;
+
+        let rank_after = rows.len();
+        if rank_after > rank_before {
+            writeln!(writer, "useful, rank {rank_before} -> {rank_after}")?;
+        } else {
+            write!(writer, "not useful, rank stays {rank_after}")?;
+
+            let row_list = rows.clone().collect::<Vec<_>>();
+            if let Some(combination) = express_in_rows(&row_list, coding_vector) {
+                write!(writer, ", as it's a combination of")?;
+                for (ridx, multiplier) in combination.iter().enumerate().filter(|&(_, &multiplier)| multiplier != 0) {
+                    write!(writer, " {multiplier:02x} x row {ridx}")?;
+                }
+            }
+            writeln!(writer)?;
+        }
+
+        write_coefficient_matrix(writer, rows, piece_count)?;
+        writeln!(writer)
+    }
+}
+
+impl Debug for EliminationTrace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EliminationTrace").field("step", &self.step).finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::express_in_rows;
+    use crate::full::{Decoder, Encoder};
+    use std::{
+        io::Write,
+        sync::{Arc, Mutex},
+    };
+
+    /// Writer, whose written bytes can be inspected, while the decoder owns a clone of it.
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_express_in_rows() {
+        let rows: [&[u8]; 3] = [&[1, 2, 0, 0], &[0, 1, 3, 0], &[1, 3, 3, 0]];
+
+        // Third row is sum of first two, so only first two are needed.
+        let combination = express_in_rows(&rows, &[2, 4, 0, 0]).expect("Must be in span");
+        assert_eq!(combination, vec![2, 0, 0]);
+
+        let combination = express_in_rows(&rows, &[1, 3, 3, 0]).expect("Must be in span");
+        assert_eq!(combination, vec![1, 1, 0]);
+
+        assert_eq!(express_in_rows(&rows, &[0, 0, 0, 1]), None);
+    }
+
+    #[test]
+    fn test_decoder_elimination_trace() {
+        let mut rng = rand::rng();
+
+        let encoder = Encoder::new(vec![7u8; 100], 4).expect("Failed to create Encoder for elimination trace test");
+        let trace = SharedBuffer::default();
+        let mut decoder = Decoder::new_with(encoder.get_params()).with_elimination_trace(trace.clone());
+
+        let piece = encoder.code(&mut rng);
+        decoder.decode(&piece).expect("First piece must be useful");
+        assert!(decoder.decode(&piece).is_err());
+
+        let trace = String::from_utf8(trace.0.lock().unwrap().clone()).expect("Trace must be UTF-8");
+        assert!(trace.starts_with("step 1: coding vector"));
+        assert!(trace.contains("useful, rank 0 -> 1"));
+        assert!(trace.contains("step 2: coding vector"));
+        assert!(trace.contains("not useful, rank stays 1, as it's a combination of 01 x row 0"));
+
+        let mut dump = Vec::new();
+        decoder.dump_matrix(&mut dump).expect("Writing into vector must not fail");
+
+        let dump = String::from_utf8(dump).expect("Dump must be UTF-8");
+        assert!(dump.starts_with("rank 1/4\nrow    0:"));
+        assert_eq!(dump.matches('[').count(), 1);
+    }
+}
@@ -1,5 +1,50 @@
+use super::audit::AuditLog;
 use super::consts::BOUNDARY_MARKER;
-use crate::{RLNCError, full::decoder_matrix::DecoderMatrix};
+use super::cooperative::yield_now;
+#[cfg(feature = "debug-tools")]
+use super::debug::{EliminationTrace, write_coefficient_matrix};
+use super::eta::{ArrivalStats, CompletionEstimate, estimate_completion};
+use super::nibble::{get_nibble_packed_piece_byte_len, nibble_unpack_piece};
+use super::params::Params;
+use super::piece::CodedPiece;
+use super::prefilter::DependencySketch;
+use super::recoder::Recoder;
+use super::seeded::SeededPiece;
+use super::snapshot::DecoderSnapshot;
+use super::storage::PieceStorage;
+use super::ttl::TtlPiece;
+use super::watermark::RankWatcher;
+use super::wire::FramedPiece;
+use crate::{
+    DecodedDataDiagnostics, RLNCError,
+    common::{
+        gf256::Gf256,
+        simd::{gf256_inplace_mul_vec_by_scalar, gf256_mul_vec_by_scalar_then_add_into_vec, gf256_strided_mul_vec_by_scalar_then_add_into_vec},
+    },
+    full::decoder_matrix::DecoderMatrix,
+};
+use rand::Rng;
+use std::{
+    io::Write,
+    sync::mpsc,
+    time::{Instant, SystemTime},
+};
+
+/// Memory layout of coded data i.e. payload, held by the decoder.
+///
+/// Optimal layout depends on the workload, benchmark both, using `make bench`, before switching.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub enum PayloadLayout {
+    /// Each row of the decoder matrix holds the coding vector, immediately followed by its payload. Gaussian elimination is
+    /// applied on full rows, so decoded data is readily available, once enough useful pieces are received. This is the default.
+    #[default]
+    RowMajor,
+    /// Payloads are stored column-major and are not touched during Gaussian elimination, which is applied only on coding vectors,
+    /// while keeping track of the row operations. All payload processing is deferred till extraction of decoded data, when each
+    /// column i.e. contiguous `piece_count` bytes, gets transformed at once, or of a recovered source piece, see `Decoder::recovered_piece_to_vec`. Suits ingestion-heavy workloads with large pieces.
+    ColumnMajor,
+}
 
 /// Random Linear Network Coding (RLNC) Decoder.
 ///
@@ -9,7 +54,23 @@ use crate::{RLNCError, full::decoder_matrix::DecoderMatrix};
 pub struct Decoder {
     /// Stores the coefficient matrix and coded data rows concatenated.
     /// Each row is a coded piece: `[coefficients | data_piece]`.
+    ///
+    /// With column-major payload layout, each row is `[coefficients | row_operations]` instead.
+    ///
+    /// Rows are kept in row echelon form, up to their order i.e. each row is zero before its pivot column, and no two rows
+    /// share one. Back-substitution is deferred till decoding completes, leaving rows sorted by pivot column, each with a unit
+    /// coding vector.
     matrix: DecoderMatrix,
+    /// Index of the row of the matrix, holding the pivot of each coefficient column, if any.
+    pivot_rows: Vec<Option<usize>>,
+    /// Memory layout of payloads.
+    layout: PayloadLayout,
+    /// Payloads of useful pieces, stored column-major i.e. `j`-th byte of `i`-th useful piece lives at
+    /// index `j * required_piece_count + i`. It is empty, unless column-major payload layout is used.
+    payloads: Vec<u8>,
+    /// Reusable row, where a received coding vector and the unit vector tracking its payload slot are put together, before being
+    /// added to the matrix. It is empty, unless column-major payload layout is used.
+    scratch_row: Vec<u8>,
     /// The byte length of each original data piece.
     piece_byte_len: usize,
     /// The minimum number of useful coded pieces required to decode.
@@ -18,6 +79,45 @@ pub struct Decoder {
     received_piece_count: usize,
     /// The number of linearly independent pieces received so far.
     useful_piece_count: usize,
+    /// Concatenated full coded pieces, which turned out to be useful, kept around so that the matrix
+    /// can be rebuilt after evicting suspect pieces. It is `None`, unless piece retention is enabled.
+    retained_pieces: Option<Vec<u8>>,
+    /// Bounded log of rejected pieces. It is `None`, unless audit log is enabled.
+    audit_log: Option<AuditLog>,
+    /// Registered rank watermarks, each notifying its own receiver.
+    rank_watchers: Vec<RankWatcher>,
+    /// Pieces whose payload got truncated on the way, as received i.e. coding vector followed by a prefix of coded data. Missing
+    /// tail bytes are treated as erasures, so these pieces only help recovering leading columns. Only pieces with linearly
+    /// independent coding vectors are kept, preferring longer ones, so there are at max `required_piece_count` many.
+    truncated_pieces: Vec<Vec<u8>>,
+    /// Generation this decoder is bound to, for strict validation of incoming pieces. It is `None`, unless strict validation is enabled.
+    strict_generation: Option<u32>,
+    /// Arrival times of received pieces, for estimating time to completion. It is `None`, unless arrival timing is enabled.
+    arrivals: Option<ArrivalStats>,
+    /// Sketch of the span of coding vectors of useful pieces, for rejecting dependent pieces without elimination. It is `None`,
+    /// unless the dependency pre-filter is enabled.
+    prefilter: Option<DependencySketch>,
+    /// Whether each row of the matrix is known to carry a unit coding vector, possibly scaled, as while receiving systematic
+    /// pieces, see `Encoder::systematic_piece`. As long as it holds, pieces with unit coding vectors skip Gaussian elimination.
+    unit_rows_only: bool,
+    /// Step-by-step elimination trace. It is `None`, unless tracing is enabled.
+    #[cfg(feature = "debug-tools")]
+    trace: Option<EliminationTrace>,
+}
+
+/// Row appended to the matrix, while it's being reduced across await points by `Decoder::decode_async`. Unless it's marked as
+/// reduced, it's removed on drop, so that a cancelled decode leaves the matrix as it was.
+struct PendingRow<'a> {
+    matrix: &'a mut DecoderMatrix,
+    is_reduced: bool,
+}
+
+impl Drop for PendingRow<'_> {
+    fn drop(&mut self) {
+        if !self.is_reduced {
+            self.matrix.remove_last_row();
+        }
+    }
 }
 
 impl Decoder {
@@ -51,6 +151,23 @@ impl Decoder {
         self.get_num_pieces_coded_together() - self.get_useful_piece_count()
     }
 
+    /// Estimates what's left, before decoding completes, using observed arrival rate of pieces and share of linearly dependent ones
+    /// among them, so that streaming UIs and timeout logic get a principled estimate, rather than `Self::get_remaining_piece_count` alone.
+    pub fn estimated_completion(&self) -> CompletionEstimate {
+        estimate_completion(
+            self.get_remaining_piece_count(),
+            self.useful_piece_count,
+            self.received_piece_count,
+            self.arrivals.as_ref(),
+        )
+    }
+
+    /// Enables timing of piece arrivals, so that `Self::estimated_completion` also estimates the time left, using recent arrival
+    /// rate. It's opt-in, as it reads the clock on every received piece. Calling it again resets arrival timing.
+    pub fn enable_arrival_timing(&mut self) {
+        self.arrivals = Some(ArrivalStats::default());
+    }
+
     /// Creates a new `Decoder` instance.
     ///
     /// # Arguments
@@ -63,6 +180,46 @@ impl Decoder {
     /// * Returns `Err(RLNCError::PieceLengthZero)` if `piece_byte_len` is zero.
     /// * Returns `Err(RLNCError::PieceCountZero)` if `required_piece_count` is zero.
     pub fn new(piece_byte_len: usize, required_piece_count: usize) -> Result<Decoder, RLNCError> {
+        Self::new_with_layout(piece_byte_len, required_piece_count, PayloadLayout::RowMajor)
+    }
+
+    /// Creates a new `Decoder` instance from already validated RLNC configuration, say parsed off the wire.
+    /// It can't fail, because `params` already guarantees non-zero piece count and piece length.
+    pub fn new_with(params: Params) -> Decoder {
+        unsafe { Self::new(params.get_piece_byte_len(), params.get_piece_count()).unwrap_unchecked() }
+    }
+
+    /// Bootstraps a decoder off the first received self-describing piece, taking RLNC configuration from its header, and binding the
+    /// decoder to its generation, with strict validation enabled. See `Self::enable_strict_validation`. The piece itself is not
+    /// decoded, pass it to `Self::decode_framed`.
+    pub fn from_framed_piece(piece: &FramedPiece) -> Decoder {
+        let mut decoder = Self::new_with(piece.get_params());
+        decoder.enable_strict_validation(piece.get_generation());
+
+        decoder
+    }
+
+    /// RLNC configuration of this decoder.
+    pub fn get_params(&self) -> Params {
+        Params {
+            piece_count: self.required_piece_count,
+            piece_byte_len: self.piece_byte_len,
+        }
+    }
+
+    /// Creates a new `Decoder` instance, which stores payloads of received pieces using the given memory layout.
+    ///
+    /// # Arguments
+    /// * `piece_byte_len` - The byte length of each original data piece.
+    /// * `required_piece_count` - The minimum number of useful coded pieces
+    ///   needed for decoding (equivalent to the number of original pieces).
+    /// * `layout` - Memory layout of payloads. See `PayloadLayout`.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Decoder)` on successful creation.
+    /// * Returns `Err(RLNCError::PieceLengthZero)` if `piece_byte_len` is zero.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `required_piece_count` is zero.
+    pub fn new_with_layout(piece_byte_len: usize, required_piece_count: usize, layout: PayloadLayout) -> Result<Decoder, RLNCError> {
         if piece_byte_len == 0 {
             return Err(RLNCError::PieceLengthZero);
         }
@@ -70,19 +227,85 @@ impl Decoder {
             return Err(RLNCError::PieceCountZero);
         }
 
+        let (payloads, scratch_row) = match layout {
+            PayloadLayout::RowMajor => (Vec::new(), Vec::new()),
+            PayloadLayout::ColumnMajor => (vec![0u8; piece_byte_len * required_piece_count], vec![0u8; 2 * required_piece_count]),
+        };
+
         Ok(Decoder {
-            matrix: DecoderMatrix::new(required_piece_count, piece_byte_len),
+            matrix: Self::new_matrix(piece_byte_len, required_piece_count, layout),
+            pivot_rows: vec![None; required_piece_count],
+            layout,
+            payloads,
+            scratch_row,
             piece_byte_len,
             required_piece_count,
             received_piece_count: 0,
             useful_piece_count: 0,
+            retained_pieces: None,
+            audit_log: None,
+            rank_watchers: Vec::new(),
+            truncated_pieces: Vec::new(),
+            strict_generation: None,
+            arrivals: None,
+            prefilter: None,
+            unit_rows_only: true,
+            #[cfg(feature = "debug-tools")]
+            trace: None,
         })
     }
 
+    /// Creates a new `Decoder` instance, which also retains a copy of every useful piece it receives.
+    ///
+    /// Retained pieces let one evict suspect (say polluted or corrupted) pieces using `Self::evict_pieces`
+    /// and rebuild the decoder state from the remaining ones, instead of throwing away the entire decoder.
+    /// This doubles the memory required by the decoder, in the worst case.
+    ///
+    /// # Arguments
+    /// * `piece_byte_len` - The byte length of each original data piece.
+    /// * `required_piece_count` - The minimum number of useful coded pieces
+    ///   needed for decoding (equivalent to the number of original pieces).
+    ///
+    /// # Returns
+    /// * Returns `Ok(Decoder)` on successful creation.
+    /// * Returns `Err(RLNCError::PieceLengthZero)` if `piece_byte_len` is zero.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `required_piece_count` is zero.
+    pub fn new_with_piece_retention(piece_byte_len: usize, required_piece_count: usize) -> Result<Decoder, RLNCError> {
+        let mut decoder = Self::new(piece_byte_len, required_piece_count)?;
+        decoder.retained_pieces = Some(Vec::new());
+
+        Ok(decoder)
+    }
+
+    /// Creates a new `Decoder` instance, which stores decoder matrix rows i.e. received coded pieces, in given storage.
+    /// See `PieceStorage`. Payloads are stored using `PayloadLayout::RowMajor` layout.
+    ///
+    /// # Arguments
+    /// * `piece_byte_len` - The byte length of each original data piece.
+    /// * `required_piece_count` - The minimum number of useful coded pieces
+    ///   needed for decoding (equivalent to the number of original pieces).
+    /// * `storage` - Backing memory for decoder matrix, which is emptied first.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Decoder)` on successful creation.
+    /// * Returns `Err(RLNCError::PieceLengthZero)` if `piece_byte_len` is zero.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `required_piece_count` is zero.
+    pub fn new_with_storage(piece_byte_len: usize, required_piece_count: usize, storage: Box<dyn PieceStorage>) -> Result<Decoder, RLNCError> {
+        let mut decoder = Self::new(piece_byte_len, required_piece_count)?;
+        decoder.matrix = DecoderMatrix::new_with_storage(required_piece_count, piece_byte_len, storage);
+
+        Ok(decoder)
+    }
+
+    /// Memory layout of payloads, held by the decoder.
+    pub fn get_payload_layout(&self) -> PayloadLayout {
+        self.layout
+    }
+
     /// Decodes a single full coded piece and adds it to the decoder's matrix.
     ///
-    /// Performs Gaussian elimination to reduce the matrix and checks if the
-    /// added piece was linearly independent of the existing ones.
+    /// Gaussian elimination is incremental. The piece is reduced by rows holding pivots, without touching any of them, which costs
+    /// one row operation per useful piece received so far, at max, and it's kept only if it's linearly independent of them.
     ///
     /// # Arguments
     /// * `full_coded_piece` - A slice containing the coefficients followed by
@@ -93,259 +316,2582 @@ impl Decoder {
     /// * Returns `Err(RLNCError::ReceivedAllPieces)` if decoding is already complete.
     /// * Returns `Err(RLNCError::PieceNotUseful)` if the piece was linearly dependent on the already received useful pieces.
     /// * Returns `Err(RLNCError::InvalidPieceLength)` if the `full_coded_piece` has an unexpected length.
+    /// * Returns `Err(RLNCError::ZeroCodingVector)` if strict validation is enabled and the coding vector is all-zero.
+    /// * Returns `Err(RLNCError::PieceStorageFailure)` if backing storage can't grow. See `Self::new_with_storage`.
     pub fn decode(&mut self, full_coded_piece: &[u8]) -> Result<(), RLNCError> {
         if self.is_already_decoded() {
             return Err(RLNCError::ReceivedAllPieces);
         }
         if full_coded_piece.len() != self.get_full_coded_piece_byte_len() {
+            if let Some(audit_log) = self.audit_log.as_mut() {
+                audit_log.record_malformed(full_coded_piece, self.required_piece_count);
+            }
+
             return Err(RLNCError::InvalidPieceLength);
         }
+        self.validate_strictly(full_coded_piece)?;
 
-        let rank_before = self.matrix.rank();
+        self.record_arrival();
+        let result = self.add_piece(full_coded_piece);
 
-        unsafe { self.matrix.add_row(full_coded_piece).unwrap_unchecked().rref() };
-        self.received_piece_count += 1;
+        if let Some(audit_log) = self.audit_log.as_mut() {
+            audit_log.record(&full_coded_piece[..self.required_piece_count], result.is_ok());
+        }
 
-        let rank_after = self.matrix.rank();
+        result
+    }
 
-        // If the rank didn't increase, the piece was not useful.
-        if rank_before == rank_after {
-            Err(RLNCError::PieceNotUseful)
-        } else {
-            self.useful_piece_count = rank_after;
-            Ok(())
+    /// Same as `Self::decode`, but for a piece carrying its expiry timestamp, which is rejected, once expired at given time.
+    /// Hop limit only matters to relays, so it's ignored.
+    ///
+    /// # Returns
+    /// * Returns `Err(RLNCError::PieceExpired)` if the piece is expired, logging it, if audit log is enabled.
+    /// * Otherwise, returns same as `Self::decode`.
+    pub fn decode_ttl(&mut self, piece: &TtlPiece, now: SystemTime) -> Result<(), RLNCError> {
+        let full_coded_piece = piece.get_full_coded_piece();
+
+        if !self.is_already_decoded() && full_coded_piece.len() == self.get_full_coded_piece_byte_len() && piece.is_expired(now) {
+            if let Some(audit_log) = self.audit_log.as_mut() {
+                audit_log.record_expired(&full_coded_piece[..self.required_piece_count]);
+            }
+
+            return Err(RLNCError::PieceExpired);
         }
-    }
 
-    /// Checks if the decoder has received enough linearly independent pieces to recover the original data.
-    pub fn is_already_decoded(&self) -> bool {
-        self.matrix.rank() == self.required_piece_count
+        self.decode(full_coded_piece)
     }
 
-    /// Recovers and returns the original data byte vector if decoding is complete.
-    ///
-    /// Assumes the matrix is in Reduced Row Echelon Form (RREF) and extracts
-    /// the original data pieces corresponding to the identity matrix part
-    /// of the coefficient matrix. It also handles the boundary marker to
-    /// determine the original data length and trims padding.
+    /// Same as `Self::decode`, but for a nibble-packed piece, produced by `Encoder::code_nibble_packed` or `Recoder::recode_nibble_packed`.
     ///
     /// # Returns
-    /// * Returns `Ok(Vec<u8>)` containing the decoded data if successful.
-    /// * Returns `Err(RLNCError::NotAllPiecesReceivedYet)` if not enough useful pieces have been received.
-    /// * Returns `Err(RLNCError::InvalidDecodedDataFormat)` if the extracted data does not follow the expected format (e.g., boundary marker issues).
-    pub fn get_decoded_data(self) -> Result<Vec<u8>, RLNCError> {
-        if !self.is_already_decoded() {
-            return Err(RLNCError::NotAllPiecesReceivedYet);
+    /// * Returns `Err(RLNCError::InvalidPieceLength)` if the piece is not of length `full::get_nibble_packed_piece_byte_len`.
+    /// * Returns `Err(RLNCError::CoefficientNotInSubfield)` if padding nibble of packed coding vector is not zero.
+    /// * Otherwise, returns same as `Self::decode`.
+    pub fn decode_nibble_packed(&mut self, packed_piece: &[u8]) -> Result<(), RLNCError> {
+        if packed_piece.len() != get_nibble_packed_piece_byte_len(self.required_piece_count, self.piece_byte_len) {
+            return Err(RLNCError::InvalidPieceLength);
         }
 
-        let required_len = self.piece_byte_len * self.required_piece_count;
-        let mut buf = vec![0u8; required_len];
+        self.decode(&nibble_unpack_piece(packed_piece, self.required_piece_count)?)
+    }
+
+    /// Same as `Self::decode`, but for a piece, whose coding vector is either expanded from a seed, see `Encoder::code_with_seed`,
+    /// or carried explicitly, say a recoded one.
+    ///
+    /// # Returns
+    /// * Returns `Err(RLNCError::InvalidPieceLength)` if the coding vector or coded data is not of expected length.
+    /// * Otherwise, returns same as `Self::decode`.
+    pub fn decode_seeded(&mut self, piece: &SeededPiece) -> Result<(), RLNCError> {
+        if piece.get_coded_data().len() != self.piece_byte_len {
+            return Err(RLNCError::InvalidPieceLength);
+        }
 
-        let mut current_pos = 0;
-        let full_coded_piece_len = self.get_full_coded_piece_byte_len();
+        let full_coded_piece = piece
+            .to_full_coded_piece(self.required_piece_count)
+            .map_err(|_| RLNCError::InvalidPieceLength)?;
+        self.decode(&full_coded_piece)
+    }
 
-        // Write the decoded data piece by piece into the output buffer
-        for chunk in self.matrix.extract_data().chunks_exact(full_coded_piece_len) {
-            let payload = &chunk[self.required_piece_count..];
-            let end_pos = current_pos + self.piece_byte_len;
-            buf[current_pos..end_pos].copy_from_slice(payload);
-            current_pos = end_pos;
+    /// Same as `Self::decode`, but for a coded piece, whose parts are validated against RLNC configuration of the decoder, before
+    /// being concatenated.
+    ///
+    /// # Returns
+    /// * Returns `Err(RLNCError::InvalidPieceLength)` if the coding vector or coded data is not of expected length.
+    /// * Otherwise, returns same as `Self::decode`.
+    pub fn decode_piece(&mut self, piece: &CodedPiece) -> Result<(), RLNCError> {
+        if !piece.matches(self.get_params()) {
+            return Err(RLNCError::InvalidPieceLength);
         }
 
-        let final_len = Self::get_final_data_len(&buf[..current_pos])?;
+        self.decode(&piece.to_bytes())
+    }
 
-        buf.truncate(final_len);
-        Ok(buf)
+    /// Same as `Self::decode_in_generation`, but for a self-describing piece, whose RLNC configuration must match that of the decoder.
+    ///
+    /// # Returns
+    /// * Returns `Err(RLNCError::ParamsMismatch)` if the piece is coded with another RLNC configuration.
+    /// * Otherwise, returns same as `Self::decode_in_generation`.
+    pub fn decode_framed(&mut self, piece: &FramedPiece) -> Result<(), RLNCError> {
+        if piece.get_params() != self.get_params() {
+            return Err(RLNCError::ParamsMismatch);
+        }
+
+        self.decode_in_generation(piece.get_generation(), &piece.get_piece().to_bytes())
     }
 
-    /// Helper to find the boundary marker, validate padding, and return the final length of the original data.
-    fn get_final_data_len(padded_data: &[u8]) -> Result<usize, RLNCError> {
-        let last_index = padded_data.len().saturating_sub(1);
+    /// Same as `Self::decode`, but for a piece tagged with the generation it belongs to. With strict validation enabled, pieces of
+    /// any other generation are rejected, instead of corrupting decoded data. Otherwise, the tag is not checked.
+    ///
+    /// # Returns
+    /// * Returns `Err(RLNCError::InvalidGenerationIndex)` if strict validation is enabled and `generation` is not the one, the decoder is bound to.
+    /// * Otherwise, returns same as `Self::decode`.
+    pub fn decode_in_generation(&mut self, generation: u32, full_coded_piece: &[u8]) -> Result<(), RLNCError> {
+        if self.strict_generation.is_some_and(|strict_generation| strict_generation != generation) {
+            return Err(RLNCError::InvalidGenerationIndex);
+        }
 
-        let boundary_marker_rev_index = padded_data.iter().rev().position(|&byte| byte == BOUNDARY_MARKER).unwrap_or(last_index);
-        let boundary_marker_index = last_index - boundary_marker_rev_index;
+        self.decode(full_coded_piece)
+    }
 
-        if boundary_marker_index == 0 {
-            return Err(RLNCError::InvalidDecodedDataFormat);
+    /// Same as `Self::decode`, but Gaussian elimination is split into slices of at max `row_ops_per_slice` row operations,
+    /// yielding to the async executor between slices, so that decoding a piece of a large generation doesn't block it for long.
+    /// It works with any executor, and with either payload layout.
+    ///
+    /// It's cancellation-safe. Reduction of the piece touches only its own row, appended to the matrix, which is removed, if the
+    /// returned future is dropped midway. So the piece is simply not added, nor counted as received.
+    ///
+    /// # Returns
+    /// Same as `Self::decode`.
+    pub async fn decode_async(&mut self, full_coded_piece: &[u8], row_ops_per_slice: usize) -> Result<(), RLNCError> {
+        if self.is_already_decoded() {
+            return Err(RLNCError::ReceivedAllPieces);
         }
-        if padded_data[(boundary_marker_index + 1)..].iter().any(|&byte| byte != 0) {
-            return Err(RLNCError::InvalidDecodedDataFormat);
+        if full_coded_piece.len() != self.get_full_coded_piece_byte_len() {
+            if let Some(audit_log) = self.audit_log.as_mut() {
+                audit_log.record_malformed(full_coded_piece, self.required_piece_count);
+            }
+
+            return Err(RLNCError::InvalidPieceLength);
         }
+        self.validate_strictly(full_coded_piece)?;
 
-        // On success, return the index of the marker, which is the new length.
-        Ok(boundary_marker_index)
+        let rank_before = self.matrix.rank();
+
+        let result = match self.add_piece_without_elimination(full_coded_piece) {
+            Some(result) => {
+                self.record_arrival();
+                result
+            }
+            None => {
+                self.push_row(full_coded_piece)?;
+                self.reduce_new_row_in_slices(row_ops_per_slice.max(1)).await;
+
+                self.record_arrival();
+                self.eliminate_new_row();
+                self.on_row_eliminated(full_coded_piece, rank_before)
+            }
+        };
+
+        if let Some(audit_log) = self.audit_log.as_mut() {
+            audit_log.record(&full_coded_piece[..self.required_piece_count], result.is_ok());
+        }
+
+        result
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::{Decoder, RLNCError};
-    use crate::full::encoder::Encoder;
-    use rand::Rng;
+    /// Accepts a coded piece, whose payload got truncated by the transport, say a datagram cut short, instead of wasting it.
+    ///
+    /// Coding vector must be intact, while missing tail bytes of the payload are treated as erasures, confined to those columns.
+    /// Such a piece doesn't increase the rank of the decoder, but it's kept around, so that leading bytes of all source pieces
+    /// can be recovered using it, before full pieces alone are enough. See `Self::recover_piece_prefixes`. A piece of full length
+    /// is decoded as usual. Note, truncated pieces are neither part of snapshots, nor subject to eviction.
+    ///
+    /// At max `self.get_num_pieces_coded_together()` many truncated pieces are kept. Once a piece's coding vector is a linear
+    /// combination of those of longer kept pieces, it's dropped, as it can't help recovering a longer prefix.
+    ///
+    /// # Arguments
+    /// * `truncated_piece` - Coding vector, followed by the received prefix of coded data. Its length must be at least
+    ///   `self.get_num_pieces_coded_together()` and at max `self.get_full_coded_piece_byte_len()`.
+    ///
+    /// # Returns
+    /// * Returns `Ok(())` if the piece was accepted.
+    /// * Returns `Err(RLNCError::ReceivedAllPieces)` if decoding is already complete.
+    /// * Returns `Err(RLNCError::PieceNotUseful)` if the piece carries no payload at all, or if it's a full piece, not useful for decoding.
+    /// * Returns `Err(RLNCError::InvalidPieceLength)` if `truncated_piece` is shorter than the coding vector or longer than a full piece.
+    pub fn decode_truncated(&mut self, truncated_piece: &[u8]) -> Result<(), RLNCError> {
+        if self.is_already_decoded() {
+            return Err(RLNCError::ReceivedAllPieces);
+        }
+        if truncated_piece.len() == self.get_full_coded_piece_byte_len() {
+            return self.decode(truncated_piece);
+        }
+        if truncated_piece.len() < self.required_piece_count || truncated_piece.len() > self.get_full_coded_piece_byte_len() {
+            return Err(RLNCError::InvalidPieceLength);
+        }
+        if truncated_piece.len() == self.required_piece_count {
+            return Err(RLNCError::PieceNotUseful);
+        }
 
-    #[test]
-    fn test_decoder_new_invalid_inputs() {
-        // Test case 1: piece_byte_len is zero
-        let piece_byte_len_zero = 0;
-        let required_piece_count_non_zero = 10;
+        self.record_arrival();
+        self.keep_truncated_pieces([truncated_piece.to_vec()]);
 
-        let result_piece_len_zero = Decoder::new(piece_byte_len_zero, required_piece_count_non_zero);
-        assert!(result_piece_len_zero.is_err());
-        assert_eq!(result_piece_len_zero.expect_err("Expected PieceLengthZero error"), RLNCError::PieceLengthZero);
+        Ok(())
+    }
 
-        // Test case 2: required_piece_count is zero
-        let piece_byte_len_non_zero = 10;
-        let required_piece_count_zero = 0;
+    /// Adds truncated pieces to the kept ones, and keeps only a maximal set of them with linearly independent coding vectors,
+    /// picking pieces longest first. Coding vectors form a matroid, so the greedy pick keeps, for every prefix length, as many
+    /// linearly independent pieces covering it, as all pieces together do.
+    fn keep_truncated_pieces(&mut self, truncated_pieces: impl IntoIterator<Item = Vec<u8>>) {
+        let piece_count = self.required_piece_count;
 
-        let result_piece_count_zero = Decoder::new(piece_byte_len_non_zero, required_piece_count_zero);
-        assert!(result_piece_count_zero.is_err());
-        assert_eq!(result_piece_count_zero.expect_err("Expected PieceCountZero error"), RLNCError::PieceCountZero);
+        self.truncated_pieces.extend(truncated_pieces);
+        self.truncated_pieces.sort_by_key(|piece| std::cmp::Reverse(piece.len()));
 
-        // Test case 3: Both piece_byte_len and required_piece_count are zero
-        let piece_byte_len_both_zero = 0;
-        let required_piece_count_both_zero = 0;
+        let mut coding_vectors = DecoderMatrix::new(piece_count, 0);
+        let mut pivot_rows = vec![None; piece_count];
 
-        let result_both_zero = Decoder::new(piece_byte_len_both_zero, required_piece_count_both_zero);
-        assert!(result_both_zero.is_err());
-        assert_eq!(
-            result_both_zero.expect_err("Expected PieceLengthZero error for both zero inputs"),
-            RLNCError::PieceLengthZero
-        );
+        self.truncated_pieces.retain(|piece| {
+            if coding_vectors.rank() == piece_count || coding_vectors.add_row(&piece[..piece_count]).is_err() {
+                return false;
+            }
 
-        // Test case 4: Valid input
-        let piece_byte_len_valid = 10;
-        let required_piece_count_valid = 5;
-        let result_valid = Decoder::new(piece_byte_len_valid, required_piece_count_valid);
-        assert!(result_valid.is_ok());
+            match coding_vectors.reduce_last_row(&pivot_rows) {
+                Some(pivot_cidx) => {
+                    pivot_rows[pivot_cidx] = Some(coding_vectors.rank() - 1);
+                    true
+                }
+                None => {
+                    coding_vectors.remove_last_row();
+                    false
+                }
+            }
+        });
     }
 
-    #[test]
-    fn test_decoder_decode_invalid_piece_length() {
-        let mut rng = rand::rng();
-
-        let data_byte_len = 1024usize;
-        let piece_count = 32usize;
-        let data = (0..data_byte_len).map(|_| rng.random()).collect::<Vec<u8>>();
-        let encoder = Encoder::new(data, piece_count).expect("Failed to create Encoder for decode invalid length test");
+    /// Enables recording of rejected pieces i.e. linearly dependent, duplicate and malformed ones, along with their coding vector digest
+    /// and arrival order. At max `capacity` many most recent entries are kept. Calling it again resets the audit log.
+    pub fn enable_audit_log(&mut self, capacity: usize) {
+        self.audit_log = Some(AuditLog::new(capacity));
+    }
 
-        let piece_byte_len = encoder.get_piece_byte_len();
-        let required_piece_count = encoder.get_piece_count();
-        let full_coded_piece_byte_len = encoder.get_full_coded_piece_byte_len();
+    /// Returns the audit log of rejected pieces, if it is enabled.
+    pub fn get_audit_log(&self) -> Option<&AuditLog> {
+        self.audit_log.as_ref()
+    }
 
-        let mut decoder = Decoder::new(piece_byte_len, required_piece_count).expect("Failed to create Decoder for decode invalid length test");
+    /// Enables strict validation of incoming pieces, binding the decoder to given generation. Structurally suspicious pieces, which
+    /// would otherwise waste an elimination pass or corrupt decoded data, are rejected with specific errors: pieces carrying an all-zero
+    /// coding vector, see `Self::decode`, and pieces tagged with another generation, see `Self::decode_in_generation`.
+    pub fn enable_strict_validation(&mut self, generation: u32) {
+        self.strict_generation = Some(generation);
+    }
 
-        // Test case 1: Piece length is shorter than expected
-        let short_piece_len = full_coded_piece_byte_len - 1;
-        let short_coded_piece: Vec<u8> = (0..short_piece_len).map(|_| rng.random()).collect();
-        let result_short = decoder.decode(&short_coded_piece);
-        assert!(result_short.is_err());
-        assert_eq!(
-            result_short.expect_err("Expected InvalidPieceLength error for short piece"),
-            RLNCError::InvalidPieceLength
-        );
+    /// Returns `true` if strict validation of incoming pieces is enabled.
+    pub fn is_strict_validation_enabled(&self) -> bool {
+        self.strict_generation.is_some()
+    }
 
-        // Test case 2: Piece length is longer than expected
-        let long_piece_len = full_coded_piece_byte_len + 1;
-        let long_coded_piece: Vec<u8> = (0..long_piece_len).map(|_| rng.random()).collect();
-        let result_long = decoder.decode(&long_coded_piece);
-        assert!(result_long.is_err());
-        assert_eq!(
-            result_long.expect_err("Expected InvalidPieceLength error for long piece"),
-            RLNCError::InvalidPieceLength
-        );
+    /// Enables the dependency pre-filter, which rejects most linearly dependent pieces, before any elimination. A small random
+    /// sketch of the space spanned by useful pieces is kept, against which coding vector of each incoming piece is tested. Only
+    /// on sketch collision, the coding vector is checked exactly, still without touching payloads. Worth it, when many redundant
+    /// pieces arrive, say from multiple relays, as rejecting them otherwise costs a full elimination pass over large payloads.
+    pub fn enable_dependency_prefilter(&mut self) {
+        if self.prefilter.is_some() {
+            return;
+        }
 
-        // Test case 3: Piece length is zero
-        let zero_piece: Vec<u8> = Vec::new();
-        let result_zero = decoder.decode(&zero_piece);
-        assert!(result_zero.is_err());
-        assert_eq!(
-            result_zero.expect_err("Expected InvalidPieceLength error for zero-length piece"),
-            RLNCError::InvalidPieceLength
-        );
+        let mut prefilter = DependencySketch::new(self.required_piece_count, &mut rand::rng());
+        self.coefficient_rows().for_each(|coding_vector| prefilter.insert(coding_vector));
 
-        // Ensure decoder state is unchanged after invalid decode attempts
-        assert_eq!(decoder.get_received_piece_count(), 0);
-        assert_eq!(decoder.get_useful_piece_count(), 0);
-        assert!(!decoder.is_already_decoded());
+        self.prefilter = Some(prefilter);
+    }
 
-        // Test case 4: Valid coded piece - check if state changes
-        let correct_coded_piece = encoder.code(&mut rng);
-        let result_correct = decoder.decode(&correct_coded_piece);
-        assert!(result_correct.is_ok() || matches!(result_correct, Err(RLNCError::PieceNotUseful)));
+    /// Returns `true` if the dependency pre-filter is enabled.
+    pub fn is_dependency_prefilter_enabled(&self) -> bool {
+        self.prefilter.is_some()
+    }
 
-        // After a valid decode attempt, received_piece_count must increase
-        assert_eq!(decoder.get_received_piece_count(), 1);
+    /// Number of linearly dependent pieces rejected by the dependency pre-filter, without elimination. It's zero, unless the
+    /// pre-filter is enabled, see `Self::enable_dependency_prefilter`.
+    pub fn get_prefiltered_piece_count(&self) -> usize {
+        self.prefilter.as_ref().map_or(0, DependencySketch::get_rejected_count)
+    }
 
-        // If the piece was useful, useful_piece_count will be 1. Otherwise, it remains 0.
-        // Given the small piece_count in this test, it's very likely to be useful.
-        if result_correct.is_ok() {
-            assert_eq!(decoder.get_useful_piece_count(), 1);
-            assert!(!decoder.is_already_decoded()); // Unless piece_count was 1
-        } else {
-            assert_eq!(decoder.get_useful_piece_count(), 0);
+    /// Counts a received, well-formed piece, noting its arrival time, if arrival timing is enabled.
+    fn record_arrival(&mut self) {
+        self.received_piece_count += 1;
+        if let Some(arrivals) = self.arrivals.as_mut() {
+            arrivals.record(Instant::now());
         }
     }
 
+    /// Rejects a well-formed piece carrying an all-zero coding vector, if strict validation is enabled.
+    fn validate_strictly(&self, full_coded_piece: &[u8]) -> Result<(), RLNCError> {
+        if self.strict_generation.is_some() && full_coded_piece[..self.required_piece_count].iter().all(|&coeff| coeff == 0) {
+            return Err(RLNCError::ZeroCodingVector);
+        }
+
+        Ok(())
+    }
+
+    /// Writes the coefficient matrix, in hex, one row per line, along with current rank. Pivot of each row i.e. its first
+    /// non-zero coefficient, is highlighted within brackets. Pivots are not normalized, till decoded data is extracted.
+    #[cfg(feature = "debug-tools")]
+    pub fn dump_matrix<W: std::io::Write + ?Sized>(&self, writer: &mut W) -> std::io::Result<()> {
+        write_coefficient_matrix(writer, self.coefficient_rows(), self.required_piece_count)
+    }
+
+    /// Enables step-by-step elimination trace, writing each received coding vector, whether it increased rank, and the resulting
+    /// coefficient matrix, into given writer. For a piece, which turns out to be not useful, it also writes which rows of the
+    /// matrix it's a linear combination of. It's verbose and slow, meant for debugging only. Failing writes are ignored.
+    /// Clones of this decoder write into the same writer.
+    #[cfg(feature = "debug-tools")]
+    pub fn with_elimination_trace<W: std::io::Write + Send + 'static>(mut self, writer: W) -> Decoder {
+        self.trace = Some(EliminationTrace::new(writer));
+        self
+    }
+
+    /// Coefficient part of each row of the decoder matrix.
+    fn coefficient_rows(&self) -> impl ExactSizeIterator<Item = &[u8]> + Clone {
+        (0..self.matrix.rank()).map(|ridx| &self.matrix.row(ridx)[..self.required_piece_count])
+    }
+
+    /// Records an elimination step, if tracing is enabled.
+    #[cfg(feature = "debug-tools")]
+    fn record_trace(&mut self, coding_vector: &[u8], rank_before: usize) {
+        if let Some(mut trace) = self.trace.take() {
+            trace.record(coding_vector, rank_before, self.coefficient_rows(), self.required_piece_count);
+            self.trace = Some(trace);
+        }
+    }
+
+    /// Seeds the decoder with an original (i.e. uncoded) source piece, which the receiver already obtained out-of-band,
+    /// say from a local cache or from an earlier partial transfer.
+    ///
+    /// The source piece is added to the decoder's matrix as a row with unit coding vector, so it counts as a useful piece,
+    /// reducing the number of coded pieces still required for decoding. Note, it doesn't count as a received piece.
+    ///
+    /// # Arguments
+    /// * `index` - Index of the source piece, must be less than `self.get_num_pieces_coded_together()`.
+    /// * `data` - Source piece, as it was after padding the original data. Its length must be `self.get_piece_byte_len()`.
+    ///
+    /// # Returns
+    /// * Returns `Ok(())` if the source piece was useful and added successfully.
+    /// * Returns `Err(RLNCError::ReceivedAllPieces)` if decoding is already complete.
+    /// * Returns `Err(RLNCError::InvalidPieceIndex)` if `index` is out of range.
+    /// * Returns `Err(RLNCError::InvalidPieceLength)` if `data` has an unexpected length.
+    /// * Returns `Err(RLNCError::PieceNotUseful)` if the source piece is already recoverable from the useful pieces received so far.
+    pub fn insert_known_piece(&mut self, index: usize, data: &[u8]) -> Result<(), RLNCError> {
+        if self.is_already_decoded() {
+            return Err(RLNCError::ReceivedAllPieces);
+        }
+        if index >= self.required_piece_count {
+            return Err(RLNCError::InvalidPieceIndex);
+        }
+        if data.len() != self.piece_byte_len {
+            return Err(RLNCError::InvalidPieceLength);
+        }
+
+        let mut full_piece = vec![0u8; self.get_full_coded_piece_byte_len()];
+        full_piece[index] = 1;
+        full_piece[self.required_piece_count..].copy_from_slice(data);
+
+        // Unit row is zero before its pivot, so unless another row holds the same pivot, it goes in as is, without elimination.
+        if self.pivot_rows[index].is_none() {
+            return self.add_unit_piece(&full_piece, index);
+        }
+        self.add_piece(&full_piece)
+    }
+
+    /// Registers rank watermarks, say at 50%, 90% and 100% of `self.get_num_pieces_coded_together()`, and returns a receiver,
+    /// over which each watermark is sent, as soon as decoder rank reaches it. It saves one from polling getters on every piece,
+    /// to drive a progress bar or to start pre-fetching the next generation. Watermarks which are already reached, are sent
+    /// right away. If rank drops because of `Self::evict_pieces`, watermarks get re-armed and are sent again, once reached.
+    ///
+    /// # Arguments
+    /// * `watermarks` - Ranks to be watched, each in `1..=self.get_num_pieces_coded_together()`.
+    ///
+    /// # Returns
+    /// * Returns `Ok(mpsc::Receiver<usize>)`, receiving reached watermarks in ascending order, on success.
+    /// * Returns `Err(RLNCError::InvalidRankWatermark)` if any of the watermarks is out of range.
+    pub fn watch_rank(&mut self, watermarks: &[usize]) -> Result<mpsc::Receiver<usize>, RLNCError> {
+        if watermarks.iter().any(|&watermark| watermark == 0 || watermark > self.required_piece_count) {
+            return Err(RLNCError::InvalidRankWatermark);
+        }
+
+        let (sender, receiver) = mpsc::channel();
+        let mut watcher = RankWatcher::new(watermarks.to_vec(), sender);
+        watcher.on_rank(self.matrix.rank());
+
+        self.rank_watchers.push(watcher);
+        Ok(receiver)
+    }
+
+    /// Evicts suspect pieces from the decoder, rebuilding the decoder state from the remaining retained pieces.
+    ///
+    /// When an integrity check (say a checksum or digest over decoded data) reveals a corrupted decode, one can
+    /// re-validate all useful pieces received so far (say against per-piece checksums) and get rid of the polluted ones.
+    /// After eviction, the decoder can keep receiving pieces, to make up for the evicted ones.
+    ///
+    /// # Arguments
+    /// * `is_suspect` - Called with each retained full coded piece i.e. coding vector followed by coded data. Returns
+    ///   `true` if the piece is to be evicted.
+    ///
+    /// # Returns
+    /// * Returns `Ok(usize)` i.e. number of evicted pieces, on success.
+    /// * Returns `Err(RLNCError::PieceRetentionDisabled)` if decoder was not created using `Self::new_with_piece_retention`.
+    /// * Returns `Err(RLNCError::PieceStorageFailure)` if backing storage can't grow, while rebuilding the decoder.
+    pub fn evict_pieces<F>(&mut self, mut is_suspect: F) -> Result<usize, RLNCError>
+    where
+        F: FnMut(&[u8]) -> bool,
+    {
+        let retained_pieces = self.retained_pieces.take().ok_or(RLNCError::PieceRetentionDisabled)?;
+        let full_coded_piece_byte_len = self.get_full_coded_piece_byte_len();
+
+        self.matrix.clear();
+        self.pivot_rows.fill(None);
+        self.useful_piece_count = 0;
+        self.unit_rows_only = true;
+        if let Some(prefilter) = self.prefilter.as_mut() {
+            prefilter.clear(&mut rand::rng());
+        }
+        self.rank_watchers.iter_mut().for_each(RankWatcher::reset);
+        self.retained_pieces = Some(Vec::with_capacity(retained_pieces.len()));
+
+        let mut num_evicted_pieces = 0;
+
+        // A subset of linearly independent pieces is still linearly independent, so each of the kept pieces must be useful.
+        for full_coded_piece in retained_pieces.chunks_exact(full_coded_piece_byte_len) {
+            if is_suspect(full_coded_piece) {
+                num_evicted_pieces += 1;
+            } else {
+                self.add_piece(full_coded_piece)?;
+            }
+        }
+
+        Ok(num_evicted_pieces)
+    }
+
+    /// Adds a full piece to the decoder's matrix, checking whether it increases the rank. If the piece is
+    /// useful and piece retention is enabled, a copy of it is retained.
+    fn add_piece(&mut self, full_piece: &[u8]) -> Result<(), RLNCError> {
+        if let Some(result) = self.add_piece_without_elimination(full_piece) {
+            return result;
+        }
+
+        let rank_before = self.matrix.rank();
+
+        self.push_row(full_piece)?;
+        self.eliminate_new_row();
+        self.on_row_eliminated(full_piece, rank_before)
+    }
+
+    /// Adds a full piece, which needs no Gaussian elimination i.e. one with unit coding vector, while all rows of the matrix carry
+    /// unit coding vectors too, see `Self::add_unit_piece`, or one rejected by the dependency pre-filter.
+    ///
+    /// # Returns
+    /// Returns same as `Self::add_piece`, or `None` if the piece needs elimination, leaving the matrix untouched.
+    fn add_piece_without_elimination(&mut self, full_piece: &[u8]) -> Option<Result<(), RLNCError>> {
+        let rank_before = self.matrix.rank();
+        let coding_vector = &full_piece[..self.required_piece_count];
+
+        if self.unit_rows_only
+            && let Some(cidx) = Self::unit_vector_column(coding_vector)
+        {
+            return Some(self.add_unit_piece(full_piece, cidx));
+        }
+        self.unit_rows_only = false;
+
+        if let Some(prefilter) = self.prefilter.as_mut()
+            && prefilter.rejects(coding_vector)
+        {
+            return Some(self.on_elimination_done(full_piece, rank_before));
+        }
+
+        None
+    }
+
+    /// Appends the row of a full piece to the matrix, as is, or with column-major payload layout, its coding vector along with
+    /// the unit vector tracking its payload slot, see `Self::add_column_major_row`. It's still to be eliminated.
+    fn push_row(&mut self, full_piece: &[u8]) -> Result<(), RLNCError> {
+        match self.layout {
+            PayloadLayout::RowMajor => {
+                self.matrix.add_row(full_piece)?;
+            }
+            PayloadLayout::ColumnMajor => {
+                self.add_column_major_row(&full_piece[..self.required_piece_count], self.matrix.rank())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Finishes adding a full piece, whose row got eliminated by `Self::eliminate_new_row`. With column-major payload layout,
+    /// payload of a useful piece is stored in its slot.
+    fn on_row_eliminated(&mut self, full_piece: &[u8], rank_before: usize) -> Result<(), RLNCError> {
+        if self.layout == PayloadLayout::ColumnMajor && self.matrix.rank() > rank_before {
+            let slot = rank_before;
+            full_piece[self.required_piece_count..]
+                .iter()
+                .zip(self.payloads.iter_mut().skip(slot).step_by(self.required_piece_count))
+                .for_each(|(&src, dst)| *dst = src);
+        }
+
+        self.on_elimination_done(full_piece, rank_before)
+    }
+
+    /// Reduces the row, just appended to the matrix, by rows holding pivots, same as `Self::eliminate_new_row` does, but in slices
+    /// of at max `row_ops_per_slice` row operations, yielding to the async executor in between. If the returned future is dropped
+    /// midway, the row is removed, leaving the matrix as it was. Pivot of the row is still to be found by `Self::eliminate_new_row`.
+    async fn reduce_new_row_in_slices(&mut self, row_ops_per_slice: usize) {
+        let piece_count = self.required_piece_count;
+        let pivot_rows = &self.pivot_rows;
+        let mut pending_row = PendingRow {
+            matrix: &mut self.matrix,
+            is_reduced: false,
+        };
+
+        let mut slice_begin = 0;
+        loop {
+            // Each slice spans as many columns, as it takes to cover `row_ops_per_slice` pivots.
+            let slice_end = pivot_rows
+                .iter()
+                .enumerate()
+                .skip(slice_begin)
+                .filter(|(_, pivot_ridx)| pivot_ridx.is_some())
+                .nth(row_ops_per_slice)
+                .map_or(piece_count, |(cidx, _)| cidx);
+
+            pending_row.matrix.reduce_last_row_by_columns(pivot_rows, slice_begin..slice_end);
+            if slice_end == piece_count {
+                break;
+            }
+
+            slice_begin = slice_end;
+            yield_now().await;
+        }
+
+        pending_row.is_reduced = true;
+    }
+
+    /// Appends a row to the matrix, with column-major payload layout. Each useful piece gets its own slot in the payload store,
+    /// which is tracked by a unit vector, appended to the coding vector. Gaussian elimination accumulates row operations on it.
+    fn add_column_major_row(&mut self, coding_vector: &[u8], slot: usize) -> Result<(), RLNCError> {
+        let (coefficients, row_operations) = self.scratch_row.split_at_mut(self.required_piece_count);
+        coefficients.copy_from_slice(coding_vector);
+        row_operations.fill(0);
+        row_operations[slot] = 1;
+
+        self.matrix.add_row(&self.scratch_row)?;
+        Ok(())
+    }
+
+    /// Reduces the row, just appended to the matrix, by existing rows, without touching them. If it's not reduced to zero, its
+    /// first non-zero coefficient becomes the pivot of a new dimension. Otherwise it's removed, leaving the matrix as it was.
+    fn eliminate_new_row(&mut self) {
+        match self.matrix.reduce_last_row(&self.pivot_rows) {
+            Some(pivot_cidx) => {
+                self.pivot_rows[pivot_cidx] = Some(self.matrix.rank() - 1);
+                self.normalize_last_unit_row();
+            }
+            None => {
+                self.matrix.remove_last_row();
+            }
+        }
+    }
+
+    /// Scales the last row of the matrix, if its coding vector is a unit vector, so that its pivot becomes one, and its payload is
+    /// the source piece, as is. Rows are never touched after insertion, till back-substitution, so they stay so. See `Self::recovered_piece`.
+    fn normalize_last_unit_row(&mut self) {
+        let piece_count = self.required_piece_count;
+        let row = self.matrix.row_mut(self.matrix.rank() - 1);
+
+        if let Some(cidx) = Self::unit_vector_column(&row[..piece_count])
+            && row[cidx] != 1
+        {
+            let inv = unsafe { Gf256::new(row[cidx]).inv().unwrap_unchecked() }.get();
+            gf256_inplace_mul_vec_by_scalar(row, inv);
+        }
+    }
+
+    /// Returns the column of the only non-zero coefficient of the coding vector, if it's a unit vector, possibly scaled.
+    fn unit_vector_column(coding_vector: &[u8]) -> Option<usize> {
+        let mut nonzero_columns = coding_vector.iter().enumerate().filter(|&(_, &coeff)| coeff != 0).map(|(cidx, _)| cidx);
+
+        match (nonzero_columns.next(), nonzero_columns.next()) {
+            (Some(cidx), None) => Some(cidx),
+            _ => None,
+        }
+    }
+
+    /// Fast path of `Self::add_piece`, for a piece with unit coding vector, while all rows of the matrix carry unit coding vectors
+    /// too. Such a piece is useful iff no row holds the pivot of the same column, in which case it's appended as is, without
+    /// even scanning other pivots. Rows stay in order of arrival, till back-substitution sorts them, with nothing to eliminate.
+    fn add_unit_piece(&mut self, full_piece: &[u8], cidx: usize) -> Result<(), RLNCError> {
+        let rank_before = self.matrix.rank();
+
+        if self.pivot_rows[cidx].is_none() {
+            match self.layout {
+                PayloadLayout::RowMajor => {
+                    self.matrix.add_row(full_piece)?;
+                }
+                PayloadLayout::ColumnMajor => {
+                    let (coding_vector, payload) = full_piece.split_at(self.required_piece_count);
+
+                    let slot = rank_before;
+                    self.add_column_major_row(coding_vector, slot)?;
+                    payload
+                        .iter()
+                        .zip(self.payloads.iter_mut().skip(slot).step_by(self.required_piece_count))
+                        .for_each(|(&src, dst)| *dst = src);
+                }
+            }
+
+            self.pivot_rows[cidx] = Some(rank_before);
+            self.normalize_last_unit_row();
+        }
+
+        self.on_elimination_done(full_piece, rank_before)
+    }
+
+    /// Checks whether elimination of a newly added full piece increased the rank. If so, updates bookkeeping and
+    /// notifies rank watchers. If the piece is useful and piece retention is enabled, a copy of it is retained.
+    fn on_elimination_done(&mut self, full_piece: &[u8], rank_before: usize) -> Result<(), RLNCError> {
+        #[cfg(feature = "debug-tools")]
+        self.record_trace(&full_piece[..self.required_piece_count], rank_before);
+
+        let rank_after = self.matrix.rank();
+
+        // If the rank didn't increase, the piece was not useful.
+        if rank_before == rank_after {
+            Err(RLNCError::PieceNotUseful)
+        } else {
+            self.useful_piece_count = rank_after;
+            if rank_after == self.required_piece_count {
+                self.back_substitute();
+            }
+            if let Some(prefilter) = self.prefilter.as_mut() {
+                prefilter.insert(&full_piece[..self.required_piece_count]);
+            }
+            if let Some(retained_pieces) = self.retained_pieces.as_mut() {
+                retained_pieces.extend_from_slice(full_piece);
+            }
+
+            // Watchers whose receiver is gone, are dropped.
+            self.rank_watchers.retain_mut(|watcher| watcher.on_rank(rank_after));
+
+            Ok(())
+        }
+    }
+
+    /// Full coded pieces, one per row of the decoder matrix, spanning the same space as all useful pieces received so far, concatenated.
+    fn basis_pieces(&self) -> Vec<u8> {
+        let piece_count = self.required_piece_count;
+
+        match self.layout {
+            PayloadLayout::RowMajor => self.matrix.to_bytes(),
+            PayloadLayout::ColumnMajor => {
+                let mut pieces = Vec::with_capacity(self.matrix.rank() * self.get_full_coded_piece_byte_len());
+                for row in self.matrix.rows() {
+                    let (coding_vector, row_operations) = row.split_at(piece_count);
+                    let payload_begin = pieces.len() + piece_count;
+
+                    pieces.extend_from_slice(coding_vector);
+                    pieces.resize(payload_begin + self.piece_byte_len, 0);
+                    self.accumulate_column_major_payload(row_operations, &mut pieces[payload_begin..]);
+                }
+
+                pieces
+            }
+        }
+    }
+
+    /// Accumulates payload of a row into given buffer, with column-major payload layout. Payload of a row is the combination of
+    /// payloads of useful pieces, as tracked by its row operations. Payload of a useful piece is a column of the payload store, so
+    /// it's accumulated strided, without transposing.
+    fn accumulate_column_major_payload(&self, row_operations: &[u8], payload: &mut [u8]) {
+        row_operations.iter().enumerate().take(self.matrix.rank()).for_each(|(slot, &coeff)| {
+            gf256_strided_mul_vec_by_scalar_then_add_into_vec(payload, 1, &self.payloads[slot..], self.required_piece_count, self.piece_byte_len, coeff)
+        });
+    }
+
+    /// Runs the deferred back-substitution, once decoding is complete, so that each row carries a unit coding vector, with
+    /// pivot of its row index. See `Self::recovered_piece_indices`.
+    fn back_substitute(&mut self) {
+        self.matrix.back_substitute().normalize_pivots();
+        self.pivot_rows.iter_mut().enumerate().for_each(|(ridx, pivot_ridx)| *pivot_ridx = Some(ridx));
+    }
+
+    /// Merges progress of another decoder of the same generation into this one, so that the union of their row spaces is decoded,
+    /// say on a device receiving over Wi-Fi and LTE at once, each interface feeding its own decoder. Payload layouts may differ.
+    /// Received piece counts are summed up and truncated pieces are carried over, while audit log, rank watchers and strict
+    /// validation of `other` are dropped. Rank watchers of this decoder get notified, as usual.
+    ///
+    /// # Returns
+    /// * Returns `Ok(usize)` holding the number of dimensions gained i.e. increase in rank.
+    /// * Returns `Err(RLNCError::ParamsMismatch)` if `other` doesn't share RLNC configuration with this decoder, which is left as is.
+    /// * Returns `Err(RLNCError::PieceStorageFailure)` if backing storage can't grow. See `Self::new_with_storage`.
+    pub fn merge(&mut self, other: Decoder) -> Result<usize, RLNCError> {
+        if other.get_params() != self.get_params() {
+            return Err(RLNCError::ParamsMismatch);
+        }
+
+        let rank_before = self.matrix.rank();
+        let full_coded_piece_byte_len = self.get_full_coded_piece_byte_len();
+
+        for piece in other.basis_pieces().chunks_exact(full_coded_piece_byte_len) {
+            if self.is_already_decoded() {
+                break;
+            }
+
+            match self.add_piece(piece) {
+                Ok(()) | Err(RLNCError::PieceNotUseful) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        self.received_piece_count += other.received_piece_count;
+        self.keep_truncated_pieces(other.truncated_pieces);
+
+        Ok(self.matrix.rank() - rank_before)
+    }
+
+    /// Produces a new coded piece, as a random linear combination of useful pieces received so far, writing it into the provided
+    /// buffer, so that a decode-and-forward node can forward innovative combinations of its current row space, without keeping a
+    /// second copy of received pieces in a `Recoder`. Rows of the decoder matrix span the same space as useful pieces, so they're
+    /// recoded in place, unless payloads are stored column-major, when rows are materialized first. Truncated pieces are not recoded.
+    ///
+    /// # Arguments
+    /// * `rng` - Used to sample the coefficient of each row.
+    /// * `full_recoded_piece` - A mutable slice to write the full coded piece into, of `self.get_full_coded_piece_byte_len()` bytes.
+    ///
+    /// # Returns
+    /// * Returns `Ok(())` when successful.
+    /// * Returns `Err(RLNCError::NotEnoughPiecesToRecode)` if no useful piece has been received yet.
+    /// * Returns `Err(RLNCError::InvalidOutputBuffer)` if the length of `full_recoded_piece` is incorrect.
+    pub fn recode_with_buf<R: Rng + ?Sized>(&self, rng: &mut R, full_recoded_piece: &mut [u8]) -> Result<(), RLNCError> {
+        if self.matrix.rank() == 0 {
+            return Err(RLNCError::NotEnoughPiecesToRecode);
+        }
+        if full_recoded_piece.len() != self.get_full_coded_piece_byte_len() {
+            return Err(RLNCError::InvalidOutputBuffer);
+        }
+
+        full_recoded_piece.fill(0);
+        match self.layout {
+            PayloadLayout::RowMajor => self
+                .matrix
+                .rows()
+                .for_each(|row| gf256_mul_vec_by_scalar_then_add_into_vec(full_recoded_piece, row, rng.random())),
+            PayloadLayout::ColumnMajor => self
+                .basis_pieces()
+                .chunks_exact(full_recoded_piece.len())
+                .for_each(|row| gf256_mul_vec_by_scalar_then_add_into_vec(full_recoded_piece, row, rng.random())),
+        }
+
+        Ok(())
+    }
+
+    /// Same as `Self::recode_with_buf`, but allocates the recoded piece.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Vec<u8>)` holding coding vector followed by recoded data.
+    /// * Returns `Err(RLNCError::NotEnoughPiecesToRecode)` if no useful piece has been received yet.
+    pub fn recode<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Vec<u8>, RLNCError> {
+        let mut full_recoded_piece = vec![0u8; self.get_full_coded_piece_byte_len()];
+        self.recode_with_buf(rng, &mut full_recoded_piece)?;
+
+        Ok(full_recoded_piece)
+    }
+
+    /// Converts a partially filled decoder into a `Recoder`, over rows of the decoder matrix, which span the same space as useful
+    /// pieces received so far, say when a node stops decoding and keeps forwarding only. At max `k` pieces get recoded together.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Recoder)` on success.
+    /// * Returns `Err(RLNCError::NotEnoughPiecesToRecode)` if no useful piece has been received yet.
+    pub fn into_recoder(self) -> Result<Recoder, RLNCError> {
+        Recoder::new_with(self.basis_pieces(), self.get_params())
+    }
+
+    /// Returns indices of coefficient columns, which still lack a pivot, in ascending order.
+    ///
+    /// Each of them is a dimension of the original data, which the received pieces don't cover yet. A receiver can report
+    /// these back to the sender, which can then bias coding coefficients towards the missing subspace. Once decoding
+    /// is complete, it's empty.
+    pub fn missing_dimensions(&self) -> Vec<usize> {
+        self.pivot_rows
+            .iter()
+            .enumerate()
+            .filter(|(_, pivot_ridx)| pivot_ridx.is_none())
+            .map(|(cidx, _)| cidx)
+            .collect()
+    }
+
+    /// Takes a snapshot of the decoder state, from which decoding can be resumed later, using `Self::from_snapshot`.
+    pub fn snapshot(&self) -> DecoderSnapshot {
+        DecoderSnapshot {
+            layout: self.layout,
+            piece_byte_len: self.piece_byte_len,
+            required_piece_count: self.required_piece_count,
+            received_piece_count: self.received_piece_count,
+            matrix_rows: self.matrix.to_bytes(),
+            payloads: self.payloads.clone(),
+        }
+    }
+
+    /// Restores a decoder from a snapshot, taken using `Self::snapshot`. Restored decoder neither retains pieces, nor keeps an audit log.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Decoder)` on success.
+    /// * Returns `Err(RLNCError::PieceLengthZero)` if piece length of the snapshot is zero.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if piece count of the snapshot is zero.
+    /// * Returns `Err(RLNCError::InvalidSnapshot)` if matrix rows or payloads are inconsistent with the snapshotted configuration.
+    pub fn from_snapshot(snapshot: DecoderSnapshot) -> Result<Decoder, RLNCError> {
+        let mut decoder = Self::new_with_layout(snapshot.piece_byte_len, snapshot.required_piece_count, snapshot.layout)?;
+
+        let row_byte_len = match snapshot.layout {
+            PayloadLayout::RowMajor => decoder.get_full_coded_piece_byte_len(),
+            PayloadLayout::ColumnMajor => 2 * decoder.required_piece_count,
+        };
+        let num_rows = snapshot.matrix_rows.len() / row_byte_len;
+
+        if num_rows * row_byte_len != snapshot.matrix_rows.len() || num_rows > decoder.required_piece_count {
+            return Err(RLNCError::InvalidSnapshot);
+        }
+        if snapshot.payloads.len() != decoder.payloads.len() {
+            return Err(RLNCError::InvalidSnapshot);
+        }
+        if snapshot.received_piece_count < num_rows {
+            return Err(RLNCError::InvalidSnapshot);
+        }
+
+        // Rows are already in row echelon form, so they are restored as is, as long as pivot columns are distinct.
+        for (ridx, row) in snapshot.matrix_rows.chunks_exact(row_byte_len).enumerate() {
+            let pivot_cidx = row[..decoder.required_piece_count]
+                .iter()
+                .position(|&coeff| coeff != 0)
+                .ok_or(RLNCError::InvalidSnapshot)?;
+            if decoder.pivot_rows[pivot_cidx].replace(ridx).is_some() {
+                return Err(RLNCError::InvalidSnapshot);
+            }
+
+            decoder.matrix.add_row(row)?;
+        }
+
+        decoder.payloads = snapshot.payloads;
+        decoder.received_piece_count = snapshot.received_piece_count;
+        decoder.useful_piece_count = num_rows;
+        decoder.unit_rows_only = num_rows == 0;
+        if num_rows == decoder.required_piece_count {
+            decoder.back_substitute();
+        }
+
+        Ok(decoder)
+    }
+
+    /// Reduces a copy of the row holding the pivot of given column, by rows holding pivots of later columns, as back-substitution
+    /// would, so that it's returned with its pivot normalized to one, if it's left with the pivot only i.e. the source piece is
+    /// recovered. If `coding_vector_only` is set, payload, or row operations, are neither reduced nor returned.
+    fn reduced_pivot_row(&self, index: usize, coding_vector_only: bool) -> Option<Vec<u8>> {
+        let piece_count = self.required_piece_count;
+        let pivot_row = self.matrix.row(self.pivot_rows.get(index).copied()??);
+        let byte_len = if coding_vector_only { piece_count } else { pivot_row.len() };
+        let mut row = pivot_row[..byte_len].to_vec();
+
+        // Rows are zero before their pivot, so a column is final, once it's reached. If no row holds its pivot, it stays non-zero.
+        for cidx in index + 1..piece_count {
+            let coeff = row[cidx];
+            if coeff == 0 {
+                continue;
+            }
+
+            let pivot_row = &self.matrix.row(self.pivot_rows[cidx]?)[..byte_len];
+            let scalar = Gf256::mul_const(coeff, unsafe { Gf256::new(pivot_row[cidx]).inv().unwrap_unchecked() }.get());
+            gf256_mul_vec_by_scalar_then_add_into_vec(&mut row, pivot_row, scalar);
+        }
+
+        let pivot_inv = unsafe { Gf256::new(row[index]).inv().unwrap_unchecked() }.get();
+        gf256_inplace_mul_vec_by_scalar(&mut row, pivot_inv);
+
+        Some(row)
+    }
+
+    /// Returns indices of source pieces, which are already recovered, in ascending order, so that streaming applications can
+    /// deliver early data, before the whole generation decodes. A source piece is recovered, once the row holding its pivot is
+    /// left with the pivot only, after being reduced by rows holding pivots of later columns i.e. once it's a unit vector in the
+    /// reduced row echelon form. Back-substitution is deferred till decoding completes, so coding vectors are reduced on demand,
+    /// which costs `O(k^3)` for `k` source pieces, in the worst case.
+    pub fn recovered_piece_indices(&self) -> Vec<usize> {
+        (0..self.required_piece_count)
+            .filter(|&index| self.reduced_pivot_row(index, true).is_some())
+            .collect()
+    }
+
+    /// Returns source piece at given index, as it is after padding the original data, borrowed from the decoder matrix, if its row
+    /// already carries the unit coding vector, say it's a systematic piece, or a piece reduced to a scaled one on arrival, or
+    /// decoding is complete. A source piece, which is recovered only by combining rows, see `Self::recovered_piece_indices`, is
+    /// not returned before decoding completes, nor any with column-major payload layout, use `Self::recovered_piece_to_vec` instead.
+    pub fn recovered_piece(&self, index: usize) -> Option<&[u8]> {
+        if self.layout != PayloadLayout::RowMajor {
+            return None;
+        }
+
+        let piece_count = self.required_piece_count;
+        let row = self.matrix.row(self.pivot_rows.get(index).copied()??);
+        let is_unit_row = Self::unit_vector_column(&row[..piece_count]) == Some(index) && row[index] == 1;
+
+        is_unit_row.then(|| &row[piece_count..piece_count + self.piece_byte_len])
+    }
+
+    /// Returns a copy of source piece at given index, as it is after padding the original data, if it's already recovered, see
+    /// `Self::recovered_piece_indices`, reducing its row on demand. With column-major payload layout, it's accumulated out of
+    /// payloads of useful pieces.
+    pub fn recovered_piece_to_vec(&self, index: usize) -> Option<Vec<u8>> {
+        let piece_count = self.required_piece_count;
+        let row = self.reduced_pivot_row(index, false)?;
+
+        match self.layout {
+            PayloadLayout::RowMajor => Some(row[piece_count..piece_count + self.piece_byte_len].to_vec()),
+            PayloadLayout::ColumnMajor => {
+                let mut piece = vec![0u8; self.piece_byte_len];
+                self.accumulate_column_major_payload(&row[piece_count..], &mut piece);
+
+                Some(piece)
+            }
+        }
+    }
+
+    /// Checks if the decoder has received enough linearly independent pieces to recover the original data.
+    pub fn is_already_decoded(&self) -> bool {
+        self.matrix.rank() == self.required_piece_count
+    }
+
+    /// Recovers leading bytes of all source pieces, as far as possible, using truncated pieces, received using
+    /// `Self::decode_truncated`, along with full ones.
+    ///
+    /// Every byte column of source pieces is an independent linear system, so first `m` bytes of all source pieces can be recovered,
+    /// once there are `self.get_num_pieces_coded_together()` linearly independent pieces, each carrying at least `m` bytes of payload.
+    /// Trailing columns are covered by full pieces only, so recovering all of the original data still requires decoding to complete.
+    /// Meanwhile, this lets applications, laying out most important bytes first in each source piece, make use of partially
+    /// received data. It runs Gaussian elimination, so it's costly.
+    ///
+    /// # Returns
+    /// Returns one prefix per source piece, all of the same length, which is zero, if nothing can be recovered yet,
+    /// or `self.get_piece_byte_len()`, if decoding is complete.
+    pub fn recover_piece_prefixes(&self) -> Vec<Vec<u8>> {
+        let (padded_data, prefix_len) = self.recover_leading_columns();
+
+        padded_data
+            .chunks_exact(self.piece_byte_len)
+            .map(|piece| piece[..prefix_len].to_vec())
+            .collect()
+    }
+
+    /// Recovers and returns the original data byte vector if decoding is complete.
+    ///
+    /// Runs the deferred back-substitution, bringing the matrix into Reduced Row Echelon Form (RREF),
+    /// normalizes each pivot exactly once, and extracts the original data pieces corresponding to
+    /// the identity matrix part of the coefficient matrix. It also handles the boundary marker to
+    /// determine the original data length and trims padding.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Vec<u8>)` containing the decoded data if successful.
+    /// * Returns `Err(RLNCError::NotAllPiecesReceivedYet)` if not enough useful pieces have been received.
+    /// * Returns `Err(RLNCError::InvalidDecodedDataFormat(_))` if the extracted data does not follow the expected format (e.g., boundary marker issues).
+    pub fn get_decoded_data(self) -> Result<Vec<u8>, RLNCError> {
+        if !self.is_already_decoded() {
+            return Err(RLNCError::NotAllPiecesReceivedYet);
+        }
+
+        let (piece_count, received_piece_count) = (self.required_piece_count, self.received_piece_count);
+        let mut buf = self.extract_padded_data();
+        let final_len = Self::get_final_data_len(&buf, piece_count, received_piece_count)?;
+
+        buf.truncate(final_len);
+        Ok(buf)
+    }
+
+    /// Same as `Self::get_decoded_data`, but writes decoded data into given buffer, instead of allocating a new vector, so that
+    /// peak memory of decoding a large object isn't doubled by the output. Padding gets written too, before being trimmed off,
+    /// so the buffer must be able to hold `self.get_piece_count() * self.get_piece_byte_len()` bytes.
+    ///
+    /// # Arguments
+    /// * `buf` - Output buffer, of at least `self.get_piece_count() * self.get_piece_byte_len()` bytes. Bytes past the returned
+    ///   length are left holding padding.
+    ///
+    /// # Returns
+    /// * Returns `Ok(usize)` holding the byte length of decoded data, written at the start of `buf`, if successful.
+    /// * Returns `Err(RLNCError::NotAllPiecesReceivedYet)` if not enough useful pieces have been received.
+    /// * Returns `Err(RLNCError::InvalidOutputBuffer)` if `buf` is too short to hold padded data.
+    /// * Returns `Err(RLNCError::InvalidDecodedDataFormat(_))` if the extracted data does not follow the expected format (e.g., boundary marker issues).
+    pub fn get_decoded_data_into(self, buf: &mut [u8]) -> Result<usize, RLNCError> {
+        if !self.is_already_decoded() {
+            return Err(RLNCError::NotAllPiecesReceivedYet);
+        }
+
+        let padded_len = self.piece_byte_len * self.required_piece_count;
+        if buf.len() < padded_len {
+            return Err(RLNCError::InvalidOutputBuffer);
+        }
+
+        let (piece_count, received_piece_count) = (self.required_piece_count, self.received_piece_count);
+        let padded_data = &mut buf[..padded_len];
+        self.write_padded_data(padded_data);
+
+        Self::get_final_data_len(padded_data, piece_count, received_piece_count)
+    }
+
+    /// Same as `Self::get_decoded_data`, but streams decoded data into given sink, say a file or a socket, piece by piece, as
+    /// they're extracted off the decoder matrix, so that reconstructing a large object never holds a second copy of it in memory.
+    /// Boundary marker and padding are located first, so that only decoded data gets written. With `PayloadLayout::ColumnMajor`
+    /// layout, source pieces are interleaved in memory, so decoded data is extracted in full, before being written.
+    ///
+    /// # Arguments
+    /// * `sink` - Writer, decoded data is written to. It's not flushed.
+    ///
+    /// # Returns
+    /// * Returns `Ok(usize)` holding the byte length of decoded data, written to `sink`, if successful.
+    /// * Returns `Err(RLNCError::NotAllPiecesReceivedYet)` if not enough useful pieces have been received.
+    /// * Returns `Err(RLNCError::InvalidDecodedDataFormat(_))` if the extracted data does not follow the expected format (e.g., boundary
+    ///   marker issues), in which case nothing is written.
+    /// * Returns `Err(RLNCError::WriteFailure)` if writing to `sink` fails.
+    pub fn write_decoded_data<W: Write>(mut self, mut sink: W) -> Result<usize, RLNCError> {
+        if !self.is_already_decoded() {
+            return Err(RLNCError::NotAllPiecesReceivedYet);
+        }
+        if self.layout == PayloadLayout::ColumnMajor {
+            let decoded_data = self.get_decoded_data()?;
+            sink.write_all(&decoded_data).map_err(|_| RLNCError::WriteFailure)?;
+
+            return Ok(decoded_data.len());
+        }
+
+        self.matrix.back_substitute().normalize_pivots();
+        let (piece_count, piece_byte_len) = (self.required_piece_count, self.piece_byte_len);
+
+        // Well-formed padded data's last non-zero byte is the boundary marker, not being the first byte, whose index is the decoded data length.
+        let last_nonzero_byte_index = (0..self.matrix.rank()).rev().find_map(|ridx| {
+            self.matrix.row(ridx)[piece_count..]
+                .iter()
+                .rposition(|&byte| byte != 0)
+                .map(|offset| (ridx, offset))
+        });
+
+        let final_len = match last_nonzero_byte_index {
+            Some((ridx, offset)) if self.matrix.row(ridx)[piece_count + offset] == BOUNDARY_MARKER && ridx * piece_byte_len + offset > 0 => {
+                ridx * piece_byte_len + offset
+            }
+            _ => {
+                let padded_data = self.matrix.rows().flat_map(|row| &row[piece_count..]).copied().collect::<Vec<u8>>();
+                return Err(RLNCError::InvalidDecodedDataFormat(Box::new(DecodedDataDiagnostics::new(
+                    &padded_data,
+                    piece_count,
+                    self.received_piece_count,
+                    BOUNDARY_MARKER,
+                ))));
+            }
+        };
+
+        let mut remaining_len = final_len;
+        for row in self.matrix.rows() {
+            if remaining_len == 0 {
+                break;
+            }
+
+            let payload_len = remaining_len.min(piece_byte_len);
+            sink.write_all(&row[piece_count..piece_count + payload_len])
+                .map_err(|_| RLNCError::WriteFailure)?;
+            remaining_len -= payload_len;
+        }
+
+        Ok(final_len)
+    }
+
+    /// Extracts all decoded source pieces, concatenated, which is nothing but the padded original data.
+    /// Must be called only after decoding is complete.
+    pub(crate) fn extract_padded_data(self) -> Vec<u8> {
+        let mut buf = vec![0u8; self.piece_byte_len * self.required_piece_count];
+        self.write_padded_data(&mut buf);
+
+        buf
+    }
+
+    /// Writes all decoded source pieces, concatenated, into given buffer, of exactly `self.piece_byte_len * self.required_piece_count`
+    /// bytes. Must be called only after decoding is complete.
+    fn write_padded_data(mut self, buf: &mut [u8]) {
+        self.matrix.back_substitute().normalize_pivots();
+
+        match self.layout {
+            PayloadLayout::RowMajor => {
+                // Write the decoded data piece by piece into the output buffer
+                for (row, piece) in self.matrix.rows().zip(buf.chunks_exact_mut(self.piece_byte_len)) {
+                    piece.copy_from_slice(&row[self.required_piece_count..]);
+                }
+            }
+            PayloadLayout::ColumnMajor => {
+                let piece_count = self.required_piece_count;
+
+                // Coefficient part of the matrix is identity now, while the other part holds the accumulated row operations `T`,
+                // s.t. i-th decoded piece = sum over j of `T[i][j]` x j-th payload. Transpose `T`, so that each column of `T` is contiguous.
+                let row_operations = self.matrix.extract_data();
+                let mut transposed_row_operations = vec![0u8; piece_count * piece_count];
+
+                row_operations.chunks_exact(2 * piece_count).enumerate().for_each(|(ridx, row)| {
+                    row[piece_count..].iter().enumerate().for_each(|(cidx, &symbol)| {
+                        transposed_row_operations[cidx * piece_count + ridx] = symbol;
+                    });
+                });
+
+                // Each column of payloads gets multiplied by `T`, as a linear combination of columns of `T`.
+                let mut decoded_column = vec![0u8; piece_count];
+
+                self.payloads.chunks_exact(piece_count).enumerate().for_each(|(cidx, payload_column)| {
+                    decoded_column.fill(0);
+
+                    payload_column
+                        .iter()
+                        .zip(transposed_row_operations.chunks_exact(piece_count))
+                        .for_each(|(&symbol, row_operation_column)| {
+                            gf256_mul_vec_by_scalar_then_add_into_vec(&mut decoded_column, row_operation_column, symbol);
+                        });
+
+                    decoded_column.iter().enumerate().for_each(|(ridx, &symbol)| {
+                        buf[ridx * self.piece_byte_len + cidx] = symbol;
+                    });
+                });
+            }
+        }
+    }
+
+    /// Returns rows of the decoder matrix as full pieces i.e. coding vector followed by payload, in row echelon form, up to their order.
+    fn reduced_full_pieces(&self) -> Vec<u8> {
+        match self.layout {
+            PayloadLayout::RowMajor => self.matrix.to_bytes(),
+            PayloadLayout::ColumnMajor => {
+                let piece_count = self.required_piece_count;
+
+                // Payload slots, row-major, so that each payload of a reduced row is a linear combination of them, as
+                // prescribed by accumulated row operations.
+                let mut slots = vec![0u8; piece_count * self.piece_byte_len];
+                self.payloads.chunks_exact(piece_count).enumerate().for_each(|(cidx, payload_column)| {
+                    payload_column.iter().enumerate().for_each(|(slot, &symbol)| {
+                        slots[slot * self.piece_byte_len + cidx] = symbol;
+                    });
+                });
+
+                let mut full_pieces = Vec::with_capacity(self.matrix.rank() * self.get_full_coded_piece_byte_len());
+                let mut payload = vec![0u8; self.piece_byte_len];
+
+                for row in self.matrix.rows() {
+                    let (coding_vector, row_operations) = row.split_at(piece_count);
+
+                    payload.fill(0);
+                    row_operations
+                        .iter()
+                        .zip(slots.chunks_exact(self.piece_byte_len))
+                        .for_each(|(&symbol, slot)| gf256_mul_vec_by_scalar_then_add_into_vec(&mut payload, slot, symbol));
+
+                    full_pieces.extend_from_slice(coding_vector);
+                    full_pieces.extend_from_slice(&payload);
+                }
+
+                full_pieces
+            }
+        }
+    }
+
+    /// Recovers leading byte columns of all source pieces, using truncated pieces along with full ones.
+    ///
+    /// Columns are split into segments, at payload lengths of truncated pieces. Each segment is solved separately, using full
+    /// pieces and those truncated pieces, which cover it completely, till a segment can't be solved.
+    ///
+    /// Returns all source pieces concatenated, along with the number of leading bytes of each, which are recovered.
+    fn recover_leading_columns(&self) -> (Vec<u8>, usize) {
+        let piece_count = self.required_piece_count;
+        let full_coded_piece_byte_len = self.get_full_coded_piece_byte_len();
+        let full_pieces = self.reduced_full_pieces();
+
+        let mut segment_ends = self.truncated_pieces.iter().map(|piece| piece.len() - piece_count).collect::<Vec<_>>();
+        segment_ends.push(self.piece_byte_len);
+        segment_ends.sort_unstable();
+        segment_ends.dedup();
+
+        let mut buf = vec![0u8; piece_count * self.piece_byte_len];
+        let mut segment_begin = 0;
+
+        for segment_end in segment_ends {
+            let segment_byte_len = segment_end - segment_begin;
+
+            let mut matrix = DecoderMatrix::new(piece_count, segment_byte_len);
+            let mut row = Vec::with_capacity(piece_count + segment_byte_len);
+
+            let covering_pieces = full_pieces.chunks_exact(full_coded_piece_byte_len).chain(
+                self.truncated_pieces
+                    .iter()
+                    .filter(|piece| piece.len() - piece_count >= segment_end)
+                    .map(Vec::as_slice),
+            );
+
+            for piece in covering_pieces {
+                row.clear();
+                row.extend_from_slice(&piece[..piece_count]);
+                row.extend_from_slice(&piece[piece_count + segment_begin..piece_count + segment_end]);
+
+                if matrix.add_row(&row).is_err() {
+                    break;
+                }
+                if matrix.rref().rank() == piece_count {
+                    break;
+                }
+            }
+
+            if matrix.pivot_columns().count() < piece_count {
+                break;
+            }
+            matrix.normalize_pivots();
+
+            // Coefficient part is identity now, so i-th row holds the segment of i-th source piece.
+            for (ridx, row) in matrix.rows().enumerate() {
+                let offset = ridx * self.piece_byte_len;
+                buf[offset + segment_begin..offset + segment_end].copy_from_slice(&row[piece_count..]);
+            }
+
+            segment_begin = segment_end;
+        }
+
+        (buf, segment_begin)
+    }
+
+    /// Sets up an empty decoder matrix, suitable for the given payload layout.
+    fn new_matrix(piece_byte_len: usize, required_piece_count: usize, layout: PayloadLayout) -> DecoderMatrix {
+        match layout {
+            PayloadLayout::RowMajor => DecoderMatrix::new(required_piece_count, piece_byte_len),
+            PayloadLayout::ColumnMajor => DecoderMatrix::new(required_piece_count, required_piece_count),
+        }
+    }
+
+    /// Helper to find the boundary marker, validate padding, and return the final length of the original data. Piece counts only
+    /// go into diagnostics, on failure.
+    pub(crate) fn get_final_data_len(padded_data: &[u8], piece_count: usize, received_piece_count: usize) -> Result<usize, RLNCError> {
+        let invalid_format = || {
+            RLNCError::InvalidDecodedDataFormat(Box::new(DecodedDataDiagnostics::new(
+                padded_data,
+                piece_count,
+                received_piece_count,
+                BOUNDARY_MARKER,
+            )))
+        };
+
+        let last_index = padded_data.len().saturating_sub(1);
+
+        let boundary_marker_rev_index = padded_data.iter().rev().position(|&byte| byte == BOUNDARY_MARKER).unwrap_or(last_index);
+        let boundary_marker_index = last_index - boundary_marker_rev_index;
+
+        if boundary_marker_index == 0 {
+            return Err(invalid_format());
+        }
+        if padded_data[(boundary_marker_index + 1)..].iter().any(|&byte| byte != 0) {
+            return Err(invalid_format());
+        }
+
+        // On success, return the index of the marker, which is the new length.
+        Ok(boundary_marker_index)
+    }
+}
+
+/// Random Linear Network Coding (RLNC) Decoder, placing all of its state in a caller provided buffer, say a statically allocated
+/// one, a stack array or a slice of a preallocated arena, so that decoding never allocates, as `Decoder` does, when a matrix row is
+/// added. It suits embedded and high-frequency server use cases, decoding full coded pieces, with row-major payloads only.
+///
+/// Buffer holds `required_piece_count` rows, of a full coded piece each. Row `i` holds the useful piece, reduced, whose pivot is at
+/// coefficient column `i`, normalized to one, or it's all zero, if there's no such piece yet. So pivot bookkeeping lives in the
+/// buffer too, and a piece is reduced in place, in one of the empty rows. Back-substitution is deferred till extraction of
+/// decoded data, which is written into another caller provided buffer, see `Self::get_decoded_data_into`.
+#[derive(Debug)]
+pub struct DecoderRef<'a> {
+    rows: &'a mut [u8],
+    piece_byte_len: usize,
+    required_piece_count: usize,
+    received_piece_count: usize,
+    useful_piece_count: usize,
+}
+
+impl<'a> DecoderRef<'a> {
+    /// Creates a new `DecoderRef`, over given buffer, whose current content is ignored.
+    ///
+    /// # Arguments
+    /// * `buffer` - Backing memory for decoder state, of at least `required_piece_count * (required_piece_count + piece_byte_len)` bytes.
+    /// * `piece_byte_len` - The byte length of each original data piece.
+    /// * `required_piece_count` - The minimum number of useful coded pieces
+    ///   needed for decoding (equivalent to the number of original pieces).
+    ///
+    /// # Returns
+    /// * Returns `Ok(DecoderRef)` on successful creation.
+    /// * Returns `Err(RLNCError::PieceLengthZero)` if `piece_byte_len` is zero.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `required_piece_count` is zero.
+    /// * Returns `Err(RLNCError::PieceStorageFailure)` if `buffer` can't hold `required_piece_count` many full coded pieces.
+    pub fn new(buffer: &'a mut [u8], piece_byte_len: usize, required_piece_count: usize) -> Result<DecoderRef<'a>, RLNCError> {
+        if piece_byte_len == 0 {
+            return Err(RLNCError::PieceLengthZero);
+        }
+        if required_piece_count == 0 {
+            return Err(RLNCError::PieceCountZero);
+        }
+
+        let rows_byte_len = required_piece_count
+            .checked_add(piece_byte_len)
+            .and_then(|full_coded_piece_byte_len| full_coded_piece_byte_len.checked_mul(required_piece_count))
+            .ok_or(RLNCError::PieceStorageFailure)?;
+        let rows = buffer.get_mut(..rows_byte_len).ok_or(RLNCError::PieceStorageFailure)?;
+        rows.fill(0);
+
+        Ok(DecoderRef {
+            rows,
+            piece_byte_len,
+            required_piece_count,
+            received_piece_count: 0,
+            useful_piece_count: 0,
+        })
+    }
+
+    /// Number of pieces original data got split into and coded together.
+    pub fn get_num_pieces_coded_together(&self) -> usize {
+        self.required_piece_count
+    }
+
+    /// After padding the original data, it gets split into `self.get_num_pieces_coded_together()` many pieces, which results into these many bytes per piece.
+    pub fn get_piece_byte_len(&self) -> usize {
+        self.piece_byte_len
+    }
+
+    /// Each full coded piece consists of `self.get_num_pieces_coded_together()` random coefficients, appended by corresponding encoded piece of `self.get_piece_byte_len()` bytes.
+    pub fn get_full_coded_piece_byte_len(&self) -> usize {
+        self.required_piece_count + self.piece_byte_len
+    }
+
+    /// Total number of pieces received by the decoder so far.
+    pub fn get_received_piece_count(&self) -> usize {
+        self.received_piece_count
+    }
+
+    /// Number of useful pieces received by the decoder so far.
+    pub fn get_useful_piece_count(&self) -> usize {
+        self.useful_piece_count
+    }
+
+    /// Number of pieces remaining to be received by the decoder for successful decoding.
+    pub fn get_remaining_piece_count(&self) -> usize {
+        self.required_piece_count - self.useful_piece_count
+    }
+
+    /// Checks if the decoder has received enough linearly independent pieces to recover the original data.
+    pub fn is_already_decoded(&self) -> bool {
+        self.useful_piece_count == self.required_piece_count
+    }
+
+    /// Returns `true` if the row at given index holds a useful piece, which is so iff its pivot, which is always one, is set.
+    fn has_pivot(&self, ridx: usize) -> bool {
+        self.rows[ridx * self.get_full_coded_piece_byte_len() + ridx] != 0
+    }
+
+    /// Adds `scalar` times row `src_ridx` into row `dst_ridx`, skipping first `begin` columns, which are zero in the source row.
+    fn add_scaled_row(&mut self, dst_ridx: usize, src_ridx: usize, scalar: u8, begin: usize) {
+        let row_byte_len = self.get_full_coded_piece_byte_len();
+        let (dst_begin, src_begin) = (dst_ridx * row_byte_len, src_ridx * row_byte_len);
+
+        let (dst, src) = if dst_ridx < src_ridx {
+            let (head, tail) = self.rows.split_at_mut(src_begin);
+            (&mut head[dst_begin..dst_begin + row_byte_len], &tail[..row_byte_len])
+        } else {
+            let (head, tail) = self.rows.split_at_mut(dst_begin);
+            (&mut tail[..row_byte_len], &head[src_begin..src_begin + row_byte_len])
+        };
+
+        gf256_mul_vec_by_scalar_then_add_into_vec(&mut dst[begin..], &src[begin..], scalar);
+    }
+
+    /// Decodes a single full coded piece, reducing it by rows holding pivots, in one of the empty rows, where it stays, if it's
+    /// useful, and its pivot column is that of the row, else it's moved to the row of its pivot column.
+    ///
+    /// # Arguments
+    /// * `full_coded_piece` - A slice containing the coefficients followed by
+    ///   the coded data for one piece. Its length must be `required_piece_count + piece_byte_len`.
+    ///
+    /// # Returns
+    /// * Returns `Ok(())` if the piece was useful and added successfully.
+    /// * Returns `Err(RLNCError::ReceivedAllPieces)` if decoding is already complete.
+    /// * Returns `Err(RLNCError::PieceNotUseful)` if the piece was linearly dependent on the already received useful pieces.
+    /// * Returns `Err(RLNCError::InvalidPieceLength)` if the `full_coded_piece` has an unexpected length.
+    pub fn decode(&mut self, full_coded_piece: &[u8]) -> Result<(), RLNCError> {
+        if self.is_already_decoded() {
+            return Err(RLNCError::ReceivedAllPieces);
+        }
+
+        let row_byte_len = self.get_full_coded_piece_byte_len();
+        if full_coded_piece.len() != row_byte_len {
+            return Err(RLNCError::InvalidPieceLength);
+        }
+        self.received_piece_count += 1;
+
+        // Some row is empty, as decoding is not complete.
+        let ridx = unsafe { (0..self.required_piece_count).find(|&ridx| !self.has_pivot(ridx)).unwrap_unchecked() };
+        let row_begin = ridx * row_byte_len;
+        self.rows[row_begin..row_begin + row_byte_len].copy_from_slice(full_coded_piece);
+
+        // Row holding pivot of a column is zero before it, so clearing columns in ascending order leaves earlier ones cleared.
+        for cidx in (0..self.required_piece_count).filter(|&cidx| cidx != ridx) {
+            let coeff = self.rows[row_begin + cidx];
+            if coeff != 0 && self.has_pivot(cidx) {
+                self.add_scaled_row(ridx, cidx, coeff, cidx);
+            }
+        }
+
+        let row = &mut self.rows[row_begin..row_begin + row_byte_len];
+        let Some(pivot_cidx) = row[..self.required_piece_count].iter().position(|&coeff| coeff != 0) else {
+            row.fill(0);
+            return Err(RLNCError::PieceNotUseful);
+        };
+
+        let pivot_inv = unsafe { Gf256::new(row[pivot_cidx]).inv().unwrap_unchecked() }.get();
+        gf256_inplace_mul_vec_by_scalar(row, pivot_inv);
+
+        if pivot_cidx != ridx {
+            let pivot_row_begin = pivot_cidx * row_byte_len;
+            self.rows.copy_within(row_begin..row_begin + row_byte_len, pivot_row_begin);
+            self.rows[row_begin..row_begin + row_byte_len].fill(0);
+        }
+
+        self.useful_piece_count += 1;
+        Ok(())
+    }
+
+    /// Writes decoded data into given buffer, running the deferred back-substitution in place, in the decoder's buffer. Padding
+    /// gets written too, before being trimmed off, so the output buffer must be able to hold `self.get_num_pieces_coded_together() *
+    /// self.get_piece_byte_len()` bytes. See `Decoder::get_decoded_data_into`.
+    ///
+    /// # Returns
+    /// * Returns `Ok(usize)` holding the byte length of decoded data, written at the start of `buf`, if successful.
+    /// * Returns `Err(RLNCError::NotAllPiecesReceivedYet)` if not enough useful pieces have been received.
+    /// * Returns `Err(RLNCError::InvalidOutputBuffer)` if `buf` is too short to hold padded data.
+    /// * Returns `Err(RLNCError::InvalidDecodedDataFormat(_))` if the extracted data does not follow the expected format (e.g., boundary marker issues).
+    pub fn get_decoded_data_into(mut self, buf: &mut [u8]) -> Result<usize, RLNCError> {
+        if !self.is_already_decoded() {
+            return Err(RLNCError::NotAllPiecesReceivedYet);
+        }
+
+        let padded_len = self.piece_byte_len * self.required_piece_count;
+        if buf.len() < padded_len {
+            return Err(RLNCError::InvalidOutputBuffer);
+        }
+
+        // Later columns are cleared first, so that each pivot row is left with its pivot only, when it's added into earlier rows.
+        let row_byte_len = self.get_full_coded_piece_byte_len();
+        for cidx in (0..self.required_piece_count).rev() {
+            for ridx in 0..cidx {
+                let coeff = self.rows[ridx * row_byte_len + cidx];
+                if coeff != 0 {
+                    self.add_scaled_row(ridx, cidx, coeff, cidx);
+                }
+            }
+        }
+
+        let padded_data = &mut buf[..padded_len];
+        for (row, piece) in self.rows.chunks_exact(row_byte_len).zip(padded_data.chunks_exact_mut(self.piece_byte_len)) {
+            piece.copy_from_slice(&row[self.required_piece_count..]);
+        }
+
+        Decoder::get_final_data_len(padded_data, self.required_piece_count, self.received_piece_count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BOUNDARY_MARKER, Decoder, DecoderRef, PayloadLayout, RLNCError};
+    use crate::full::{encoder::Encoder, session::NonZeroRng};
+    use rand::Rng;
+
+    #[test]
+    fn test_decoder_new_invalid_inputs() {
+        // Test case 1: piece_byte_len is zero
+        let piece_byte_len_zero = 0;
+        let required_piece_count_non_zero = 10;
+
+        let result_piece_len_zero = Decoder::new(piece_byte_len_zero, required_piece_count_non_zero);
+        assert!(result_piece_len_zero.is_err());
+        assert_eq!(result_piece_len_zero.expect_err("Expected PieceLengthZero error"), RLNCError::PieceLengthZero);
+
+        // Test case 2: required_piece_count is zero
+        let piece_byte_len_non_zero = 10;
+        let required_piece_count_zero = 0;
+
+        let result_piece_count_zero = Decoder::new(piece_byte_len_non_zero, required_piece_count_zero);
+        assert!(result_piece_count_zero.is_err());
+        assert_eq!(result_piece_count_zero.expect_err("Expected PieceCountZero error"), RLNCError::PieceCountZero);
+
+        // Test case 3: Both piece_byte_len and required_piece_count are zero
+        let piece_byte_len_both_zero = 0;
+        let required_piece_count_both_zero = 0;
+
+        let result_both_zero = Decoder::new(piece_byte_len_both_zero, required_piece_count_both_zero);
+        assert!(result_both_zero.is_err());
+        assert_eq!(
+            result_both_zero.expect_err("Expected PieceLengthZero error for both zero inputs"),
+            RLNCError::PieceLengthZero
+        );
+
+        // Test case 4: Valid input
+        let piece_byte_len_valid = 10;
+        let required_piece_count_valid = 5;
+        let result_valid = Decoder::new(piece_byte_len_valid, required_piece_count_valid);
+        assert!(result_valid.is_ok());
+    }
+
+    #[test]
+    fn test_decoder_decode_invalid_piece_length() {
+        let mut rng = rand::rng();
+
+        let data_byte_len = 1024usize;
+        let piece_count = 32usize;
+        let data = (0..data_byte_len).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data, piece_count).expect("Failed to create Encoder for decode invalid length test");
+
+        let piece_byte_len = encoder.get_piece_byte_len();
+        let required_piece_count = encoder.get_piece_count();
+        let full_coded_piece_byte_len = encoder.get_full_coded_piece_byte_len();
+
+        let mut decoder = Decoder::new(piece_byte_len, required_piece_count).expect("Failed to create Decoder for decode invalid length test");
+
+        // Test case 1: Piece length is shorter than expected
+        let short_piece_len = full_coded_piece_byte_len - 1;
+        let short_coded_piece: Vec<u8> = (0..short_piece_len).map(|_| rng.random()).collect();
+        let result_short = decoder.decode(&short_coded_piece);
+        assert!(result_short.is_err());
+        assert_eq!(
+            result_short.expect_err("Expected InvalidPieceLength error for short piece"),
+            RLNCError::InvalidPieceLength
+        );
+
+        // Test case 2: Piece length is longer than expected
+        let long_piece_len = full_coded_piece_byte_len + 1;
+        let long_coded_piece: Vec<u8> = (0..long_piece_len).map(|_| rng.random()).collect();
+        let result_long = decoder.decode(&long_coded_piece);
+        assert!(result_long.is_err());
+        assert_eq!(
+            result_long.expect_err("Expected InvalidPieceLength error for long piece"),
+            RLNCError::InvalidPieceLength
+        );
+
+        // Test case 3: Piece length is zero
+        let zero_piece: Vec<u8> = Vec::new();
+        let result_zero = decoder.decode(&zero_piece);
+        assert!(result_zero.is_err());
+        assert_eq!(
+            result_zero.expect_err("Expected InvalidPieceLength error for zero-length piece"),
+            RLNCError::InvalidPieceLength
+        );
+
+        // Ensure decoder state is unchanged after invalid decode attempts
+        assert_eq!(decoder.get_received_piece_count(), 0);
+        assert_eq!(decoder.get_useful_piece_count(), 0);
+        assert!(!decoder.is_already_decoded());
+
+        // Test case 4: Valid coded piece - check if state changes
+        let correct_coded_piece = encoder.code(&mut rng);
+        let result_correct = decoder.decode(&correct_coded_piece);
+        assert!(result_correct.is_ok() || matches!(result_correct, Err(RLNCError::PieceNotUseful)));
+
+        // After a valid decode attempt, received_piece_count must increase
+        assert_eq!(decoder.get_received_piece_count(), 1);
+
+        // If the piece was useful, useful_piece_count will be 1. Otherwise, it remains 0.
+        // Given the small piece_count in this test, it's very likely to be useful.
+        if result_correct.is_ok() {
+            assert_eq!(decoder.get_useful_piece_count(), 1);
+            assert!(!decoder.is_already_decoded()); // Unless piece_count was 1
+        } else {
+            assert_eq!(decoder.get_useful_piece_count(), 0);
+        }
+    }
+
+    #[test]
+    fn test_decoder_getters() {
+        let mut rng = rand::rng();
+
+        let data_byte_len = 1024usize;
+        let piece_count = 32usize;
+        let data = (0..data_byte_len).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), piece_count).expect("Failed to create Encoder for getters test");
+
+        let piece_byte_len = encoder.get_piece_byte_len();
+        let required_piece_count = encoder.get_piece_count();
+        let full_coded_piece_byte_len = encoder.get_full_coded_piece_byte_len();
+
+        let mut decoder = Decoder::new(piece_byte_len, required_piece_count).expect("Failed to create Decoder for getters test");
+
+        assert_eq!(decoder.get_num_pieces_coded_together(), required_piece_count);
+        assert_eq!(decoder.get_piece_byte_len(), piece_byte_len);
+        assert_eq!(decoder.get_full_coded_piece_byte_len(), full_coded_piece_byte_len);
+        assert_eq!(decoder.get_received_piece_count(), 0);
+        assert_eq!(decoder.get_useful_piece_count(), 0);
+        assert_eq!(decoder.get_remaining_piece_count(), required_piece_count);
+        assert!(!decoder.is_already_decoded());
+
+        // Add some pieces and track useful ones
+        let num_pieces_to_decode_initially = required_piece_count / 2;
+        let mut expected_useful_pieces_after_initial = 0;
+
+        for _ in 0..num_pieces_to_decode_initially {
+            let coded_piece = encoder.code(&mut rng);
+            match decoder.decode(&coded_piece) {
+                Ok(_) => {
+                    expected_useful_pieces_after_initial += 1;
+                }
+                Err(RLNCError::PieceNotUseful) => {}
+                Err(e) => panic!("Unexpected error during initial decoding phase: {e:?}"),
+            }
+        }
+
+        assert_eq!(decoder.get_received_piece_count(), num_pieces_to_decode_initially);
+        assert_eq!(decoder.get_useful_piece_count(), expected_useful_pieces_after_initial);
+        assert_eq!(decoder.get_remaining_piece_count(), required_piece_count - expected_useful_pieces_after_initial);
+
+        // Add remaining pieces to complete decoding
+        let mut total_pieces_received = num_pieces_to_decode_initially;
+        while !decoder.is_already_decoded() {
+            let coded_piece = encoder.code(&mut rng);
+
+            match decoder.decode(&coded_piece) {
+                Ok(_) => {}
+                Err(RLNCError::PieceNotUseful) => {}
+                Err(RLNCError::ReceivedAllPieces) => break,
+                Err(e) => panic!("Unexpected error during final decoding phase: {e:?}"),
+            }
+
+            total_pieces_received += 1;
+        }
+
+        assert_eq!(decoder.get_useful_piece_count(), required_piece_count);
+        assert_eq!(decoder.get_remaining_piece_count(), 0);
+        assert!(decoder.is_already_decoded());
+        assert_eq!(decoder.get_received_piece_count(), total_pieces_received);
+    }
+
+    #[test]
+    fn test_decoder_snapshot_and_restore() {
+        let mut rng = rand::rng();
+
+        let data_byte_len = 1024usize;
+        let piece_count = 32usize;
+        let data = (0..data_byte_len).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), piece_count).expect("Failed to create Encoder for snapshot test");
+
+        for layout in [PayloadLayout::RowMajor, PayloadLayout::ColumnMajor] {
+            let mut decoder = Decoder::new_with_layout(encoder.get_piece_byte_len(), piece_count, layout).expect("Failed to create Decoder for snapshot test");
+
+            while decoder.get_useful_piece_count() < piece_count / 2 {
+                let _ = decoder.decode(&encoder.code(&mut rng));
+            }
+
+            let snapshot = decoder.snapshot();
+            assert_eq!(snapshot.get_payload_layout(), layout);
+            assert_eq!(snapshot.get_received_piece_count(), decoder.get_received_piece_count());
+
+            // Inconsistent snapshots must be rejected.
+            let mut truncated = snapshot.clone();
+            truncated.matrix_rows.pop();
+            assert!(matches!(Decoder::from_snapshot(truncated), Err(RLNCError::InvalidSnapshot)));
+
+            let mut restored = Decoder::from_snapshot(snapshot).expect("Snapshot must be valid");
+            assert_eq!(restored.get_useful_piece_count(), decoder.get_useful_piece_count());
+            assert_eq!(restored.get_received_piece_count(), decoder.get_received_piece_count());
+            assert_eq!(restored.missing_dimensions(), decoder.missing_dimensions());
+
+            while !restored.is_already_decoded() {
+                match restored.decode(&encoder.code(&mut rng)) {
+                    Ok(_) | Err(RLNCError::PieceNotUseful) => {}
+                    Err(e) => panic!("Unexpected error during decoding: {e:?}"),
+                }
+            }
+
+            assert_eq!(restored.get_decoded_data(), Ok(data.clone()));
+        }
+    }
+
+    #[cfg(feature = "rkyv")]
+    #[test]
+    fn test_decoder_snapshot_rkyv_archive() {
+        use crate::full::{ArchivedDecoderSnapshot, DecoderSnapshot};
+
+        let mut rng = rand::rng();
+
+        let encoder = Encoder::new(vec![7u8; 1024], 16).expect("Failed to create Encoder for snapshot test");
+        let mut decoder = Decoder::new(encoder.get_piece_byte_len(), encoder.get_piece_count()).expect("Failed to create Decoder for snapshot test");
+        (0..8).for_each(|_| {
+            let _ = decoder.decode(&encoder.code(&mut rng));
+        });
+
+        let snapshot = decoder.snapshot();
+        let archive = rkyv::to_bytes::<rkyv::rancor::Error>(&snapshot).expect("Archiving must not fail");
+        let archived = rkyv::access::<ArchivedDecoderSnapshot, rkyv::rancor::Error>(&archive).expect("Archive must be valid");
+        let deserialized = rkyv::deserialize::<DecoderSnapshot, rkyv::rancor::Error>(archived).expect("Deserializing must not fail");
+
+        assert_eq!(deserialized, snapshot);
+    }
+
+    #[test]
+    fn test_decoder_rank_watermarks() {
+        let mut rng = rand::rng();
+
+        let piece_count = 20usize;
+        let encoder = Encoder::new(vec![1u8; 1024], piece_count).expect("Failed to create Encoder for watermark test");
+        let mut decoder = Decoder::new_with_piece_retention(encoder.get_piece_byte_len(), piece_count).expect("Failed to create Decoder for watermark test");
+
+        assert!(matches!(decoder.watch_rank(&[0]), Err(RLNCError::InvalidRankWatermark)));
+        assert!(matches!(decoder.watch_rank(&[piece_count + 1]), Err(RLNCError::InvalidRankWatermark)));
+
+        let watermarks = decoder
+            .watch_rank(&[piece_count, piece_count / 2, piece_count * 9 / 10])
+            .expect("Watermarks must be valid");
+        let dropped_watermarks = decoder.watch_rank(&[1]).expect("Watermarks must be valid");
+        drop(dropped_watermarks);
+
+        while decoder.get_useful_piece_count() < piece_count / 2 {
+            assert!(watermarks.try_recv().is_err());
+            let _ = decoder.decode(&encoder.code(&mut rng));
+        }
+        assert_eq!(watermarks.try_recv(), Ok(piece_count / 2));
+        assert!(watermarks.try_recv().is_err());
+
+        // Evicting a piece re-arms the watermarks.
+        let mut num_evicted = 0;
+        decoder
+            .evict_pieces(|_| {
+                num_evicted += 1;
+                num_evicted == 1
+            })
+            .expect("Eviction must not fail");
+        assert!(watermarks.try_recv().is_err());
+
+        // A watcher registered late, gets already reached watermarks right away.
+        let late_watermarks = decoder.watch_rank(&[1, 2]).expect("Watermarks must be valid");
+        assert_eq!(late_watermarks.try_iter().collect::<Vec<_>>(), vec![1, 2]);
+
+        while !decoder.is_already_decoded() {
+            let _ = decoder.decode(&encoder.code(&mut rng));
+        }
+        assert_eq!(
+            watermarks.try_iter().collect::<Vec<_>>(),
+            vec![piece_count / 2, piece_count * 9 / 10, piece_count]
+        );
+    }
+
+    #[test]
+    fn test_decoder_missing_dimensions() {
+        let mut rng = rand::rng();
+
+        let data_byte_len = 1024usize;
+        let piece_count = 32usize;
+        let data = (0..data_byte_len).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), piece_count).expect("Failed to create Encoder for missing dimensions test");
+
+        let piece_byte_len = encoder.get_piece_byte_len();
+
+        let mut padded_data = data.clone();
+        padded_data.push(BOUNDARY_MARKER);
+        padded_data.resize(piece_byte_len * piece_count, 0);
+
+        for layout in [PayloadLayout::RowMajor, PayloadLayout::ColumnMajor] {
+            let mut decoder = Decoder::new_with_layout(piece_byte_len, piece_count, layout).expect("Failed to create Decoder for missing dimensions test");
+            assert_eq!(decoder.missing_dimensions(), (0..piece_count).collect::<Vec<_>>());
+
+            // Seeding every other source piece leaves exactly the odd dimensions missing.
+            padded_data.chunks_exact(piece_byte_len).enumerate().step_by(2).for_each(|(piece_idx, piece)| {
+                decoder.insert_known_piece(piece_idx, piece).expect("Known piece must be useful");
+            });
+            assert_eq!(decoder.missing_dimensions(), (1..piece_count).step_by(2).collect::<Vec<_>>());
+
+            while !decoder.is_already_decoded() {
+                match decoder.decode(&encoder.code(&mut rng)) {
+                    Ok(_) | Err(RLNCError::PieceNotUseful) => {}
+                    Err(e) => panic!("Unexpected error during decoding: {e:?}"),
+                }
+
+                assert_eq!(decoder.missing_dimensions().len(), decoder.get_remaining_piece_count());
+            }
+
+            assert!(decoder.missing_dimensions().is_empty());
+            assert_eq!(decoder.get_decoded_data(), Ok(data.clone()));
+        }
+    }
+
+    #[test]
+    fn test_decoder_insert_known_piece() {
+        let mut rng = rand::rng();
+
+        let data_byte_len = 1024usize;
+        let piece_count = 32usize;
+        let data = (0..data_byte_len).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), piece_count).expect("Failed to create Encoder for known piece test");
+
+        let piece_byte_len = encoder.get_piece_byte_len();
+
+        // Replicate encoder side padding, so that we get hold of original source pieces.
+        let mut padded_data = data.clone();
+        padded_data.push(BOUNDARY_MARKER);
+        padded_data.resize(piece_byte_len * piece_count, 0);
+
+        let mut decoder = Decoder::new(piece_byte_len, piece_count).expect("Failed to create Decoder for known piece test");
+
+        // Test case 1: Invalid piece index
+        assert_eq!(
+            decoder.insert_known_piece(piece_count, &padded_data[..piece_byte_len]),
+            Err(RLNCError::InvalidPieceIndex)
+        );
+
+        // Test case 2: Invalid piece length
+        assert_eq!(
+            decoder.insert_known_piece(0, &padded_data[..piece_byte_len - 1]),
+            Err(RLNCError::InvalidPieceLength)
+        );
+
+        // Test case 3: Seed every other source piece
+        let num_known_pieces = padded_data
+            .chunks_exact(piece_byte_len)
+            .enumerate()
+            .step_by(2)
+            .map(|(piece_idx, piece)| decoder.insert_known_piece(piece_idx, piece).expect("Known piece must be useful"))
+            .count();
+
+        assert_eq!(decoder.get_received_piece_count(), 0);
+        assert_eq!(decoder.get_useful_piece_count(), num_known_pieces);
+        assert_eq!(decoder.get_remaining_piece_count(), piece_count - num_known_pieces);
+
+        // Test case 4: Same source piece again, must not be useful
+        assert_eq!(decoder.insert_known_piece(0, &padded_data[..piece_byte_len]), Err(RLNCError::PieceNotUseful));
+
+        // Test case 5: Known piece, inserted after coded ones, goes in as a pivot row, as is
+        while decoder.get_received_piece_count() < 4 {
+            let _ = decoder.decode(&encoder.code(&mut rng));
+        }
+        let last_idx = piece_count - 1;
+        let known_piece = &padded_data[last_idx * piece_byte_len..];
+        let useful_piece_count = decoder.get_useful_piece_count();
+        assert!(!decoder.recovered_piece_indices().contains(&last_idx));
+        assert_eq!(decoder.insert_known_piece(last_idx, known_piece), Ok(()));
+        assert_eq!(decoder.get_useful_piece_count(), useful_piece_count + 1);
+        assert!(decoder.recovered_piece_indices().contains(&last_idx));
+        assert_eq!(decoder.recovered_piece(last_idx), Some(known_piece));
+
+        // Test case 6: Finish decoding with coded pieces
+        while !decoder.is_already_decoded() {
+            match decoder.decode(&encoder.code(&mut rng)) {
+                Ok(_) | Err(RLNCError::PieceNotUseful) => {}
+                Err(e) => panic!("Unexpected error during decoding: {e:?}"),
+            }
+        }
+
+        assert_eq!(decoder.get_useful_piece_count(), piece_count);
+        assert_eq!(decoder.get_decoded_data().expect("Decoding must succeed"), data);
+    }
+
+    #[test]
+    fn test_decoder_evict_polluted_pieces() {
+        use std::collections::HashSet;
+
+        let mut rng = rand::rng();
+
+        let data_byte_len = 1024usize;
+        let piece_count = 32usize;
+        let data = (0..data_byte_len).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), piece_count).expect("Failed to create Encoder for eviction test");
+
+        // Test case 1: Eviction requires piece retention
+        let mut decoder = Decoder::new(encoder.get_piece_byte_len(), encoder.get_piece_count()).expect("Failed to create Decoder");
+        assert_eq!(decoder.evict_pieces(|_| true), Err(RLNCError::PieceRetentionDisabled));
+
+        // Test case 2: Decode with one polluted piece, then evict it, using known good pieces as checksums. Coding vectors are
+        // fixed, so that the polluted piece is surely useful, and the decoder is full rank only with it.
+        let mut decoder =
+            Decoder::new_with_piece_retention(encoder.get_piece_byte_len(), encoder.get_piece_count()).expect("Failed to create Decoder with piece retention");
+
+        let mut polluted_piece = vec![1u8; encoder.get_full_coded_piece_byte_len()];
+        let (coding_vector, coded_data) = polluted_piece.split_at_mut(piece_count);
+        encoder.code_with_coding_vector(coding_vector, coded_data).expect("Coding must not fail");
+        let last_idx = polluted_piece.len() - 1;
+        polluted_piece[last_idx] ^= 0xff;
+
+        let good_pieces = (1..piece_count)
+            .map(|idx| encoder.systematic_piece(idx).expect("Systematic piece must exist"))
+            .collect::<HashSet<_>>();
+
+        assert_eq!(decoder.decode(&polluted_piece), Ok(()));
+        good_pieces
+            .iter()
+            .for_each(|piece| decoder.decode(piece).expect("Systematic piece must be useful"));
+        assert!(decoder.is_already_decoded());
+        assert_ne!(decoder.clone().get_decoded_data(), Ok(data.clone()));
+
+        let num_evicted_pieces = decoder
+            .evict_pieces(|full_coded_piece| !good_pieces.contains(full_coded_piece))
+            .expect("Eviction must not fail");
+
+        assert_eq!(num_evicted_pieces, 1);
+        assert_eq!(decoder.get_useful_piece_count(), piece_count - 1);
+        assert!(!decoder.is_already_decoded());
+
+        while !decoder.is_already_decoded() {
+            match decoder.decode(&encoder.code(&mut rng)) {
+                Ok(_) | Err(RLNCError::PieceNotUseful) => {}
+                Err(e) => panic!("Unexpected error during decoding: {e:?}"),
+            }
+        }
+
+        assert_eq!(decoder.get_decoded_data(), Ok(data));
+    }
+
     #[test]
-    fn test_decoder_getters() {
+    fn test_decoder_estimated_completion() {
+        let mut rng = rand::rng();
+
+        let encoder = Encoder::new(vec![7u8; 1000], 10).expect("Failed to create Encoder for completion estimate test");
+        let mut decoder = Decoder::new_with(encoder.get_params());
+
+        let estimate = decoder.estimated_completion();
+        assert_eq!(estimate.remaining_useful_piece_count, 10);
+        assert_eq!(estimate.expected_piece_count, 10.0);
+        assert_eq!(estimate.expected_time, None);
+
+        // Half of the received pieces are duplicates, so twice as many pieces are expected to be needed. Time is estimated only
+        // once arrival timing is enabled.
+        for round in 0..4 {
+            let piece = encoder.code(&mut rng);
+            decoder.decode(&piece).expect("Fresh piece must be useful");
+            assert_eq!(decoder.decode(&piece), Err(RLNCError::PieceNotUseful));
+            std::thread::sleep(std::time::Duration::from_millis(2));
+
+            if round == 0 {
+                assert_eq!(decoder.estimated_completion().expected_time, None);
+                decoder.enable_arrival_timing();
+            }
+        }
+
+        let estimate = decoder.estimated_completion();
+        assert_eq!(estimate.remaining_useful_piece_count, 6);
+        assert_eq!(estimate.expected_piece_count, 12.0);
+        assert!(estimate.expected_time.is_some_and(|expected_time| !expected_time.is_zero()));
+    }
+
+    #[test]
+    fn test_decoder_strict_validation() {
+        let mut rng = rand::rng();
+
+        let data = (0..1000).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 8).expect("Failed to create Encoder for strict validation test");
+
+        let mut zero_piece = encoder.code(&mut rng);
+        zero_piece[..8].fill(0);
+
+        // Lenient decoder lets the all-zero piece waste an elimination pass.
+        let mut decoder = Decoder::new_with(encoder.get_params());
+        assert!(!decoder.is_strict_validation_enabled());
+        assert_eq!(decoder.decode(&zero_piece), Err(RLNCError::PieceNotUseful));
+        assert_eq!(decoder.decode_in_generation(7, &encoder.code(&mut rng)), Ok(()));
+
+        let mut decoder = Decoder::new_with(encoder.get_params());
+        decoder.enable_strict_validation(3);
+        assert!(decoder.is_strict_validation_enabled());
+
+        assert_eq!(decoder.decode(&zero_piece), Err(RLNCError::ZeroCodingVector));
+        assert_eq!(decoder.decode_in_generation(4, &encoder.code(&mut rng)), Err(RLNCError::InvalidGenerationIndex));
+        assert_eq!(decoder.decode_in_generation(3, &zero_piece[1..]), Err(RLNCError::InvalidPieceLength));
+        assert_eq!(decoder.get_received_piece_count(), 0);
+
+        while !decoder.is_already_decoded() {
+            let _ = decoder.decode_in_generation(3, &encoder.code(&mut rng));
+        }
+
+        assert_eq!(decoder.get_decoded_data(), Ok(data));
+    }
+
+    #[test]
+    fn test_decoder_audit_log() {
+        use crate::full::RejectionReason;
+
         let mut rng = rand::rng();
 
         let data_byte_len = 1024usize;
         let piece_count = 32usize;
         let data = (0..data_byte_len).map(|_| rng.random()).collect::<Vec<u8>>();
-        let encoder = Encoder::new(data.clone(), piece_count).expect("Failed to create Encoder for getters test");
+        let encoder = Encoder::new(data, piece_count).expect("Failed to create Encoder for audit log test");
 
-        let piece_byte_len = encoder.get_piece_byte_len();
-        let required_piece_count = encoder.get_piece_count();
+        let mut decoder = Decoder::new(encoder.get_piece_byte_len(), encoder.get_piece_count()).expect("Failed to create Decoder");
+        assert!(decoder.get_audit_log().is_none());
+
+        decoder.enable_audit_log(3);
+
+        let coded_piece = encoder.code(&mut rng);
+        let zero_piece = vec![0u8; coded_piece.len()];
+
+        assert_eq!(decoder.decode(&coded_piece), Ok(()));
+        assert_eq!(decoder.decode(&coded_piece), Err(RLNCError::PieceNotUseful));
+        assert_eq!(decoder.decode(&coded_piece[1..]), Err(RLNCError::InvalidPieceLength));
+        assert_eq!(decoder.decode(&zero_piece), Err(RLNCError::PieceNotUseful));
+
+        let audit_log = decoder.get_audit_log().expect("Audit log must be enabled");
+        let reasons = audit_log.entries().map(|entry| (entry.arrival_index, entry.reason)).collect::<Vec<_>>();
+
+        assert_eq!(audit_log.get_capacity(), 3);
+        assert_eq!(audit_log.get_dropped_entry_count(), 0);
+        assert_eq!(
+            reasons,
+            vec![
+                (1, RejectionReason::Duplicate),
+                (2, RejectionReason::Malformed),
+                (3, RejectionReason::Dependent)
+            ]
+        );
+
+        // Oldest entry gets dropped, once the audit log is full.
+        assert_eq!(decoder.decode(&zero_piece), Err(RLNCError::PieceNotUseful));
+
+        let audit_log = decoder.get_audit_log().expect("Audit log must be enabled");
+        let entries = audit_log.entries().collect::<Vec<_>>();
+
+        assert_eq!(audit_log.get_dropped_entry_count(), 1);
+        assert_eq!(entries[0].reason, RejectionReason::Malformed);
+        assert_eq!(entries[2].arrival_index, 4);
+        assert_eq!(entries[2].reason, RejectionReason::Duplicate);
+        assert_eq!(entries[1].coding_vector_digest, entries[2].coding_vector_digest);
+    }
+
+    #[test]
+    fn test_decoder_column_major_payload_layout() {
+        let mut rng = rand::rng();
+
+        let data_byte_len = 1024usize;
+        let piece_count = 32usize;
+        let data = (0..data_byte_len).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), piece_count).expect("Failed to create Encoder for column-major layout test");
+
+        let mut decoder = Decoder::new_with_layout(encoder.get_piece_byte_len(), encoder.get_piece_count(), PayloadLayout::ColumnMajor)
+            .expect("Failed to create Decoder with column-major payload layout");
+        assert_eq!(decoder.get_payload_layout(), PayloadLayout::ColumnMajor);
+
+        let first_coded_piece = encoder.code(&mut rng);
+        assert_eq!(decoder.decode(&first_coded_piece), Ok(()));
+        assert_eq!(decoder.decode(&first_coded_piece), Err(RLNCError::PieceNotUseful));
+
+        while !decoder.is_already_decoded() {
+            match decoder.decode(&encoder.code(&mut rng)) {
+                Ok(_) | Err(RLNCError::PieceNotUseful) => {}
+                Err(e) => panic!("Unexpected error during decoding: {e:?}"),
+            }
+        }
+
+        assert_eq!(decoder.get_useful_piece_count(), piece_count);
+        assert_eq!(decoder.get_decoded_data(), Ok(data));
+    }
+
+    #[test]
+    fn test_decoder_truncated_pieces() {
+        let mut rng = rand::rng();
+
+        let data = (0..1000).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 8).expect("Failed to create Encoder for truncated pieces test");
         let full_coded_piece_byte_len = encoder.get_full_coded_piece_byte_len();
+        let piece_count = encoder.get_piece_count();
 
-        let mut decoder = Decoder::new(piece_byte_len, required_piece_count).expect("Failed to create Decoder for getters test");
+        let expected_prefixes = |prefix_len: usize| {
+            (0..piece_count)
+                .map(|idx| encoder.source_piece(idx).expect("Source piece must exist")[..prefix_len].to_vec())
+                .collect::<Vec<_>>()
+        };
 
-        assert_eq!(decoder.get_num_pieces_coded_together(), required_piece_count);
-        assert_eq!(decoder.get_piece_byte_len(), piece_byte_len);
-        assert_eq!(decoder.get_full_coded_piece_byte_len(), full_coded_piece_byte_len);
+        for layout in [PayloadLayout::RowMajor, PayloadLayout::ColumnMajor] {
+            let mut decoder = Decoder::new_with_layout(encoder.get_piece_byte_len(), piece_count, layout).expect("Failed to create Decoder");
+
+            // Coded pieces, with random coefficients for first `k - 3` source pieces and given ones for the last three, so
+            // that the ones below are independent of each other, whatever coefficients are sampled.
+            let mut code_with_tail = |tail: [u8; 3]| {
+                let mut coding_vector = (0..piece_count - 3).map(|_| rng.random()).collect::<Vec<u8>>();
+                coding_vector.extend_from_slice(&tail);
+
+                let mut piece = vec![0u8; full_coded_piece_byte_len];
+                let (coeffs, coded_data) = piece.split_at_mut(piece_count);
+                coeffs.copy_from_slice(&coding_vector);
+                encoder.code_with_coding_vector(&coding_vector, coded_data).unwrap();
+                piece
+            };
+
+            for idx in 0..piece_count - 3 {
+                let mut piece = vec![0u8; piece_count];
+                piece[idx] = 1;
+                piece.extend_from_slice(encoder.source_piece(idx).unwrap());
+                assert_eq!(decoder.decode(&piece), Ok(()));
+            }
+            assert_eq!(decoder.recover_piece_prefixes(), vec![Vec::new(); piece_count]);
+
+            // Three pieces, covering first 20 bytes of each source piece, only two of them covering first 40 bytes.
+            for (tail, payload_len) in [([1, 0, 0], 40), ([0, 1, 0], 40), ([0, 0, 1], 20)] {
+                let piece = code_with_tail(tail);
+                assert_eq!(decoder.decode_truncated(&piece[..piece_count + payload_len]), Ok(()));
+            }
+            assert_eq!(decoder.recover_piece_prefixes(), expected_prefixes(20));
+
+            let piece = code_with_tail([1, 0, 1]);
+            assert_eq!(decoder.decode_truncated(&piece[..piece_count + 60]), Ok(()));
+            assert_eq!(decoder.recover_piece_prefixes(), expected_prefixes(40));
+            assert!(!decoder.is_already_decoded());
+
+            while !decoder.is_already_decoded() {
+                let _ = decoder.decode_truncated(&encoder.code(&mut rng));
+            }
+            assert_eq!(decoder.recover_piece_prefixes(), expected_prefixes(encoder.get_piece_byte_len()));
+            assert_eq!(decoder.get_decoded_data(), Ok(data.clone()));
+        }
+
+        let mut decoder = Decoder::new_with(encoder.get_params());
+        let piece = encoder.code(&mut rng);
+
+        assert_eq!(decoder.decode_truncated(&piece[..piece_count - 1]), Err(RLNCError::InvalidPieceLength));
+        assert_eq!(decoder.decode_truncated(&[piece.as_slice(), &[0]].concat()), Err(RLNCError::InvalidPieceLength));
+        assert_eq!(decoder.decode_truncated(&piece[..piece_count]), Err(RLNCError::PieceNotUseful));
         assert_eq!(decoder.get_received_piece_count(), 0);
-        assert_eq!(decoder.get_useful_piece_count(), 0);
-        assert_eq!(decoder.get_remaining_piece_count(), required_piece_count);
-        assert!(!decoder.is_already_decoded());
+        assert_eq!(decoder.decode_truncated(&piece[..full_coded_piece_byte_len]), Ok(()));
 
-        // Add some pieces and track useful ones
-        let num_pieces_to_decode_initially = required_piece_count / 2;
-        let mut expected_useful_pieces_after_initial = 0;
+        // Truncated pieces are bounded, keeping the longest prefix per linearly independent coding vector.
+        let mut decoder = Decoder::new_with(encoder.get_params());
+        for payload_len in [20, 40, 30] {
+            for piece in encoder.systematic_pieces() {
+                assert_eq!(decoder.decode_truncated(&piece[..piece_count + payload_len]), Ok(()));
+            }
+        }
 
-        for _ in 0..num_pieces_to_decode_initially {
-            let coded_piece = encoder.code(&mut rng);
-            match decoder.decode(&coded_piece) {
-                Ok(_) => {
-                    expected_useful_pieces_after_initial += 1;
+        assert_eq!(decoder.get_received_piece_count(), 3 * piece_count);
+        assert_eq!(decoder.recover_piece_prefixes(), expected_prefixes(40));
+
+        // Longer prefix is recovered, only once every coding vector is covered by a longer piece.
+        for piece in encoder.systematic_pieces().take(piece_count - 1) {
+            assert_eq!(decoder.decode_truncated(&piece[..piece_count + 50]), Ok(()));
+        }
+        assert_eq!(decoder.recover_piece_prefixes(), expected_prefixes(40));
+
+        let last_piece = encoder.systematic_piece(piece_count - 1).unwrap();
+        assert_eq!(decoder.decode_truncated(&last_piece[..piece_count + 50]), Ok(()));
+        assert_eq!(decoder.recover_piece_prefixes(), expected_prefixes(50));
+    }
+
+    #[test]
+    fn test_decoder_decode_async_with_cancellation() {
+        use std::{
+            pin::pin,
+            task::{Context, Poll, Waker},
+        };
+
+        let mut rng = rand::rng();
+        let mut cx = Context::from_waker(Waker::noop());
+
+        let data = (0..10_000).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 32).expect("Failed to create Encoder for async decoding test");
+
+        for layout in [PayloadLayout::RowMajor, PayloadLayout::ColumnMajor] {
+            let mut decoder = Decoder::new_with_layout(encoder.get_piece_byte_len(), encoder.get_piece_count(), layout).expect("Failed to create Decoder");
+            let mut num_yields = 0;
+
+            for round in 0.. {
+                if decoder.is_already_decoded() {
+                    break;
+                }
+                let piece = encoder.code(&mut rng);
+
+                // Every other piece gets cancelled after being polled a few times, midway through its reduction, if at all.
+                // Either way, the decoder must be left consistent.
+                if round % 2 == 1 {
+                    let num_polls = decoder.get_useful_piece_count() + round % 4;
+
+                    let mut future = pin!(decoder.decode_async(&piece, 1));
+                    for _ in 0..num_polls {
+                        if future.as_mut().poll(&mut cx).is_ready() {
+                            break;
+                        }
+                        num_yields += 1;
+                    }
+
+                    continue;
+                }
+
+                let mut future = pin!(decoder.decode_async(&piece, 4));
+                loop {
+                    if let Poll::Ready(result) = future.as_mut().poll(&mut cx) {
+                        assert!(matches!(result, Ok(()) | Err(RLNCError::PieceNotUseful)));
+                        break;
+                    }
+                    num_yields += 1;
                 }
-                Err(RLNCError::PieceNotUseful) => {}
-                Err(e) => panic!("Unexpected error during initial decoding phase: {e:?}"),
             }
+
+            assert!(num_yields > 0);
+            assert_eq!(decoder.get_decoded_data(), Ok(data.clone()));
         }
+    }
 
-        assert_eq!(decoder.get_received_piece_count(), num_pieces_to_decode_initially);
-        assert_eq!(decoder.get_useful_piece_count(), expected_useful_pieces_after_initial);
-        assert_eq!(decoder.get_remaining_piece_count(), required_piece_count - expected_useful_pieces_after_initial);
+    #[test]
+    fn test_decoder_invalid_decoded_data_format_diagnostics() {
+        let mut rng = rand::rng();
 
-        // Add remaining pieces to complete decoding
-        let mut total_pieces_received = num_pieces_to_decode_initially;
-        while !decoder.is_already_decoded() {
-            let coded_piece = encoder.code(&mut rng);
+        let mut decode = |padded_data: Vec<u8>| {
+            let encoder = Encoder::without_padding(Box::new(padded_data), 4).expect("Failed to create Encoder for diagnostics test");
+            let mut decoder = Decoder::new_with(encoder.get_params());
 
-            match decoder.decode(&coded_piece) {
-                Ok(_) => {}
-                Err(RLNCError::PieceNotUseful) => {}
-                Err(RLNCError::ReceivedAllPieces) => break,
-                Err(e) => panic!("Unexpected error during final decoding phase: {e:?}"),
+            while !decoder.is_already_decoded() {
+                let _ = decoder.decode(&encoder.code(&mut rng));
             }
+            let received_piece_count = decoder.get_received_piece_count();
 
-            total_pieces_received += 1;
+            match decoder.get_decoded_data() {
+                Err(RLNCError::InvalidDecodedDataFormat(diagnostics)) => {
+                    assert_eq!((diagnostics.piece_count, diagnostics.received_piece_count), (4, received_piece_count));
+                    diagnostics
+                }
+                result => panic!("Unexpected decoding result: {result:?}"),
+            }
+        };
+
+        let diagnostics = decode(vec![0x11; 64]);
+        assert_eq!(diagnostics.padded_data_byte_len, 64);
+        assert_eq!((diagnostics.boundary_marker_index, diagnostics.nonzero_padding_index), (None, None));
+        assert_eq!(diagnostics.trailing_bytes, vec![0x11; 32]);
+
+        let mut padded_data = vec![0u8; 64];
+        padded_data[..10].fill(0x22);
+        padded_data[10] = BOUNDARY_MARKER;
+        padded_data[60] = 1;
+
+        let diagnostics = decode(padded_data);
+        assert_eq!((diagnostics.boundary_marker_index, diagnostics.nonzero_padding_index), (Some(10), Some(60)));
+        assert!(
+            diagnostics
+                .to_string()
+                .starts_with("boundary marker at byte 10, non-zero padding at byte 60 of 64 bytes")
+        );
+        assert!(diagnostics.to_string().ends_with("00 01 00 00 00"));
+    }
+
+    #[test]
+    fn test_decoder_merge() {
+        let mut rng = rand::rng();
+
+        let data = (0..3000).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 8).expect("Failed to create Encoder for decoder merge test");
+        let params = encoder.get_params();
+
+        for (layout, other_layout) in [
+            (PayloadLayout::RowMajor, PayloadLayout::ColumnMajor),
+            (PayloadLayout::ColumnMajor, PayloadLayout::RowMajor),
+        ] {
+            let mut wifi = Decoder::new_with_layout(params.get_piece_byte_len(), 8, layout).unwrap();
+            let mut lte = Decoder::new_with_layout(params.get_piece_byte_len(), 8, other_layout).unwrap();
+
+            // Both interfaces received a piece in common.
+            let common_piece = encoder.code(&mut rng);
+            wifi.decode(&common_piece).unwrap();
+            lte.decode(&common_piece).unwrap();
+            for _ in 0..4 {
+                wifi.decode(&encoder.code(&mut rng)).unwrap();
+                lte.decode(&encoder.code(&mut rng)).unwrap();
+            }
+
+            assert_eq!(wifi.merge(lte.clone()), Ok(3));
+            assert_eq!(wifi.get_received_piece_count(), 10);
+            assert!(wifi.is_already_decoded());
+            assert_eq!(wifi.get_decoded_data(), Ok(data.clone()));
+
+            assert_eq!(lte.merge(Decoder::new(params.get_piece_byte_len(), 4).unwrap()), Err(RLNCError::ParamsMismatch));
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_decoder_parallel_elimination_of_large_pieces() {
+        let mut rng = rand::rng();
+
+        // Pieces are large enough for payload part of row operations to run data-parallel, but not a multiple of chunk alignment.
+        let data = (0..8 * 70_001).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 8).expect("Failed to create Encoder for parallel decoding test");
+
+        let thread_pool = rayon::ThreadPoolBuilder::new().num_threads(3).build().expect("Failed to build thread pool");
+        let decoded_data = thread_pool.install(|| {
+            let mut rng = rand::rng();
+            let mut decoder = Decoder::new_with(encoder.get_params());
+            while !decoder.is_already_decoded() {
+                let _ = decoder.decode(&encoder.code(&mut rng));
+            }
+
+            decoder.get_decoded_data()
+        });
+
+        assert_eq!(decoded_data, Ok(data));
+    }
+
+    #[test]
+    fn test_decoder_recode_and_into_recoder() {
+        let mut rng = rand::rng();
+
+        let data = (0..1000).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 8).expect("Failed to create Encoder for decode-and-forward test");
+        let params = encoder.get_params();
+
+        for layout in [PayloadLayout::RowMajor, PayloadLayout::ColumnMajor] {
+            let mut relay = Decoder::new_with_layout(params.get_piece_byte_len(), 8, layout).unwrap();
+            assert_eq!(relay.recode(&mut rng), Err(RLNCError::NotEnoughPiecesToRecode));
+            assert_eq!(relay.recode_with_buf(&mut rng, &mut [0; 3]), Err(RLNCError::NotEnoughPiecesToRecode));
+
+            encoder.systematic_pieces().take(4).for_each(|piece| relay.decode(&piece).unwrap());
+
+            // Recoded pieces never leave the row space of the relay.
+            let mut sink = Decoder::new_with(params);
+            for _ in 0..20 {
+                let _ = sink.decode(&relay.recode(&mut rng).unwrap());
+            }
+            assert_eq!(sink.get_useful_piece_count(), 4);
+
+            let mut recoder = relay.into_recoder().expect("Relay holds useful pieces");
+            assert_eq!(recoder.get_num_pieces_recoded_together(), 4);
+
+            encoder.systematic_pieces().skip(4).for_each(|piece| sink.decode(&piece).unwrap());
+            assert_eq!(sink.decode(&recoder.recode(&mut rng)), Err(RLNCError::ReceivedAllPieces));
+            assert_eq!(sink.get_decoded_data(), Ok(data.clone()));
+        }
+
+        assert_eq!(Decoder::new_with(params).into_recoder().unwrap_err(), RLNCError::NotEnoughPiecesToRecode);
+    }
+
+    #[test]
+    fn test_decoder_get_decoded_data_into() {
+        let mut rng = rand::rng();
+
+        let data = (0..1000).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 8).expect("Failed to create Encoder for decoded data into buffer test");
+        let params = encoder.get_params();
+        let padded_len = params.get_piece_count() * params.get_piece_byte_len();
+
+        for layout in [PayloadLayout::RowMajor, PayloadLayout::ColumnMajor] {
+            let mut decoder = Decoder::new_with_layout(params.get_piece_byte_len(), 8, layout).unwrap();
+            let mut buf = vec![0u8; padded_len + 3];
+            assert_eq!(decoder.clone().get_decoded_data_into(&mut buf), Err(RLNCError::NotAllPiecesReceivedYet));
+
+            encoder.systematic_pieces().for_each(|piece| decoder.decode(&piece).unwrap());
+            assert_eq!(
+                decoder.clone().get_decoded_data_into(&mut buf[..padded_len - 1]),
+                Err(RLNCError::InvalidOutputBuffer)
+            );
+
+            let decoded_len = decoder.get_decoded_data_into(&mut buf).expect("Decoding is complete");
+            assert_eq!(&buf[..decoded_len], data);
+        }
+    }
+
+    #[test]
+    fn test_decoder_write_decoded_data() {
+        let mut rng = rand::rng();
+
+        // Short data leaves whole source pieces holding padding only.
+        for data_len in [1000, 10] {
+            let data = (0..data_len).map(|_| rng.random()).collect::<Vec<u8>>();
+            let encoder = Encoder::new(data.clone(), 8).expect("Failed to create Encoder for streaming decoded data test");
+            let params = encoder.get_params();
+
+            for layout in [PayloadLayout::RowMajor, PayloadLayout::ColumnMajor] {
+                let mut decoder = Decoder::new_with_layout(params.get_piece_byte_len(), 8, layout).unwrap();
+                assert_eq!(decoder.clone().write_decoded_data(Vec::new()), Err(RLNCError::NotAllPiecesReceivedYet));
+
+                encoder.systematic_pieces().for_each(|piece| decoder.decode(&piece).unwrap());
+                assert_eq!(decoder.clone().write_decoded_data(&mut [0u8; 3][..]), Err(RLNCError::WriteFailure));
+
+                let mut sink = Vec::new();
+                assert_eq!(decoder.write_decoded_data(&mut sink), Ok(data_len));
+                assert_eq!(sink, data);
+            }
+        }
+
+        let mut padded_data = vec![0u8; 64];
+        padded_data[..10].fill(0x22);
+        padded_data[10] = BOUNDARY_MARKER;
+        padded_data[60] = 1;
+
+        let encoder = Encoder::without_padding(Box::new(padded_data), 4).expect("Failed to create Encoder for streaming decoded data test");
+        let mut decoder = Decoder::new_with(encoder.get_params());
+        encoder.systematic_pieces().for_each(|piece| decoder.decode(&piece).unwrap());
+
+        let mut sink = Vec::new();
+        assert_eq!(decoder.clone().write_decoded_data(&mut sink), decoder.get_decoded_data().map(|data| data.len()));
+        assert!(sink.is_empty());
+    }
+
+    #[test]
+    fn test_decoder_recovered_pieces() {
+        let mut rng = rand::rng();
+
+        let data = (0..1000).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 8).expect("Failed to create Encoder for recovered pieces test");
+        let params = encoder.get_params();
+
+        // Coded with given coefficients of source pieces.
+        let coded = |coeffs: &[(usize, u8)]| {
+            let mut full_coded_piece = vec![0u8; params.get_full_coded_piece_byte_len()];
+            coeffs.iter().for_each(|&(index, coeff)| full_coded_piece[index] = coeff);
+
+            let (coding_vector, coded_data) = full_coded_piece.split_at_mut(8);
+            encoder.code_with_coding_vector(coding_vector, coded_data).unwrap();
+            full_coded_piece
+        };
+
+        for layout in [PayloadLayout::RowMajor, PayloadLayout::ColumnMajor] {
+            let mut decoder = Decoder::new_with_layout(params.get_piece_byte_len(), 8, layout).unwrap();
+            assert!(decoder.recovered_piece_indices().is_empty());
+
+            decoder.decode(&encoder.systematic_piece(5).unwrap()).unwrap();
+            decoder.decode(&coded(&[(3, 7)])).unwrap();
+            assert_eq!(decoder.recovered_piece_indices(), [3, 5]);
+
+            // Reduced by the systematic piece, it's left with a scaled unit coding vector.
+            decoder.decode(&coded(&[(1, 9), (5, 2)])).unwrap();
+            decoder.decode(&encoder.code(&mut rng)).unwrap();
+            assert_eq!(decoder.recovered_piece_indices(), [1, 3, 5]);
+
+            for index in [1, 3, 5] {
+                let source_piece = encoder.source_piece(index).ok();
+                assert_eq!(decoder.recovered_piece_to_vec(index).as_deref(), source_piece);
+                if layout == PayloadLayout::RowMajor {
+                    assert_eq!(decoder.recovered_piece(index), source_piece);
+                }
+            }
+
+            // Recovered only by combining rows, as its row is reduced by a later one, on demand.
+            decoder.decode(&coded(&[(6, 4), (7, 5)])).unwrap();
+            assert_eq!(decoder.recovered_piece_indices(), [1, 3, 5]);
+            decoder.decode(&coded(&[(7, 3)])).unwrap();
+            assert_eq!(decoder.recovered_piece_indices(), [1, 3, 5, 6, 7]);
+            assert_eq!(decoder.recovered_piece(6), None);
+            for index in [6, 7] {
+                assert_eq!(decoder.recovered_piece_to_vec(index).as_deref(), encoder.source_piece(index).ok());
+            }
+
+            assert_eq!(decoder.recovered_piece(0), None);
+            assert_eq!(decoder.recovered_piece_to_vec(0), None);
+            assert_eq!(decoder.recovered_piece(8), None);
+            assert_eq!(decoder.recovered_piece_to_vec(8), None);
+
+            while !decoder.is_already_decoded() {
+                let _ = decoder.decode(&encoder.code(&mut rng));
+            }
+            assert_eq!(decoder.get_decoded_data(), Ok(data.clone()));
+        }
+    }
+
+    #[test]
+    fn test_decoder_recovers_all_pieces_at_full_rank() {
+        let mut rng = rand::rng();
+
+        let data = (0..1000).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 8).expect("Failed to create Encoder for recovered pieces test");
+
+        for layout in [PayloadLayout::RowMajor, PayloadLayout::ColumnMajor] {
+            let mut decoder = Decoder::new_with_layout(encoder.get_piece_byte_len(), 8, layout).unwrap();
+
+            // Coding vectors with no zero coefficient, so that no piece is systematic.
+            let mut rng = NonZeroRng::new(&mut rng);
+            while !decoder.is_already_decoded() {
+                let _ = decoder.decode(&encoder.code(&mut rng));
+            }
+            assert_eq!(decoder.recovered_piece_indices(), (0..8).collect::<Vec<_>>());
+            for index in 0..8 {
+                let source_piece = encoder.source_piece(index).ok();
+                assert_eq!(decoder.recovered_piece_to_vec(index).as_deref(), source_piece);
+                if layout == PayloadLayout::RowMajor {
+                    assert_eq!(decoder.recovered_piece(index), source_piece);
+                }
+            }
+
+            let restored = Decoder::from_snapshot(decoder.snapshot()).expect("Snapshot must restore");
+            assert_eq!(restored.recovered_piece_indices(), (0..8).collect::<Vec<_>>());
+            assert_eq!(decoder.get_decoded_data(), Ok(data.clone()));
+        }
+    }
+
+    #[test]
+    fn test_decoder_systematic_pieces() {
+        let mut rng = rand::rng();
+
+        let data = (0..3000).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 8).expect("Failed to create Encoder for systematic decoding test");
+        let params = encoder.get_params();
+
+        assert_eq!(encoder.systematic_piece(8), Err(RLNCError::InvalidPieceIndex));
+
+        for layout in [PayloadLayout::RowMajor, PayloadLayout::ColumnMajor] {
+            // Lossless path, reordered, needs no elimination at all.
+            let mut decoder = Decoder::new_with_layout(params.get_piece_byte_len(), 8, layout).unwrap();
+            for (index, piece) in encoder.systematic_pieces().enumerate().rev() {
+                decoder.decode(&piece).expect("Systematic piece must be useful");
+                assert_eq!(decoder.recovered_piece_indices(), (index..8).collect::<Vec<_>>());
+            }
+
+            assert_eq!(decoder.get_decoded_data(), Ok(data.clone()));
+
+            // Lossy path, falling back to random coded pieces, once systematic ones are exhausted.
+            let mut decoder = Decoder::new_with_layout(params.get_piece_byte_len(), 8, layout).unwrap();
+            for piece in encoder
+                .systematic_pieces()
+                .enumerate()
+                .filter(|(index, _)| index % 3 != 1)
+                .map(|(_, piece)| piece)
+            {
+                decoder.decode(&piece).expect("Systematic piece must be useful");
+                assert_eq!(decoder.decode(&piece), Err(RLNCError::PieceNotUseful));
+            }
+
+            assert_eq!(decoder.get_useful_piece_count(), 5);
+            assert_eq!(decoder.recovered_piece_indices(), [0, 2, 3, 5, 6]);
+            for index in [0, 2, 3, 5, 6] {
+                assert_eq!(decoder.recovered_piece_to_vec(index).as_deref(), encoder.source_piece(index).ok());
+            }
+
+            while !decoder.is_already_decoded() {
+                let _ = decoder.decode(&encoder.code(&mut rng));
+            }
+
+            assert_eq!(decoder.get_decoded_data(), Ok(data.clone()));
+        }
+    }
+
+    #[test]
+    fn test_decoder_ref() {
+        const DATA_BYTE_LEN: usize = 1024;
+        const PIECE_COUNT: usize = 16;
+
+        let mut rng = rand::rng();
+        let data = (0..DATA_BYTE_LEN).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), PIECE_COUNT).expect("Failed to create Encoder");
+        let piece_byte_len = encoder.get_piece_byte_len();
+        let full_coded_piece_byte_len = encoder.get_full_coded_piece_byte_len();
+
+        let mut buffer = [0xffu8; 2 * PIECE_COUNT * (PIECE_COUNT + DATA_BYTE_LEN / PIECE_COUNT)];
+        let matrix_byte_len = PIECE_COUNT * full_coded_piece_byte_len;
+
+        assert!(matches!(DecoderRef::new(&mut buffer, 0, PIECE_COUNT), Err(RLNCError::PieceLengthZero)));
+        assert!(matches!(DecoderRef::new(&mut buffer, piece_byte_len, 0), Err(RLNCError::PieceCountZero)));
+        assert!(matches!(
+            DecoderRef::new(&mut buffer[..matrix_byte_len - 1], piece_byte_len, PIECE_COUNT),
+            Err(RLNCError::PieceStorageFailure)
+        ));
+
+        let mut decoder = DecoderRef::new(&mut buffer, piece_byte_len, PIECE_COUNT).expect("Failed to create DecoderRef");
+        assert_eq!(decoder.get_num_pieces_coded_together(), PIECE_COUNT);
+        assert_eq!(decoder.get_piece_byte_len(), piece_byte_len);
+        assert_eq!(decoder.get_full_coded_piece_byte_len(), full_coded_piece_byte_len);
+        assert_eq!(decoder.decode(&vec![0; full_coded_piece_byte_len - 1]), Err(RLNCError::InvalidPieceLength));
+
+        // Replaying a useful piece, it must be found linearly dependent, leaving decoder state unchanged.
+        let first_piece = encoder.code(&mut rng);
+        assert_eq!(decoder.decode(&first_piece), Ok(()));
+        assert_eq!(decoder.decode(&first_piece), Err(RLNCError::PieceNotUseful));
+        assert_eq!(decoder.get_received_piece_count(), 2);
+        assert_eq!(decoder.get_useful_piece_count(), 1);
+
+        let mut decoded_data = vec![0u8; PIECE_COUNT * piece_byte_len];
+        while !decoder.is_already_decoded() {
+            match decoder.decode(&encoder.code(&mut rng)) {
+                Ok(_) | Err(RLNCError::PieceNotUseful) => {}
+                Err(e) => panic!("Unexpected error during decoding: {e:?}"),
+            }
         }
 
-        assert_eq!(decoder.get_useful_piece_count(), required_piece_count);
         assert_eq!(decoder.get_remaining_piece_count(), 0);
-        assert!(decoder.is_already_decoded());
-        assert_eq!(decoder.get_received_piece_count(), total_pieces_received);
+        assert_eq!(decoder.decode(&encoder.code(&mut rng)), Err(RLNCError::ReceivedAllPieces));
+
+        let decoded_data_byte_len = decoder.get_decoded_data_into(&mut decoded_data).expect("Failed to extract decoded data");
+        assert_eq!(&decoded_data[..decoded_data_byte_len], &data);
     }
 }
@@ -1,18 +1,55 @@
+use super::storage::PieceStorage;
 use crate::{
     RLNCError,
     common::{
         gf256::Gf256,
-        simd::{gf256_inplace_mul_vec_by_scalar, gf256_mul_vec_by_scalar_then_add_into_vec},
+        simd::{MAX_FUSED_VEC_COUNT, gf256_inplace_mul_vec_by_scalar, gf256_mul_vec_by_scalar_then_add_into_vec, gf256_mul_vecs_by_scalars_then_add_into_vec},
     },
 };
-use std::ops::{Index, IndexMut};
+use std::ops::Range;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Byte length, each row is padded to a multiple of, unless rows live in caller provided storage. It's the width of the widest
+/// SIMD register we use, so that row operations never fall into the scalar remainder loop of a kernel.
+pub const ROW_ALIGNMENT: usize = 64;
+
+/// Zero bytes, padding each row.
+static ROW_PADDING: [u8; ROW_ALIGNMENT] = [0u8; ROW_ALIGNMENT];
+
+/// Payload byte length, from which on, payload part of row operations runs data-parallel. Below it, handing work over to
+/// threads costs more than it saves.
+#[cfg(feature = "parallel")]
+const PARALLEL_ELIMINATION_MIN_PAYLOAD_BYTE_LEN: usize = 1 << 16;
+
+/// Base pointer of matrix elements, shared by threads, each touching a disjoint range of payload columns of all rows.
+#[cfg(feature = "parallel")]
+#[derive(Clone, Copy)]
+struct SharedElements(*mut u8);
+
+#[cfg(feature = "parallel")]
+unsafe impl Send for SharedElements {}
+#[cfg(feature = "parallel")]
+unsafe impl Sync for SharedElements {}
+
+#[cfg(feature = "parallel")]
+impl SharedElements {
+    /// Returns the base pointer. Being a method, closures capture the whole wrapper, not the bare pointer.
+    fn get(self) -> *mut u8 {
+        self.0
+    }
+}
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug)]
 pub struct DecoderMatrix {
     num_pieces_coded_together: usize,
     rows: usize,
     cols: usize,
-    elements: Vec<u8>,
+    /// Distance between starts of consecutive rows, in bytes, which is `cols` rounded up to a multiple of `ROW_ALIGNMENT`, or
+    /// exactly `cols`, if rows live in caller provided storage. Padding is always zero.
+    stride: usize,
+    elements: Box<dyn PieceStorage>,
 }
 
 impl DecoderMatrix {
@@ -28,19 +65,47 @@ impl DecoderMatrix {
     ///
     /// # Returns
     /// An instance of decoder matrix - ready to use for decoding.
+    ///
+    /// Each row is internally padded with zeros, to a multiple of 64 bytes, so that rows start at 64-byte boundaries, relative
+    /// to the start of the storage, and row operations run on whole SIMD registers. Rows, as returned, are of exact length.
     pub fn new(num_pieces_coded_together: usize, piece_byte_length: usize) -> Self {
         let full_coded_piece_byte_len = num_pieces_coded_together + piece_byte_length;
-        let total_byte_len = num_pieces_coded_together * full_coded_piece_byte_len;
-        let elements = Vec::with_capacity(total_byte_len);
+        Self::new_with_stride(
+            num_pieces_coded_together,
+            full_coded_piece_byte_len,
+            full_coded_piece_byte_len.next_multiple_of(ROW_ALIGNMENT),
+            Box::new(Vec::new()),
+        )
+    }
+
+    /// Same as `Self::new`, but rows are stored in given storage, which is emptied first. Rows are stored back to back,
+    /// without padding, so that storage sized for `num_pieces_coded_together` many full coded pieces suffices.
+    pub fn new_with_storage(num_pieces_coded_together: usize, piece_byte_length: usize, storage: Box<dyn PieceStorage>) -> Self {
+        let full_coded_piece_byte_len = num_pieces_coded_together + piece_byte_length;
+        Self::new_with_stride(num_pieces_coded_together, full_coded_piece_byte_len, full_coded_piece_byte_len, storage)
+    }
+
+    fn new_with_stride(num_pieces_coded_together: usize, cols: usize, stride: usize, mut storage: Box<dyn PieceStorage>) -> Self {
+        storage.truncate(0);
+        storage.reserve(num_pieces_coded_together * stride);
 
         Self {
             num_pieces_coded_together,
             rows: 0,
-            cols: full_coded_piece_byte_len,
-            elements,
+            cols,
+            stride,
+            elements: storage,
         }
     }
 
+    /// Removes all rows, keeping the underlying storage, so that the matrix can be rebuilt from scratch.
+    pub fn clear(&mut self) -> &mut Self {
+        self.elements.truncate(0);
+        self.rows = 0;
+
+        self
+    }
+
     /// Adds a new row to the decoder matrix.
     ///
     /// # Arguments
@@ -50,12 +115,18 @@ impl DecoderMatrix {
     /// # Returns
     /// * Ok(&mut Self) - If full erasure-coded piece is of valid length.
     /// * Err(RLNCError::InvalidPieceLength) - If full erasure-coded piece length doesn't match expected value.
+    /// * Err(RLNCError::PieceStorageFailure) - If underlying storage can't grow.
     pub fn add_row(&mut self, row: &[u8]) -> Result<&mut Self, RLNCError> {
         if row.len() != self.cols {
             return Err(RLNCError::InvalidPieceLength);
         }
 
-        self.elements.extend_from_slice(row);
+        let row_begin = self.elements.len();
+        self.elements.try_extend_from_slice(row)?;
+        if let Err(e) = self.elements.try_extend_from_slice(&ROW_PADDING[..self.stride - self.cols]) {
+            self.elements.truncate(row_begin);
+            return Err(e);
+        }
         self.rows += 1;
 
         Ok(self)
@@ -73,43 +144,332 @@ impl DecoderMatrix {
 
         let (r1, r2) = if row1_idx < row2_idx { (row1_idx, row2_idx) } else { (row2_idx, row1_idx) };
 
-        let start1 = r1 * self.cols;
-        let end1 = start1 + self.cols;
-        let start2 = r2 * self.cols;
+        let start1 = r1 * self.stride;
+        let end1 = start1 + self.stride;
+        let start2 = r2 * self.stride;
 
-        let (left, right) = self.elements.split_at_mut(start2);
+        let (left, right) = self.elements.as_mut_slice().split_at_mut(start2);
 
         // row1 is in the first part
         let row1 = &mut left[start1..end1];
         // row2 is the beginning of the second part
-        let row2 = &mut right[..self.cols];
+        let row2 = &mut right[..self.stride];
 
         row1.swap_with_slice(row2);
 
         self
     }
 
-    /// Computes the Reduced Row Echelon Form (RREF) of the matrix.
+    /// Computes the Reduced Row Echelon Form (RREF) of the matrix, except that pivots are not normalized to one.
     ///
     /// This involves forward elimination (`Self::clean_forward`), backward elimination
     /// (`Self::clean_backward`), and removing any resulting zero rows (`Self::remove_zero_rows`).
     ///
     /// This function updates the number of rows to reflect the current rank of the matrix.
     /// It is safe to call `Self::rank` after calling this function.
+    ///
+    /// Normalizing pivots costs a full row scalar multiplication each, which would otherwise be paid on every incoming
+    /// piece, so it's deferred till extraction. Call `Self::normalize_pivots`, before reading rows as decoded data.
     pub fn rref(&mut self) -> &mut Self {
         self.clean_forward().clean_backward().remove_zero_rows()
     }
 
+    /// Divides each row by its pivot i.e. first non-zero coefficient, so that pivots become one. After `Self::rref`,
+    /// on a full rank matrix, coefficient part of it becomes identity.
+    pub fn normalize_pivots(&mut self) -> &mut Self {
+        let num_pieces_coded_together = self.num_pieces_coded_together;
+
+        for ridx in 0..self.rows {
+            let row = self.row_mut(ridx);
+            let Some(pivot_cidx) = row[..num_pieces_coded_together].iter().position(|&coeff| coeff != 0) else {
+                continue;
+            };
+            if row[pivot_cidx] == 1 {
+                continue;
+            }
+
+            let inv = unsafe { Gf256::new(row[pivot_cidx]).inv().unwrap_unchecked().get() };
+            row[pivot_cidx] = 1;
+            gf256_inplace_mul_vec_by_scalar(&mut row[pivot_cidx + 1..], inv);
+        }
+
+        self
+    }
+
+    /// Reduces the last row by rows holding pivots, in ascending order of their pivot columns, leaving all other rows untouched.
+    /// It's the forward step of incremental Gaussian elimination, costing one row operation per pivot, at max, instead of a
+    /// full `Self::rref`. Rows holding pivots must be zero before their pivot columns, but they need not be in any order.
+    ///
+    /// # Arguments
+    /// * `pivot_rows` - Index of the row holding the pivot of each coefficient column, if any. Last row must hold none.
+    ///
+    /// # Returns
+    /// Returns pivot column of the reduced row i.e. index of its first non-zero coefficient, or `None`, if it got reduced to zero.
+    pub fn reduce_last_row(&mut self, pivot_rows: &[Option<usize>]) -> Option<usize> {
+        self.reduce_last_row_by_columns(pivot_rows, 0..self.num_pieces_coded_together);
+        self.row(self.rows - 1)[..self.num_pieces_coded_together].iter().position(|&coeff| coeff != 0)
+    }
+
+    /// Same as `Self::reduce_last_row`, but only by rows holding pivots in given range of coefficient columns, so that the last
+    /// row can be reduced in slices. Reducing by consecutive ranges of columns, in ascending order, is same as reducing at once.
+    pub fn reduce_last_row_by_columns(&mut self, pivot_rows: &[Option<usize>], cols: Range<usize>) {
+        let last_ridx = self.rows - 1;
+
+        self.eliminate_all(
+            pivot_rows[cols.clone()]
+                .iter()
+                .zip(cols)
+                .filter_map(|(&pivot_ridx, cidx)| pivot_ridx.map(|pivot_ridx| (pivot_ridx, last_ridx, cidx))),
+        );
+    }
+
+    /// Removes the last row, say after it got reduced to zero by `Self::reduce_last_row`.
+    pub fn remove_last_row(&mut self) -> &mut Self {
+        self.rows -= 1;
+        self.elements.truncate(self.rows * self.stride);
+
+        self
+    }
+
+    /// Completes incremental Gaussian elimination, by running the deferred back-substitution. Rows, each zero before its pivot
+    /// column, with all pivot columns distinct, as built using `Self::reduce_last_row`, are sorted by their pivot columns, using
+    /// swaps only, and each pivot column is cleared off all other rows. Result is in reduced row echelon form, except that pivots
+    /// are not normalized to one, see `Self::normalize_pivots`.
+    pub fn back_substitute(&mut self) -> &mut Self {
+        let mut pivots = self.pivot_columns().collect::<Vec<_>>();
+
+        for ridx in 0..pivots.len() {
+            let min_ridx = (ridx..pivots.len()).min_by_key(|&other_ridx| pivots[other_ridx]).unwrap_or(ridx);
+
+            self.swap_rows(ridx, min_ridx);
+            pivots.swap(ridx, min_ridx);
+        }
+
+        // Rows below a pivot are zero in its column already, and rows processed earlier are zero in this row's pivot column.
+        self.eliminate_all((0..pivots.len()).rev().flat_map(|ridx| {
+            let pivot_cidx = pivots[ridx];
+            (0..ridx).map(move |other_ridx| (ridx, other_ridx, pivot_cidx))
+        }));
+
+        self
+    }
+
     /// Returns the current rank of the matrix, which is same as the number
     /// of rows, after calling `Self::rref`.
     pub fn rank(&self) -> usize {
         self.rows
     }
 
-    /// Returns underlying data i.e. `self.rows` many full erasure-coded pieces.
+    /// Returns pivot column i.e. index of first non-zero coefficient, of each row, after calling `Self::rref`.
+    pub fn pivot_columns(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.rows).filter_map(|ridx| self.row(ridx)[..self.num_pieces_coded_together].iter().position(|&coeff| coeff != 0))
+    }
+
+    /// Returns row at given index, which must be less than `self.rank()`.
+    pub fn row(&self, ridx: usize) -> &[u8] {
+        let row_begin = ridx * self.stride;
+        &self.elements.as_slice()[row_begin..row_begin + self.cols]
+    }
+
+    /// Returns mutable row at given index, which must be less than `self.rank()`.
+    pub fn row_mut(&mut self, ridx: usize) -> &mut [u8] {
+        let row_begin = ridx * self.stride;
+        &mut self.elements.as_mut_slice()[row_begin..row_begin + self.cols]
+    }
+
+    /// Returns all `self.rows` many rows, in order.
+    pub fn rows(&self) -> impl Iterator<Item = &[u8]> + '_ {
+        (0..self.rows).map(|ridx| self.row(ridx))
+    }
+
+    /// Returns all `self.rows` many rows, concatenated, without consuming the decoder matrix.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        if self.stride == self.cols {
+            return self.elements.as_slice().to_vec();
+        }
+
+        self.rows().flatten().copied().collect()
+    }
+
+    /// Returns underlying data i.e. `self.rows` many full erasure-coded pieces, concatenated.
     /// Calling this function, consumes the decoder matrix instance.
-    pub fn extract_data(self) -> Vec<u8> {
-        self.elements
+    pub fn extract_data(mut self) -> Vec<u8> {
+        if self.stride != self.cols {
+            for ridx in 1..self.rows {
+                self.elements
+                    .as_mut_slice()
+                    .copy_within(ridx * self.stride..ridx * self.stride + self.cols, ridx * self.cols);
+            }
+            self.elements.truncate(self.rows * self.cols);
+        }
+
+        self.elements.into_vec()
+    }
+
+    /// Returns disjoint rows at given indices, padding included, which must be distinct and less than `self.rows`, the first
+    /// one immutable. Lets a row operation borrow both rows it touches, once, instead of computing index of each element.
+    fn row_pair_mut(&mut self, src_ridx: usize, dst_ridx: usize) -> (&[u8], &mut [u8]) {
+        let stride = self.stride;
+        let elements = self.elements.as_mut_slice();
+
+        if src_ridx < dst_ridx {
+            let (left, right) = elements.split_at_mut(dst_ridx * stride);
+            (&left[src_ridx * stride..(src_ridx + 1) * stride], &mut right[..stride])
+        } else {
+            let (left, right) = elements.split_at_mut(src_ridx * stride);
+            (&right[..stride], &mut left[dst_ridx * stride..(dst_ridx + 1) * stride])
+        }
+    }
+
+    /// Clears column `cidx` of row `dst_ridx`, by adding a multiple of row `src_ridx`, whose element in that column is non-zero.
+    /// Source row must be zero before column `cidx`, so only the rest of the rows needs to be touched. On padded rows, it
+    /// starts at the preceding 64-byte boundary instead, adding zeros there, so that the kernel runs on whole SIMD registers.
+    fn eliminate(&mut self, src_ridx: usize, dst_ridx: usize, cidx: usize) {
+        let padded = self.stride != self.cols;
+        let (src_row, dst_row) = self.row_pair_mut(src_ridx, dst_ridx);
+        if dst_row[cidx] == 0 {
+            return;
+        }
+
+        let quotient = unsafe { (Gf256::new(dst_row[cidx]) / Gf256::new(src_row[cidx])).unwrap_unchecked().get() };
+        let begin = if padded { cidx - cidx % ROW_ALIGNMENT } else { cidx };
+        gf256_mul_vec_by_scalar_then_add_into_vec(&mut dst_row[begin..], &src_row[begin..], quotient);
+    }
+
+    /// Runs `Self::eliminate`, for each of `(src_ridx, dst_ridx, cidx)`, in order. The coefficient part of each row operation runs
+    /// first, as quotients depend on it, while the payload part of consecutive row operations on same destination row is deferred,
+    /// so that up to `MAX_FUSED_VEC_COUNT` source rows are added into it in a single pass, see `Self::eliminate_payload`. With
+    /// `parallel` feature, on large payloads, the payload part of all row operations runs afterwards, data-parallel, see
+    /// `Self::eliminate_payloads`.
+    fn eliminate_all(&mut self, eliminations: impl Iterator<Item = (usize, usize, usize)>) {
+        #[cfg(feature = "parallel")]
+        if self.cols - self.num_pieces_coded_together >= PARALLEL_ELIMINATION_MIN_PAYLOAD_BYTE_LEN {
+            let row_ops = eliminations
+                .filter_map(|(src_ridx, dst_ridx, cidx)| {
+                    self.eliminate_coefficients(src_ridx, dst_ridx, cidx)
+                        .map(|quotient| (src_ridx, dst_ridx, quotient))
+                })
+                .collect::<Vec<_>>();
+
+            self.eliminate_payloads(&row_ops);
+            return;
+        }
+
+        let mut row_ops = [(0, 0); MAX_FUSED_VEC_COUNT];
+        let mut row_op_count = 0;
+
+        let mut eliminations = eliminations.peekable();
+        while let Some((src_ridx, dst_ridx, cidx)) = eliminations.next() {
+            if let Some(quotient) = self.eliminate_coefficients(src_ridx, dst_ridx, cidx) {
+                row_ops[row_op_count] = (src_ridx, quotient);
+                row_op_count += 1;
+            }
+
+            let is_last_on_dst_row = eliminations.peek().is_none_or(|&(_, next_dst_ridx, _)| next_dst_ridx != dst_ridx);
+            if row_op_count == MAX_FUSED_VEC_COUNT || (is_last_on_dst_row && row_op_count > 0) {
+                self.eliminate_payload(dst_ridx, &row_ops[..row_op_count]);
+                row_op_count = 0;
+            }
+        }
+    }
+
+    /// Column, payload part of row operations, deferred by `Self::eliminate_all`, starts at. On padded rows, it's the first 64-byte
+    /// boundary past coefficients, so that fused kernels run on whole SIMD registers, while coefficient part of row operations
+    /// covers the few payload columns before it.
+    fn payload_begin(&self) -> usize {
+        if self.stride != self.cols {
+            self.num_pieces_coded_together.next_multiple_of(ROW_ALIGNMENT).min(self.cols)
+        } else {
+            self.num_pieces_coded_together
+        }
+    }
+
+    /// Same as `Self::eliminate`, but touching the destination row only before `Self::payload_begin`.
+    ///
+    /// # Returns
+    /// Returns the multiple of source row, which is still to be added to payload part of destination row, or `None`, if column
+    /// `cidx` of destination row is already zero.
+    fn eliminate_coefficients(&mut self, src_ridx: usize, dst_ridx: usize, cidx: usize) -> Option<u8> {
+        let padded = self.stride != self.cols;
+        let payload_begin = self.payload_begin();
+
+        let (src_row, dst_row) = self.row_pair_mut(src_ridx, dst_ridx);
+        if dst_row[cidx] == 0 {
+            return None;
+        }
+
+        let quotient = unsafe { (Gf256::new(dst_row[cidx]) / Gf256::new(src_row[cidx])).unwrap_unchecked().get() };
+        let begin = if padded { cidx - cidx % ROW_ALIGNMENT } else { cidx };
+        gf256_mul_vec_by_scalar_then_add_into_vec(&mut dst_row[begin..payload_begin], &src_row[begin..payload_begin], quotient);
+
+        Some(quotient)
+    }
+
+    /// Adds multiples of source rows i.e. `(src_ridx, quotient)`, recorded by `Self::eliminate_coefficients`, to payload part of
+    /// row `dst_ridx`, in a single pass. At most `MAX_FUSED_VEC_COUNT` source rows, all distinct from destination row, are taken.
+    fn eliminate_payload(&mut self, dst_ridx: usize, row_ops: &[(usize, u8)]) {
+        let (stride, payload_begin) = (self.stride, self.payload_begin());
+
+        let (rows_before, rows_after) = self.elements.as_mut_slice().split_at_mut(dst_ridx * stride);
+        let (dst_row, rows_after) = rows_after.split_at_mut(stride);
+
+        let mut src_payloads: [&[u8]; MAX_FUSED_VEC_COUNT] = [&[]; MAX_FUSED_VEC_COUNT];
+        let mut quotients = [0u8; MAX_FUSED_VEC_COUNT];
+
+        for ((src_payload, quotient), &(src_ridx, row_op_quotient)) in src_payloads.iter_mut().zip(&mut quotients).zip(row_ops) {
+            let src_row_begin = if src_ridx < dst_ridx {
+                src_ridx * stride
+            } else {
+                (src_ridx - dst_ridx - 1) * stride
+            };
+            let src_rows = if src_ridx < dst_ridx { &*rows_before } else { &*rows_after };
+
+            *src_payload = &src_rows[src_row_begin + payload_begin..src_row_begin + stride];
+            *quotient = row_op_quotient;
+        }
+
+        gf256_mul_vecs_by_scalars_then_add_into_vec(&mut dst_row[payload_begin..], &src_payloads[..row_ops.len()], &quotients[..row_ops.len()]);
+    }
+
+    /// Replays row operations i.e. `(src_ridx, dst_ridx, quotient)`, recorded by `Self::eliminate_coefficients`, on payload part
+    /// of rows. Payload columns are partitioned into disjoint ranges, one or more per thread, each replaying all row operations,
+    /// in order, as columns don't depend on each other. Consecutive row operations on same destination row are fused, same as
+    /// in `Self::eliminate_payload`.
+    #[cfg(feature = "parallel")]
+    fn eliminate_payloads(&mut self, row_ops: &[(usize, usize, u8)]) {
+        let (stride, cols, payload_begin) = (self.stride, self.cols, self.payload_begin());
+
+        let num_chunks = rayon::current_num_threads();
+        let chunk_byte_len = (cols - payload_begin).div_ceil(num_chunks).next_multiple_of(ROW_ALIGNMENT);
+        let elements = SharedElements(self.elements.as_mut_slice().as_mut_ptr());
+
+        (0..num_chunks).into_par_iter().for_each(|chunk_idx| {
+            let chunk_begin = payload_begin + chunk_idx * chunk_byte_len;
+            if chunk_begin >= cols {
+                return;
+            }
+            let chunk_len = chunk_byte_len.min(cols - chunk_begin);
+
+            for fused_row_ops in row_ops
+                .chunk_by(|a, b| a.1 == b.1)
+                .flat_map(|dst_row_ops| dst_row_ops.chunks(MAX_FUSED_VEC_COUNT))
+            {
+                let dst_ridx = fused_row_ops[0].1;
+
+                let mut src_chunks: [&[u8]; MAX_FUSED_VEC_COUNT] = [&[]; MAX_FUSED_VEC_COUNT];
+                let mut quotients = [0u8; MAX_FUSED_VEC_COUNT];
+
+                for ((src_chunk, quotient), &(src_ridx, _, row_op_quotient)) in src_chunks.iter_mut().zip(&mut quotients).zip(fused_row_ops) {
+                    // Source rows are distinct from destination row, which is the only one written to.
+                    *src_chunk = unsafe { std::slice::from_raw_parts(elements.get().add(src_ridx * stride + chunk_begin), chunk_len) };
+                    *quotient = row_op_quotient;
+                }
+
+                // Other threads touch other columns only, so destination chunk never aliases with anything else.
+                let dst_chunk = unsafe { std::slice::from_raw_parts_mut(elements.get().add(dst_ridx * stride + chunk_begin), chunk_len) };
+                gf256_mul_vecs_by_scalars_then_add_into_vec(dst_chunk, &src_chunks[..fused_row_ops.len()], &quotients[..fused_row_ops.len()]);
+            }
+        });
     }
 
     /// Performs the forward phase of Gaussian elimination (to row echelon form).
@@ -121,44 +481,16 @@ impl DecoderMatrix {
         let boundary = self.rows.min(self.cols);
 
         for i in 0..boundary {
-            if self[(i, i)] == Gf256::zero() {
-                let mut is_non_zero_col = false;
-                let mut pivot_row_idx = i + 1;
-
-                while pivot_row_idx < self.rows {
-                    if self[(pivot_row_idx, i)] != Gf256::zero() {
-                        is_non_zero_col = true;
-                        break;
-                    }
-                    pivot_row_idx += 1;
-                }
-
-                if !is_non_zero_col {
+            if self.row(i)[i] == 0 {
+                let Some(pivot_row_idx) = ((i + 1)..self.rows).find(|&ridx| self.row(ridx)[i] != 0) else {
                     continue;
-                }
+                };
 
                 self.swap_rows(i, pivot_row_idx);
             }
 
             for j in (i + 1)..self.rows {
-                if self[(j, i)] == Gf256::zero() {
-                    continue;
-                }
-
-                let quotient = unsafe { (self[(j, i)] / self[(i, i)]).unwrap_unchecked().get() };
-
-                let i_th_row_starts_at = i * self.cols;
-                let i_th_row_ends_at = i_th_row_starts_at + self.cols;
-
-                let j_th_row_starts_at = j * self.cols;
-                let j_th_row_ends_at = j_th_row_starts_at + self.cols;
-
-                let (left, right) = self.elements.split_at_mut(i_th_row_ends_at);
-
-                let i_th_row = &left[(i_th_row_starts_at + i)..];
-                let j_th_row = &mut right[(j_th_row_starts_at - i_th_row_ends_at + i)..(j_th_row_ends_at - i_th_row_ends_at)];
-
-                gf256_mul_vec_by_scalar_then_add_into_vec(j_th_row, i_th_row, quotient);
+                self.eliminate(i, j, i);
             }
         }
 
@@ -167,48 +499,18 @@ impl DecoderMatrix {
 
     /// Performs the backward phase of Gaussian elimination (to reduced row echelon form).
     ///
-    /// Clears entries above the pivots and normalizes pivots to 1.
+    /// Clears entries above the pivots. Pivots are left as they are, see `Self::normalize_pivots`.
     fn clean_backward(&mut self) -> &mut Self {
         let boundary = self.rows.min(self.cols);
 
         for i in (0..boundary).rev() {
-            if self[(i, i)] == Gf256::zero() {
+            if self.row(i)[i] == 0 {
                 continue;
             }
 
             for j in 0..i {
-                if self[(j, i)] == Gf256::zero() {
-                    continue;
-                }
-
-                let quotient = unsafe { (self[(j, i)] / self[(i, i)]).unwrap_unchecked().get() };
-
-                let j_th_row_starts_at = j * self.cols;
-                let j_th_row_ends_at = j_th_row_starts_at + self.cols;
-
-                let i_th_row_starts_at = i * self.cols;
-                let i_th_row_ends_at = i_th_row_starts_at + self.cols;
-
-                let (left, right) = self.elements.split_at_mut(j_th_row_ends_at);
-
-                let j_th_row = &mut left[(j_th_row_starts_at + i)..];
-                let i_th_row = &right[(i_th_row_starts_at - j_th_row_ends_at + i)..(i_th_row_ends_at - j_th_row_ends_at)];
-
-                gf256_mul_vec_by_scalar_then_add_into_vec(j_th_row, i_th_row, quotient);
-            }
-
-            if self[(i, i)] == Gf256::one() {
-                continue;
+                self.eliminate(i, j, i);
             }
-
-            let inv = unsafe { self[(i, i)].inv().unwrap_unchecked().get() };
-            self[(i, i)] = Gf256::one();
-
-            let i_th_row_starts_at = i * self.cols;
-            let i_th_row_ends_at = i_th_row_starts_at + self.cols;
-
-            let i_th_row = &mut self.elements[(i_th_row_starts_at + (i + 1))..i_th_row_ends_at];
-            gf256_inplace_mul_vec_by_scalar(i_th_row, inv);
         }
 
         self
@@ -222,71 +524,37 @@ impl DecoderMatrix {
     fn remove_zero_rows(&mut self) -> &mut Self {
         let mut i = 0;
         while i < self.rows {
-            let is_nonzero_row = (0..self.num_pieces_coded_together).any(|cidx| self[(i, cidx)] != Gf256::zero());
+            let is_nonzero_row = self.row(i)[..self.num_pieces_coded_together].iter().any(|&coeff| coeff != 0);
             if is_nonzero_row {
                 i += 1;
                 continue;
             }
 
-            let start_idx_of_row_to_remove = i * self.cols;
-            let start_idx_of_next_row = (i + 1) * self.cols;
+            let start_idx_of_row_to_remove = i * self.stride;
+            let start_idx_of_next_row = (i + 1) * self.stride;
 
             if start_idx_of_next_row < self.elements.len() {
-                self.elements.copy_within(start_idx_of_next_row.., start_idx_of_row_to_remove);
+                self.elements.as_mut_slice().copy_within(start_idx_of_next_row.., start_idx_of_row_to_remove);
             }
             self.rows -= 1;
         }
 
-        let updated_num_elements = self.rows * self.cols;
+        let updated_num_elements = self.rows * self.stride;
         self.elements.truncate(updated_num_elements);
 
         self
     }
 }
 
-impl Index<(usize, usize)> for DecoderMatrix {
-    type Output = Gf256;
-
-    /// Returns an immutable reference to an element of matrix at the specified row and column,
-    /// converting it to a `Gf256` element.
-    ///
-    /// # Arguments
-    /// * `index` - A tuple `(row_index, col_index)` specifying the position.
-    ///
-    /// # Returns
-    /// Returns the element as a `Gf256`.
-    ///
-    /// # Panics
-    /// Panics if the index is out of bounds.
-    fn index(&self, index: (usize, usize)) -> &Self::Output {
-        let (row_idx, col_idx) = index;
-        let lin_idx = row_idx * self.cols + col_idx;
-
-        unsafe { std::mem::transmute(self.elements.get_unchecked(lin_idx)) }
-    }
-}
-
-impl IndexMut<(usize, usize)> for DecoderMatrix {
-    /// Returns a mutable reference to an element of matrix at the specified row and column,
-    /// converting it to a `Gf256` element.
-    ///
-    /// # Arguments
-    /// * `index` - A tuple `(row_index, col_index)` specifying the position.
-    /// * `val` - The `Gf256` value to set.
-    ///
-    /// # Panics
-    /// Panics if the index is out of bounds.
-    fn index_mut(&mut self, index: (usize, usize)) -> &mut Self::Output {
-        let (row_idx, col_idx) = index;
-        let lin_idx = row_idx * self.cols + col_idx;
-
-        unsafe { std::mem::transmute(self.elements.get_unchecked_mut(lin_idx)) }
+impl PartialEq for DecoderMatrix {
+    fn eq(&self, other: &Self) -> bool {
+        self.num_pieces_coded_together == other.num_pieces_coded_together && self.rows == other.rows && self.cols == other.cols && self.rows().eq(other.rows())
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::full::decoder_matrix::DecoderMatrix;
+    use crate::{common::gf256::Gf256, full::decoder_matrix::DecoderMatrix};
     use rand::Rng;
 
     fn make_random_matrix<R: Rng + ?Sized>(num_rows: usize, num_cols: usize, rng: &mut R) -> DecoderMatrix {
@@ -379,4 +647,89 @@ mod test {
         expected_4.add_row(&[1, 1, 1, 10, 10]).unwrap(); // Swapped from row 0
         assert_eq!(matrix_case_4, expected_4, "Failed swap of first and last rows (0, 3)");
     }
+
+    #[test]
+    fn test_padded_rows_match_unpadded_rows() {
+        let mut rng = rand::rng();
+
+        for (num_pieces, piece_len) in [(4, 3), (20, 44), (70, 130), (16, 0)] {
+            let mut padded = DecoderMatrix::new(num_pieces, piece_len);
+            let mut unpadded = DecoderMatrix::new_with_storage(num_pieces, piece_len, Box::new(Vec::new()));
+
+            for _ in 0..num_pieces + 2 {
+                let row = (0..num_pieces + piece_len).map(|_| rng.random()).collect::<Vec<u8>>();
+                padded.add_row(&row).unwrap().rref();
+                unpadded.add_row(&row).unwrap().rref();
+            }
+            padded.swap_rows(0, 1).normalize_pivots();
+            unpadded.swap_rows(0, 1).normalize_pivots();
+
+            assert_eq!(padded.rank(), unpadded.rank());
+            assert!(padded.rows().all(|row| row.len() == num_pieces + piece_len));
+            assert_eq!(padded.to_bytes(), unpadded.to_bytes());
+            assert_eq!(padded.extract_data(), unpadded.extract_data());
+        }
+    }
+
+    #[test]
+    fn test_incremental_elimination_matches_rref() {
+        const PIECE_BYTE_LEN: usize = 90;
+        let mut rng = rand::rng();
+
+        for (num_cols, num_rows) in [(16, 40), (70, 50), (33, 10)] {
+            let mut matrix = DecoderMatrix::new(num_cols, PIECE_BYTE_LEN);
+            let mut incremental = DecoderMatrix::new(num_cols, PIECE_BYTE_LEN);
+            let mut pivot_rows = vec![None; num_cols];
+
+            let pieces = (0..num_cols * PIECE_BYTE_LEN).map(|_| rng.random()).collect::<Vec<u8>>();
+
+            // Every other coding vector is zero in every third column, so that pivot columns don't follow the order of rows.
+            for ridx in 0..num_rows {
+                let mut row = (0..num_cols)
+                    .map(|cidx| if ridx % 2 == 0 || cidx % 3 != 0 { rng.random() } else { 0 })
+                    .collect::<Vec<u8>>();
+                let mut coded_piece = [0u8; PIECE_BYTE_LEN];
+                for (&coeff, piece) in row.iter().zip(pieces.chunks_exact(PIECE_BYTE_LEN)) {
+                    coded_piece.iter_mut().zip(piece).for_each(|(dst, &src)| *dst ^= Gf256::mul_const(coeff, src));
+                }
+                row.extend_from_slice(&coded_piece);
+
+                matrix.add_row(&row).unwrap().rref();
+                match incremental.add_row(&row).unwrap().reduce_last_row(&pivot_rows) {
+                    Some(cidx) => pivot_rows[cidx] = Some(incremental.rank() - 1),
+                    None => {
+                        incremental.remove_last_row();
+                    }
+                }
+            }
+
+            // Random rows are linearly independent, with overwhelming probability, as long as there are spare dimensions.
+            assert_eq!(incremental.rank(), num_cols.min(num_rows));
+            assert!(pivot_rows.iter().flatten().all(|&ridx| ridx < incremental.rank()));
+
+            // Reduced row echelon form, with normalized pivots, is unique, so both must agree, on full rank.
+            if num_rows > num_cols {
+                assert_eq!(incremental.back_substitute().normalize_pivots(), matrix.normalize_pivots());
+            }
+        }
+    }
+
+    #[test]
+    fn test_normalize_pivots_after_rref() {
+        let mut rng = rand::rng();
+
+        let num_cols = 32;
+        let mut matrix = make_random_matrix(num_cols, num_cols, &mut rng);
+
+        // Full rank, with overwhelming probability, so coefficient part becomes identity, once pivots are normalized.
+        if matrix.rref().rank() < num_cols {
+            return;
+        }
+        matrix.normalize_pivots();
+
+        for ridx in 0..num_cols {
+            assert!(matrix.row(ridx).iter().enumerate().all(|(cidx, &coeff)| coeff == u8::from(cidx == ridx)));
+        }
+        assert_eq!(matrix.clone().normalize_pivots(), &matrix);
+    }
 }
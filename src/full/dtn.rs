@@ -0,0 +1,348 @@
+use super::{
+    encoder::Encoder,
+    mapping::{ContiguousMapping, GenerationMapping},
+    params::Params,
+    plan::{PieceDescriptor, PieceKind, PlanOrdering, SendPlan},
+};
+use crate::RLNCError;
+use rand::Rng;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Byte length of the header of a serialized `ExpiringPiece` i.e. big-endian generation index and expiry timestamp.
+const EXPIRING_PIECE_HEADER_BYTE_LEN: usize = size_of::<u32>() + size_of::<u64>();
+
+/// Configuration for delay-tolerant networking (DTN) links, say satellite or deep space ones, where round trip takes minutes
+/// or more, so rank feedback is impractical.
+///
+/// Sender never waits for feedback. It sends a fixed, generous number of pieces per generation, decided up front by estimated
+/// loss and a high overhead factor. Generations are large, so that coding overhead gets amortized, and pieces of all generations
+/// are interleaved, spreading long outages over all of them. Each piece carries an expiry timestamp, after which it's stale
+/// and can be dropped by custody nodes, instead of being stored and forwarded.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DtnProfile {
+    piece_count: usize,
+    generation_byte_len: usize,
+    overhead_factor: f64,
+    piece_lifetime: Duration,
+}
+
+impl DtnProfile {
+    /// Creates a custom DTN profile.
+    ///
+    /// # Arguments
+    /// * `piece_count` - Number of pieces, each generation gets split into.
+    /// * `generation_byte_len` - Byte length of original data in each generation.
+    /// * `overhead_factor` - Multiplicative overhead on top of the expected number of pieces, must be `>= 1`. See `SendPlan`.
+    /// * `piece_lifetime` - Duration, after being sent, for which a piece stays useful.
+    ///
+    /// # Returns
+    /// * Returns `Ok(DtnProfile)` on success.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `piece_count` is zero.
+    /// * Returns `Err(RLNCError::DataLengthZero)` if `generation_byte_len` is zero.
+    /// * Returns `Err(RLNCError::InvalidSendPlanParameter)` if `overhead_factor` is out of range.
+    pub fn new(piece_count: usize, generation_byte_len: usize, overhead_factor: f64, piece_lifetime: Duration) -> Result<DtnProfile, RLNCError> {
+        if piece_count == 0 {
+            return Err(RLNCError::PieceCountZero);
+        }
+        if generation_byte_len == 0 {
+            return Err(RLNCError::DataLengthZero);
+        }
+        if !overhead_factor.is_finite() || overhead_factor < 1.0 {
+            return Err(RLNCError::InvalidSendPlanParameter);
+        }
+
+        Ok(DtnProfile {
+            piece_count,
+            generation_byte_len,
+            overhead_factor,
+            piece_lifetime,
+        })
+    }
+
+    /// Preset for geostationary or low earth orbit satellite links i.e. seconds of round trip and intermittent outages.
+    /// Generations of 1MB, split into 128 pieces, 50% overhead, pieces live for an hour.
+    pub fn satellite() -> DtnProfile {
+        DtnProfile {
+            piece_count: 128,
+            generation_byte_len: 1 << 20,
+            overhead_factor: 1.5,
+            piece_lifetime: Duration::from_secs(60 * 60),
+        }
+    }
+
+    /// Preset for deep space links i.e. minutes to hours of round trip and long scheduled outages.
+    /// Generations of 4MB, split into 256 pieces, 100% overhead, pieces live for a day.
+    pub fn deep_space() -> DtnProfile {
+        DtnProfile {
+            piece_count: 256,
+            generation_byte_len: 4 << 20,
+            overhead_factor: 2.0,
+            piece_lifetime: Duration::from_secs(24 * 60 * 60),
+        }
+    }
+
+    /// Number of pieces, each generation gets split into.
+    pub fn get_piece_count(&self) -> usize {
+        self.piece_count
+    }
+
+    /// Byte length of original data in each generation.
+    pub fn get_generation_byte_len(&self) -> usize {
+        self.generation_byte_len
+    }
+
+    /// Multiplicative overhead on top of the expected number of pieces.
+    pub fn get_overhead_factor(&self) -> f64 {
+        self.overhead_factor
+    }
+
+    /// Duration, after being sent, for which a piece stays useful.
+    pub fn get_piece_lifetime(&self) -> Duration {
+        self.piece_lifetime
+    }
+
+    /// Computes the feedback-free send plan for original data of `data_byte_len` bytes, over a link losing `loss_estimate`
+    /// fraction of pieces. Each generation starts with its source pieces, followed by coded ones, all generations interleaved.
+    ///
+    /// # Returns
+    /// * Returns `Ok(SendPlan)` on success.
+    /// * Returns `Err(RLNCError::GenerationCountZero)` if `data_byte_len` is zero.
+    /// * Returns `Err(RLNCError::InvalidSendPlanParameter)` if `loss_estimate` is not in `[0, 1)`.
+    pub fn send_plan(&self, data_byte_len: usize, loss_estimate: f64) -> Result<SendPlan, RLNCError> {
+        let generation_count = ContiguousMapping::new(self.generation_byte_len).generation_count(data_byte_len);
+        SendPlan::new(
+            self.piece_count,
+            generation_count,
+            loss_estimate,
+            self.overhead_factor,
+            true,
+            PlanOrdering::Interleaved,
+        )
+    }
+}
+
+/// Full coded piece, stamped with the generation it belongs to and its expiry timestamp, in seconds since UNIX epoch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExpiringPiece {
+    generation: u32,
+    expires_at: u64,
+    full_coded_piece: Vec<u8>,
+}
+
+impl ExpiringPiece {
+    /// Stamps a full coded piece with its generation and expiry timestamp, in seconds since UNIX epoch.
+    pub fn new(generation: u32, expires_at: u64, full_coded_piece: Vec<u8>) -> ExpiringPiece {
+        ExpiringPiece {
+            generation,
+            expires_at,
+            full_coded_piece,
+        }
+    }
+
+    /// Index of the generation, this piece belongs to.
+    pub fn get_generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// Expiry timestamp, in seconds since UNIX epoch.
+    pub fn get_expires_at(&self) -> u64 {
+        self.expires_at
+    }
+
+    /// Coding vector followed by coded data, ready to be passed to `Decoder::decode`.
+    pub fn get_full_coded_piece(&self) -> &[u8] {
+        &self.full_coded_piece
+    }
+
+    /// Returns `true` if the piece is stale at given time, so that it can be dropped, instead of being decoded or forwarded.
+    pub fn is_expired(&self, now: SystemTime) -> bool {
+        unix_timestamp(now) >= self.expires_at
+    }
+
+    /// Serializes the piece as big-endian generation index (4 bytes) and expiry timestamp (8 bytes), followed by the full coded piece.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        [
+            self.generation.to_be_bytes().as_slice(),
+            self.expires_at.to_be_bytes().as_slice(),
+            &self.full_coded_piece,
+        ]
+        .concat()
+    }
+
+    /// Deserializes a piece, serialized using `Self::to_bytes`.
+    ///
+    /// # Returns
+    /// * Returns `Ok(ExpiringPiece)` on success.
+    /// * Returns `Err(RLNCError::SerializationFailure)` if `bytes` is not longer than the header.
+    pub fn from_bytes(bytes: &[u8]) -> Result<ExpiringPiece, RLNCError> {
+        if bytes.len() <= EXPIRING_PIECE_HEADER_BYTE_LEN {
+            return Err(RLNCError::SerializationFailure);
+        }
+
+        let (generation, rest) = bytes.split_at(size_of::<u32>());
+        let (expires_at, full_coded_piece) = rest.split_at(size_of::<u64>());
+
+        Ok(ExpiringPiece {
+            generation: u32::from_be_bytes(unsafe { generation.try_into().unwrap_unchecked() }),
+            expires_at: u64::from_be_bytes(unsafe { expires_at.try_into().unwrap_unchecked() }),
+            full_coded_piece: full_coded_piece.to_vec(),
+        })
+    }
+}
+
+/// Feedback-free sender, which follows a `DtnProfile` send plan, stamping each piece with its expiry timestamp.
+///
+/// Receiver sets up a decoder per generation, using `Decoder::new_with(sender.get_params())`, drops expired pieces and
+/// decodes the rest, without ever talking back.
+#[derive(Clone, Debug)]
+pub struct DtnSender {
+    profile: DtnProfile,
+    params: Params,
+    plan: SendPlan,
+    encoders: Vec<Encoder>,
+    /// Descriptors of pieces, yet to be sent, in order.
+    remaining: std::vec::IntoIter<PieceDescriptor>,
+}
+
+impl DtnSender {
+    /// Splits original data into generations and computes the send plan, as prescribed by the profile.
+    ///
+    /// # Returns
+    /// * Returns `Ok(DtnSender)` on success.
+    /// * Returns `Err(RLNCError::DataLengthZero)` if `data` is empty.
+    /// * Returns `Err(RLNCError::InvalidSendPlanParameter)` if `loss_estimate` is not in `[0, 1)`.
+    pub fn new(data: Vec<u8>, profile: DtnProfile, loss_estimate: f64) -> Result<DtnSender, RLNCError> {
+        if data.is_empty() {
+            return Err(RLNCError::DataLengthZero);
+        }
+
+        let mapping = ContiguousMapping::new(profile.generation_byte_len);
+        let params = Params::from_data_len(mapping.max_generation_byte_len(data.len()), profile.piece_count)?;
+        let plan = profile.send_plan(data.len(), loss_estimate)?;
+
+        let encoders = (0..mapping.generation_count(data.len()))
+            .map(|generation| Encoder::new_with(mapping.gather(&data, generation), params))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(DtnSender {
+            profile,
+            params,
+            plan,
+            encoders,
+            remaining: plan.iter().collect::<Vec<_>>().into_iter(),
+        })
+    }
+
+    /// RLNC configuration shared by all generations.
+    pub fn get_params(&self) -> Params {
+        self.params
+    }
+
+    /// The send plan being followed.
+    pub fn get_plan(&self) -> SendPlan {
+        self.plan
+    }
+
+    /// Number of pieces, yet to be sent.
+    pub fn get_remaining_piece_count(&self) -> usize {
+        self.remaining.len()
+    }
+
+    /// Produces the next piece of the send plan, expiring `piece_lifetime` after `now`.
+    ///
+    /// # Returns
+    /// * Returns `Some(ExpiringPiece)` if the plan has pieces left.
+    /// * Returns `None` once the whole plan is sent.
+    pub fn next_piece<R: Rng + ?Sized>(&mut self, rng: &mut R, now: SystemTime) -> Option<ExpiringPiece> {
+        let PieceDescriptor { generation, kind } = self.remaining.next()?;
+        let encoder = &self.encoders[generation];
+
+        let full_coded_piece = match kind {
+            PieceKind::Systematic(index) => {
+                let mut full_piece = vec![0u8; encoder.get_full_coded_piece_byte_len()];
+                full_piece[index] = 1;
+                full_piece[encoder.get_piece_count()..].copy_from_slice(unsafe { encoder.source_piece(index).unwrap_unchecked() });
+
+                full_piece
+            }
+            PieceKind::Coded => encoder.code(rng),
+        };
+
+        let expires_at = unix_timestamp(now).saturating_add(self.profile.piece_lifetime.as_secs());
+        Some(ExpiringPiece::new(generation as u32, expires_at, full_coded_piece))
+    }
+}
+
+/// Seconds since UNIX epoch, which is zero for earlier times.
+pub(crate) fn unix_timestamp(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DtnProfile, DtnSender, ExpiringPiece};
+    use crate::{RLNCError, full::Decoder};
+    use rand::Rng;
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn test_dtn_profile_invalid_inputs() {
+        assert_eq!(DtnProfile::new(0, 1024, 1.5, Duration::ZERO), Err(RLNCError::PieceCountZero));
+        assert_eq!(DtnProfile::new(16, 0, 1.5, Duration::ZERO), Err(RLNCError::DataLengthZero));
+        assert_eq!(DtnProfile::new(16, 1024, 0.5, Duration::ZERO), Err(RLNCError::InvalidSendPlanParameter));
+
+        assert!(DtnProfile::deep_space().get_piece_count() > DtnProfile::satellite().get_piece_count());
+        assert!(DtnProfile::satellite().send_plan(1 << 30, 0.1).is_ok());
+        assert!(DtnSender::new(vec![], DtnProfile::satellite(), 0.1).is_err());
+        assert_eq!(ExpiringPiece::from_bytes(&[0; 12]), Err(RLNCError::SerializationFailure));
+    }
+
+    #[test]
+    fn test_dtn_sender_feedback_free_delivery() {
+        let mut rng = rand::rng();
+
+        let data = (0..50_000).map(|_| rng.random()).collect::<Vec<u8>>();
+        let profile = DtnProfile::new(16, 8192, 3.0, Duration::from_secs(600)).expect("Profile must be valid");
+        let loss_estimate = 0.3;
+
+        let mut sender = DtnSender::new(data.clone(), profile, loss_estimate).expect("Sender must be valid");
+        let plan = sender.get_plan();
+        assert_eq!(plan.get_generation_count(), 7);
+        assert_eq!(sender.get_remaining_piece_count(), plan.get_total_piece_count());
+
+        let mut decoders = (0..plan.get_generation_count())
+            .map(|_| Decoder::new_with(sender.get_params()))
+            .collect::<Vec<_>>();
+
+        let sent_at = SystemTime::now();
+        let arrived_at = sent_at + Duration::from_secs(300);
+        let mut num_expired = 0;
+
+        // Link loses 30% of pieces, while every eighth piece takes too long, arriving after it expired.
+        let mut idx = 0;
+        while let Some(piece) = sender.next_piece(&mut rng, sent_at) {
+            let piece = ExpiringPiece::from_bytes(&piece.to_bytes()).expect("Piece must be valid");
+            idx += 1;
+
+            if rng.random_bool(loss_estimate) {
+                continue;
+            }
+            if idx % 8 == 0 && piece.is_expired(arrived_at + Duration::from_secs(600)) {
+                num_expired += 1;
+                continue;
+            }
+
+            assert!(!piece.is_expired(arrived_at));
+            let _ = decoders[piece.get_generation() as usize].decode(piece.get_full_coded_piece());
+        }
+
+        assert!(num_expired > 0);
+        assert_eq!(sender.get_remaining_piece_count(), 0);
+
+        let decoded = decoders
+            .into_iter()
+            .map(|decoder| decoder.get_decoded_data().expect("Each generation must decode without feedback"))
+            .collect::<Vec<_>>();
+        assert_eq!(decoded.concat(), data);
+    }
+}
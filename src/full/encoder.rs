@@ -1,25 +1,60 @@
-use super::consts::BOUNDARY_MARKER;
-use crate::RLNCError;
+use super::{consts::BOUNDARY_MARKER, nibble, params::Params, seeded::SeededPiece, storage::PieceStorage, vectored::VectoredPiece};
+use crate::{
+    RLNCError,
+    common::simd::{MAX_FUSED_VEC_COUNT, gf256_mul_vec_by_scalar_then_add_into_vec, gf256_mul_vecs_by_scalars_then_add_into_vec},
+};
 use rand::Rng;
+use std::{
+    io::{IoSlice, Read},
+    ops::Deref,
+    sync::Arc,
+};
+
+#[cfg(feature = "mmap")]
+use super::storage::MmapStorage;
 
-#[cfg(all(feature = "parallel", not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"))))]
-use crate::common::gf256::Gf256;
-#[cfg(not(feature = "parallel"))]
-use crate::common::simd::gf256_mul_vec_by_scalar_then_add_into_vec;
-#[cfg(all(feature = "parallel", any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")))]
-use crate::common::simd::{gf256_inplace_add_vectors, gf256_inplace_mul_vec_by_scalar};
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
+/// Byte length of coded data chunks, computed by separate threads, is rounded up to a multiple of this,
+/// so that all but the last chunk are processed by full-width SIMD kernels. It's also the cache line size, so that
+/// no two threads ever write into the same cache line. Each thread reads only its own byte range of all source pieces
+/// and writes only the same range of coded data, so working sets of threads never overlap, and on a NUMA machine, no
+/// cache line bounces between nodes.
+#[cfg(feature = "parallel")]
+const PARALLEL_CODING_CHUNK_ALIGNMENT: usize = 64;
+
+/// Default byte length of column tiles, coded data is computed in, so that a tile of coded data stays in L2 cache, while it
+/// accumulates the same tile of all source pieces. It's a multiple of SIMD register width.
+const DEFAULT_CODING_TILE_BYTE_LEN: usize = 128 * 1024;
+
+/// Coding tile byte length is rounded up to a multiple of this, so that all but the last tile are processed by full-width SIMD kernels.
+const CODING_TILE_ALIGNMENT: usize = 64;
+
+/// Byte length of column tiles, batched coding sweeps source pieces in, so that a tile of each source piece is loaded once,
+/// for all coded pieces of the batch, while their tiles stay in cache. It's a multiple of SIMD register width.
+const BATCH_CODING_TILE_BYTE_LEN: usize = 4096;
+
 /// Random Linear Network Coding (RLNC) Encoder.
 ///
 /// It is responsible for ensuring pading, dividing padded data into pieces and
 /// generating coded pieces based on random sampled coding vectors.
+///
+/// Padded data is immutable after construction and shared among clones, so cloning an
+/// encoder, say for handing it over to another worker thread, doesn't copy the data.
 #[derive(Clone, Debug)]
 pub struct Encoder {
-    data: Vec<u8>,
+    data: Arc<dyn PieceStorage>,
+    /// Trailing source pieces, holding end of original data, boundary marker and padding, if they're kept apart from `data`, which
+    /// then holds leading source pieces only, so that original data, say in a memory mapped file, is never copied for padding it.
+    tail: Option<Arc<[u8]>>,
     piece_count: usize,
     piece_byte_len: usize,
+    /// Byte length of column tiles, coded data is computed in.
+    tile_byte_len: usize,
+    /// Thread pool running data-parallel coding, global `rayon` pool is used if unset.
+    #[cfg(feature = "parallel")]
+    thread_pool: Option<Arc<rayon::ThreadPool>>,
 }
 
 impl Encoder {
@@ -38,16 +73,71 @@ impl Encoder {
         self.get_piece_count() + self.get_piece_byte_len()
     }
 
+    /// Returns source piece at given index, as it is after padding the original data, so that it can be sent verbatim,
+    /// along with a unit coding vector, or be hashed or signed. Receiver can feed it to `Decoder::insert_known_piece`.
+    ///
+    /// # Returns
+    /// * Returns `Ok(&[u8])` of length `self.get_piece_byte_len()` on success.
+    /// * Returns `Err(RLNCError::InvalidPieceIndex)` if `index` is not less than `self.get_piece_count()`.
+    pub fn source_piece(&self, index: usize) -> Result<&[u8], RLNCError> {
+        if index >= self.piece_count {
+            return Err(RLNCError::InvalidPieceIndex);
+        }
+
+        let offset = index * self.piece_byte_len;
+        let data = self.data.as_slice();
+
+        Ok(match offset.checked_sub(data.len()) {
+            Some(tail_offset) => &self.get_tail()[tail_offset..tail_offset + self.piece_byte_len],
+            None => &data[offset..offset + self.piece_byte_len],
+        })
+    }
+
+    /// Trailing source pieces, kept apart from storage, if any.
+    fn get_tail(&self) -> &[u8] {
+        self.tail.as_deref().unwrap_or_default()
+    }
+
+    /// Returns an iterator over all source pieces, in order of their indices, each of `self.get_piece_byte_len()` bytes.
+    fn source_pieces(&self) -> impl Iterator<Item = &[u8]> + '_ {
+        self.data
+            .as_slice()
+            .chunks_exact(self.piece_byte_len)
+            .chain(self.get_tail().chunks_exact(self.piece_byte_len))
+    }
+
+    /// Returns systematic piece at given index i.e. the source piece, unchanged, preceded by a unit coding vector, which is one at
+    /// `index` and zero elsewhere. Sending all systematic pieces first, before switching to random coded pieces, lets receivers on
+    /// lossless paths recover data without any Gaussian elimination, as `Decoder` fast-paths pieces with unit coding vectors.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Vec<u8>)` of length `self.get_full_coded_piece_byte_len()` on success.
+    /// * Returns `Err(RLNCError::InvalidPieceIndex)` if `index` is not less than `self.get_piece_count()`.
+    pub fn systematic_piece(&self, index: usize) -> Result<Vec<u8>, RLNCError> {
+        let source_piece = self.source_piece(index)?;
+
+        let mut full_coded_piece = vec![0u8; self.get_full_coded_piece_byte_len()];
+        full_coded_piece[index] = 1;
+        full_coded_piece[self.piece_count..].copy_from_slice(source_piece);
+
+        Ok(full_coded_piece)
+    }
+
+    /// Returns an iterator over all systematic pieces, in order of their indices. See `Self::systematic_piece`.
+    pub fn systematic_pieces(&self) -> impl DoubleEndedIterator<Item = Vec<u8>> + ExactSizeIterator + '_ {
+        (0..self.piece_count).map(|index| unsafe { self.systematic_piece(index).unwrap_unchecked() })
+    }
+
     /// Creates a new `Encoder` without adding any padding to the input data.
     /// This is suitable if the input data length is already a multiple of the
-    /// desired piece count. This interface is used by Recoder.
+    /// desired piece count. Data lives in given storage. This interface is used by Recoder.
     ///
     /// # Returns
     /// * Returns `Ok(Encoder)` on success.
     /// * Returns `Err(RLNCError::DataLengthZero)` if `data` is empty.
     /// * Returns `Err(RLNCError::PieceCountZero)` if `piece_count` is zero.
     /// * Returns `Err(RLNCError::DataLengthMismatch)` if the data length is not a multiple of the piece count.
-    pub(crate) fn without_padding(data: Vec<u8>, piece_count: usize) -> Result<Encoder, RLNCError> {
+    pub(crate) fn without_padding(data: Box<dyn PieceStorage>, piece_count: usize) -> Result<Encoder, RLNCError> {
         if data.is_empty() {
             return Err(RLNCError::DataLengthZero);
         }
@@ -64,12 +154,52 @@ impl Encoder {
         }
 
         Ok(Encoder {
-            data,
+            data: Arc::from(data),
             piece_count,
             piece_byte_len,
+            tail: None,
+            tile_byte_len: DEFAULT_CODING_TILE_BYTE_LEN,
+            #[cfg(feature = "parallel")]
+            thread_pool: None,
         })
     }
 
+    /// Storage of source pieces, copied first, if it's shared with clones of this encoder, so that they're left untouched.
+    fn get_storage_mut(&mut self) -> &mut dyn PieceStorage {
+        if Arc::get_mut(&mut self.data).is_none() {
+            self.data = Arc::from(self.data.clone_boxed());
+        }
+
+        unsafe { Arc::get_mut(&mut self.data).unwrap_unchecked() }
+    }
+
+    /// Appends a source piece of `self.get_piece_byte_len()` bytes, say a coded piece received by a `Recoder` over time.
+    ///
+    /// # Returns
+    /// * Returns `Ok(())` on success.
+    /// * Returns `Err(RLNCError::PieceStorageFailure)` if storage can't grow to hold the piece.
+    pub(crate) fn push_source_piece(&mut self, piece: &[u8]) -> Result<(), RLNCError> {
+        self.get_storage_mut().try_extend_from_slice(piece)?;
+        self.piece_count += 1;
+
+        Ok(())
+    }
+
+    /// Removes the source piece at given index, which must be less than `self.get_piece_count()`, moving the last source piece
+    /// in its place, so that only one piece is copied.
+    pub(crate) fn swap_remove_source_piece(&mut self, index: usize) {
+        let piece_byte_len = self.piece_byte_len;
+        let last_offset = (self.piece_count - 1) * piece_byte_len;
+
+        let storage = self.get_storage_mut();
+        storage
+            .as_mut_slice()
+            .copy_within(last_offset..last_offset + piece_byte_len, index * piece_byte_len);
+        storage.truncate(last_offset);
+
+        self.piece_count -= 1;
+    }
+
     /// Creates a new `Encoder` while padding the input data.
     ///
     /// The input data is padded with zeros to ensure its length is a multiple
@@ -82,35 +212,261 @@ impl Encoder {
     /// * Returns `Ok(Encoder)` on success.
     /// * Returns `Err(RLNCError::DataLengthZero)` if `data` is empty.
     /// * Returns `Err(RLNCError::PieceCountZero)` if `piece_count` is zero.
-    pub fn new(mut data: Vec<u8>, piece_count: usize) -> Result<Encoder, RLNCError> {
+    pub fn new(data: Vec<u8>, piece_count: usize) -> Result<Encoder, RLNCError> {
+        let params = Params::from_data_len(data.len(), piece_count)?;
+        Self::new_with(data, params)
+    }
+
+    /// Creates a new `Encoder` from already validated RLNC configuration, padding the input data
+    /// with a boundary marker followed by zeros, so that it fills all `params.get_piece_count()` pieces.
+    ///
+    /// # Arguments
+    /// * `data` - Original data to be erasure-coded.
+    /// * `params` - RLNC configuration, shared with recoders and decoders.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Encoder)` on success.
+    /// * Returns `Err(RLNCError::DataLengthZero)` if `data` is empty.
+    /// * Returns `Err(RLNCError::DataLengthMismatch)` if `data` is longer than `params.get_max_data_byte_len()`.
+    pub fn new_with(mut data: Vec<u8>, params: Params) -> Result<Encoder, RLNCError> {
         if data.is_empty() {
             return Err(RLNCError::DataLengthZero);
         }
-        if piece_count == 0 {
-            return Err(RLNCError::PieceCountZero);
+        if data.len() > params.get_max_data_byte_len() {
+            return Err(RLNCError::DataLengthMismatch);
         }
 
         let in_data_len = data.len();
-        let boundary_marker_len = 1;
-        let piece_byte_len = (in_data_len + boundary_marker_len).div_ceil(piece_count);
-        let padded_data_len = piece_count * piece_byte_len;
+        let padded_data_len = params.get_piece_count() * params.get_piece_byte_len();
 
         data.resize(padded_data_len, 0);
         data[in_data_len] = BOUNDARY_MARKER;
 
         Ok(Encoder {
-            data,
-            piece_count,
-            piece_byte_len,
+            data: Arc::new(data),
+            piece_count: params.get_piece_count(),
+            piece_byte_len: params.get_piece_byte_len(),
+            tail: None,
+            tile_byte_len: DEFAULT_CODING_TILE_BYTE_LEN,
+            #[cfg(feature = "parallel")]
+            thread_pool: None,
+        })
+    }
+
+    /// Same as `Self::new`, but original data is a list of borrowed segments, treated as one logical payload, in order, say ring
+    /// buffer fragments or protocol-split chunks. Segments are copied straight into place, in the padded buffer, so that the caller
+    /// doesn't need to concatenate them first, which would copy all data once more.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Encoder)` on success.
+    /// * Returns `Err(RLNCError::DataLengthZero)` if all segments together are empty.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `piece_count` is zero.
+    pub fn from_segments(segments: &[&[u8]], piece_count: usize) -> Result<Encoder, RLNCError> {
+        Self::from_slices(segments, piece_count)
+    }
+
+    /// Same as `Self::from_segments`, but segments are any byte buffers, say `&[u8]`s, `IoSlice`s of a scatter-gather read, `Vec`s
+    /// or a chain of reference counted buffers, so that network servers, holding a payload as such a chain, can encode it as is.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Encoder)` on success.
+    /// * Returns `Err(RLNCError::DataLengthZero)` if all segments together are empty.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `piece_count` is zero.
+    pub fn from_slices<S: Deref<Target = [u8]>>(segments: &[S], piece_count: usize) -> Result<Encoder, RLNCError> {
+        let in_data_len = segments.iter().map(|segment| segment.len()).sum();
+        let params = Params::from_data_len(in_data_len, piece_count)?;
+        let padded_data_len = params.get_piece_count() * params.get_piece_byte_len();
+
+        let mut data = Vec::with_capacity(padded_data_len);
+        segments.iter().for_each(|segment| data.extend_from_slice(segment));
+        data.push(BOUNDARY_MARKER);
+        data.resize(padded_data_len, 0);
+
+        Ok(Encoder {
+            data: Arc::new(data),
+            piece_count: params.get_piece_count(),
+            piece_byte_len: params.get_piece_byte_len(),
+            tail: None,
+            tile_byte_len: DEFAULT_CODING_TILE_BYTE_LEN,
+            #[cfg(feature = "parallel")]
+            thread_pool: None,
+        })
+    }
+
+    /// Same as `Self::new`, but original data, of `data_byte_len` bytes, is read off given reader, say a file or a socket, straight
+    /// into the padded buffer, so that the caller doesn't need to load it into a vector first, which would be copied once more.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Encoder)` on success.
+    /// * Returns `Err(RLNCError::DataLengthZero)` if `data_byte_len` is zero.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `piece_count` is zero.
+    /// * Returns `Err(RLNCError::ReadFailure)` if reading `data_byte_len` bytes off `reader` fails.
+    pub fn from_reader<R: Read>(mut reader: R, data_byte_len: usize, piece_count: usize) -> Result<Encoder, RLNCError> {
+        let params = Params::from_data_len(data_byte_len, piece_count)?;
+        let padded_data_len = params.get_piece_count() * params.get_piece_byte_len();
+
+        let mut data = vec![0u8; padded_data_len];
+        reader.read_exact(&mut data[..data_byte_len]).map_err(|_| RLNCError::ReadFailure)?;
+        data[data_byte_len] = BOUNDARY_MARKER;
+
+        Ok(Encoder {
+            data: Arc::new(data),
+            piece_count: params.get_piece_count(),
+            piece_byte_len: params.get_piece_byte_len(),
+            tail: None,
+            tile_byte_len: DEFAULT_CODING_TILE_BYTE_LEN,
+            #[cfg(feature = "parallel")]
+            thread_pool: None,
+        })
+    }
+
+    /// Same as `Self::new`, but original data is the content of given file, which is memory mapped, copy-on-write, instead of being
+    /// read, so that it's paged in on demand, as pieces are coded. File itself is never written to. Trailing source pieces, holding
+    /// end of the file, boundary marker and padding, are kept apart, in a small buffer, so that the mapping is never extended.
+    ///
+    /// File must not be modified by anyone else, while the encoder or any of its clones is alive.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Encoder)` on success.
+    /// * Returns `Err(RLNCError::DataLengthZero)` if the file is empty.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `piece_count` is zero.
+    /// * Returns `Err(RLNCError::ReadFailure)` if the file can't be opened or mapped.
+    #[cfg(feature = "mmap")]
+    pub fn from_file<P: AsRef<std::path::Path>>(path: P, piece_count: usize) -> Result<Encoder, RLNCError> {
+        let file = std::fs::File::open(path).map_err(|_| RLNCError::ReadFailure)?;
+        let data_byte_len = file.metadata().map_err(|_| RLNCError::ReadFailure)?.len() as usize;
+
+        let params = Params::from_data_len(data_byte_len, piece_count)?;
+
+        // Leading source pieces, fully made of original data, stay in the mapping.
+        let mut data = MmapStorage::file_copy_on_write(&file, data_byte_len).map_err(|_| RLNCError::ReadFailure)?;
+        let (leading_data_len, tail) = padded_tail(data.as_slice(), params);
+        data.truncate(leading_data_len);
+
+        Ok(Encoder {
+            data: Arc::new(data),
+            piece_count: params.get_piece_count(),
+            piece_byte_len: params.get_piece_byte_len(),
+            tail: Some(Arc::from(tail)),
+            tile_byte_len: DEFAULT_CODING_TILE_BYTE_LEN,
+            #[cfg(feature = "parallel")]
+            thread_pool: None,
+        })
+    }
+
+    /// Same as `Self::new_with`, but padded data lives in given storage, which is emptied first, say huge page backed memory
+    /// for multi-GB workloads. See `PieceStorage`.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Encoder)` on success.
+    /// * Returns `Err(RLNCError::DataLengthZero)` if `data` is empty.
+    /// * Returns `Err(RLNCError::DataLengthMismatch)` if `data` is longer than `params.get_max_data_byte_len()`.
+    /// * Returns `Err(RLNCError::PieceStorageFailure)` if `storage` can't grow to hold padded data.
+    pub fn new_with_storage(data: &[u8], params: Params, mut storage: Box<dyn PieceStorage>) -> Result<Encoder, RLNCError> {
+        if data.is_empty() {
+            return Err(RLNCError::DataLengthZero);
+        }
+        if data.len() > params.get_max_data_byte_len() {
+            return Err(RLNCError::DataLengthMismatch);
+        }
+
+        let padded_data_len = params.get_piece_count() * params.get_piece_byte_len();
+
+        storage.truncate(0);
+        storage.reserve(padded_data_len);
+        storage.try_extend_from_slice(data)?;
+        storage.try_extend_from_slice(&[BOUNDARY_MARKER])?;
+        storage.try_extend_from_slice(&vec![0u8; padded_data_len - data.len() - 1])?;
+
+        Ok(Encoder {
+            data: Arc::from(storage),
+            piece_count: params.get_piece_count(),
+            piece_byte_len: params.get_piece_byte_len(),
+            tail: None,
+            tile_byte_len: DEFAULT_CODING_TILE_BYTE_LEN,
+            #[cfg(feature = "parallel")]
+            thread_pool: None,
         })
     }
 
-    /// Erasure codes the data held by the encoder using a provided coding vector. This function
-    /// is used by the Recoder, to avoid any memory allocation during recoding.
+    /// Sets byte length of column tiles, coded data is computed in, rounded up to a multiple of 64 bytes. Each tile of coded data
+    /// accumulates the same tile of all source pieces, before moving on to the next one, so that it stays in cache, instead of
+    /// whole coded data being streamed through memory once per source piece, which thrashes the cache for pieces of megabytes.
+    /// Default is 128 KiB, fitting in L2 cache of most CPUs, while tuning it to the actual cache size of the target may pay off.
+    /// Pieces not longer than a tile are coded in one go, as before. Tile byte length is kept by clones of this encoder.
+    pub fn with_coding_tile_byte_len(mut self, tile_byte_len: usize) -> Encoder {
+        self.tile_byte_len = tile_byte_len.max(1).next_multiple_of(CODING_TILE_ALIGNMENT);
+        self
+    }
+
+    /// Byte length of column tiles, coded data is computed in. See `Self::with_coding_tile_byte_len`.
+    pub fn get_coding_tile_byte_len(&self) -> usize {
+        self.tile_byte_len
+    }
+
+    /// Computes coded data, starting at byte `offset` of each source piece, tile by tile, into given chunk, which is zeroed first.
+    /// Source pieces are accumulated into each tile `MAX_FUSED_VEC_COUNT` at a time, so that the tile is loaded and stored once
+    /// for all of them.
+    fn code_chunk(&self, coding_vector: &[u8], coded_chunk: &mut [u8], offset: usize) {
+        coded_chunk.fill(0);
+
+        for (tile_idx, coded_tile) in coded_chunk.chunks_mut(self.tile_byte_len).enumerate() {
+            let tile_begin = offset + tile_idx * self.tile_byte_len;
+            let tile_end = tile_begin + coded_tile.len();
+
+            let mut source_tiles = self.source_pieces().map(|piece| &piece[tile_begin..tile_end]).zip(coding_vector);
+            loop {
+                let mut fused_tiles: [&[u8]; MAX_FUSED_VEC_COUNT] = [&[]; MAX_FUSED_VEC_COUNT];
+                let mut random_symbols = [0u8; MAX_FUSED_VEC_COUNT];
+
+                let mut fused_tile_count = 0;
+                for ((fused_tile, random_symbol), (source_tile, &source_random_symbol)) in
+                    fused_tiles.iter_mut().zip(&mut random_symbols).zip(source_tiles.by_ref())
+                {
+                    *fused_tile = source_tile;
+                    *random_symbol = source_random_symbol;
+                    fused_tile_count += 1;
+                }
+                if fused_tile_count == 0 {
+                    break;
+                }
+
+                gf256_mul_vecs_by_scalars_then_add_into_vec(coded_tile, &fused_tiles[..fused_tile_count], &random_symbols[..fused_tile_count]);
+            }
+        }
+    }
+
+    /// Makes data-parallel coding run on given `rayon` thread pool, instead of the global one, so that RLNC work can be
+    /// isolated from latency-sensitive tasks and the number of threads used can be controlled per encoder. The pool is
+    /// shared among clones of this encoder.
+    #[cfg(feature = "parallel")]
+    pub fn with_thread_pool(mut self, thread_pool: Arc<rayon::ThreadPool>) -> Encoder {
+        self.thread_pool = Some(thread_pool);
+        self
+    }
+
+    /// Thread pool, data-parallel coding runs on, if set using `Self::with_thread_pool`.
+    #[cfg(feature = "parallel")]
+    pub(crate) fn get_thread_pool(&self) -> Option<Arc<rayon::ThreadPool>> {
+        self.thread_pool.clone()
+    }
+
+    /// RLNC configuration of this encoder, to be shared with recoders and decoders.
+    pub fn get_params(&self) -> Params {
+        Params {
+            piece_count: self.piece_count,
+            piece_byte_len: self.piece_byte_len,
+        }
+    }
+
+    /// Erasure codes the data held by the encoder using a provided coding vector, without any memory allocation.
+    /// It lets one implement custom coefficient schedules, say systematic rows, Vandermonde columns or
+    /// feedback-biased sampling. This function is also used by the Recoder.
     ///
     /// The output buffer `coded_data` will contain only coded data portion of the
     /// full erasure-coded piece. Its length must be equal to `self.get_piece_byte_len()`.
-    /// It's caller responsibility to fill `coding_vector` with random coding coefficients.
+    /// It's caller responsibility to fill `coding_vector` with coding coefficients, and to send
+    /// it along with coded data, so that the receiver can decode.
     ///
     /// This implementation might benefit from SIMD assisted fast GF(2^8) arithmetic on some targets
     /// such as `x86_64` and `aarch64`. In case you want `rayon` data-parallelism to kick-in, you have
@@ -125,7 +481,7 @@ impl Encoder {
     /// * Returns `Err(RLNCError::CodingVectorLengthMismatch)` if the length of `coding_vector` is not `self.get_piece_count()`.
     /// * Returns `Err(RLNCError::InvalidOutputBuffer)` if the length of `coded_data` is not `self.get_piece_byte_len()`.
     #[cfg(not(feature = "parallel"))]
-    pub(crate) fn code_with_coding_vector(&self, coding_vector: &[u8], coded_data: &mut [u8]) -> Result<(), RLNCError> {
+    pub fn code_with_coding_vector(&self, coding_vector: &[u8], coded_data: &mut [u8]) -> Result<(), RLNCError> {
         if coding_vector.len() != self.piece_count {
             return Err(RLNCError::CodingVectorLengthMismatch);
         }
@@ -133,27 +489,24 @@ impl Encoder {
             return Err(RLNCError::InvalidOutputBuffer);
         }
 
-        coded_data.fill(0);
-
-        self.data
-            .chunks_exact(self.piece_byte_len)
-            .zip(coding_vector)
-            .for_each(|(piece, &random_symbol)| gf256_mul_vec_by_scalar_then_add_into_vec(coded_data, piece, random_symbol));
-
+        self.code_chunk(coding_vector, coded_data, 0);
         Ok(())
     }
 
-    /// Erasure codes the data held by the encoder using a provided coding vector. This function
-    /// is used by the Recoder, to avoid any memory allocation during recoding.
+    /// Erasure codes the data held by the encoder using a provided coding vector, without any memory allocation.
+    /// It lets one implement custom coefficient schedules, say systematic rows, Vandermonde columns or
+    /// feedback-biased sampling. This function is also used by the Recoder.
     ///
     /// The output buffer `coded_data` will contain only coded data portion of the
     /// full erasure-coded piece. Its length must be equal to `self.get_piece_byte_len()`.
-    /// It's caller responsibility to fill `coding_vector` with random coding coefficients.
+    /// It's caller responsibility to fill `coding_vector` with coding coefficients, and to send
+    /// it along with coded data, so that the receiver can decode.
     ///
-    /// This implementation uses `rayon` data-parallelism for fast erasure-coding. One might
+    /// This implementation uses `rayon` data-parallelism for fast erasure-coding, without any memory allocation. One might
     /// want that in some scenarios, but note this same function without `parallel` feature-gate also
     /// performs well when running on target for which this library has GF(2^8) SIMD support.
     /// Currently we support optimized GF(2^8) vector arithmetic for `x86_64` and `aarchh64`.
+    /// Work runs on the thread pool set using `Self::with_thread_pool`, or on the global `rayon` pool.
     ///
     /// # Arguments
     /// * `coding_vector` - A slice to random coding vector which is to be used for preparing a new coded piece.
@@ -164,7 +517,7 @@ impl Encoder {
     /// * Returns `Err(RLNCError::CodingVectorLengthMismatch)` if the length of `coding_vector` is not `self.get_piece_count()`.
     /// * Returns `Err(RLNCError::InvalidOutputBuffer)` if the length of `coded_data` is not `self.get_piece_byte_len()`.
     #[cfg(feature = "parallel")]
-    pub(crate) fn code_with_coding_vector(&self, coding_vector: &[u8], coded_data: &mut [u8]) -> Result<(), RLNCError> {
+    pub fn code_with_coding_vector(&self, coding_vector: &[u8], coded_data: &mut [u8]) -> Result<(), RLNCError> {
         if coding_vector.len() != self.piece_count {
             return Err(RLNCError::CodingVectorLengthMismatch);
         }
@@ -172,54 +525,23 @@ impl Encoder {
             return Err(RLNCError::InvalidOutputBuffer);
         }
 
-        coded_data.copy_from_slice(
-            &self
-                .data
-                .par_chunks_exact(self.piece_byte_len)
-                .zip(coding_vector)
-                .map(|(piece, &random_symbol)| {
-                    #[cfg(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"))]
-                    {
-                        let mut scalar_x_piece = piece.to_vec();
-                        gf256_inplace_mul_vec_by_scalar(&mut scalar_x_piece, random_symbol);
-
-                        scalar_x_piece
-                    }
-
-                    #[cfg(not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")))]
-                    {
-                        piece.iter().map(move |&symbol| (Gf256::new(symbol) * Gf256::new(random_symbol)).get())
-                    }
-                })
-                .fold(
-                    || vec![0u8; self.piece_byte_len],
-                    |mut acc, cur| {
-                        #[cfg(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"))]
-                        gf256_inplace_add_vectors(&mut acc, &cur);
-
-                        #[cfg(not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")))]
-                        acc.iter_mut().zip(cur).for_each(|(a, b)| {
-                            *a ^= b;
-                        });
-
-                        acc
-                    },
-                )
-                .reduce(
-                    || vec![0u8; self.piece_byte_len],
-                    |mut acc, cur| {
-                        #[cfg(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"))]
-                        gf256_inplace_add_vectors(&mut acc, &cur);
-
-                        #[cfg(not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")))]
-                        acc.iter_mut().zip(cur).for_each(|(a, b)| {
-                            *a ^= b;
-                        });
-
-                        acc
-                    },
-                ),
-        );
+        // Coded data is partitioned into disjoint byte ranges, one or more per thread, each computed by fused
+        // scale-and-accumulate over the same byte range of all source pieces, tile by tile. So there are neither
+        // temporary rows to allocate, nor partial results to reduce.
+        let mut code = || {
+            let num_chunks = rayon::current_num_threads();
+            let chunk_byte_len = self.piece_byte_len.div_ceil(num_chunks).next_multiple_of(PARALLEL_CODING_CHUNK_ALIGNMENT);
+
+            coded_data
+                .par_chunks_mut(chunk_byte_len)
+                .enumerate()
+                .for_each(|(chunk_idx, coded_chunk)| self.code_chunk(coding_vector, coded_chunk, chunk_idx * chunk_byte_len));
+        };
+
+        match &self.thread_pool {
+            Some(thread_pool) => thread_pool.install(code),
+            None => code(),
+        }
 
         Ok(())
     }
@@ -249,6 +571,32 @@ impl Encoder {
         self.code_with_coding_vector(coding_vector, coded_data)
     }
 
+    /// Produces a new coded piece, random sampling coding coefficients and writing coding vector and coded data
+    /// into separate buffers of the provided vectored piece, which can be reused across calls.
+    ///
+    /// Returned I/O slices i.e. application defined header, coding vector and coded data, in that order, can be
+    /// directly passed to `Write::write_vectored`, avoiding concatenation into one buffer per coded piece.
+    ///
+    /// # Arguments
+    /// * `rng` - A mutable reference to a random number generator.
+    /// * `piece` - A vectored piece, created with `self.get_piece_count()` and `self.get_piece_byte_len()`.
+    ///
+    /// # Returns
+    /// * Returns `Ok([IoSlice; 3])` on success.
+    /// * Returns `Err(RLNCError::InvalidOutputBuffer)` if `piece` is not suitable for holding a coded piece produced by this encoder.
+    pub fn code_into_ioslices<'a, R: Rng + ?Sized>(&self, rng: &mut R, piece: &'a mut VectoredPiece) -> Result<[IoSlice<'a>; 3], RLNCError> {
+        if piece.get_coding_vector().len() != self.piece_count {
+            return Err(RLNCError::InvalidOutputBuffer);
+        }
+
+        let (coding_vector, coded_data) = piece.get_coding_vector_and_coded_data_mut();
+
+        rng.fill_bytes(coding_vector);
+        self.code_with_coding_vector(coding_vector, coded_data)?;
+
+        Ok(piece.as_io_slices())
+    }
+
     /// Produces a new coded piece, random sampling a coding vector.
     ///
     /// This is a convenience method that allocates a new `Vec<u8>` internally and
@@ -267,12 +615,318 @@ impl Encoder {
 
         full_coded_piece
     }
+
+    /// Produces a batch of coded pieces, random sampling a coding vector for each, writing them into provided buffers, in a single
+    /// pass over source data. Source pieces are swept in column tiles, each of which gets scaled and accumulated into the same tile
+    /// of all coded pieces, while it's in cache, instead of walking through all source data once per coded piece, as calling
+    /// `Self::code_with_buf` for each buffer does. So it's much faster for large data, which doesn't fit in cache, where coding
+    /// is memory bandwidth bound. Coded pieces are same as those of `Self::code_with_coding_vector`, for same coding vectors.
+    /// Coding runs on the calling thread.
+    ///
+    /// # Arguments
+    /// * `rng` - A mutable reference to a random number generator.
+    /// * `full_coded_pieces` - Mutable slices to write full coded pieces (coding vector + coded data) into, each of
+    ///   `self.get_full_coded_piece_byte_len()` bytes.
+    ///
+    /// # Returns
+    /// * Returns `Ok(())` on success.
+    /// * Returns `Err(RLNCError::InvalidOutputBuffer)` if the length of any of `full_coded_pieces` is incorrect.
+    pub fn code_batch_with_bufs<R: Rng + ?Sized>(&self, rng: &mut R, full_coded_pieces: &mut [&mut [u8]]) -> Result<(), RLNCError> {
+        if full_coded_pieces.iter().any(|piece| piece.len() != self.get_full_coded_piece_byte_len()) {
+            return Err(RLNCError::InvalidOutputBuffer);
+        }
+
+        let mut batch = full_coded_pieces
+            .iter_mut()
+            .map(|piece| {
+                let (coding_vector, coded_data) = piece.split_at_mut(self.piece_count);
+
+                rng.fill_bytes(coding_vector);
+                coded_data.fill(0);
+
+                (&*coding_vector, coded_data)
+            })
+            .collect::<Vec<_>>();
+
+        for tile_begin in (0..self.piece_byte_len).step_by(BATCH_CODING_TILE_BYTE_LEN) {
+            let tile_end = (tile_begin + BATCH_CODING_TILE_BYTE_LEN).min(self.piece_byte_len);
+
+            for (sidx, source_piece) in self.source_pieces().enumerate() {
+                let source_tile = &source_piece[tile_begin..tile_end];
+
+                batch.iter_mut().for_each(|(coding_vector, coded_data)| {
+                    gf256_mul_vec_by_scalar_then_add_into_vec(&mut coded_data[tile_begin..tile_end], source_tile, coding_vector[sidx])
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Produces a new coded piece, targeted at dimensions, the receiver lacks, as reported by `Decoder::missing_dimensions`, for
+    /// feedback-driven retransmission. Its coding vector is non-zero over missing dimensions only, so its first non-zero coefficient
+    /// can't be at a pivot column of the receiver, which makes it useful for sure, as long as the report is up to date. With no
+    /// missing dimension reported, it's same as `Self::code`.
+    ///
+    /// # Arguments
+    /// * `rng` - Used to sample non-zero coefficients of missing dimensions.
+    /// * `missing_pivots` - Coefficient columns lacking a pivot at the receiver, each less than `self.get_piece_count()`.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Vec<u8>)` holding coding vector followed by coded data, of `self.get_full_coded_piece_byte_len()` bytes.
+    /// * Returns `Err(RLNCError::InvalidPieceIndex)` if any of `missing_pivots` is out of range.
+    pub fn code_for_pivots<R: Rng + ?Sized>(&self, rng: &mut R, missing_pivots: &[usize]) -> Result<Vec<u8>, RLNCError> {
+        if missing_pivots.iter().any(|&cidx| cidx >= self.piece_count) {
+            return Err(RLNCError::InvalidPieceIndex);
+        }
+        if missing_pivots.is_empty() {
+            return Ok(self.code(rng));
+        }
+
+        let mut full_coded_piece = vec![0u8; self.get_full_coded_piece_byte_len()];
+        let (coding_vector, coded_data) = full_coded_piece.split_at_mut(self.piece_count);
+        missing_pivots.iter().for_each(|&cidx| coding_vector[cidx] = rng.random_range(1..=u8::MAX));
+
+        self.code_with_coding_vector(coding_vector, coded_data)?;
+        Ok(full_coded_piece)
+    }
+
+    /// Produces a new coded piece, whose coding vector is expanded from given seed, see `SeededPiece::expand_seed`, so that
+    /// only the 8-byte seed needs to be transmitted, in place of the `self.get_piece_count()` bytes long coding vector. Seeds
+    /// should not repeat, say use a counter, as pieces coded with the same seed are identical.
+    ///
+    /// # Arguments
+    /// * `seed` - Seed of the generator, which expands into the coding vector.
+    ///
+    /// # Returns
+    /// A `SeededPiece::Seeded`, holding the seed and the coded data, to be decoded with `Decoder::decode_seeded`.
+    pub fn code_with_seed(&self, seed: u64) -> SeededPiece {
+        let coding_vector = SeededPiece::expand_seed(seed, self.piece_count);
+        let mut coded_data = vec![0u8; self.piece_byte_len];
+        unsafe { self.code_with_coding_vector(&coding_vector, &mut coded_data).unwrap_unchecked() };
+
+        SeededPiece::Seeded { seed, coded_data }
+    }
+
+    /// Codes `count` pieces, one at a time, into an internal reusable buffer, writing each to given writer, say a TCP stream,
+    /// as soon as it's coded, so that senders never materialize pieces as `Vec<u8>`s. Pieces are written back to back, each
+    /// framed as a full coded piece of `self.get_full_coded_piece_byte_len()` bytes, which is what `AsyncPieceReader` expects.
+    /// Partial writes are retried till each piece is written completely, and the writer is flushed at the end.
+    ///
+    /// # Arguments
+    /// * `rng` - A mutable reference to a random number generator.
+    /// * `writer` - Where coded pieces are written to.
+    /// * `count` - Number of pieces to be coded and written.
+    /// * `pacing` - Called after writing each piece, with the number of pieces written so far, and the returned future is awaited
+    ///   before coding the next one, say a timer, spacing pieces out to match a target rate. Pass `|_| std::future::ready(())` to write
+    ///   at full speed. It keeps the encoder independent of any async runtime.
+    ///
+    /// # Returns
+    /// * Returns `Ok(())` once all pieces are written and the writer is flushed.
+    /// * Returns `Err(RLNCError::WriteFailure)` if writing or flushing fails.
+    #[cfg(feature = "futures")]
+    pub async fn stream_pieces<R, W, P, F>(&self, rng: &mut R, writer: &mut W, count: usize, mut pacing: P) -> Result<(), RLNCError>
+    where
+        R: Rng + ?Sized,
+        W: futures::AsyncWrite + Unpin,
+        P: FnMut(usize) -> F,
+        F: std::future::Future<Output = ()>,
+    {
+        use futures::AsyncWriteExt;
+
+        let mut full_coded_piece = vec![0u8; self.get_full_coded_piece_byte_len()];
+
+        for written_piece_count in 1..=count {
+            unsafe { self.code_with_buf(rng, &mut full_coded_piece).unwrap_unchecked() };
+            writer.write_all(&full_coded_piece).await.map_err(|_| RLNCError::WriteFailure)?;
+
+            pacing(written_piece_count).await;
+        }
+
+        writer.flush().await.map_err(|_| RLNCError::WriteFailure)
+    }
+
+    /// Produces a new coded piece, random sampling coding coefficients from the GF(2^4) subfield of GF(2^8), and packs them two
+    /// per byte, halving the coding vector header, say for small-MTU sensor networks. Coefficients drawn from 16, instead of 256,
+    /// values make a piece linearly dependent more often, so a few more pieces may be needed for decoding.
+    ///
+    /// Feed it to `Decoder::decode_nibble_packed`, or unpack using `full::nibble_unpack_piece`, before handing it to a `Recoder`.
+    ///
+    /// # Returns
+    /// A `Vec<u8>` of length `full::get_nibble_packed_piece_byte_len(self.get_piece_count(), self.get_piece_byte_len())`, holding
+    /// packed coding vector followed by coded data.
+    pub fn code_nibble_packed<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<u8> {
+        let mut full_coded_piece = vec![0u8; self.get_full_coded_piece_byte_len()];
+        let (coding_vector, coded_data) = full_coded_piece.split_at_mut(self.piece_count);
+
+        nibble::sample_subfield_coding_vector(rng, coding_vector);
+        unsafe {
+            self.code_with_coding_vector(coding_vector, coded_data).unwrap_unchecked();
+            nibble::nibble_pack_piece(&full_coded_piece, self.piece_count).unwrap_unchecked()
+        }
+    }
+}
+
+/// Splits original data, which must fit in given RLNC configuration, into leading source pieces, fully made of original data, and
+/// trailing ones, holding end of original data, boundary marker and padding, so that padding never touches original data.
+///
+/// # Returns
+/// Byte length of leading source pieces, which are a prefix of `data`, and trailing source pieces, padded.
+fn padded_tail(data: &[u8], params: Params) -> (usize, Vec<u8>) {
+    let padded_data_len = params.get_piece_count() * params.get_piece_byte_len();
+    let leading_data_len = data.len() - data.len() % params.get_piece_byte_len();
+
+    let mut tail = Vec::with_capacity(padded_data_len - leading_data_len);
+    tail.extend_from_slice(&data[leading_data_len..]);
+    tail.push(BOUNDARY_MARKER);
+    tail.resize(padded_data_len - leading_data_len, 0);
+
+    (leading_data_len, tail)
+}
+
+/// Random Linear Network Coding (RLNC) Encoder, borrowing original data, instead of owning a padded copy of it, as `Encoder` does.
+///
+/// Leading source pieces, fully made of original data, are coded straight off the borrowed slice, while trailing ones, holding end
+/// of original data, boundary marker and padding, live in a small side buffer, of at most `piece_count + piece_byte_len` bytes. So,
+/// when the caller must keep original data around anyway, coding a large payload doesn't need a second copy of it. Coded pieces
+/// are same as those of `Encoder`, for same coding vectors, and coding always runs on the calling thread.
+#[derive(Clone, Debug)]
+pub struct EncoderRef<'a> {
+    /// Leading source pieces.
+    data: &'a [u8],
+    /// Trailing source pieces, padded.
+    tail: Vec<u8>,
+    piece_count: usize,
+    piece_byte_len: usize,
+}
+
+impl<'a> EncoderRef<'a> {
+    /// Creates a new `EncoderRef`, borrowing original data, which is split into `piece_count` pieces, after being padded with
+    /// a boundary marker, followed by zeros, same as `Encoder::new` does.
+    ///
+    /// # Returns
+    /// * Returns `Ok(EncoderRef)` on success.
+    /// * Returns `Err(RLNCError::DataLengthZero)` if `data` is empty.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `piece_count` is zero.
+    pub fn new(data: &'a [u8], piece_count: usize) -> Result<EncoderRef<'a>, RLNCError> {
+        let params = Params::from_data_len(data.len(), piece_count)?;
+        let (leading_data_len, tail) = padded_tail(data, params);
+
+        Ok(EncoderRef {
+            data: &data[..leading_data_len],
+            tail,
+            piece_count: params.get_piece_count(),
+            piece_byte_len: params.get_piece_byte_len(),
+        })
+    }
+
+    /// Number of pieces original data got split into and being coded together.
+    pub fn get_piece_count(&self) -> usize {
+        self.piece_count
+    }
+
+    /// After padding the original data, it gets split into `self.get_piece_count()` many pieces, which results into these many bytes per piece.
+    pub fn get_piece_byte_len(&self) -> usize {
+        self.piece_byte_len
+    }
+
+    /// Each full coded piece consists of `self.get_piece_count()` random coefficients, appended by corresponding encoded piece of `self.get_piece_byte_len()` bytes.
+    pub fn get_full_coded_piece_byte_len(&self) -> usize {
+        self.piece_count + self.piece_byte_len
+    }
+
+    /// RLNC configuration of this encoder, to be shared with recoders and decoders.
+    pub fn get_params(&self) -> Params {
+        Params {
+            piece_count: self.piece_count,
+            piece_byte_len: self.piece_byte_len,
+        }
+    }
+
+    /// Returns source piece at given index, as it is after padding the original data. See `Encoder::source_piece`.
+    ///
+    /// # Returns
+    /// * Returns `Ok(&[u8])` of length `self.get_piece_byte_len()` on success.
+    /// * Returns `Err(RLNCError::InvalidPieceIndex)` if `index` is not less than `self.get_piece_count()`.
+    pub fn source_piece(&self, index: usize) -> Result<&[u8], RLNCError> {
+        if index >= self.piece_count {
+            return Err(RLNCError::InvalidPieceIndex);
+        }
+
+        let offset = index * self.piece_byte_len;
+        Ok(match offset.checked_sub(self.data.len()) {
+            Some(tail_offset) => &self.tail[tail_offset..tail_offset + self.piece_byte_len],
+            None => &self.data[offset..offset + self.piece_byte_len],
+        })
+    }
+
+    /// Erasure codes the borrowed data using a provided coding vector, without any memory allocation. See `Encoder::code_with_coding_vector`.
+    ///
+    /// # Arguments
+    /// * `coding_vector` - A slice to coding vector, of `self.get_piece_count()` coefficients.
+    /// * `coded_data` - A mutable slice to write the coded data into, of `self.get_piece_byte_len()` bytes.
+    ///
+    /// # Returns
+    /// * Returns `Ok(())` on success.
+    /// * Returns `Err(RLNCError::CodingVectorLengthMismatch)` if the length of `coding_vector` is not `self.get_piece_count()`.
+    /// * Returns `Err(RLNCError::InvalidOutputBuffer)` if the length of `coded_data` is not `self.get_piece_byte_len()`.
+    pub fn code_with_coding_vector(&self, coding_vector: &[u8], coded_data: &mut [u8]) -> Result<(), RLNCError> {
+        if coding_vector.len() != self.piece_count {
+            return Err(RLNCError::CodingVectorLengthMismatch);
+        }
+        if coded_data.len() != self.piece_byte_len {
+            return Err(RLNCError::InvalidOutputBuffer);
+        }
+
+        coded_data.fill(0);
+
+        self.data
+            .chunks_exact(self.piece_byte_len)
+            .chain(self.tail.chunks_exact(self.piece_byte_len))
+            .zip(coding_vector)
+            .for_each(|(piece, &random_symbol)| gf256_mul_vec_by_scalar_then_add_into_vec(coded_data, piece, random_symbol));
+
+        Ok(())
+    }
+
+    /// Produces a new coded piece, random sampling coding coefficients and writing full coded piece into the provided buffer.
+    ///
+    /// # Arguments
+    /// * `rng` - A mutable reference to a random number generator.
+    /// * `full_coded_piece` - A mutable slice to write the full coded piece (coding vector + coded data) into.
+    ///
+    /// # Returns
+    /// * Returns `Ok(())` on success.
+    /// * Returns `Err(RLNCError::InvalidOutputBuffer)` if the length of `full_coded_piece` is incorrect.
+    pub fn code_with_buf<R: Rng + ?Sized>(&self, rng: &mut R, full_coded_piece: &mut [u8]) -> Result<(), RLNCError> {
+        if full_coded_piece.len() != self.get_full_coded_piece_byte_len() {
+            return Err(RLNCError::InvalidOutputBuffer);
+        }
+
+        let (coding_vector, coded_data) = full_coded_piece.split_at_mut(self.piece_count);
+
+        rng.fill_bytes(coding_vector);
+        self.code_with_coding_vector(coding_vector, coded_data)
+    }
+
+    /// Produces a new coded piece, random sampling a coding vector.
+    ///
+    /// # Returns
+    /// A `Vec<u8>` containing the random sampled coding vector followed by the coded data, of `self.get_full_coded_piece_byte_len()` bytes.
+    pub fn code<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<u8> {
+        let mut full_coded_piece = vec![0u8; self.get_full_coded_piece_byte_len()];
+        unsafe { self.code_with_buf(rng, &mut full_coded_piece).unwrap_unchecked() };
+
+        full_coded_piece
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{Encoder, RLNCError};
+    use super::{Encoder, EncoderRef, RLNCError};
+    use crate::{common::gf256::Gf256, full::Decoder};
     use rand::Rng;
+    use std::io::IoSlice;
 
     #[test]
     fn test_encoder_without_padding_invalid_data() {
@@ -283,7 +937,7 @@ mod tests {
         let piece_count_non_zero = 10usize;
         let data_zero: Vec<u8> = (0..data_byte_len_zero).map(|_| rng.random()).collect();
 
-        let result_data_zero = Encoder::without_padding(data_zero, piece_count_non_zero);
+        let result_data_zero = Encoder::without_padding(Box::new(data_zero), piece_count_non_zero);
         assert!(result_data_zero.is_err());
         assert_eq!(result_data_zero.expect_err("Expected DataLengthZero error"), RLNCError::DataLengthZero);
 
@@ -292,7 +946,7 @@ mod tests {
         let piece_count_zero = 0usize;
         let data_non_zero: Vec<u8> = (0..data_byte_len_non_zero).map(|_| rng.random()).collect();
 
-        let result_piece_count_zero = Encoder::without_padding(data_non_zero, piece_count_zero);
+        let result_piece_count_zero = Encoder::without_padding(Box::new(data_non_zero), piece_count_zero);
         assert!(result_piece_count_zero.is_err());
         assert_eq!(result_piece_count_zero.expect_err("Expected PieceCountZero error"), RLNCError::PieceCountZero);
 
@@ -301,7 +955,7 @@ mod tests {
         let piece_count = 32usize;
         let data = (0..data_byte_len).map(|_| rng.random()).collect::<Vec<u8>>();
 
-        let result = Encoder::without_padding(data, piece_count);
+        let result = Encoder::without_padding(Box::new(data), piece_count);
         assert!(result.is_err());
         assert_eq!(result.expect_err("Expected DataLengthMismatch error"), RLNCError::DataLengthMismatch);
 
@@ -310,7 +964,7 @@ mod tests {
         let piece_count_valid = 10usize;
         let data_valid = (0..data_byte_len_valid).map(|_| rng.random()).collect::<Vec<u8>>();
 
-        let result_valid = Encoder::without_padding(data_valid, piece_count_valid);
+        let result_valid = Encoder::without_padding(Box::new(data_valid), piece_count_valid);
         assert!(result_valid.is_ok());
     }
 
@@ -493,6 +1147,399 @@ mod tests {
         assert!(result_valid.is_ok());
     }
 
+    #[test]
+    fn test_encoder_clone_shares_data() {
+        let mut rng = rand::rng();
+
+        let data = (0..1024).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data, 32).expect("Failed to create Encoder for clone test");
+        let encoder_clone = encoder.clone();
+
+        assert!(std::ptr::eq(encoder.data.as_slice().as_ptr(), encoder_clone.data.as_slice().as_ptr()));
+    }
+
+    #[test]
+    fn test_encoder_code_with_coding_vector_matches_scalar_coding() {
+        let mut rng = rand::rng();
+
+        // Piece byte length, which is not a multiple of SIMD register width, nor of parallel coding chunk alignment.
+        let data = (0..10_000).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data, 7).expect("Failed to create Encoder for scalar coding test");
+
+        let coding_vector = (0..encoder.get_piece_count()).map(|_| rng.random()).collect::<Vec<u8>>();
+        let mut coded_data = vec![0xffu8; encoder.get_piece_byte_len()];
+        encoder.code_with_coding_vector(&coding_vector, &mut coded_data).expect("Coding must not fail");
+
+        let mut expected_coded_data = vec![0u8; encoder.get_piece_byte_len()];
+        encoder
+            .data
+            .as_slice()
+            .chunks_exact(encoder.get_piece_byte_len())
+            .zip(&coding_vector)
+            .for_each(|(piece, &coeff)| {
+                expected_coded_data
+                    .iter_mut()
+                    .zip(piece)
+                    .for_each(|(res, &symbol)| *res ^= Gf256::mul_const(symbol, coeff));
+            });
+
+        assert_eq!(coded_data, expected_coded_data);
+
+        // Many column tiles, the last one being short.
+        let tiled_encoder = encoder.with_coding_tile_byte_len(100);
+        assert_eq!(tiled_encoder.get_coding_tile_byte_len(), 128);
+
+        coded_data.fill(0xff);
+        tiled_encoder
+            .code_with_coding_vector(&coding_vector, &mut coded_data)
+            .expect("Coding must not fail");
+        assert_eq!(coded_data, expected_coded_data);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_encoder_with_thread_pool() {
+        let mut rng = rand::rng();
+
+        let data = (0..10_000).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data, 32).expect("Failed to create Encoder for thread pool test");
+
+        let thread_pool = rayon::ThreadPoolBuilder::new().num_threads(2).build().expect("Failed to build thread pool");
+        let pooled_encoder = encoder.clone().with_thread_pool(std::sync::Arc::new(thread_pool));
+
+        let coding_vector = (0..encoder.get_piece_count()).map(|_| rng.random()).collect::<Vec<u8>>();
+        let mut coded_data = vec![0u8; encoder.get_piece_byte_len()];
+        let mut pooled_coded_data = vec![0u8; encoder.get_piece_byte_len()];
+
+        encoder.code_with_coding_vector(&coding_vector, &mut coded_data).expect("Coding must not fail");
+        pooled_encoder
+            .code_with_coding_vector(&coding_vector, &mut pooled_coded_data)
+            .expect("Coding must not fail");
+
+        assert_eq!(coded_data, pooled_coded_data);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_encoder_parallel_coding_with_varying_piece_lengths() {
+        let mut rng = rand::rng();
+
+        let thread_pool = std::sync::Arc::new(rayon::ThreadPoolBuilder::new().num_threads(3).build().expect("Failed to build thread pool"));
+
+        // Threads of the pool code pieces of shorter and longer lengths, one after another, so no state may leak between encoders.
+        for (data_byte_len, piece_count) in [(10_000, 7), (100, 5), (20_000, 3), (100, 5)] {
+            let data = (0..data_byte_len).map(|_| rng.random()).collect::<Vec<u8>>();
+            let encoder = Encoder::new(data, piece_count).expect("Failed to create Encoder for varying piece lengths test");
+
+            let coding_vector = (0..piece_count).map(|_| rng.random()).collect::<Vec<u8>>();
+            let mut expected_coded_data = vec![0u8; encoder.get_piece_byte_len()];
+            encoder
+                .data
+                .as_slice()
+                .chunks_exact(encoder.get_piece_byte_len())
+                .zip(&coding_vector)
+                .for_each(|(piece, &coeff)| {
+                    expected_coded_data
+                        .iter_mut()
+                        .zip(piece)
+                        .for_each(|(res, &symbol)| *res ^= Gf256::mul_const(symbol, coeff));
+                });
+
+            let mut coded_data = vec![0xffu8; encoder.get_piece_byte_len()];
+            encoder
+                .with_thread_pool(thread_pool.clone())
+                .code_with_coding_vector(&coding_vector, &mut coded_data)
+                .expect("Coding must not fail");
+
+            assert_eq!(coded_data, expected_coded_data);
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_encoder_parallel_coding_partitions() {
+        let mut rng = rand::rng();
+
+        // Piece byte lengths, shorter than a single chunk, leaving some threads without a chunk, and not a multiple of chunk alignment.
+        for data_byte_len in [100, 1000, 100_000] {
+            let data = (0..data_byte_len).map(|_| rng.random()).collect::<Vec<u8>>();
+            let encoder = Encoder::new(data, 4).expect("Failed to create Encoder for partitioned coding test");
+            let coding_vector = (0..encoder.get_piece_count()).map(|_| rng.random()).collect::<Vec<u8>>();
+
+            // With a single thread, coded data is computed as a single chunk.
+            let coded_data = (1..=5)
+                .map(|num_threads| {
+                    let thread_pool = rayon::ThreadPoolBuilder::new()
+                        .num_threads(num_threads)
+                        .build()
+                        .expect("Failed to build thread pool");
+                    let pooled_encoder = encoder.clone().with_thread_pool(std::sync::Arc::new(thread_pool));
+
+                    let mut coded_data = vec![0xffu8; encoder.get_piece_byte_len()];
+                    pooled_encoder
+                        .code_with_coding_vector(&coding_vector, &mut coded_data)
+                        .expect("Coding must not fail");
+                    coded_data
+                })
+                .collect::<Vec<_>>();
+
+            assert!(coded_data.iter().all(|pooled_coded_data| *pooled_coded_data == coded_data[0]));
+        }
+    }
+
+    #[test]
+    fn test_encoder_code_into_ioslices() {
+        use crate::full::{Decoder, VectoredPiece};
+        use std::io::Write;
+
+        let mut rng = rand::rng();
+
+        let data = (0..1024).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 32).expect("Failed to create Encoder for vectored piece test");
+
+        // Test case 1: Vectored piece of wrong shape
+        let mut bad_piece = VectoredPiece::new(0, encoder.get_piece_count() + 1, encoder.get_piece_byte_len());
+        assert_eq!(encoder.code_into_ioslices(&mut rng, &mut bad_piece).err(), Some(RLNCError::InvalidOutputBuffer));
+
+        let mut bad_piece = VectoredPiece::new(0, encoder.get_piece_count(), encoder.get_piece_byte_len() - 1);
+        assert_eq!(encoder.code_into_ioslices(&mut rng, &mut bad_piece).err(), Some(RLNCError::InvalidOutputBuffer));
+
+        // Test case 2: Write vectored pieces out, with a header, and decode them back
+        let header_byte_len = 4;
+        let mut piece = VectoredPiece::new(header_byte_len, encoder.get_piece_count(), encoder.get_piece_byte_len());
+        let mut decoder = Decoder::new(encoder.get_piece_byte_len(), encoder.get_piece_count()).expect("Failed to create Decoder");
+
+        let mut seq_num = 0u32;
+        while !decoder.is_already_decoded() {
+            piece.get_header_mut().copy_from_slice(&seq_num.to_le_bytes());
+
+            let mut wire = Vec::with_capacity(piece.get_byte_len());
+            let io_slices = encoder
+                .code_into_ioslices(&mut rng, &mut piece)
+                .expect("Coding into vectored piece must not fail");
+            let num_written_bytes = wire.write_vectored(&io_slices).expect("Writing to vector must not fail");
+
+            assert_eq!(num_written_bytes, piece.get_byte_len());
+            assert_eq!(wire.len(), piece.get_byte_len());
+            assert_eq!(wire[..header_byte_len], seq_num.to_le_bytes());
+
+            match decoder.decode(&wire[header_byte_len..]) {
+                Ok(_) | Err(RLNCError::PieceNotUseful) => {}
+                Err(e) => panic!("Unexpected error during decoding: {e:?}"),
+            }
+
+            seq_num += 1;
+        }
+
+        assert_eq!(decoder.get_decoded_data(), Ok(data));
+    }
+
+    #[test]
+    fn test_encoder_code_with_systematic_coding_vectors() {
+        let mut rng = rand::rng();
+
+        let data = (0..1000).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 10).expect("Failed to create Encoder for systematic coding test");
+        let mut decoder = crate::full::Decoder::new_with(encoder.get_params());
+
+        // Unit coding vectors, one per source piece, are enough for decoding.
+        for piece_idx in 0..encoder.get_piece_count() {
+            let mut full_coded_piece = vec![0u8; encoder.get_full_coded_piece_byte_len()];
+            let (coding_vector, coded_data) = full_coded_piece.split_at_mut(encoder.get_piece_count());
+            coding_vector[piece_idx] = 1;
+
+            encoder.code_with_coding_vector(coding_vector, coded_data).expect("Coding must not fail");
+            decoder.decode(&full_coded_piece).expect("Systematic piece must be useful");
+        }
+
+        assert_eq!(decoder.get_decoded_data(), Ok(data));
+    }
+
+    #[test]
+    fn test_encoder_code_for_pivots() {
+        let mut rng = rand::rng();
+
+        let data = (0..1000).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 16).expect("Failed to create Encoder for targeted coding test");
+        assert_eq!(encoder.code_for_pivots(&mut rng, &[3, 16]), Err(RLNCError::InvalidPieceIndex));
+
+        let mut decoder = Decoder::new_with(encoder.get_params());
+        for _ in 0..5 {
+            decoder.decode(&encoder.code(&mut rng)).unwrap();
+        }
+
+        // Each targeted piece is useful, so that decoding completes without a single redundant piece.
+        while !decoder.is_already_decoded() {
+            let missing = decoder.missing_dimensions();
+            let piece = encoder.code_for_pivots(&mut rng, &missing).unwrap();
+            assert!(piece[..16].iter().enumerate().all(|(cidx, &coeff)| (coeff != 0) == missing.contains(&cidx)));
+            decoder.decode(&piece).expect("Targeted piece must be useful");
+        }
+
+        assert_eq!(decoder.get_received_piece_count(), 16);
+        assert_eq!(decoder.get_decoded_data(), Ok(data));
+    }
+
+    #[test]
+    fn test_encoder_source_piece() {
+        let mut rng = rand::rng();
+
+        let data = (0..1000).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 10).expect("Failed to create Encoder for source piece test");
+        let mut decoder = crate::full::Decoder::new_with(encoder.get_params());
+
+        for piece_idx in 0..encoder.get_piece_count() {
+            let source_piece = encoder.source_piece(piece_idx).expect("Source piece must exist");
+            assert_eq!(source_piece.len(), encoder.get_piece_byte_len());
+
+            decoder.insert_known_piece(piece_idx, source_piece).expect("Source piece must be useful");
+        }
+
+        assert_eq!(encoder.source_piece(encoder.get_piece_count()), Err(RLNCError::InvalidPieceIndex));
+        assert_eq!(decoder.get_decoded_data(), Ok(data));
+    }
+
+    #[test]
+    fn test_encoder_from_segments() {
+        let mut rng = rand::rng();
+
+        let data = (0..1000).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 7).expect("Failed to create Encoder");
+
+        let segments = [&data[..10], &[], &data[10..999], &data[999..]];
+        let segmented = Encoder::from_segments(&segments, 7).expect("Failed to create Encoder from segments");
+
+        assert_eq!(segmented.get_params(), encoder.get_params());
+        for index in 0..7 {
+            assert_eq!(segmented.source_piece(index), encoder.source_piece(index));
+        }
+
+        assert!(matches!(Encoder::from_segments(&[&[], &[]], 7), Err(RLNCError::DataLengthZero)));
+        assert!(matches!(Encoder::from_segments(&segments, 0), Err(RLNCError::PieceCountZero)));
+    }
+
+    #[test]
+    fn test_encoder_from_slices() {
+        let mut rng = rand::rng();
+
+        let data = (0..1000).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 7).expect("Failed to create Encoder");
+
+        let slices: [&[u8]; 3] = [&data[..333], &data[333..334], &data[334..]];
+        let borrowed = Encoder::from_slices(&slices, 7).expect("Failed to create Encoder from slices");
+        assert_eq!(borrowed.get_params(), encoder.get_params());
+
+        let io_slices = slices.map(IoSlice::new);
+        let scattered = Encoder::from_slices(&io_slices, 7).expect("Failed to create Encoder from I/O slices");
+
+        let owned_slices = [data[..500].to_vec(), Vec::new(), data[500..].to_vec()];
+        let owned = Encoder::from_slices(&owned_slices, 7).expect("Failed to create Encoder from owned slices");
+
+        for index in 0..7 {
+            assert_eq!(borrowed.source_piece(index), encoder.source_piece(index));
+            assert_eq!(scattered.source_piece(index), encoder.source_piece(index));
+            assert_eq!(owned.source_piece(index), encoder.source_piece(index));
+        }
+
+        assert!(matches!(
+            Encoder::from_slices::<Vec<u8>>(&[Vec::new(), Vec::new()], 7),
+            Err(RLNCError::DataLengthZero)
+        ));
+        assert!(matches!(Encoder::from_slices(&io_slices, 0), Err(RLNCError::PieceCountZero)));
+    }
+
+    #[test]
+    fn test_encoder_from_reader_and_file() {
+        let mut rng = rand::rng();
+
+        // Tiny data leaves more than one trailing source piece, holding padding only.
+        for data_len in [1000, 2] {
+            let data = (0..data_len).map(|_| rng.random()).collect::<Vec<u8>>();
+            let encoder = Encoder::new(data.clone(), 7).expect("Failed to create Encoder");
+
+            let coding_vector = (0..7).map(|_| rng.random()).collect::<Vec<u8>>();
+            let mut expected_coded_data = vec![0u8; encoder.get_piece_byte_len()];
+            encoder.code_with_coding_vector(&coding_vector, &mut expected_coded_data).unwrap();
+
+            let assert_same_as_encoder = |other: Encoder| {
+                assert_eq!(other.get_params(), encoder.get_params());
+                for index in 0..7 {
+                    assert_eq!(other.source_piece(index), encoder.source_piece(index));
+                }
+
+                let mut coded_data = vec![0u8; other.get_piece_byte_len()];
+                other.code_with_coding_vector(&coding_vector, &mut coded_data).unwrap();
+                assert_eq!(coded_data, expected_coded_data);
+            };
+
+            assert_same_as_encoder(Encoder::from_reader(data.as_slice(), data_len, 7).expect("Failed to create Encoder from reader"));
+
+            #[cfg(feature = "mmap")]
+            {
+                let path = std::env::temp_dir().join(format!("rlnc-encoder-from-file-test-{}-{data_len}", std::process::id()));
+                std::fs::write(&path, &data).expect("Temporary file must be writable");
+
+                assert_same_as_encoder(Encoder::from_file(&path, 7).expect("Failed to create Encoder from file"));
+                assert_eq!(std::fs::read(&path).expect("Temporary file must be readable"), data);
+                let _ = std::fs::remove_file(path);
+            }
+        }
+
+        assert!(matches!(Encoder::from_reader(&[0u8; 9][..], 10, 7), Err(RLNCError::ReadFailure)));
+        assert!(matches!(Encoder::from_reader(&[0u8; 9][..], 0, 7), Err(RLNCError::DataLengthZero)));
+    }
+
+    #[test]
+    fn test_encoder_ref_borrows_data() {
+        let mut rng = rand::rng();
+
+        for data_len in [1000, 2] {
+            let data = (0..data_len).map(|_| rng.random()).collect::<Vec<u8>>();
+            let encoder = Encoder::new(data.clone(), 7).expect("Failed to create Encoder");
+            let encoder_ref = EncoderRef::new(&data, 7).expect("Failed to create EncoderRef");
+
+            assert_eq!(encoder_ref.get_params(), encoder.get_params());
+            for index in 0..8 {
+                assert_eq!(encoder_ref.source_piece(index), encoder.source_piece(index));
+            }
+            assert!(encoder_ref.tail.len() < encoder_ref.get_full_coded_piece_byte_len());
+
+            let mut decoder = Decoder::new_with(encoder_ref.get_params());
+            while !decoder.is_already_decoded() {
+                let _ = decoder.decode(&encoder_ref.code(&mut rng));
+            }
+            assert_eq!(decoder.get_decoded_data(), Ok(data));
+        }
+
+        assert!(matches!(EncoderRef::new(&[], 7), Err(RLNCError::DataLengthZero)));
+        assert!(matches!(EncoderRef::new(&[1], 0), Err(RLNCError::PieceCountZero)));
+    }
+
+    #[test]
+    fn test_encoder_code_batch_with_bufs() {
+        let mut rng = rand::rng();
+
+        // Piece byte length, spanning a few column tiles, but not a multiple of tile length.
+        let data = (0..30_000).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data, 3).expect("Failed to create Encoder for batched coding test");
+
+        let mut full_coded_pieces = vec![vec![0xffu8; encoder.get_full_coded_piece_byte_len()]; 5];
+        let mut bufs = full_coded_pieces.iter_mut().map(|piece| piece.as_mut_slice()).collect::<Vec<_>>();
+        encoder.code_batch_with_bufs(&mut rng, &mut bufs).expect("Batched coding must not fail");
+
+        for full_coded_piece in &full_coded_pieces {
+            let (coding_vector, coded_data) = full_coded_piece.split_at(encoder.get_piece_count());
+
+            let mut expected_coded_data = vec![0u8; encoder.get_piece_byte_len()];
+            encoder.code_with_coding_vector(coding_vector, &mut expected_coded_data).unwrap();
+            assert_eq!(coded_data, expected_coded_data);
+        }
+
+        let mut short_buf = vec![0u8; encoder.get_full_coded_piece_byte_len() - 1];
+        let mut bufs = vec![full_coded_pieces[0].as_mut_slice(), short_buf.as_mut_slice()];
+        assert_eq!(encoder.code_batch_with_bufs(&mut rng, &mut bufs), Err(RLNCError::InvalidOutputBuffer));
+    }
+
     #[test]
     fn test_encoder_getters() {
         let mut rng = rand::rng();
@@ -542,4 +1589,74 @@ mod tests {
             piece_count_large + (data_byte_len_large + 1).div_ceil(piece_count_large)
         );
     }
+
+    #[cfg(feature = "futures")]
+    #[test]
+    fn test_encoder_stream_pieces() {
+        use crate::full::{AsyncPieceReader, DecodeEvent, Decoder};
+        use futures::{AsyncWrite, StreamExt, executor::block_on, io::Cursor};
+        use std::{
+            io,
+            pin::Pin,
+            task::{Context, Poll},
+        };
+
+        /// Accepts at most a few bytes per write, forcing partial writes, and fails once its capacity is exhausted.
+        struct TrickleWriter {
+            bytes: Vec<u8>,
+            capacity: usize,
+        }
+
+        impl AsyncWrite for TrickleWriter {
+            fn poll_write(mut self: Pin<&mut Self>, _: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+                if self.bytes.len() >= self.capacity {
+                    return Poll::Ready(Err(io::Error::from(io::ErrorKind::BrokenPipe)));
+                }
+
+                let len = buf.len().min(7);
+                self.bytes.extend_from_slice(&buf[..len]);
+                Poll::Ready(Ok(len))
+            }
+
+            fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+                Poll::Ready(Ok(()))
+            }
+
+            fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+                Poll::Ready(Ok(()))
+            }
+        }
+
+        let mut rng = rand::rng();
+
+        let data = (0..4096).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 16).expect("Failed to create Encoder for streaming test");
+
+        let mut writer = TrickleWriter {
+            bytes: Vec::new(),
+            capacity: usize::MAX,
+        };
+        let mut paced = Vec::new();
+        block_on(encoder.stream_pieces(&mut rng, &mut writer, 24, |written| {
+            paced.push(written);
+            std::future::ready(())
+        }))
+        .expect("Streaming pieces must not fail");
+
+        assert_eq!(paced, (1..=24).collect::<Vec<_>>());
+        assert_eq!(writer.bytes.len(), 24 * encoder.get_full_coded_piece_byte_len());
+
+        let reader = AsyncPieceReader::new(Cursor::new(writer.bytes), Decoder::new_with(encoder.get_params()));
+        let events = block_on(reader.collect::<Vec<_>>());
+        assert_eq!(events.last(), Some(&Ok(DecodeEvent::Decoded(data))));
+
+        let mut broken_writer = TrickleWriter {
+            bytes: Vec::new(),
+            capacity: 100,
+        };
+        assert_eq!(
+            block_on(encoder.stream_pieces(&mut rng, &mut broken_writer, 4, |_| std::future::ready(()))),
+            Err(RLNCError::WriteFailure)
+        );
+    }
 }
@@ -0,0 +1,102 @@
+use std::time::{Duration, Instant};
+
+/// Estimate of what's left, before a decoder can recover the original data. See `Decoder::estimated_completion`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CompletionEstimate {
+    /// Number of useful pieces still needed, which is exact.
+    pub remaining_useful_piece_count: usize,
+    /// Expected number of pieces still to be received, accounting for observed share of linearly dependent ones.
+    pub expected_piece_count: f64,
+    /// Expected time, till enough pieces are received, given recent arrival rate. It is `None`, unless arrival timing is enabled,
+    /// see `Decoder::enable_arrival_timing`, and at least two pieces arrived since, at distinct instants.
+    pub expected_time: Option<Duration>,
+}
+
+/// Weight of the latest inter-arrival time in the exponentially decayed mean of inter-arrival times. Influence of older ones halves
+/// every 5 or so arrivals, so that the estimated rate follows changes of link conditions, instead of averaging over the whole transfer.
+const INTERVAL_SMOOTHING_FACTOR: f64 = 0.125;
+
+/// Arrival times of pieces, as observed by a decoder, for estimating arrival rate.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct ArrivalStats {
+    /// Last arrival instant.
+    last_arrival: Option<Instant>,
+    /// Exponentially decayed mean of inter-arrival times, in seconds, if at least two pieces arrived.
+    mean_interval: Option<f64>,
+}
+
+impl ArrivalStats {
+    /// Records arrival of a piece, at given instant.
+    pub(crate) fn record(&mut self, now: Instant) {
+        if let Some(last_arrival) = self.last_arrival {
+            let interval = now.saturating_duration_since(last_arrival).as_secs_f64();
+
+            self.mean_interval = Some(match self.mean_interval {
+                Some(mean_interval) => mean_interval + INTERVAL_SMOOTHING_FACTOR * (interval - mean_interval),
+                None => interval,
+            });
+        }
+
+        self.last_arrival = Some(now);
+    }
+
+    /// Recent number of arrivals per second, if at least two pieces arrived, at distinct instants.
+    pub(crate) fn rate(&self) -> Option<f64> {
+        self.mean_interval
+            .filter(|&mean_interval| mean_interval > 0.0)
+            .map(|mean_interval| mean_interval.recip())
+    }
+}
+
+/// Given remaining useful pieces and observed useful and received piece counts, estimates what's left to be received.
+pub(crate) fn estimate_completion(
+    remaining_useful_piece_count: usize,
+    useful_piece_count: usize,
+    received_piece_count: usize,
+    arrivals: Option<&ArrivalStats>,
+) -> CompletionEstimate {
+    // Each arriving piece is assumed to be useful with the observed probability. Till something useful is observed, every piece is
+    // assumed to be useful, as random coding vectors over GF(2^8) almost always are.
+    let useful_ratio = if useful_piece_count == 0 {
+        1.0
+    } else {
+        useful_piece_count as f64 / received_piece_count as f64
+    };
+
+    let expected_piece_count = remaining_useful_piece_count as f64 / useful_ratio;
+    let expected_time = arrivals
+        .and_then(ArrivalStats::rate)
+        .map(|rate| Duration::from_secs_f64(expected_piece_count / rate));
+
+    CompletionEstimate {
+        remaining_useful_piece_count,
+        expected_piece_count,
+        expected_time,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ArrivalStats;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_arrival_rate_follows_recent_arrivals() {
+        let mut arrivals = ArrivalStats::default();
+        let mut now = Instant::now();
+
+        arrivals.record(now);
+        assert_eq!(arrivals.rate(), None);
+        arrivals.record(now);
+        assert_eq!(arrivals.rate(), None);
+
+        // Link slows down from 10 to 1 arrivals per second. Lifetime average would be stuck below 2 per second.
+        for interval in [Duration::from_millis(100); 10].into_iter().chain([Duration::from_secs(1); 30]) {
+            now += interval;
+            arrivals.record(now);
+        }
+
+        let rate = arrivals.rate().expect("Arrivals are at distinct instants");
+        assert!((1.0..1.05).contains(&rate), "Unexpected arrival rate: {rate}");
+    }
+}
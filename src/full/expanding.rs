@@ -0,0 +1,331 @@
+use crate::{
+    RLNCError,
+    common::{
+        gf256::Gf256,
+        simd::{gf256_inplace_mul_vec_by_scalar, gf256_mul_vec_by_scalar_then_add_into_vec},
+    },
+};
+use rand::Rng;
+
+/// Byte length of big-endian window length, leading each expanding-window coded piece.
+const WINDOW_LEN_BYTE_LEN: usize = size_of::<u32>();
+
+/// Sender side of expanding-window coding, for interactive streams, where waiting for a full generation adds unavoidable batching delay.
+///
+/// Source symbols of fixed byte length are pushed as they get produced. Each coded piece is a random linear combination of all symbols
+/// pushed so far i.e. symbols `0..window_len`, always including the latest one. A coded piece is laid out as
+///
+/// ```text
+/// | window_len (u32, big-endian) | coding vector (window_len bytes) | coded symbol (symbol_byte_len bytes) |
+/// ```
+#[derive(Clone, Debug)]
+pub struct ExpandingWindowEncoder {
+    symbol_byte_len: usize,
+    symbols: Vec<u8>,
+}
+
+impl ExpandingWindowEncoder {
+    /// Creates an encoder with an empty window.
+    ///
+    /// # Returns
+    /// * Returns `Ok(ExpandingWindowEncoder)` on success.
+    /// * Returns `Err(RLNCError::PieceLengthZero)` if `symbol_byte_len` is zero.
+    pub fn new(symbol_byte_len: usize) -> Result<ExpandingWindowEncoder, RLNCError> {
+        if symbol_byte_len == 0 {
+            return Err(RLNCError::PieceLengthZero);
+        }
+
+        Ok(ExpandingWindowEncoder {
+            symbol_byte_len,
+            symbols: Vec::new(),
+        })
+    }
+
+    /// Byte length of each source symbol.
+    pub fn get_symbol_byte_len(&self) -> usize {
+        self.symbol_byte_len
+    }
+
+    /// Number of source symbols pushed so far, all of which are coded together.
+    pub fn get_window_len(&self) -> usize {
+        self.symbols.len() / self.symbol_byte_len
+    }
+
+    /// Byte length of a coded piece, given current window length.
+    pub fn get_coded_piece_byte_len(&self) -> usize {
+        WINDOW_LEN_BYTE_LEN + self.get_window_len() + self.symbol_byte_len
+    }
+
+    /// Appends a source symbol to the window.
+    ///
+    /// # Returns
+    /// * Returns `Ok(usize)` holding index of the symbol in the stream.
+    /// * Returns `Err(RLNCError::InvalidPieceLength)` if `symbol` is not of `self.get_symbol_byte_len()` bytes.
+    pub fn push_symbol(&mut self, symbol: &[u8]) -> Result<usize, RLNCError> {
+        if symbol.len() != self.symbol_byte_len {
+            return Err(RLNCError::InvalidPieceLength);
+        }
+
+        self.symbols.extend_from_slice(symbol);
+        Ok(self.get_window_len() - 1)
+    }
+
+    /// Codes all symbols pushed so far together, with random coefficients, making sure the latest symbol is included.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Vec<u8>)` holding the coded piece.
+    /// * Returns `Err(RLNCError::DataLengthZero)` if no symbol is pushed yet.
+    pub fn code<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Vec<u8>, RLNCError> {
+        let window_len = self.get_window_len();
+        if window_len == 0 {
+            return Err(RLNCError::DataLengthZero);
+        }
+
+        let mut coded_piece = vec![0u8; self.get_coded_piece_byte_len()];
+        let (header, body) = coded_piece.split_at_mut(WINDOW_LEN_BYTE_LEN);
+        let (coding_vector, coded_symbol) = body.split_at_mut(window_len);
+
+        header.copy_from_slice(&(window_len as u32).to_be_bytes());
+        rng.fill_bytes(coding_vector);
+        coding_vector[window_len - 1] = rng.random_range(1..=u8::MAX);
+
+        self.symbols
+            .chunks_exact(self.symbol_byte_len)
+            .zip(coding_vector.iter())
+            .for_each(|(symbol, &coeff)| gf256_mul_vec_by_scalar_then_add_into_vec(coded_symbol, symbol, coeff));
+
+        Ok(coded_piece)
+    }
+}
+
+/// Row of an expanding-window decoder. Coding vector is kept without trailing zeros, so it's only as long as the widest window it depends on.
+#[derive(Clone, Debug)]
+struct WindowRow {
+    coefficients: Vec<u8>,
+    payload: Vec<u8>,
+}
+
+impl WindowRow {
+    /// Adds `scalar x other` into this row, growing it if `other` is wider.
+    fn add_scaled(&mut self, other: &WindowRow, scalar: u8) {
+        if other.coefficients.len() > self.coefficients.len() {
+            self.coefficients.resize(other.coefficients.len(), 0);
+        }
+
+        gf256_mul_vec_by_scalar_then_add_into_vec(&mut self.coefficients[..other.coefficients.len()], &other.coefficients, scalar);
+        gf256_mul_vec_by_scalar_then_add_into_vec(&mut self.payload, &other.payload, scalar);
+
+        let len = self.coefficients.iter().rposition(|&coeff| coeff != 0).map_or(0, |cidx| cidx + 1);
+        self.coefficients.truncate(len);
+    }
+}
+
+/// Receiver side of expanding-window coding, releasing source symbols in order, as soon as each gets decoded.
+///
+/// Pivot rows are kept in reduced row echelon form, with pivots normalized to one, so that a symbol is decoded, as soon as
+/// its pivot row has no other non-zero coefficient. Symbol `i` is released once it and all symbols before it are decoded.
+#[derive(Clone, Debug)]
+pub struct ExpandingWindowDecoder {
+    symbol_byte_len: usize,
+    received_piece_count: usize,
+    useful_piece_count: usize,
+    /// Pivot row of each column, if any. Its leading coefficient is in that column and is one.
+    pivot_rows: Vec<Option<WindowRow>>,
+    /// Index of the next symbol to be released.
+    next_symbol: usize,
+}
+
+impl ExpandingWindowDecoder {
+    /// Creates a decoder, for a stream of symbols of given byte length.
+    ///
+    /// # Returns
+    /// * Returns `Ok(ExpandingWindowDecoder)` on success.
+    /// * Returns `Err(RLNCError::PieceLengthZero)` if `symbol_byte_len` is zero.
+    pub fn new(symbol_byte_len: usize) -> Result<ExpandingWindowDecoder, RLNCError> {
+        if symbol_byte_len == 0 {
+            return Err(RLNCError::PieceLengthZero);
+        }
+
+        Ok(ExpandingWindowDecoder {
+            symbol_byte_len,
+            received_piece_count: 0,
+            useful_piece_count: 0,
+            pivot_rows: Vec::new(),
+            next_symbol: 0,
+        })
+    }
+
+    /// Byte length of each source symbol.
+    pub fn get_symbol_byte_len(&self) -> usize {
+        self.symbol_byte_len
+    }
+
+    /// Widest window seen so far.
+    pub fn get_window_len(&self) -> usize {
+        self.pivot_rows.len()
+    }
+
+    /// Total number of pieces received by the decoder so far.
+    pub fn get_received_piece_count(&self) -> usize {
+        self.received_piece_count
+    }
+
+    /// Number of useful pieces received by the decoder so far.
+    pub fn get_useful_piece_count(&self) -> usize {
+        self.useful_piece_count
+    }
+
+    /// Number of symbols released so far, by `Self::next_symbol`.
+    pub fn get_released_symbol_count(&self) -> usize {
+        self.next_symbol
+    }
+
+    /// Decodes a coded piece, produced by `ExpandingWindowEncoder::code`.
+    ///
+    /// # Returns
+    /// * Returns `Ok(())` if the piece was useful.
+    /// * Returns `Err(RLNCError::PieceNotUseful)` if the piece was linearly dependent on the already received useful pieces.
+    /// * Returns `Err(RLNCError::InvalidPieceLength)` if `coded_piece` is malformed or its window is empty.
+    pub fn decode(&mut self, coded_piece: &[u8]) -> Result<(), RLNCError> {
+        let Some((header, body)) = coded_piece.split_at_checked(WINDOW_LEN_BYTE_LEN) else {
+            return Err(RLNCError::InvalidPieceLength);
+        };
+
+        let window_len = u32::from_be_bytes(unsafe { header.try_into().unwrap_unchecked() }) as usize;
+        if window_len == 0 || body.len() != window_len + self.symbol_byte_len {
+            return Err(RLNCError::InvalidPieceLength);
+        }
+
+        self.received_piece_count += 1;
+        if window_len > self.pivot_rows.len() {
+            self.pivot_rows.resize(window_len, None);
+        }
+
+        let (coding_vector, payload) = body.split_at(window_len);
+        let mut row = WindowRow {
+            coefficients: coding_vector.to_vec(),
+            payload: payload.to_vec(),
+        };
+
+        // Pivot rows have zeros in all other pivot columns, so a single pass over columns clears all of them.
+        let mut cidx = 0;
+        while cidx < row.coefficients.len() {
+            let coeff = row.coefficients[cidx];
+            if coeff != 0
+                && let Some(pivot_row) = &self.pivot_rows[cidx]
+            {
+                row.add_scaled(pivot_row, coeff);
+            }
+            cidx += 1;
+        }
+
+        let Some(leading_cidx) = row.coefficients.iter().position(|&coeff| coeff != 0) else {
+            return Err(RLNCError::PieceNotUseful);
+        };
+
+        let inv = unsafe { Gf256::new(row.coefficients[leading_cidx]).inv().unwrap_unchecked().get() };
+        gf256_inplace_mul_vec_by_scalar(&mut row.coefficients, inv);
+        gf256_inplace_mul_vec_by_scalar(&mut row.payload, inv);
+
+        // Keep other pivot rows reduced, by clearing the new pivot column off them.
+        for pivot_row in self.pivot_rows.iter_mut().flatten() {
+            let coeff = pivot_row.coefficients.get(leading_cidx).copied().unwrap_or(0);
+            if coeff != 0 {
+                pivot_row.add_scaled(&row, coeff);
+            }
+        }
+
+        self.pivot_rows[leading_cidx] = Some(row);
+        self.useful_piece_count += 1;
+
+        Ok(())
+    }
+
+    /// Releases the next source symbol of the stream, if it and all symbols before it are decoded.
+    pub fn next_symbol(&mut self) -> Option<Vec<u8>> {
+        let pivot_row = self.pivot_rows.get(self.next_symbol)?.as_ref()?;
+
+        // Pivot is normalized and trailing zeros are trimmed, so a row as long as its pivot column is a unit vector.
+        if pivot_row.coefficients.len() != self.next_symbol + 1 {
+            return None;
+        }
+
+        self.next_symbol += 1;
+        Some(pivot_row.payload.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ExpandingWindowDecoder, ExpandingWindowEncoder};
+    use crate::RLNCError;
+    use rand::Rng;
+
+    #[test]
+    fn test_expanding_window_in_order_release() {
+        const SYMBOL_BYTE_LEN: usize = 32;
+        const SYMBOL_COUNT: usize = 24;
+
+        let mut rng = rand::rng();
+
+        let symbols = (0..SYMBOL_COUNT)
+            .map(|_| (0..SYMBOL_BYTE_LEN).map(|_| rng.random()).collect::<Vec<u8>>())
+            .collect::<Vec<_>>();
+
+        let mut encoder = ExpandingWindowEncoder::new(SYMBOL_BYTE_LEN).expect("Failed to create expanding-window encoder");
+        let mut decoder = ExpandingWindowDecoder::new(SYMBOL_BYTE_LEN).expect("Failed to create expanding-window decoder");
+        let mut released = Vec::new();
+
+        // One coded piece per produced symbol, every third of them lost, so that lossless bursts let decoding catch up.
+        for (sidx, symbol) in symbols.iter().enumerate() {
+            assert_eq!(encoder.push_symbol(symbol), Ok(sidx));
+
+            let coded_piece = encoder.code(&mut rng).expect("Window must not be empty");
+            assert_eq!(coded_piece.len(), encoder.get_coded_piece_byte_len());
+
+            if sidx % 3 != 2 {
+                let _ = decoder.decode(&coded_piece);
+            }
+            while let Some(symbol) = decoder.next_symbol() {
+                released.push(symbol);
+            }
+        }
+
+        assert!(released.len() < SYMBOL_COUNT);
+        assert_eq!(released[..], symbols[..released.len()]);
+
+        // Repair pieces, once the stream ends, let the rest of the symbols be released.
+        while decoder.get_released_symbol_count() < SYMBOL_COUNT {
+            let _ = decoder.decode(&encoder.code(&mut rng).expect("Window must not be empty"));
+            while let Some(symbol) = decoder.next_symbol() {
+                released.push(symbol);
+            }
+        }
+
+        assert_eq!(released, symbols);
+        assert_eq!(decoder.get_useful_piece_count(), SYMBOL_COUNT);
+    }
+
+    #[test]
+    fn test_expanding_window_invalid_inputs() {
+        let mut rng = rand::rng();
+
+        assert!(matches!(ExpandingWindowEncoder::new(0), Err(RLNCError::PieceLengthZero)));
+        assert!(matches!(ExpandingWindowDecoder::new(0), Err(RLNCError::PieceLengthZero)));
+
+        let mut encoder = ExpandingWindowEncoder::new(4).unwrap();
+        assert_eq!(encoder.code(&mut rng), Err(RLNCError::DataLengthZero));
+        assert_eq!(encoder.push_symbol(&[1, 2, 3]), Err(RLNCError::InvalidPieceLength));
+
+        let mut decoder = ExpandingWindowDecoder::new(4).unwrap();
+        assert_eq!(decoder.decode(&[0, 0, 0]), Err(RLNCError::InvalidPieceLength));
+        assert_eq!(decoder.decode(&[0, 0, 0, 0, 1, 2, 3, 4]), Err(RLNCError::InvalidPieceLength));
+        assert_eq!(decoder.decode(&[0, 0, 0, 1, 1, 2, 3]), Err(RLNCError::InvalidPieceLength));
+
+        encoder.push_symbol(&[1, 2, 3, 4]).unwrap();
+        let coded_piece = encoder.code(&mut rng).unwrap();
+        assert_eq!(decoder.decode(&coded_piece), Ok(()));
+        assert_eq!(decoder.decode(&coded_piece), Err(RLNCError::PieceNotUseful));
+        assert_eq!(decoder.next_symbol(), Some(vec![1, 2, 3, 4]));
+    }
+}
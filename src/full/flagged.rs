@@ -0,0 +1,159 @@
+use super::piece::CodedPiece;
+use crate::RLNCError;
+
+/// Flag, leading a payload which carries a coded symbol, followed by its coefficients.
+const CODED_FLAG: u8 = 0;
+/// Flag, leading a payload which carries an uncoded source symbol, preceded by its index.
+const SYSTEMATIC_FLAG: u8 = 1;
+/// Byte length of big-endian source symbol index, in systematic payloads.
+const SYMBOL_INDEX_BYTE_LEN: usize = size_of::<u32>();
+
+impl CodedPiece {
+    /// Serializes the coded piece into a flagged payload, where a leading flag tells whether it carries a coded piece or an
+    /// uncoded source piece. Coded data comes first and coefficients last, while a source piece is sent along with its index,
+    /// instead of a unit coding vector. Note, it's a layout of this crate, which is not meant to match that of any other library.
+    ///
+    /// ```text
+    /// coded:      | 0x00 | coded symbol (piece_byte_len bytes) | coefficients (piece_count bytes) |
+    /// systematic: | 0x01 | source symbol index (u32, big-endian) | source symbol (piece_byte_len bytes) |
+    /// ```
+    ///
+    /// A piece, whose coding vector is a unit vector, is an uncoded source piece, which is serialized in systematic form.
+    pub fn to_flagged_payload(&self) -> Vec<u8> {
+        let coding_vector = self.get_coding_vector();
+        let coded_data = self.get_coded_data();
+
+        let mut nonzero_coeffs = coding_vector.iter().enumerate().filter(|&(_, &coeff)| coeff != 0);
+        let systematic_index = match (nonzero_coeffs.next(), nonzero_coeffs.next()) {
+            (Some((index, 1)), None) => u32::try_from(index).ok(),
+            _ => None,
+        };
+
+        match systematic_index {
+            Some(index) => [&[SYSTEMATIC_FLAG], index.to_be_bytes().as_slice(), coded_data].concat(),
+            None => [&[CODED_FLAG], coded_data, coding_vector].concat(),
+        }
+    }
+
+    /// Deserializes a coded piece, out of a flagged payload.
+    /// See `Self::to_flagged_payload` for the layout. Systematic payloads turn into pieces with unit coding vector.
+    ///
+    /// # Arguments
+    /// * `payload` - Serialized piece, as produced by `Self::to_flagged_payload`.
+    /// * `piece_count` - Number of pieces coded together, which the layout doesn't carry.
+    /// * `piece_byte_len` - Byte length of each piece, which the layout doesn't carry.
+    ///
+    /// # Returns
+    /// * Returns `Ok(CodedPiece)` on success.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `piece_count` is zero.
+    /// * Returns `Err(RLNCError::PieceLengthZero)` if `piece_byte_len` is zero.
+    /// * Returns `Err(RLNCError::InvalidPieceLength)` if `payload` length doesn't match its flag and given configuration.
+    /// * Returns `Err(RLNCError::InvalidPieceIndex)` if source symbol index of a systematic payload is not less than `piece_count`.
+    /// * Returns `Err(RLNCError::SerializationFailure)` if `payload` is empty or its flag is unknown.
+    pub fn from_flagged_payload(payload: &[u8], piece_count: usize, piece_byte_len: usize) -> Result<CodedPiece, RLNCError> {
+        if piece_count == 0 {
+            return Err(RLNCError::PieceCountZero);
+        }
+        if piece_byte_len == 0 {
+            return Err(RLNCError::PieceLengthZero);
+        }
+
+        let Some((&flag, body)) = payload.split_first() else {
+            return Err(RLNCError::SerializationFailure);
+        };
+
+        match flag {
+            CODED_FLAG => {
+                if body.len() != piece_byte_len + piece_count {
+                    return Err(RLNCError::InvalidPieceLength);
+                }
+
+                let (coded_data, coding_vector) = body.split_at(piece_byte_len);
+                Ok(CodedPiece::from_parts(coding_vector.to_vec(), coded_data.to_vec()))
+            }
+            SYSTEMATIC_FLAG => {
+                if body.len() != SYMBOL_INDEX_BYTE_LEN + piece_byte_len {
+                    return Err(RLNCError::InvalidPieceLength);
+                }
+
+                let (index, source_piece) = body.split_at(SYMBOL_INDEX_BYTE_LEN);
+                let index = u32::from_be_bytes(unsafe { index.try_into().unwrap_unchecked() }) as usize;
+                if index >= piece_count {
+                    return Err(RLNCError::InvalidPieceIndex);
+                }
+
+                let mut coding_vector = vec![0u8; piece_count];
+                coding_vector[index] = 1;
+
+                Ok(CodedPiece::from_parts(coding_vector, source_piece.to_vec()))
+            }
+            _ => Err(RLNCError::SerializationFailure),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        RLNCError,
+        full::{CodedPiece, Decoder, Encoder},
+    };
+    use rand::Rng;
+
+    #[test]
+    fn test_flagged_payload_roundtrip() {
+        let mut rng = rand::rng();
+
+        let data = (0..1000).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 8).expect("Failed to create Encoder for flagged payload test");
+        let (piece_count, piece_byte_len) = (encoder.get_piece_count(), encoder.get_piece_byte_len());
+
+        let mut decoder = Decoder::new_with(encoder.get_params());
+
+        // Half of the source pieces go systematic, rest of the pieces coded.
+        for index in 0..piece_count / 2 {
+            let mut coding_vector = vec![0u8; piece_count];
+            coding_vector[index] = 1;
+
+            let piece = CodedPiece::from_bytes(&[coding_vector.as_slice(), encoder.source_piece(index).unwrap()].concat(), piece_count).unwrap();
+            let payload = piece.to_flagged_payload();
+
+            assert_eq!(payload.len(), 1 + 4 + piece_byte_len);
+            assert_eq!(payload[..5], [1, 0, 0, 0, index as u8]);
+            assert_eq!(CodedPiece::from_flagged_payload(&payload, piece_count, piece_byte_len), Ok(piece.clone()));
+
+            decoder.decode(&piece.to_bytes()).expect("Source piece must be useful");
+        }
+
+        while !decoder.is_already_decoded() {
+            let piece = CodedPiece::from_bytes(&encoder.code(&mut rng), piece_count).unwrap();
+            let payload = piece.to_flagged_payload();
+
+            assert_eq!(payload[0], 0);
+            assert_eq!(&payload[1..1 + piece_byte_len], piece.get_coded_data());
+            assert_eq!(&payload[1 + piece_byte_len..], piece.get_coding_vector());
+
+            let deserialized = CodedPiece::from_flagged_payload(&payload, piece_count, piece_byte_len).expect("Payload must be valid");
+            let _ = decoder.decode(&deserialized.to_bytes());
+        }
+
+        assert_eq!(decoder.get_decoded_data(), Ok(data));
+    }
+
+    #[test]
+    fn test_flagged_payload_invalid_inputs() {
+        assert_eq!(CodedPiece::from_flagged_payload(&[], 4, 4), Err(RLNCError::SerializationFailure));
+        assert_eq!(CodedPiece::from_flagged_payload(&[2; 9], 4, 4), Err(RLNCError::SerializationFailure));
+        assert_eq!(CodedPiece::from_flagged_payload(&[0; 9], 0, 4), Err(RLNCError::PieceCountZero));
+        assert_eq!(CodedPiece::from_flagged_payload(&[0; 9], 4, 0), Err(RLNCError::PieceLengthZero));
+        assert_eq!(CodedPiece::from_flagged_payload(&[0; 8], 4, 4), Err(RLNCError::InvalidPieceLength));
+        assert_eq!(
+            CodedPiece::from_flagged_payload(&[1, 0, 0, 0, 4, 9, 9, 9, 9], 4, 4),
+            Err(RLNCError::InvalidPieceIndex)
+        );
+        assert_eq!(
+            CodedPiece::from_flagged_payload(&[1, 0, 0, 0, 3, 9, 9, 9], 4, 4),
+            Err(RLNCError::InvalidPieceLength)
+        );
+    }
+}
@@ -0,0 +1,332 @@
+use super::{consts::BOUNDARY_MARKER, params::Params};
+use crate::{RLNCError, common::simd::gf256_mul_vec_by_scalar_then_add_into_vec};
+use rand::Rng;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Debug,
+    io,
+};
+
+/// Source of the original data, which `LazyEncoder` fetches chunks of, on demand, say a network-backed blob or an object in a bucket.
+pub trait ChunkProvider: Debug + Send + Sync {
+    /// Byte length of the whole original data.
+    fn byte_len(&self) -> usize;
+
+    /// Reads `buf.len()` bytes of original data, starting at byte `offset`, into `buf`. It's never asked for bytes past `self.byte_len()`.
+    fn read_chunk(&self, offset: usize, buf: &mut [u8]) -> io::Result<()>;
+}
+
+/// Original data, already in memory, meant for testing.
+impl ChunkProvider for Vec<u8> {
+    fn byte_len(&self) -> usize {
+        self.len()
+    }
+
+    fn read_chunk(&self, offset: usize, buf: &mut [u8]) -> io::Result<()> {
+        let chunk = self.get(offset..offset + buf.len()).ok_or(io::ErrorKind::UnexpectedEof)?;
+        buf.copy_from_slice(chunk);
+        Ok(())
+    }
+}
+
+/// Original data, in a file, read using positioned reads, so that the file is shared among threads.
+#[cfg(unix)]
+impl ChunkProvider for std::fs::File {
+    fn byte_len(&self) -> usize {
+        self.metadata().map(|metadata| metadata.len() as usize).unwrap_or_default()
+    }
+
+    fn read_chunk(&self, offset: usize, buf: &mut [u8]) -> io::Result<()> {
+        std::os::unix::fs::FileExt::read_exact_at(self, buf, offset as u64)
+    }
+}
+
+/// Random Linear Network Coding (RLNC) Encoder, which never holds the whole original data. Source pieces are fetched through a
+/// `ChunkProvider`, as coding vectors ask for them, and kept in a least recently used cache of bounded number of pieces.
+///
+/// Coded pieces are identical to the ones produced by `Encoder`, given the same data and coding vector, so receivers can't tell them
+/// apart. Coding can start before the whole object is local. Each coded piece needs all source pieces with non-zero coefficient, so if
+/// the cache can't hold all `k` pieces, cached ones are used first and at max `k - cache capacity` pieces are fetched per coded piece.
+#[derive(Debug)]
+pub struct LazyEncoder<P: ChunkProvider> {
+    provider: P,
+    params: Params,
+    data_byte_len: usize,
+    cache_capacity: usize,
+    /// Cached source pieces, along with the tick, they were last used at.
+    cache: HashMap<usize, (u64, Vec<u8>)>,
+    /// Indices of cached source pieces, keyed by the tick, they were last used at, so that the least recently used one is the first.
+    last_uses: BTreeMap<u64, usize>,
+    tick: u64,
+    fetched_piece_count: usize,
+}
+
+impl<P: ChunkProvider> LazyEncoder<P> {
+    /// Creates an encoder over given provider, splitting the original data into `piece_count` pieces, padded the way `Encoder::new`
+    /// does. Nothing is fetched yet.
+    ///
+    /// # Arguments
+    /// * `provider` - Source of the original data.
+    /// * `piece_count` - Number of pieces, original data gets split into.
+    /// * `cache_capacity` - Maximum number of source pieces kept in the cache.
+    ///
+    /// # Returns
+    /// * Returns `Ok(LazyEncoder)` on success.
+    /// * Returns `Err(RLNCError::DataLengthZero)` if the provider holds no data.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `piece_count` is zero.
+    /// * Returns `Err(RLNCError::InvalidCacheCapacity)` if `cache_capacity` is zero.
+    pub fn new(provider: P, piece_count: usize, cache_capacity: usize) -> Result<LazyEncoder<P>, RLNCError> {
+        let data_byte_len = provider.byte_len();
+        let params = Params::from_data_len(data_byte_len, piece_count)?;
+        if cache_capacity == 0 {
+            return Err(RLNCError::InvalidCacheCapacity);
+        }
+
+        Ok(LazyEncoder {
+            provider,
+            params,
+            data_byte_len,
+            cache_capacity,
+            cache: HashMap::with_capacity(cache_capacity.min(piece_count)),
+            last_uses: BTreeMap::new(),
+            tick: 0,
+            fetched_piece_count: 0,
+        })
+    }
+
+    /// RLNC configuration of this encoder, to be shared with recoders and decoders.
+    pub fn get_params(&self) -> Params {
+        self.params
+    }
+
+    /// Number of source pieces fetched from the provider so far, counting each re-fetch, after eviction.
+    pub fn get_fetched_piece_count(&self) -> usize {
+        self.fetched_piece_count
+    }
+
+    /// Number of source pieces currently cached.
+    pub fn get_cached_piece_count(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Returns `true` if the source piece at given index is cached.
+    pub fn is_cached(&self, index: usize) -> bool {
+        self.cache.contains_key(&index)
+    }
+
+    /// Fetches the source piece at given index, padding it with the boundary marker and zeros, if it's past the end of original data.
+    fn fetch_piece(&mut self, index: usize, piece: &mut [u8]) -> Result<(), RLNCError> {
+        let offset = index * self.params.get_piece_byte_len();
+        let fetched_byte_len = self.data_byte_len.saturating_sub(offset).min(piece.len());
+
+        // Trailing pieces, holding padding only, may start past the end of data, so nothing is read for them.
+        piece.fill(0);
+        if fetched_byte_len > 0 {
+            self.provider
+                .read_chunk(offset, &mut piece[..fetched_byte_len])
+                .map_err(|_| RLNCError::ChunkFetchFailure)?;
+        }
+        if let Some(marker) = (self.data_byte_len >= offset)
+            .then(|| self.data_byte_len - offset)
+            .and_then(|index| piece.get_mut(index))
+        {
+            *marker = BOUNDARY_MARKER;
+        }
+
+        self.fetched_piece_count += 1;
+        Ok(())
+    }
+
+    /// Returns the source piece at given index, fetching it if not cached, evicting the least recently used piece, if the cache is full.
+    fn cached_piece(&mut self, index: usize) -> Result<&[u8], RLNCError> {
+        self.tick += 1;
+        let tick = self.tick;
+
+        if let Some((last_used, _)) = self.cache.get_mut(&index) {
+            self.last_uses.remove(last_used);
+            *last_used = tick;
+        } else {
+            let mut piece = match self.last_uses.first_key_value() {
+                Some((_, &evicted)) if self.cache.len() >= self.cache_capacity => {
+                    let (last_used, piece) = unsafe { self.cache.remove(&evicted).unwrap_unchecked() };
+                    self.last_uses.remove(&last_used);
+                    piece
+                }
+                _ => vec![0u8; self.params.get_piece_byte_len()],
+            };
+
+            self.fetch_piece(index, &mut piece)?;
+            self.cache.insert(index, (tick, piece));
+        }
+        self.last_uses.insert(tick, index);
+
+        Ok(unsafe { &self.cache.get(&index).unwrap_unchecked().1 })
+    }
+
+    /// Returns the source piece at given index, as it is after padding the original data, fetching it if not cached.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Vec<u8>)` of length `self.get_params().get_piece_byte_len()` on success.
+    /// * Returns `Err(RLNCError::InvalidPieceIndex)` if `index` is not less than piece count.
+    /// * Returns `Err(RLNCError::ChunkFetchFailure)` if the provider fails to read the piece.
+    pub fn source_piece(&mut self, index: usize) -> Result<Vec<u8>, RLNCError> {
+        if index >= self.params.get_piece_count() {
+            return Err(RLNCError::InvalidPieceIndex);
+        }
+
+        self.cached_piece(index).map(|piece| piece.to_vec())
+    }
+
+    /// Erasure codes the original data using a provided coding vector, fetching source pieces with non-zero coefficient, which
+    /// are not cached. Cached pieces are used first, so that they aren't evicted by the ones fetched for this coded piece.
+    ///
+    /// # Arguments
+    /// * `coding_vector` - Coding vector to be used for preparing a new coded piece.
+    /// * `coded_data` - A mutable slice to write the coded data into.
+    ///
+    /// # Returns
+    /// * Returns `Ok(())` on success.
+    /// * Returns `Err(RLNCError::CodingVectorLengthMismatch)` if the length of `coding_vector` is not piece count.
+    /// * Returns `Err(RLNCError::InvalidOutputBuffer)` if the length of `coded_data` is not piece byte length.
+    /// * Returns `Err(RLNCError::ChunkFetchFailure)` if the provider fails to read some source piece.
+    pub fn code_with_coding_vector(&mut self, coding_vector: &[u8], coded_data: &mut [u8]) -> Result<(), RLNCError> {
+        if coding_vector.len() != self.params.get_piece_count() {
+            return Err(RLNCError::CodingVectorLengthMismatch);
+        }
+        if coded_data.len() != self.params.get_piece_byte_len() {
+            return Err(RLNCError::InvalidOutputBuffer);
+        }
+
+        let mut indices = (0..coding_vector.len()).filter(|&index| coding_vector[index] != 0).collect::<Vec<_>>();
+        indices.sort_by_key(|index| !self.cache.contains_key(index));
+
+        coded_data.fill(0);
+        for index in indices {
+            let piece = self.cached_piece(index)?;
+            gf256_mul_vec_by_scalar_then_add_into_vec(coded_data, piece, coding_vector[index]);
+        }
+
+        Ok(())
+    }
+
+    /// Produces a new coded piece, random sampling coding coefficients, same as `Encoder::code`.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Vec<u8>)` holding the full coded piece.
+    /// * Returns `Err(RLNCError::ChunkFetchFailure)` if the provider fails to read some source piece.
+    pub fn code<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Result<Vec<u8>, RLNCError> {
+        let mut full_coded_piece = vec![0u8; self.params.get_full_coded_piece_byte_len()];
+        let (coding_vector, coded_data) = full_coded_piece.split_at_mut(self.params.get_piece_count());
+
+        rng.fill_bytes(coding_vector);
+        self.code_with_coding_vector(coding_vector, coded_data)?;
+
+        Ok(full_coded_piece)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChunkProvider, LazyEncoder};
+    use crate::{
+        RLNCError,
+        full::{Decoder, Encoder},
+    };
+    use rand::Rng;
+    use std::io;
+
+    #[test]
+    fn test_lazy_encoder_matches_encoder() {
+        let mut rng = rand::rng();
+
+        let data = (0..5_000).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 16).expect("Failed to create Encoder");
+        let mut lazy_encoder = LazyEncoder::new(data.clone(), 16, 4).expect("Failed to create LazyEncoder");
+        assert_eq!(lazy_encoder.get_params(), encoder.get_params());
+
+        for index in 0..16 {
+            assert_eq!(lazy_encoder.source_piece(index).unwrap(), encoder.source_piece(index).unwrap());
+        }
+        assert_eq!(lazy_encoder.get_cached_piece_count(), 4);
+        assert!((12..16).all(|index| lazy_encoder.is_cached(index)));
+
+        // Cached pieces are used first, so only the others are fetched.
+        let mut coding_vector = [1u8; 16];
+        let mut coded_data = vec![0u8; encoder.get_piece_byte_len()];
+        let mut expected_coded_data = coded_data.clone();
+
+        lazy_encoder.code_with_coding_vector(&coding_vector, &mut coded_data).unwrap();
+        encoder.code_with_coding_vector(&coding_vector, &mut expected_coded_data).unwrap();
+        assert_eq!(coded_data, expected_coded_data);
+        assert_eq!(lazy_encoder.get_fetched_piece_count(), 16 + 12);
+
+        coding_vector[..12].fill(0);
+        lazy_encoder.code_with_coding_vector(&coding_vector, &mut coded_data).unwrap();
+        assert_eq!(lazy_encoder.get_fetched_piece_count(), 16 + 12 + 4);
+
+        let mut decoder = Decoder::new_with(lazy_encoder.get_params());
+        while !decoder.is_already_decoded() {
+            let _ = decoder.decode(&lazy_encoder.code(&mut rng).unwrap());
+        }
+        assert_eq!(decoder.get_decoded_data(), Ok(data));
+    }
+
+    #[test]
+    fn test_lazy_encoder_evicts_least_recently_used_piece() {
+        let mut lazy_encoder = LazyEncoder::new(vec![7u8; 1_000], 8, 3).expect("Failed to create LazyEncoder");
+
+        for index in [0, 1, 2, 0, 3, 4, 0, 5] {
+            lazy_encoder.source_piece(index).unwrap();
+        }
+        assert_eq!(lazy_encoder.get_fetched_piece_count(), 6);
+        assert_eq!(lazy_encoder.get_cached_piece_count(), 3);
+        assert!([0, 4, 5].into_iter().all(|index| lazy_encoder.is_cached(index)));
+        assert_eq!(lazy_encoder.last_uses.len(), 3);
+    }
+
+    #[test]
+    fn test_lazy_encoder_padding_only_pieces() {
+        let mut rng = rand::rng();
+
+        // Tiny data leaves trailing source pieces, starting past the end of data, holding padding only.
+        for (data_len, piece_count) in [(1, 4), (2, 8), (3, 5)] {
+            let data = (0..data_len).map(|_| rng.random()).collect::<Vec<u8>>();
+            let encoder = Encoder::new(data.clone(), piece_count).expect("Failed to create Encoder");
+            let mut lazy_encoder = LazyEncoder::new(data.clone(), piece_count, 2).expect("Failed to create LazyEncoder");
+
+            for index in 0..piece_count {
+                assert_eq!(lazy_encoder.source_piece(index).unwrap(), encoder.source_piece(index).unwrap());
+            }
+
+            let mut decoder = Decoder::new_with(lazy_encoder.get_params());
+            while !decoder.is_already_decoded() {
+                let _ = decoder.decode(&lazy_encoder.code(&mut rng).unwrap());
+            }
+            assert_eq!(decoder.get_decoded_data(), Ok(data));
+        }
+    }
+
+    #[derive(Debug)]
+    struct FailingProvider;
+
+    impl ChunkProvider for FailingProvider {
+        fn byte_len(&self) -> usize {
+            100
+        }
+
+        fn read_chunk(&self, _: usize, _: &mut [u8]) -> io::Result<()> {
+            Err(io::ErrorKind::ConnectionReset.into())
+        }
+    }
+
+    #[test]
+    fn test_lazy_encoder_errors() {
+        assert!(matches!(LazyEncoder::new(Vec::new(), 4, 1), Err(RLNCError::DataLengthZero)));
+        assert!(matches!(LazyEncoder::new(vec![1; 100], 4, 0), Err(RLNCError::InvalidCacheCapacity)));
+
+        let mut lazy_encoder = LazyEncoder::new(FailingProvider, 4, 2).unwrap();
+        assert_eq!(lazy_encoder.code(&mut rand::rng()), Err(RLNCError::ChunkFetchFailure));
+        assert_eq!(lazy_encoder.source_piece(4), Err(RLNCError::InvalidPieceIndex));
+        assert_eq!(lazy_encoder.get_cached_piece_count(), 0);
+    }
+}
@@ -0,0 +1,346 @@
+use super::{decoder::Decoder, decoder_matrix::ROW_ALIGNMENT, params::Params};
+use crate::RLNCError;
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::Hash,
+    time::{Duration, Instant},
+};
+
+/// Identifies a generation being decoded by `DecoderManager`, as the `generation`-th generation of an object, uploaded by a peer i.e. tenant.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DecoderKey<P> {
+    /// Peer, uploading the object.
+    pub peer: P,
+    /// Identifier of the object, the generation belongs to.
+    pub object_id: String,
+    /// Index of the generation, within its object.
+    pub generation: usize,
+}
+
+/// Counters of a single tenant, kept by `DecoderManager`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TenantStats {
+    /// Number of decoders currently held, decoded ones, which are not yet taken, included.
+    pub decoder_count: usize,
+    /// Bytes of memory budget currently reserved by decoders of this tenant.
+    pub reserved_byte_len: usize,
+    /// Number of pieces received so far, useful or not.
+    pub received_piece_count: usize,
+    /// Number of useful pieces received so far.
+    pub useful_piece_count: usize,
+    /// Number of generations fully decoded so far.
+    pub decoded_generation_count: usize,
+    /// Number of generations evicted before being decoded, either to make room or for being stalled.
+    pub evicted_generation_count: usize,
+}
+
+/// Position of a decoder, not yet decoded, in the eviction order of `DecoderManager`. It's the instant it last made progress,
+/// ties broken by the order they made progress in.
+type ProgressKey = (Instant, u64);
+
+/// A managed decoder, along with its memory reservation and its position in the eviction order.
+#[derive(Clone, Debug)]
+struct ManagedDecoder {
+    decoder: Decoder,
+    reserved_byte_len: usize,
+    last_progress: ProgressKey,
+}
+
+/// Decoders of many generations, uploaded by many peers, sharing a global memory budget, say in a server accepting RLNC uploads
+/// from thousands of clients.
+///
+/// A decoder is set up on the first piece of a generation, given that piece is well-formed, reserving memory of its coefficient
+/// matrix in the worst case, which is `k` full coded pieces, each padded to a multiple of 64 bytes, as the matrix stores rows. When the budget doesn't have room for a new one, generations which are not yet decoded are evicted, least
+/// recently progressed first, where progress means receiving a useful piece. Decoded generations are never evicted, they are kept,
+/// till taken out using `Self::take_decoded_data`. Generations which made no progress for a while, can be evicted using
+/// `Self::evict_stalled`. Counters are kept per tenant i.e. peer, see `TenantStats`.
+#[derive(Clone, Debug)]
+pub struct DecoderManager<P: Clone + Eq + Hash> {
+    memory_budget: usize,
+    reserved_byte_len: usize,
+    decoders: HashMap<DecoderKey<P>, ManagedDecoder>,
+    /// Generations not yet decoded, least recently progressed first.
+    eviction_order: BTreeMap<ProgressKey, DecoderKey<P>>,
+    progress_count: u64,
+    tenants: HashMap<P, TenantStats>,
+}
+
+impl<P: Clone + Eq + Hash> DecoderManager<P> {
+    /// Creates a manager with no decoders, which can reserve at max `memory_budget` bytes, for all decoders together.
+    pub fn new(memory_budget: usize) -> DecoderManager<P> {
+        DecoderManager {
+            memory_budget,
+            reserved_byte_len: 0,
+            decoders: HashMap::new(),
+            eviction_order: BTreeMap::new(),
+            progress_count: 0,
+            tenants: HashMap::new(),
+        }
+    }
+
+    /// Memory budget, in bytes, shared by all decoders.
+    pub fn get_memory_budget(&self) -> usize {
+        self.memory_budget
+    }
+
+    /// Bytes of memory budget, currently reserved by all decoders together.
+    pub fn get_reserved_byte_len(&self) -> usize {
+        self.reserved_byte_len
+    }
+
+    /// Number of decoders currently held, decoded ones, which are not yet taken, included.
+    pub fn get_decoder_count(&self) -> usize {
+        self.decoders.len()
+    }
+
+    /// Decoder of given generation, if it's held.
+    pub fn get_decoder(&self, key: &DecoderKey<P>) -> Option<&Decoder> {
+        self.decoders.get(key).map(|managed| &managed.decoder)
+    }
+
+    /// Counters of given tenant, if it has ever sent a piece.
+    pub fn get_tenant_stats(&self, peer: &P) -> Option<TenantStats> {
+        self.tenants.get(peer).copied()
+    }
+
+    /// Bytes of memory budget, a decoder of given RLNC configuration reserves, which is the byte length of `k` full coded pieces,
+    /// each padded to a multiple of the row alignment of decoder matrix.
+    fn reservation_byte_len(params: Params) -> usize {
+        params.get_piece_count() * params.get_full_coded_piece_byte_len().next_multiple_of(ROW_ALIGNMENT)
+    }
+
+    /// Next position in the eviction order, for a decoder making progress at given instant.
+    fn next_progress_key(&mut self, now: Instant) -> ProgressKey {
+        self.progress_count += 1;
+        (now, self.progress_count)
+    }
+
+    /// Drops the decoder of given generation, releasing its reservation.
+    fn remove(&mut self, key: &DecoderKey<P>) -> Option<ManagedDecoder> {
+        let managed = self.decoders.remove(key)?;
+
+        self.eviction_order.remove(&managed.last_progress);
+        self.reserved_byte_len -= managed.reserved_byte_len;
+        if let Some(stats) = self.tenants.get_mut(&key.peer) {
+            stats.decoder_count -= 1;
+            stats.reserved_byte_len -= managed.reserved_byte_len;
+        }
+
+        Some(managed)
+    }
+
+    /// Drops the decoder of given generation, which is not yet decoded, counting it as evicted.
+    fn evict(&mut self, key: &DecoderKey<P>) {
+        if self.remove(key).is_some()
+            && let Some(stats) = self.tenants.get_mut(&key.peer)
+        {
+            stats.evicted_generation_count += 1;
+        }
+    }
+
+    /// Feeds a full coded piece to the decoder of given generation, setting one up, if it's the first piece of the generation.
+    /// If the budget doesn't have room for a new decoder, generations not yet decoded are evicted, least recently progressed first.
+    ///
+    /// # Arguments
+    /// * `key` - Generation, the piece belongs to.
+    /// * `params` - RLNC configuration of the generation, used only when setting up its decoder.
+    /// * `full_coded_piece` - Coding vector followed by coded data.
+    /// * `now` - Instant the piece is received at.
+    ///
+    /// # Returns
+    /// * Returns `Err(RLNCError::InvalidPieceLength)` if it's the first piece of the generation and it's not of the length `params`
+    ///   ask for, in which case no decoder is set up and nothing is evicted.
+    /// * Returns `Err(RLNCError::PieceNotUseful)` if it's the first piece of the generation and its coding vector is all-zero, in
+    ///   which case no decoder is set up and nothing is evicted.
+    /// * Returns `Err(RLNCError::MemoryBudgetExceeded)` if even after evicting all generations not yet decoded, there's no room for a new decoder.
+    /// * Otherwise, returns same as `Decoder::decode`.
+    pub fn decode(&mut self, key: &DecoderKey<P>, params: Params, full_coded_piece: &[u8], now: Instant) -> Result<(), RLNCError> {
+        if !self.decoders.contains_key(key) {
+            if full_coded_piece.len() != params.get_full_coded_piece_byte_len() {
+                return Err(RLNCError::InvalidPieceLength);
+            }
+            if full_coded_piece[..params.get_piece_count()].iter().all(|&coeff| coeff == 0) {
+                return Err(RLNCError::PieceNotUseful);
+            }
+
+            let reserved_byte_len = Self::reservation_byte_len(params);
+
+            while self.reserved_byte_len + reserved_byte_len > self.memory_budget {
+                let victim = self
+                    .eviction_order
+                    .first_key_value()
+                    .map(|(_, victim)| victim.clone())
+                    .ok_or(RLNCError::MemoryBudgetExceeded)?;
+
+                self.evict(&victim);
+            }
+
+            let last_progress = self.next_progress_key(now);
+            self.eviction_order.insert(last_progress, key.clone());
+            self.decoders.insert(
+                key.clone(),
+                ManagedDecoder {
+                    decoder: Decoder::new_with(params),
+                    reserved_byte_len,
+                    last_progress,
+                },
+            );
+            self.reserved_byte_len += reserved_byte_len;
+
+            let stats = self.tenants.entry(key.peer.clone()).or_default();
+            stats.decoder_count += 1;
+            stats.reserved_byte_len += reserved_byte_len;
+        }
+
+        let next_progress = self.next_progress_key(now);
+        let managed = unsafe { self.decoders.get_mut(key).unwrap_unchecked() };
+        let result = managed.decoder.decode(full_coded_piece);
+
+        let stats = self.tenants.entry(key.peer.clone()).or_default();
+        stats.received_piece_count += 1;
+        if result.is_ok() {
+            self.eviction_order.remove(&managed.last_progress);
+            managed.last_progress = next_progress;
+            if managed.decoder.is_already_decoded() {
+                stats.decoded_generation_count += 1;
+            } else {
+                self.eviction_order.insert(next_progress, key.clone());
+            }
+            stats.useful_piece_count += 1;
+        }
+
+        result
+    }
+
+    /// Evicts generations, not yet decoded, which made no progress in given duration, returning their keys.
+    pub fn evict_stalled(&mut self, stalled_for: Duration, now: Instant) -> Vec<DecoderKey<P>> {
+        let stalled = self
+            .eviction_order
+            .iter()
+            .take_while(|((last_progress_at, _), _)| now.saturating_duration_since(*last_progress_at) >= stalled_for)
+            .map(|(_, key)| key.clone())
+            .collect::<Vec<_>>();
+
+        stalled.iter().for_each(|key| self.evict(key));
+        stalled
+    }
+
+    /// Takes the decoded data of given generation out, releasing its reservation.
+    ///
+    /// # Returns
+    /// * Returns `None` if no decoder is held for the generation or it's not yet decoded, in which case it's kept.
+    /// * Otherwise, returns same as `Decoder::get_decoded_data`.
+    pub fn take_decoded_data(&mut self, key: &DecoderKey<P>) -> Option<Result<Vec<u8>, RLNCError>> {
+        if !self.decoders.get(key)?.decoder.is_already_decoded() {
+            return None;
+        }
+
+        self.remove(key).map(|managed| managed.decoder.get_decoded_data())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DecoderKey, DecoderManager};
+    use crate::{RLNCError, full::Encoder};
+    use std::time::{Duration, Instant};
+
+    fn key(peer: u32, generation: usize) -> DecoderKey<u32> {
+        DecoderKey {
+            peer,
+            object_id: "object".to_string(),
+            generation,
+        }
+    }
+
+    #[test]
+    fn test_decoder_manager_budget_and_eviction() {
+        let mut rng = rand::rng();
+        let start = Instant::now();
+
+        let data = (0..=255).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 4).expect("Failed to create Encoder for decoder manager test");
+        let params = encoder.get_params();
+        let reservation = 4 * params.get_full_coded_piece_byte_len().next_multiple_of(64);
+
+        // Budget fits two decoders.
+        let mut manager = DecoderManager::new(2 * reservation + 1);
+
+        manager.decode(&key(1, 0), params, &encoder.code(&mut rng), start).unwrap();
+        manager
+            .decode(&key(2, 0), params, &encoder.code(&mut rng), start + Duration::from_secs(1))
+            .unwrap();
+        assert_eq!(manager.get_reserved_byte_len(), 2 * reservation);
+
+        // Peer 1 is the least recently progressed, so it makes room for peer 3.
+        manager
+            .decode(&key(3, 0), params, &encoder.code(&mut rng), start + Duration::from_secs(2))
+            .unwrap();
+        assert!(manager.get_decoder(&key(1, 0)).is_none());
+        assert_eq!(
+            manager.get_tenant_stats(&1).map(|stats| (stats.decoder_count, stats.evicted_generation_count)),
+            Some((0, 1))
+        );
+
+        let mut now = start + Duration::from_secs(3);
+        while manager.take_decoded_data(&key(2, 0)).is_none() {
+            let _ = manager.decode(&key(2, 0), params, &encoder.code(&mut rng), now);
+            now += Duration::from_millis(1);
+        }
+        assert_eq!(manager.get_decoder_count(), 1);
+
+        let stats = manager.get_tenant_stats(&2).unwrap();
+        assert_eq!((stats.useful_piece_count, stats.decoded_generation_count, stats.reserved_byte_len), (4, 1, 0));
+
+        assert_eq!(manager.evict_stalled(Duration::from_secs(60), now), []);
+        assert_eq!(manager.evict_stalled(Duration::from_secs(1), now), [key(3, 0)]);
+        assert_eq!(manager.get_reserved_byte_len(), 0);
+    }
+
+    #[test]
+    fn test_decoder_manager_keeps_decoded_generations() {
+        let mut rng = rand::rng();
+        let now = Instant::now();
+
+        let data = (0..=255).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 4).expect("Failed to create Encoder for decoder manager test");
+        let params = encoder.get_params();
+
+        let mut manager = DecoderManager::new(4 * params.get_full_coded_piece_byte_len().next_multiple_of(64));
+        while !manager.get_decoder(&key(1, 0)).is_some_and(|decoder| decoder.is_already_decoded()) {
+            let _ = manager.decode(&key(1, 0), params, &encoder.code(&mut rng), now);
+        }
+
+        assert_eq!(
+            manager.decode(&key(1, 1), params, &encoder.code(&mut rng), now),
+            Err(RLNCError::MemoryBudgetExceeded)
+        );
+        assert_eq!(manager.take_decoded_data(&key(1, 0)), Some(Ok(data)));
+        assert!(manager.decode(&key(1, 1), params, &encoder.code(&mut rng), now).is_ok());
+    }
+
+    #[test]
+    fn test_decoder_manager_rejects_malformed_first_piece() {
+        let mut rng = rand::rng();
+        let now = Instant::now();
+
+        let data = (0..=255).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data, 4).expect("Failed to create Encoder for decoder manager test");
+        let params = encoder.get_params();
+
+        let mut manager = DecoderManager::new(4 * params.get_full_coded_piece_byte_len().next_multiple_of(64));
+        manager.decode(&key(1, 0), params, &encoder.code(&mut rng), now).unwrap();
+
+        let piece = encoder.code(&mut rng);
+        assert_eq!(manager.decode(&key(2, 0), params, &piece[1..], now), Err(RLNCError::InvalidPieceLength));
+
+        let mut zero_piece = piece.clone();
+        zero_piece[..4].fill(0);
+        assert_eq!(manager.decode(&key(2, 0), params, &zero_piece, now), Err(RLNCError::PieceNotUseful));
+
+        // Neither set up a decoder, nor evicted the one of peer 1.
+        assert!(manager.get_decoder(&key(1, 0)).is_some());
+        assert_eq!(manager.get_decoder_count(), 1);
+        assert_eq!(manager.get_tenant_stats(&2), None);
+    }
+}
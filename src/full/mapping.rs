@@ -0,0 +1,203 @@
+use std::{fmt::Debug, ops::Range};
+
+/// Strategy deciding how bytes of a long object map to generations, each of which is coded independently.
+///
+/// Implementors only need to describe which byte ranges of the object belong to a generation, in order. Gathering
+/// generation data on the sender side and scattering decoded generation data back into the object on the receiver
+/// side is built on top of it. Contiguous blocks suit sequential streaming, while interleaved striding spreads a burst
+/// of lost bytes over many generations and size-capped mapping keeps generations equally sized.
+pub trait GenerationMapping: Debug + Send + Sync {
+    /// Number of generations, an object of `data_len` bytes gets split into.
+    fn generation_count(&self, data_len: usize) -> usize;
+
+    /// Byte ranges of an object of `data_len` bytes, which belong to given generation, in order.
+    /// Ranges of all generations must be disjoint and cover the whole object.
+    fn byte_ranges(&self, data_len: usize, generation: usize) -> Vec<Range<usize>>;
+
+    /// Byte length of given generation.
+    fn generation_byte_len(&self, data_len: usize, generation: usize) -> usize {
+        self.byte_ranges(data_len, generation).iter().map(|range| range.len()).sum()
+    }
+
+    /// Byte length of the largest generation, which decides RLNC configuration shared by all generations.
+    fn max_generation_byte_len(&self, data_len: usize) -> usize {
+        (0..self.generation_count(data_len))
+            .map(|generation| self.generation_byte_len(data_len, generation))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Collects bytes of given generation out of the object.
+    fn gather(&self, data: &[u8], generation: usize) -> Vec<u8> {
+        self.byte_ranges(data.len(), generation)
+            .into_iter()
+            .flat_map(|range| data[range].iter().copied())
+            .collect()
+    }
+
+    /// Writes decoded bytes of given generation back into their place in the object, which is the inverse of `Self::gather`.
+    /// It does nothing, if `generation_data` doesn't have the expected length.
+    fn scatter(&self, generation_data: &[u8], generation: usize, data: &mut [u8]) {
+        if generation_data.len() != self.generation_byte_len(data.len(), generation) {
+            return;
+        }
+
+        let mut offset = 0;
+        for range in self.byte_ranges(data.len(), generation) {
+            let range_byte_len = range.len();
+            data[range].copy_from_slice(&generation_data[offset..offset + range_byte_len]);
+            offset += range_byte_len;
+        }
+    }
+}
+
+/// Maps consecutive blocks of `generation_byte_len` bytes to consecutive generations, the last one can be shorter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ContiguousMapping {
+    generation_byte_len: usize,
+}
+
+impl ContiguousMapping {
+    /// Byte length of each generation must be non-zero, otherwise there are no generations.
+    pub fn new(generation_byte_len: usize) -> ContiguousMapping {
+        ContiguousMapping { generation_byte_len }
+    }
+}
+
+impl GenerationMapping for ContiguousMapping {
+    fn generation_count(&self, data_len: usize) -> usize {
+        if self.generation_byte_len == 0 {
+            return 0;
+        }
+
+        data_len.div_ceil(self.generation_byte_len)
+    }
+
+    fn byte_ranges(&self, data_len: usize, generation: usize) -> Vec<Range<usize>> {
+        let begin = (generation * self.generation_byte_len).min(data_len);
+        let end = (begin + self.generation_byte_len).min(data_len);
+
+        std::iter::once(begin..end).collect()
+    }
+}
+
+/// Splits the object into blocks of `stride_byte_len` bytes, dealing them out to `generation_count` generations in
+/// round-robin fashion, so that block `i` belongs to generation `i % generation_count`. A burst of consecutive bytes
+/// lost on the way is spread over many generations.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InterleavedMapping {
+    generation_count: usize,
+    stride_byte_len: usize,
+}
+
+impl InterleavedMapping {
+    /// Both number of generations and stride byte length must be non-zero, otherwise there are no generations.
+    pub fn new(generation_count: usize, stride_byte_len: usize) -> InterleavedMapping {
+        InterleavedMapping {
+            generation_count,
+            stride_byte_len,
+        }
+    }
+}
+
+impl GenerationMapping for InterleavedMapping {
+    fn generation_count(&self, data_len: usize) -> usize {
+        if self.stride_byte_len == 0 {
+            return 0;
+        }
+
+        // Short objects don't fill all generations.
+        self.generation_count.min(data_len.div_ceil(self.stride_byte_len))
+    }
+
+    fn byte_ranges(&self, data_len: usize, generation: usize) -> Vec<Range<usize>> {
+        if self.stride_byte_len == 0 || self.generation_count == 0 {
+            return Vec::new();
+        }
+
+        (generation * self.stride_byte_len..data_len)
+            .step_by(self.generation_count * self.stride_byte_len)
+            .map(|begin| begin..(begin + self.stride_byte_len).min(data_len))
+            .collect()
+    }
+
+    fn max_generation_byte_len(&self, data_len: usize) -> usize {
+        // The first generation gets the most blocks. If the last, partial block belongs to it, it has one more block than the rest.
+        self.generation_byte_len(data_len, 0)
+    }
+}
+
+/// Splits the object into as few contiguous generations as possible, such that none is longer than `max_generation_byte_len`
+/// bytes, while keeping them equally sized, so that there is no tiny last generation, paying the full coding overhead.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SizeCappedMapping {
+    max_generation_byte_len: usize,
+}
+
+impl SizeCappedMapping {
+    /// Maximum byte length of a generation must be non-zero, otherwise there are no generations.
+    pub fn new(max_generation_byte_len: usize) -> SizeCappedMapping {
+        SizeCappedMapping { max_generation_byte_len }
+    }
+
+    /// Equal byte length of all generations, but possibly the last one.
+    fn balanced_generation_byte_len(&self, data_len: usize) -> usize {
+        if self.max_generation_byte_len == 0 || data_len == 0 {
+            return 0;
+        }
+
+        data_len.div_ceil(data_len.div_ceil(self.max_generation_byte_len))
+    }
+}
+
+impl GenerationMapping for SizeCappedMapping {
+    fn generation_count(&self, data_len: usize) -> usize {
+        ContiguousMapping::new(self.balanced_generation_byte_len(data_len)).generation_count(data_len)
+    }
+
+    fn byte_ranges(&self, data_len: usize, generation: usize) -> Vec<Range<usize>> {
+        ContiguousMapping::new(self.balanced_generation_byte_len(data_len)).byte_ranges(data_len, generation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ContiguousMapping, GenerationMapping, InterleavedMapping, SizeCappedMapping};
+
+    #[test]
+    fn test_generation_mappings_cover_object() {
+        let data_len = 1000;
+        let data = (0..data_len).map(|i| i as u8).collect::<Vec<u8>>();
+
+        let mappings: [(&dyn GenerationMapping, usize, usize); 4] = [
+            (&ContiguousMapping::new(300), 4, 300),
+            (&InterleavedMapping::new(3, 64), 3, 5 * 64 + 40),
+            (&InterleavedMapping::new(100, 64), 16, 64),
+            (&SizeCappedMapping::new(300), 4, 250),
+        ];
+
+        for (mapping, expected_generation_count, expected_max_generation_byte_len) in mappings {
+            assert_eq!(mapping.generation_count(data_len), expected_generation_count);
+            assert_eq!(mapping.max_generation_byte_len(data_len), expected_max_generation_byte_len);
+
+            // Each byte belongs to exactly one generation.
+            let mut ownership = vec![0usize; data_len];
+            for generation in 0..mapping.generation_count(data_len) {
+                mapping
+                    .byte_ranges(data_len, generation)
+                    .into_iter()
+                    .flatten()
+                    .for_each(|idx| ownership[idx] += 1);
+            }
+            assert!(ownership.iter().all(|&count| count == 1));
+
+            // Scattering gathered generations reassembles the object.
+            let mut reassembled = vec![0u8; data_len];
+            for generation in 0..mapping.generation_count(data_len) {
+                let generation_data = mapping.gather(&data, generation);
+                mapping.scatter(&generation_data, generation, &mut reassembled);
+            }
+            assert_eq!(reassembled, data);
+        }
+    }
+}
@@ -1,11 +1,109 @@
-mod consts;
+#[cfg(feature = "parallel")]
+mod affinity;
+#[cfg(feature = "futures")]
+mod async_reader;
+mod audit;
+mod bounded;
+mod builder;
+#[cfg(feature = "postcard")]
+mod compact;
+pub(crate) mod consts;
+mod control;
+mod cooperative;
+#[cfg(feature = "debug-tools")]
+mod debug;
 mod decoder;
 mod decoder_matrix;
+mod dtn;
 mod encoder;
+mod eta;
+mod expanding;
+mod flagged;
+mod lazy;
+mod manager;
+mod mapping;
+mod nibble;
+#[cfg(all(feature = "numa", target_os = "linux"))]
+mod numa;
+mod object_store;
+mod params;
+mod piece;
+mod pipeline;
+mod plan;
+mod prefilter;
+mod product;
+#[cfg(feature = "protobuf")]
+pub mod proto;
 mod recoder;
+mod repair;
+mod sampler;
+mod scrub;
+#[cfg(feature = "seed")]
+mod seed;
+mod seeded;
+mod session;
+#[cfg(feature = "futures")]
+mod sink;
+mod snapshot;
+mod sparse_decoder;
+mod storage;
 
 mod tests;
+mod ttl;
+mod vectored;
+mod watermark;
+mod wire;
 
-pub use decoder::Decoder;
-pub use encoder::Encoder;
+#[cfg(feature = "parallel")]
+pub use affinity::{WorkerPoolConfig, pin_current_thread};
+#[cfg(feature = "futures")]
+pub use async_reader::{AsyncPieceReader, DecodeEvent};
+pub use audit::{AuditLog, RejectedPiece, RejectionReason};
+pub use bounded::BoundedRecoder;
+pub use builder::EncoderBuilder;
+pub use control::ControlMessage;
+#[cfg(feature = "rkyv")]
+pub use decoder::ArchivedPayloadLayout;
+pub use decoder::{Decoder, DecoderRef, PayloadLayout};
+pub use dtn::{DtnProfile, DtnSender, ExpiringPiece};
+pub use encoder::{Encoder, EncoderRef};
+pub use eta::CompletionEstimate;
+pub use expanding::{ExpandingWindowDecoder, ExpandingWindowEncoder};
+pub use lazy::{ChunkProvider, LazyEncoder};
+pub use manager::{DecoderKey, DecoderManager, TenantStats};
+pub use mapping::{ContiguousMapping, GenerationMapping, InterleavedMapping, SizeCappedMapping};
+pub use nibble::{get_nibble_packed_piece_byte_len, nibble_pack_piece, nibble_unpack_piece};
+#[cfg(all(feature = "numa", target_os = "linux"))]
+pub use numa::get_numa_node_cpus;
+#[cfg(all(feature = "numa", target_os = "linux", feature = "parallel"))]
+pub use numa::numa_thread_pool;
+pub use object_store::{DirectoryTarget, MemoryTarget, ObjectManifest, ObjectStore, PieceKey, PieceLocation, StorageTarget};
+pub use params::Params;
+#[cfg(feature = "rkyv")]
+pub use piece::ArchivedCodedPiece;
+pub use piece::{CodedPiece, CodedPieceRef, PieceId};
+pub use pipeline::GenerationPipeline;
+pub use plan::{PieceDescriptor, PieceKind, PlanOrdering, SendPlan};
+pub use product::{ProductDecoder, ProductEncoder};
 pub use recoder::Recoder;
+pub use repair::{RecodeRequest, RepairPlan, SurvivingPiece};
+pub use sampler::AdaptiveSampler;
+pub use scrub::{GenerationCensus, GenerationHealth, PieceRepair, ScrubPlan};
+#[cfg(feature = "seed")]
+pub use seed::{GENERATION_SEED_LABEL, GenerationSeed, PIECE_SEED_LABEL, SessionSeed};
+pub use seeded::SeededPiece;
+pub use session::{NonZeroRng, SessionRng};
+#[cfg(feature = "futures")]
+pub use sink::{DecodeCompletion, DecoderSink};
+#[cfg(feature = "rkyv")]
+pub use snapshot::ArchivedDecoderSnapshot;
+pub use snapshot::DecoderSnapshot;
+pub use sparse_decoder::SparseDecoder;
+#[cfg(all(feature = "numa", target_os = "linux"))]
+pub use storage::HugePageStorage;
+#[cfg(feature = "mmap")]
+pub use storage::MmapStorage;
+pub use storage::{BufferStorage, PieceStorage};
+pub use ttl::{TtlPiece, TtlRecoder};
+pub use vectored::VectoredPiece;
+pub use wire::{FramedPiece, WIRE_FORMAT_VERSION, WIRE_HEADER_BYTE_LEN, WIRE_MAGIC};
@@ -0,0 +1,157 @@
+use crate::RLNCError;
+use rand::Rng;
+
+/// GF(2^4) subfield of GF(2^8), as a 4-bit vector space over GF(2), with basis `1, a, a^2, a^3`, where `a = 3^17` generates
+/// the subfield. Bit `i` of a nibble selects `a^i`, so that XOR-ing nibbles corresponds to adding subfield elements.
+const NIBBLE_TO_GF256: [u8; 16] = [0, 1, 225, 224, 92, 93, 189, 188, 12, 13, 237, 236, 80, 81, 177, 176];
+
+/// Inverse of `NIBBLE_TO_GF256`, mapping each GF(2^8) element, outside of the subfield, to `u8::MAX`.
+const GF256_TO_NIBBLE: [u8; 256] = {
+    let mut table = [u8::MAX; 256];
+
+    let mut nibble = 0;
+    while nibble < NIBBLE_TO_GF256.len() {
+        table[NIBBLE_TO_GF256[nibble] as usize] = nibble as u8;
+        nibble += 1;
+    }
+
+    table
+};
+
+/// Byte length of a nibble-packed piece i.e. `piece_count` coefficients packed two per byte, followed by `piece_byte_len` bytes of coded data.
+pub fn get_nibble_packed_piece_byte_len(piece_count: usize, piece_byte_len: usize) -> usize {
+    piece_count.div_ceil(2) + piece_byte_len
+}
+
+/// Samples a coding vector, with coefficients drawn uniformly from the GF(2^4) subfield, as GF(2^8) elements.
+pub(crate) fn sample_subfield_coding_vector<R: Rng + ?Sized>(rng: &mut R, coding_vector: &mut [u8]) {
+    rng.fill_bytes(coding_vector);
+    coding_vector.iter_mut().for_each(|coeff| *coeff = NIBBLE_TO_GF256[(*coeff & 0x0f) as usize]);
+}
+
+/// Packs coding vector of a full coded piece, two coefficients per byte, keeping coded data as is. Coefficient `2i` goes to the
+/// low nibble of byte `i` and coefficient `2i + 1` to its high nibble. With odd `piece_count`, the last high nibble is zero.
+///
+/// Coefficients must belong to the GF(2^4) subfield, which they do, if the piece is produced by `Encoder::code_nibble_packed` or
+/// `Recoder::recode_nibble_packed`, out of such pieces only. Subfield is closed under addition and multiplication, so recoding
+/// with subfield coefficients keeps coding vectors in it.
+///
+/// # Returns
+/// * Returns `Ok(Vec<u8>)` holding the nibble-packed piece.
+/// * Returns `Err(RLNCError::PieceLengthTooShort)` if `full_coded_piece` is not longer than `piece_count`.
+/// * Returns `Err(RLNCError::CoefficientNotInSubfield)` if some coefficient doesn't belong to the GF(2^4) subfield.
+pub fn nibble_pack_piece(full_coded_piece: &[u8], piece_count: usize) -> Result<Vec<u8>, RLNCError> {
+    if full_coded_piece.len() <= piece_count {
+        return Err(RLNCError::PieceLengthTooShort);
+    }
+
+    let (coding_vector, coded_data) = full_coded_piece.split_at(piece_count);
+    let mut packed_piece = Vec::with_capacity(get_nibble_packed_piece_byte_len(piece_count, coded_data.len()));
+
+    for pair in coding_vector.chunks(2) {
+        let mut packed = 0u8;
+        for (shift, &coeff) in [0, 4].into_iter().zip(pair) {
+            let nibble = GF256_TO_NIBBLE[coeff as usize];
+            if nibble == u8::MAX {
+                return Err(RLNCError::CoefficientNotInSubfield);
+            }
+
+            packed |= nibble << shift;
+        }
+
+        packed_piece.push(packed);
+    }
+    packed_piece.extend_from_slice(coded_data);
+
+    Ok(packed_piece)
+}
+
+/// Unpacks a piece, packed using `nibble_pack_piece`, back to a full coded piece, with one GF(2^8) coefficient per byte, as
+/// expected by `Decoder` and `Recoder`.
+///
+/// # Returns
+/// * Returns `Ok(Vec<u8>)` holding the full coded piece.
+/// * Returns `Err(RLNCError::PieceLengthTooShort)` if `packed_piece` is not longer than packed coding vector.
+/// * Returns `Err(RLNCError::CoefficientNotInSubfield)` if `piece_count` is odd, but the padding nibble is not zero.
+pub fn nibble_unpack_piece(packed_piece: &[u8], piece_count: usize) -> Result<Vec<u8>, RLNCError> {
+    let packed_coding_vector_byte_len = piece_count.div_ceil(2);
+    if packed_piece.len() <= packed_coding_vector_byte_len {
+        return Err(RLNCError::PieceLengthTooShort);
+    }
+
+    let (packed_coding_vector, coded_data) = packed_piece.split_at(packed_coding_vector_byte_len);
+    if piece_count % 2 == 1 && packed_coding_vector[packed_coding_vector_byte_len - 1] >> 4 != 0 {
+        return Err(RLNCError::CoefficientNotInSubfield);
+    }
+
+    let mut full_coded_piece = Vec::with_capacity(piece_count + coded_data.len());
+    full_coded_piece.extend(
+        packed_coding_vector
+            .iter()
+            .flat_map(|&packed| [packed & 0x0f, packed >> 4])
+            .take(piece_count)
+            .map(|nibble| NIBBLE_TO_GF256[nibble as usize]),
+    );
+    full_coded_piece.extend_from_slice(coded_data);
+
+    Ok(full_coded_piece)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NIBBLE_TO_GF256, get_nibble_packed_piece_byte_len, nibble_pack_piece, nibble_unpack_piece};
+    use crate::{
+        RLNCError,
+        common::gf256::Gf256,
+        full::{Decoder, Encoder, Recoder},
+    };
+
+    #[test]
+    fn test_nibble_table_is_subfield() {
+        for &a in &NIBBLE_TO_GF256 {
+            // Elements of GF(2^4) are exactly the roots of x^16 = x.
+            assert_eq!((0..4).fold(a, |x, _| Gf256::mul_const(x, x)), a);
+
+            for &b in &NIBBLE_TO_GF256 {
+                assert!(NIBBLE_TO_GF256.contains(&Gf256::mul_const(a, b)));
+                assert!(NIBBLE_TO_GF256.contains(&(a ^ b)));
+            }
+        }
+
+        assert_eq!(nibble_pack_piece(&[2, 0, 7], 2), Err(RLNCError::CoefficientNotInSubfield));
+        assert_eq!(nibble_unpack_piece(&[0xf0, 7], 1), Err(RLNCError::CoefficientNotInSubfield));
+        assert_eq!(nibble_unpack_piece(&[0x21], 2), Err(RLNCError::PieceLengthTooShort));
+    }
+
+    #[test]
+    fn test_nibble_packed_encode_recode_decode() {
+        let mut rng = rand::rng();
+
+        let data = (0..1500).map(|i| (i % 241) as u8).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 15).expect("Failed to create Encoder for nibble-packed coding test");
+        let params = encoder.get_params();
+
+        let packed_piece_byte_len = get_nibble_packed_piece_byte_len(params.get_piece_count(), params.get_piece_byte_len());
+        assert_eq!(packed_piece_byte_len, params.get_full_coded_piece_byte_len() - 7);
+
+        let packed_pieces = (0..20).map(|_| encoder.code_nibble_packed(&mut rng)).collect::<Vec<_>>();
+        assert!(packed_pieces.iter().all(|piece| piece.len() == packed_piece_byte_len));
+
+        let received = packed_pieces
+            .iter()
+            .flat_map(|piece| nibble_unpack_piece(piece, params.get_piece_count()).unwrap())
+            .collect::<Vec<u8>>();
+        let mut recoder = Recoder::new_with(received, params).expect("Failed to create Recoder for nibble-packed coding test");
+
+        let mut decoder = Decoder::new_with(params);
+        while !decoder.is_already_decoded() {
+            let recoded = recoder.recode_nibble_packed(&mut rng).expect("Recoding subfield pieces must stay in subfield");
+            let _ = decoder.decode_nibble_packed(&recoded);
+        }
+        assert_eq!(decoder.get_decoded_data(), Ok(data));
+
+        // Pieces coded over the whole field can't be recoded into nibble-packed ones.
+        let mut recoder = Recoder::new_with((0..15).flat_map(|_| encoder.code(&mut rng)).collect(), params).unwrap();
+        assert_eq!(recoder.recode_nibble_packed(&mut rng), Err(RLNCError::CoefficientNotInSubfield));
+    }
+}
@@ -0,0 +1,93 @@
+use std::{fs, io};
+
+/// CPUs of given NUMA node, as listed by the kernel in `/sys/devices/system/node/node<N>/cpulist`.
+///
+/// # Returns
+/// * Returns `Ok(Vec<usize>)` holding CPU indices, in ascending order.
+/// * Returns `Err(io::Error)` if the node doesn't exist or its CPU list can't be parsed.
+pub fn get_numa_node_cpus(node: usize) -> io::Result<Vec<usize>> {
+    let cpu_list = fs::read_to_string(format!("/sys/devices/system/node/node{node}/cpulist"))?;
+    parse_cpu_list(&cpu_list).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "malformed NUMA node CPU list"))
+}
+
+/// Parses a kernel CPU list, such as `0-3,8-11`, into CPU indices.
+fn parse_cpu_list(cpu_list: &str) -> Option<Vec<usize>> {
+    let mut cpus = Vec::new();
+
+    for range in cpu_list.trim().split(',').filter(|range| !range.is_empty()) {
+        match range.split_once('-') {
+            Some((first, last)) => cpus.extend(first.parse::<usize>().ok()?..=last.parse::<usize>().ok()?),
+            None => cpus.push(range.parse().ok()?),
+        }
+    }
+
+    Some(cpus)
+}
+
+/// Builds a `rayon` thread pool, with one worker per CPU of given NUMA node, each pinned to that node's CPUs.
+///
+/// Running parallel encoding or decoding inside `ThreadPool::install`, over an `Encoder` or `Decoder` backed by
+/// `HugePageStorage` bound to the same node, keeps both the work and the memory it sweeps through on one node.
+///
+/// # Returns
+/// * Returns `Ok(rayon::ThreadPool)` on success.
+/// * Returns `Err(io::Error)` if the node's CPUs can't be found or the pool can't be built.
+#[cfg(feature = "parallel")]
+pub fn numa_thread_pool(node: usize) -> io::Result<rayon::ThreadPool> {
+    let cpus = get_numa_node_cpus(node)?;
+
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(cpus.len())
+        .thread_name(move |index| format!("rlnc-numa{node}-{index}"))
+        .start_handler(move |_| {
+            // Pinning is best-effort, an unpinned worker is only slower.
+            let _ = super::affinity::pin_current_thread(&cpus);
+        })
+        .build()
+        .map_err(io::Error::other)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_cpu_list;
+    use crate::full::{Decoder, Encoder, HugePageStorage, Params};
+
+    #[test]
+    fn test_parse_cpu_list() {
+        assert_eq!(parse_cpu_list("0\n"), Some(vec![0]));
+        assert_eq!(parse_cpu_list("0-3,8-9,12\n"), Some(vec![0, 1, 2, 3, 8, 9, 12]));
+        assert_eq!(parse_cpu_list(""), Some(vec![]));
+        assert_eq!(parse_cpu_list("0-x"), None);
+    }
+
+    #[test]
+    fn test_huge_page_storage_encode_decode() {
+        let mut rng = rand::rng();
+
+        let data = (0..4096).map(|i| (i % 253) as u8).collect::<Vec<u8>>();
+        let params = Params::from_data_len(data.len(), 16).expect("Failed to create Params for huge page test");
+
+        let source = HugePageStorage::new(0).expect("Huge page mapping must not fail");
+        let encoder = Encoder::new_with_storage(&data, params, Box::new(source)).expect("Failed to create Encoder with huge page storage");
+
+        let matrix = HugePageStorage::on_node(params.get_full_coded_piece_byte_len(), 0)
+            .or_else(|_| HugePageStorage::new(params.get_full_coded_piece_byte_len()))
+            .expect("Huge page mapping must not fail");
+        let mut decoder = Decoder::new_with_storage(params.get_piece_byte_len(), params.get_piece_count(), Box::new(matrix))
+            .expect("Failed to create Decoder with huge page storage");
+
+        while !decoder.is_already_decoded() {
+            let _ = decoder.decode(&encoder.code(&mut rng));
+        }
+
+        assert_eq!(decoder.get_decoded_data(), Ok(data));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_numa_thread_pool() {
+        if let Ok(pool) = super::numa_thread_pool(0) {
+            assert_eq!(pool.current_num_threads(), super::get_numa_node_cpus(0).unwrap().len());
+        }
+    }
+}
@@ -0,0 +1,341 @@
+use super::{
+    audit::fnv1a_digest,
+    decoder::Decoder,
+    encoder::Encoder,
+    mapping::{ContiguousMapping, GenerationMapping},
+    params::Params,
+};
+use crate::RLNCError;
+use rand::Rng;
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    fs, io,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+/// Identifies a stored coded piece, as the `index`-th piece of the `generation`-th generation of an object.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "postcard", derive(serde::Serialize, serde::Deserialize))]
+pub struct PieceKey {
+    /// Identifier of the object, the piece belongs to.
+    pub object_id: String,
+    /// Index of the generation, the piece belongs to.
+    pub generation: usize,
+    /// Index of the piece, within its generation.
+    pub index: usize,
+}
+
+/// A place, full coded pieces can be written to and read back from, say a disk, a remote node or a bucket.
+/// `ObjectStore` writes pieces of a generation to distinct targets, so that losing a target loses at max one piece per generation.
+pub trait StorageTarget: Debug + Send + Sync {
+    /// Writes a full coded piece under given key, overwriting what's there.
+    fn write_piece(&self, key: &PieceKey, full_coded_piece: &[u8]) -> io::Result<()>;
+
+    /// Reads the full coded piece, stored under given key.
+    fn read_piece(&self, key: &PieceKey) -> io::Result<Vec<u8>>;
+}
+
+/// Storage target, keeping pieces in memory, meant for testing and simulation.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryTarget {
+    pieces: Arc<Mutex<HashMap<PieceKey, Vec<u8>>>>,
+}
+
+impl MemoryTarget {
+    /// Creates an empty in-memory target. Its clones share the same pieces.
+    pub fn new() -> MemoryTarget {
+        MemoryTarget::default()
+    }
+
+    /// Forgets all pieces, simulating loss of the target.
+    pub fn clear(&self) {
+        if let Ok(mut pieces) = self.pieces.lock() {
+            pieces.clear();
+        }
+    }
+
+    /// Overwrites a stored piece, simulating silent corruption. Does nothing if there is no such piece.
+    pub fn corrupt(&self, key: &PieceKey, full_coded_piece: &[u8]) {
+        if let Some(piece) = self.pieces.lock().ok().as_mut().and_then(|pieces| pieces.get_mut(key)) {
+            *piece = full_coded_piece.to_vec();
+        }
+    }
+}
+
+impl StorageTarget for MemoryTarget {
+    fn write_piece(&self, key: &PieceKey, full_coded_piece: &[u8]) -> io::Result<()> {
+        let mut pieces = self.pieces.lock().map_err(|_| io::Error::other("poisoned lock"))?;
+        pieces.insert(key.clone(), full_coded_piece.to_vec());
+
+        Ok(())
+    }
+
+    fn read_piece(&self, key: &PieceKey) -> io::Result<Vec<u8>> {
+        let pieces = self.pieces.lock().map_err(|_| io::Error::other("poisoned lock"))?;
+        pieces.get(key).cloned().ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))
+    }
+}
+
+/// Storage target, keeping each piece in its own file `<object_id>.<generation>.<index>.piece`, under a directory.
+/// Object identifiers must be valid file names.
+#[derive(Clone, Debug)]
+pub struct DirectoryTarget {
+    root: PathBuf,
+}
+
+impl DirectoryTarget {
+    /// Stores pieces under given directory, which must exist.
+    pub fn new(root: impl Into<PathBuf>) -> DirectoryTarget {
+        DirectoryTarget { root: root.into() }
+    }
+
+    fn piece_path(&self, key: &PieceKey) -> PathBuf {
+        self.root.join(format!("{}.{}.{}.piece", key.object_id, key.generation, key.index))
+    }
+}
+
+impl StorageTarget for DirectoryTarget {
+    fn write_piece(&self, key: &PieceKey, full_coded_piece: &[u8]) -> io::Result<()> {
+        fs::write(self.piece_path(key), full_coded_piece)
+    }
+
+    fn read_piece(&self, key: &PieceKey) -> io::Result<Vec<u8>> {
+        fs::read(self.piece_path(key))
+    }
+}
+
+/// Where a stored coded piece lives and what it must look like.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "postcard", derive(serde::Serialize, serde::Deserialize))]
+pub struct PieceLocation {
+    /// Index of the piece within its generation, which is part of its key.
+    pub index: usize,
+    /// Index of the storage target, holding the piece.
+    pub target: usize,
+    /// 64-bit FNV-1a digest of the full coded piece, catching silent corruption, but not tampering.
+    pub digest: u64,
+}
+
+/// Everything needed to reconstruct a stored object, given the storage targets: generation parameters, along with
+/// location and digest of each stored piece. Persist it separately from pieces, say with `postcard` feature enabled.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "postcard", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectManifest {
+    /// Identifier of the object, which is part of the key of each piece.
+    pub object_id: String,
+    /// Byte length of the object.
+    pub data_byte_len: usize,
+    /// Byte length of original data in each generation, but possibly the last one.
+    pub generation_byte_len: usize,
+    /// Number of pieces coded together, in each generation.
+    pub piece_count: usize,
+    /// Byte length of each piece, shared by all generations.
+    pub piece_byte_len: usize,
+    /// Locations of pieces, for each generation.
+    pub generations: Vec<Vec<PieceLocation>>,
+}
+
+impl ObjectManifest {
+    /// RLNC configuration shared by all generations.
+    pub fn get_params(&self) -> Params {
+        Params {
+            piece_count: self.piece_count,
+            piece_byte_len: self.piece_byte_len,
+        }
+    }
+}
+
+/// Erasure-coded object store, writing `n` coded pieces of each generation of an object to `n` distinct storage targets,
+/// so that almost surely, any `k` surviving pieces of each generation reconstruct it, where `k` is the number of pieces coded together.
+#[derive(Clone, Debug)]
+pub struct ObjectStore {
+    targets: Vec<Arc<dyn StorageTarget>>,
+}
+
+impl ObjectStore {
+    /// Creates an object store over given storage targets, which are referred to by their index, in manifests.
+    pub fn new(targets: Vec<Arc<dyn StorageTarget>>) -> ObjectStore {
+        ObjectStore { targets }
+    }
+
+    /// Number of storage targets.
+    pub fn get_target_count(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// Splits an object into generations, codes `pieces_per_generation` many pieces of each, writes them to distinct targets,
+    /// spreading generations over targets in round-robin fashion, and returns the manifest.
+    ///
+    /// # Arguments
+    /// * `object_id` - Identifier of the object, which is part of the key of each piece.
+    /// * `data` - The object.
+    /// * `generation_byte_len` - Byte length of original data in each generation.
+    /// * `piece_count` - Number of pieces coded together, in each generation i.e. `k`.
+    /// * `pieces_per_generation` - Number of coded pieces stored, per generation i.e. `n`.
+    /// * `rng` - Randomness source for coding.
+    ///
+    /// # Returns
+    /// * Returns `Ok(ObjectManifest)` on success.
+    /// * Returns `Err(RLNCError::DataLengthZero)` if `data` is empty or `generation_byte_len` is zero.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `piece_count` is zero.
+    /// * Returns `Err(RLNCError::InvalidPlacement)` if `pieces_per_generation` is less than `piece_count` or more than the number of targets.
+    /// * Returns `Err(RLNCError::WriteFailure)` if writing any piece fails.
+    pub fn put_object<R: Rng + ?Sized>(
+        &self,
+        object_id: &str,
+        data: &[u8],
+        generation_byte_len: usize,
+        piece_count: usize,
+        pieces_per_generation: usize,
+        rng: &mut R,
+    ) -> Result<ObjectManifest, RLNCError> {
+        let mapping = ContiguousMapping::new(generation_byte_len);
+        let params = Params::from_data_len(mapping.max_generation_byte_len(data.len()), piece_count)?;
+
+        if pieces_per_generation < piece_count || pieces_per_generation > self.targets.len() {
+            return Err(RLNCError::InvalidPlacement);
+        }
+
+        let mut manifest = ObjectManifest {
+            object_id: object_id.to_string(),
+            data_byte_len: data.len(),
+            generation_byte_len,
+            piece_count,
+            piece_byte_len: params.get_piece_byte_len(),
+            generations: Vec::with_capacity(mapping.generation_count(data.len())),
+        };
+
+        for generation in 0..mapping.generation_count(data.len()) {
+            let encoder = Encoder::new_with(mapping.gather(data, generation), params)?;
+
+            let locations = (0..pieces_per_generation)
+                .map(|index| {
+                    let target = (generation + index) % self.targets.len();
+                    let full_coded_piece = encoder.code(rng);
+
+                    self.write_piece(&manifest.object_id, generation, index, target, &full_coded_piece)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            manifest.generations.push(locations);
+        }
+
+        Ok(manifest)
+    }
+
+    /// Reconstructs an object, reading pieces listed in its manifest, generation by generation, till each one is decoded.
+    /// Pieces which can't be read or don't match their digest, are skipped.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Vec<u8>)` holding the object, on success.
+    /// * Returns `Err(RLNCError::NotAllPiecesReceivedYet)` if some generation has less than `piece_count` intact, linearly independent pieces.
+    /// * Returns `Err(RLNCError::InvalidDecodedDataFormat(_))` if some generation decodes into malformed data.
+    pub fn get_object(&self, manifest: &ObjectManifest) -> Result<Vec<u8>, RLNCError> {
+        let mapping = ContiguousMapping::new(manifest.generation_byte_len);
+        let mut data = vec![0u8; manifest.data_byte_len];
+
+        for (generation, locations) in manifest.generations.iter().enumerate() {
+            let mut decoder = Decoder::new_with(manifest.get_params());
+
+            for location in locations {
+                if decoder.is_already_decoded() {
+                    break;
+                }
+                if let Some(full_coded_piece) = self.read_piece(manifest, generation, location) {
+                    let _ = decoder.decode(&full_coded_piece);
+                }
+            }
+
+            mapping.scatter(&decoder.get_decoded_data()?, generation, &mut data);
+        }
+
+        Ok(data)
+    }
+
+    /// Reads a piece, listed in the manifest, checking its digest.
+    ///
+    /// # Returns
+    /// Returns `None` if the piece can't be read or it doesn't match its digest.
+    pub fn read_piece(&self, manifest: &ObjectManifest, generation: usize, location: &PieceLocation) -> Option<Vec<u8>> {
+        let key = PieceKey {
+            object_id: manifest.object_id.clone(),
+            generation,
+            index: location.index,
+        };
+
+        let full_coded_piece = self.targets.get(location.target)?.read_piece(&key).ok()?;
+        (fnv1a_digest(&full_coded_piece) == location.digest).then_some(full_coded_piece)
+    }
+
+    /// Writes a full coded piece to given target, returning its location.
+    ///
+    /// # Returns
+    /// * Returns `Ok(PieceLocation)` on success.
+    /// * Returns `Err(RLNCError::InvalidPlacement)` if `target` doesn't exist.
+    /// * Returns `Err(RLNCError::WriteFailure)` if writing fails.
+    pub fn write_piece(&self, object_id: &str, generation: usize, index: usize, target: usize, full_coded_piece: &[u8]) -> Result<PieceLocation, RLNCError> {
+        let key = PieceKey {
+            object_id: object_id.to_string(),
+            generation,
+            index,
+        };
+
+        self.targets
+            .get(target)
+            .ok_or(RLNCError::InvalidPlacement)?
+            .write_piece(&key, full_coded_piece)
+            .map_err(|_| RLNCError::WriteFailure)?;
+
+        Ok(PieceLocation {
+            index,
+            target,
+            digest: fnv1a_digest(full_coded_piece),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MemoryTarget, ObjectStore, PieceKey, StorageTarget};
+    use crate::RLNCError;
+    use rand::Rng;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_object_store_survives_target_loss() {
+        let mut rng = rand::rng();
+
+        let targets = (0..8).map(|_| MemoryTarget::new()).collect::<Vec<_>>();
+        let store = ObjectStore::new(targets.iter().map(|target| Arc::new(target.clone()) as Arc<dyn StorageTarget>).collect());
+
+        let data = (0..20_000).map(|_| rng.random()).collect::<Vec<u8>>();
+        assert_eq!(store.put_object("obj", &data, 4096, 4, 3, &mut rng), Err(RLNCError::InvalidPlacement));
+        assert_eq!(store.put_object("obj", &data, 4096, 4, 9, &mut rng), Err(RLNCError::InvalidPlacement));
+
+        let manifest = store.put_object("obj", &data, 4096, 4, 8, &mut rng).expect("Storing must not fail");
+        assert_eq!(manifest.generations.len(), 5);
+        assert!(manifest.generations.iter().all(|locations| locations.len() == 8));
+        assert_eq!(store.get_object(&manifest), Ok(data.clone()));
+
+        // Each generation lives on 8 distinct targets, so losing any two of them keeps all generations recoverable.
+        targets[0].clear();
+        targets[5].clear();
+
+        // Silently corrupted piece gets skipped, because of digest mismatch.
+        let location = manifest.generations[1][1];
+        let key = PieceKey {
+            object_id: "obj".to_string(),
+            generation: 1,
+            index: location.index,
+        };
+        targets[location.target].corrupt(&key, &[0u8; 8]);
+
+        assert_eq!(store.read_piece(&manifest, 1, &location), None);
+        assert_eq!(store.get_object(&manifest), Ok(data));
+
+        targets.iter().take(4).for_each(|target| target.clear());
+        assert_eq!(store.get_object(&manifest), Err(RLNCError::NotAllPiecesReceivedYet));
+    }
+}
@@ -0,0 +1,170 @@
+use crate::RLNCError;
+
+/// Validated RLNC configuration, shared by encoder, recoder and decoder.
+///
+/// Create it once, either from original data length on sender side or from the numbers received over the wire
+/// on receiver side, and pass it to `Encoder::new_with`, `Recoder::new_with` and `Decoder::new_with`, so that
+/// piece count and piece length never silently drift apart.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Params {
+    pub(crate) piece_count: usize,
+    pub(crate) piece_byte_len: usize,
+}
+
+impl Params {
+    /// Creates RLNC configuration from number of pieces coded together and byte length of each piece.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Params)` on success.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `piece_count` is zero.
+    /// * Returns `Err(RLNCError::PieceLengthZero)` if `piece_byte_len` is zero.
+    pub fn new(piece_count: usize, piece_byte_len: usize) -> Result<Params, RLNCError> {
+        if piece_count == 0 {
+            return Err(RLNCError::PieceCountZero);
+        }
+        if piece_byte_len == 0 {
+            return Err(RLNCError::PieceLengthZero);
+        }
+
+        Ok(Params { piece_count, piece_byte_len })
+    }
+
+    /// Computes RLNC configuration for erasure-coding original data of `data_byte_len` bytes, split into `piece_count` pieces,
+    /// accounting for the boundary marker and padding, exactly the way `Encoder::new` does.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Params)` on success.
+    /// * Returns `Err(RLNCError::DataLengthZero)` if `data_byte_len` is zero.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `piece_count` is zero.
+    pub fn from_data_len(data_byte_len: usize, piece_count: usize) -> Result<Params, RLNCError> {
+        if data_byte_len == 0 {
+            return Err(RLNCError::DataLengthZero);
+        }
+        if piece_count == 0 {
+            return Err(RLNCError::PieceCountZero);
+        }
+
+        let boundary_marker_len = 1;
+        let piece_byte_len = (data_byte_len + boundary_marker_len).div_ceil(piece_count);
+
+        Ok(Params { piece_count, piece_byte_len })
+    }
+
+    /// Creates RLNC configuration from byte length of a full coded piece i.e. coding vector followed by coded data,
+    /// and number of pieces coded together.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Params)` on success.
+    /// * Returns `Err(RLNCError::PieceLengthZero)` if `full_coded_piece_byte_len` is zero.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `piece_count` is zero.
+    /// * Returns `Err(RLNCError::PieceLengthTooShort)` if `full_coded_piece_byte_len` is not greater than `piece_count`.
+    pub fn from_full_coded_piece_len(full_coded_piece_byte_len: usize, piece_count: usize) -> Result<Params, RLNCError> {
+        if full_coded_piece_byte_len == 0 {
+            return Err(RLNCError::PieceLengthZero);
+        }
+        if piece_count == 0 {
+            return Err(RLNCError::PieceCountZero);
+        }
+        if full_coded_piece_byte_len <= piece_count {
+            return Err(RLNCError::PieceLengthTooShort);
+        }
+
+        Ok(Params {
+            piece_count,
+            piece_byte_len: full_coded_piece_byte_len - piece_count,
+        })
+    }
+
+    /// Number of pieces coded together.
+    pub fn get_piece_count(&self) -> usize {
+        self.piece_count
+    }
+
+    /// Byte length of each piece, after padding the original data.
+    pub fn get_piece_byte_len(&self) -> usize {
+        self.piece_byte_len
+    }
+
+    /// Each full coded piece consists of `self.get_piece_count()` coefficients, appended by `self.get_piece_byte_len()` bytes of coded data.
+    pub fn get_full_coded_piece_byte_len(&self) -> usize {
+        self.piece_count + self.piece_byte_len
+    }
+
+    /// Maximum byte length of original data, which can be erasure-coded using this configuration.
+    pub fn get_max_data_byte_len(&self) -> usize {
+        let boundary_marker_len = 1;
+        self.piece_count * self.piece_byte_len - boundary_marker_len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Params;
+    use crate::{
+        RLNCError,
+        full::{Decoder, Encoder, Recoder},
+    };
+    use rand::Rng;
+
+    #[test]
+    fn test_params_invalid_inputs() {
+        assert_eq!(Params::new(0, 10), Err(RLNCError::PieceCountZero));
+        assert_eq!(Params::new(10, 0), Err(RLNCError::PieceLengthZero));
+
+        assert_eq!(Params::from_data_len(0, 10), Err(RLNCError::DataLengthZero));
+        assert_eq!(Params::from_data_len(10, 0), Err(RLNCError::PieceCountZero));
+
+        assert_eq!(Params::from_full_coded_piece_len(0, 10), Err(RLNCError::PieceLengthZero));
+        assert_eq!(Params::from_full_coded_piece_len(10, 0), Err(RLNCError::PieceCountZero));
+        assert_eq!(Params::from_full_coded_piece_len(10, 10), Err(RLNCError::PieceLengthTooShort));
+    }
+
+    #[test]
+    fn test_params_shared_by_encoder_recoder_decoder() {
+        let mut rng = rand::rng();
+
+        let data_byte_len = 1024usize;
+        let piece_count = 32usize;
+        let data = (0..data_byte_len).map(|_| rng.random()).collect::<Vec<u8>>();
+
+        let params = Params::from_data_len(data_byte_len, piece_count).expect("Params must be valid");
+        assert!(params.get_max_data_byte_len() >= data_byte_len);
+        assert_eq!(
+            Params::from_full_coded_piece_len(params.get_full_coded_piece_byte_len(), piece_count),
+            Ok(params)
+        );
+
+        // Original data doesn't fit in given configuration.
+        let too_long_data = vec![0u8; params.get_max_data_byte_len() + 1];
+        assert!(matches!(Encoder::new_with(too_long_data, params), Err(RLNCError::DataLengthMismatch)));
+
+        let encoder = Encoder::new_with(data.clone(), params).expect("Failed to create Encoder");
+        assert_eq!(encoder.get_params(), params);
+        assert_eq!(
+            encoder.get_params(),
+            Encoder::new(data.clone(), piece_count).expect("Failed to create Encoder").get_params()
+        );
+
+        let coded_pieces = (0..piece_count / 2).flat_map(|_| encoder.code(&mut rng)).collect::<Vec<u8>>();
+        let mut recoder = Recoder::new_with(coded_pieces, params).expect("Failed to create Recoder");
+        assert_eq!(recoder.get_params(), params);
+
+        let mut decoder = Decoder::new_with(params);
+        assert_eq!(decoder.get_params(), params);
+
+        while !decoder.is_already_decoded() {
+            let piece = if decoder.get_received_piece_count().is_multiple_of(2) {
+                recoder.recode(&mut rng)
+            } else {
+                encoder.code(&mut rng)
+            };
+
+            match decoder.decode(&piece) {
+                Ok(_) | Err(RLNCError::PieceNotUseful) => {}
+                Err(e) => panic!("Unexpected error during decoding: {e:?}"),
+            }
+        }
+
+        assert_eq!(decoder.get_decoded_data(), Ok(data));
+    }
+}
@@ -0,0 +1,339 @@
+use super::{audit::fnv1a_digest, params::Params};
+use crate::RLNCError;
+
+/// A coded piece, split into its coding vector and coded data.
+///
+/// Encoder, recoder and decoder exchange coded pieces as a single byte buffer i.e. coding vector followed by coded data.
+/// This type gives those two parts a name, so that they can be serialized by other means, say `rkyv` archives, `postcard` or protobuf, when the
+/// corresponding feature is enabled.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "postcard", derive(serde::Serialize, serde::Deserialize))]
+pub struct CodedPiece {
+    coding_vector: Vec<u8>,
+    coded_data: Vec<u8>,
+}
+
+impl CodedPiece {
+    /// Splits a full coded piece, as produced by `Encoder::code` or `Recoder::recode`, into coding vector and coded data.
+    ///
+    /// # Arguments
+    /// * `full_coded_piece` - Coding vector followed by coded data.
+    /// * `piece_count` - Number of pieces coded together i.e. length of the coding vector.
+    ///
+    /// # Returns
+    /// * Returns `Ok(CodedPiece)` on success.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `piece_count` is zero.
+    /// * Returns `Err(RLNCError::PieceLengthTooShort)` if `full_coded_piece` is not longer than `piece_count`.
+    pub fn from_bytes(full_coded_piece: &[u8], piece_count: usize) -> Result<CodedPiece, RLNCError> {
+        if piece_count == 0 {
+            return Err(RLNCError::PieceCountZero);
+        }
+        if full_coded_piece.len() <= piece_count {
+            return Err(RLNCError::PieceLengthTooShort);
+        }
+
+        let (coding_vector, coded_data) = full_coded_piece.split_at(piece_count);
+
+        Ok(CodedPiece::from_parts(coding_vector.to_vec(), coded_data.to_vec()))
+    }
+
+    /// Splits a full coded piece, received off the wire, validating it against RLNC configuration, so that malformed buffers are
+    /// rejected at parse time, rather than by the decoder or recoder.
+    ///
+    /// # Returns
+    /// * Returns `Ok(CodedPiece)` on success.
+    /// * Returns `Err(RLNCError::InvalidPieceLength)` if `full_coded_piece` is not of length `params.get_full_coded_piece_byte_len()`.
+    pub fn from_bytes_with(full_coded_piece: &[u8], params: Params) -> Result<CodedPiece, RLNCError> {
+        if full_coded_piece.len() != params.get_full_coded_piece_byte_len() {
+            return Err(RLNCError::InvalidPieceLength);
+        }
+
+        let (coding_vector, coded_data) = full_coded_piece.split_at(params.get_piece_count());
+
+        Ok(CodedPiece::from_parts(coding_vector.to_vec(), coded_data.to_vec()))
+    }
+
+    /// Assembles a coded piece from already validated parts.
+    pub(crate) fn from_parts(coding_vector: Vec<u8>, coded_data: Vec<u8>) -> CodedPiece {
+        CodedPiece { coding_vector, coded_data }
+    }
+
+    /// Coding vector of the coded piece.
+    pub fn get_coding_vector(&self) -> &[u8] {
+        &self.coding_vector
+    }
+
+    /// Coded data of the coded piece.
+    pub fn get_coded_data(&self) -> &[u8] {
+        &self.coded_data
+    }
+
+    /// Returns `true` if coding vector and coded data are of lengths, given RLNC configuration demands.
+    pub fn matches(&self, params: Params) -> bool {
+        self.coding_vector.len() == params.get_piece_count() && self.coded_data.len() == params.get_piece_byte_len()
+    }
+
+    /// Concatenates coding vector and coded data, ready to be passed to `Decoder::decode`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        [self.coding_vector.as_slice(), self.coded_data.as_slice()].concat()
+    }
+
+    /// Identity of the coded piece, given the generation it belongs to.
+    pub fn get_id(&self, generation: u32) -> PieceId {
+        PieceId::new(generation, &self.coding_vector)
+    }
+}
+
+/// Identity of a coded piece, for set-based bookkeeping, say deduplication, gossip "have" lists or retransmission tracking.
+///
+/// It's made of the generation the piece belongs to and the 64-bit FNV-1a digest of its coding vector, so that the same
+/// linear combination is recognized, no matter which peer it came from. Ordered by generation first, so that a `BTreeMap`
+/// or `BTreeSet` keeps pieces grouped by generation. Digest is not cryptographically secure, so don't rely on it against
+/// adversarial peers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PieceId {
+    /// Index of the generation, the piece belongs to.
+    pub generation: u32,
+    /// 64-bit FNV-1a digest of the coding vector.
+    pub coding_vector_digest: u64,
+}
+
+impl PieceId {
+    /// Computes identity of a piece of given generation, carrying given coding vector.
+    pub fn new(generation: u32, coding_vector: &[u8]) -> PieceId {
+        PieceId {
+            generation,
+            coding_vector_digest: fnv1a_digest(coding_vector),
+        }
+    }
+}
+
+/// A borrowed full coded piece i.e. coding vector followed by coded data, as produced by `Encoder::code` or `Recoder::recode`,
+/// validated to be longer than its coding vector. It derefs to the full coded piece via `AsRef<[u8]>`, so it can be handed to
+/// `Decoder::decode` or written to a socket, without copying.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CodedPieceRef<'a> {
+    full_coded_piece: &'a [u8],
+    piece_count: usize,
+}
+
+impl<'a> CodedPieceRef<'a> {
+    /// Borrows a full coded piece, validating it the same way as `CodedPiece::from_bytes`.
+    ///
+    /// # Returns
+    /// * Returns `Ok(CodedPieceRef)` on success.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `piece_count` is zero.
+    /// * Returns `Err(RLNCError::PieceLengthTooShort)` if `full_coded_piece` is not longer than `piece_count`.
+    pub fn new(full_coded_piece: &'a [u8], piece_count: usize) -> Result<CodedPieceRef<'a>, RLNCError> {
+        if piece_count == 0 {
+            return Err(RLNCError::PieceCountZero);
+        }
+        if full_coded_piece.len() <= piece_count {
+            return Err(RLNCError::PieceLengthTooShort);
+        }
+
+        Ok(CodedPieceRef { full_coded_piece, piece_count })
+    }
+
+    /// Coding vector of the coded piece.
+    pub fn get_coding_vector(&self) -> &'a [u8] {
+        &self.full_coded_piece[..self.piece_count]
+    }
+
+    /// Coded data of the coded piece.
+    pub fn get_coded_data(&self) -> &'a [u8] {
+        &self.full_coded_piece[self.piece_count..]
+    }
+
+    /// Identity of the coded piece, given the generation it belongs to.
+    pub fn get_id(&self, generation: u32) -> PieceId {
+        PieceId::new(generation, self.get_coding_vector())
+    }
+}
+
+impl AsRef<[u8]> for CodedPieceRef<'_> {
+    fn as_ref(&self) -> &[u8] {
+        self.full_coded_piece
+    }
+}
+
+impl<'a> TryFrom<(&'a [u8], usize)> for CodedPieceRef<'a> {
+    type Error = RLNCError;
+
+    /// Borrows a full coded piece, given along with the number of pieces coded together. See `CodedPieceRef::new`.
+    fn try_from((full_coded_piece, piece_count): (&'a [u8], usize)) -> Result<Self, Self::Error> {
+        CodedPieceRef::new(full_coded_piece, piece_count)
+    }
+}
+
+impl TryFrom<(&[u8], usize)> for CodedPiece {
+    type Error = RLNCError;
+
+    /// Copies a full coded piece, given along with the number of pieces coded together. See `CodedPiece::from_bytes`.
+    fn try_from((full_coded_piece, piece_count): (&[u8], usize)) -> Result<Self, Self::Error> {
+        CodedPiece::from_bytes(full_coded_piece, piece_count)
+    }
+}
+
+impl From<CodedPieceRef<'_>> for CodedPiece {
+    fn from(piece: CodedPieceRef<'_>) -> Self {
+        CodedPiece::from_parts(piece.get_coding_vector().to_vec(), piece.get_coded_data().to_vec())
+    }
+}
+
+impl From<&CodedPiece> for Vec<u8> {
+    fn from(piece: &CodedPiece) -> Self {
+        piece.to_bytes()
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl ArchivedCodedPiece {
+    /// Coding vector of the archived coded piece, accessed in place.
+    pub fn get_coding_vector(&self) -> &[u8] {
+        &self.coding_vector
+    }
+
+    /// Coded data of the archived coded piece, accessed in place.
+    pub fn get_coded_data(&self) -> &[u8] {
+        &self.coded_data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CodedPiece, CodedPieceRef, PieceId};
+    use crate::{
+        RLNCError,
+        full::{Decoder, Encoder, Recoder},
+    };
+    use std::collections::{BTreeSet, HashSet};
+
+    #[test]
+    fn test_coded_piece_from_bytes() {
+        let mut rng = rand::rng();
+
+        let encoder = Encoder::new(vec![7u8; 1024], 16).expect("Failed to create Encoder");
+        let full_coded_piece = encoder.code(&mut rng);
+
+        assert_eq!(CodedPiece::from_bytes(&full_coded_piece, 0), Err(RLNCError::PieceCountZero));
+        assert_eq!(CodedPiece::from_bytes(&full_coded_piece[..16], 16), Err(RLNCError::PieceLengthTooShort));
+
+        let piece = CodedPiece::from_bytes(&full_coded_piece, encoder.get_piece_count()).expect("Piece must be valid");
+        assert_eq!(piece.get_coding_vector(), &full_coded_piece[..16]);
+        assert_eq!(piece.get_coded_data(), &full_coded_piece[16..]);
+        assert_eq!(piece.to_bytes(), full_coded_piece);
+    }
+
+    #[test]
+    fn test_coded_piece_conversion_traits() {
+        let mut rng = rand::rng();
+
+        let data = vec![7u8; 1024];
+        let encoder = Encoder::new(data.clone(), 16).expect("Failed to create Encoder");
+        let mut decoder = Decoder::new_with(encoder.get_params());
+
+        let full_coded_piece = encoder.code(&mut rng);
+        assert_eq!(CodedPieceRef::try_from((&full_coded_piece[..16], 16)), Err(RLNCError::PieceLengthTooShort));
+        assert_eq!(CodedPiece::try_from((full_coded_piece.as_slice(), 0)), Err(RLNCError::PieceCountZero));
+
+        let piece_ref = CodedPieceRef::try_from((full_coded_piece.as_slice(), 16)).expect("Piece must be valid");
+        assert_eq!(piece_ref.get_coding_vector(), &full_coded_piece[..16]);
+        assert_eq!(piece_ref.get_coded_data(), &full_coded_piece[16..]);
+
+        let piece = CodedPiece::from(piece_ref);
+        assert_eq!(Ok(piece.clone()), CodedPiece::try_from((full_coded_piece.as_slice(), 16)));
+        assert_eq!(Vec::from(&piece), full_coded_piece);
+
+        decoder.decode(piece_ref.as_ref()).expect("First piece must be useful");
+        while !decoder.is_already_decoded() {
+            let full_coded_piece = encoder.code(&mut rng);
+            let _ = decoder.decode(CodedPieceRef::new(&full_coded_piece, 16).unwrap().as_ref());
+        }
+
+        assert_eq!(decoder.get_decoded_data(), Ok(data));
+    }
+
+    #[test]
+    fn test_coded_piece_validated_against_params() {
+        let mut rng = rand::rng();
+
+        let data = vec![7u8; 1024];
+        let encoder = Encoder::new(data.clone(), 16).expect("Failed to create Encoder");
+        let params = encoder.get_params();
+
+        let full_coded_piece = encoder.code(&mut rng);
+        assert_eq!(CodedPiece::from_bytes_with(&full_coded_piece[1..], params), Err(RLNCError::InvalidPieceLength));
+
+        // Piece of another configuration parses, but is rejected by decoder and recoder, before touching their state.
+        let mismatching = CodedPiece::from_bytes(&full_coded_piece, 15).expect("Piece must be valid");
+        assert!(!mismatching.matches(params));
+        assert_eq!(
+            Recoder::from_pieces(std::slice::from_ref(&mismatching), params).unwrap_err(),
+            RLNCError::InvalidPieceLength
+        );
+        assert_eq!(Recoder::from_pieces(&[], params).unwrap_err(), RLNCError::NotEnoughPiecesToRecode);
+
+        let mut decoder = Decoder::new_with(params);
+        assert_eq!(decoder.decode_piece(&mismatching), Err(RLNCError::InvalidPieceLength));
+        assert_eq!(decoder.get_received_piece_count(), 0);
+
+        let pieces = (0..8)
+            .map(|_| CodedPiece::from_bytes_with(&encoder.code(&mut rng), params).expect("Piece must be valid"))
+            .collect::<Vec<_>>();
+        let mut recoder = Recoder::from_pieces(&pieces, params).expect("Failed to create Recoder");
+
+        while decoder.get_useful_piece_count() < 8 {
+            let _ = decoder.decode_piece(&CodedPiece::from_bytes_with(&recoder.recode(&mut rng), params).unwrap());
+        }
+        while !decoder.is_already_decoded() {
+            let _ = decoder.decode_piece(&CodedPiece::from_bytes_with(&encoder.code(&mut rng), params).unwrap());
+        }
+
+        assert_eq!(decoder.get_decoded_data(), Ok(data));
+    }
+
+    #[test]
+    fn test_piece_id_bookkeeping() {
+        let mut rng = rand::rng();
+
+        let encoder = Encoder::new(vec![7u8; 1024], 16).expect("Failed to create Encoder");
+        let pieces = (0..4).map(|_| encoder.code(&mut rng)).collect::<Vec<_>>();
+
+        // Same piece, seen twice, as owned and as borrowed, is a duplicate.
+        let mut seen = HashSet::new();
+        for piece in &pieces {
+            assert!(seen.insert(CodedPieceRef::new(piece, 16).unwrap().get_id(0)));
+        }
+        assert!(!seen.insert(CodedPiece::from_bytes(&pieces[0], 16).unwrap().get_id(0)));
+        assert!(seen.insert(CodedPiece::from_bytes(&pieces[0], 16).unwrap().get_id(1)));
+
+        // A recoded piece is a different linear combination, so it's a different piece.
+        let mut recoder = Recoder::new(pieces.concat(), pieces[0].len(), 16).expect("Failed to create Recoder");
+        assert!(seen.insert(PieceId::new(0, &recoder.recode(&mut rng)[..16])));
+
+        let ordered = seen.into_iter().collect::<BTreeSet<_>>();
+        assert_eq!(ordered.iter().map(|id| id.generation).collect::<Vec<_>>(), [0, 0, 0, 0, 0, 1]);
+    }
+
+    #[cfg(feature = "rkyv")]
+    #[test]
+    fn test_coded_piece_rkyv_archive() {
+        let mut rng = rand::rng();
+
+        let encoder = Encoder::new(vec![7u8; 1024], 16).expect("Failed to create Encoder");
+        let piece = CodedPiece::from_bytes(&encoder.code(&mut rng), encoder.get_piece_count()).expect("Piece must be valid");
+
+        let archive = rkyv::to_bytes::<rkyv::rancor::Error>(&piece).expect("Archiving must not fail");
+        let archived = rkyv::access::<super::ArchivedCodedPiece, rkyv::rancor::Error>(&archive).expect("Archive must be valid");
+
+        assert_eq!(archived.get_coding_vector(), piece.get_coding_vector());
+        assert_eq!(archived.get_coded_data(), piece.get_coded_data());
+        let deserialized = rkyv::deserialize::<CodedPiece, rkyv::rancor::Error>(archived).expect("Deserializing must not fail");
+        assert_eq!(deserialized, piece);
+
+        // Truncated archive must be rejected, not read out of bounds.
+        assert!(rkyv::access::<super::ArchivedCodedPiece, rkyv::rancor::Error>(&archive[..archive.len() / 2]).is_err());
+    }
+}
@@ -0,0 +1,277 @@
+use super::{
+    control::ControlMessage,
+    encoder::Encoder,
+    mapping::{ContiguousMapping, GenerationMapping},
+    params::Params,
+};
+use crate::RLNCError;
+use rand::Rng;
+use std::{collections::VecDeque, sync::Arc};
+
+/// Sender side pipeline, which splits long data into generations and keeps a bounded window of them in flight.
+///
+/// Pieces of all in-flight generations are handed out in round-robin fashion, so that the sender keeps encoding and
+/// sending pieces of generation `i + 1`, while generation `i` is still being decoded and acknowledged by the receiver,
+/// keeping both directions of the link busy. Once a generation gets acknowledged, it leaves the window and the next
+/// generation is admitted, till all generations are acknowledged.
+///
+/// How object bytes map to generations is decided by a `GenerationMapping` strategy. All generations share the same
+/// `Params`, shorter ones are padded like any other, so that the receiver can set up a decoder per generation, using
+/// `Decoder::new_with(pipeline.get_params())`, and put decoded generations back in place, using `GenerationMapping::scatter`.
+#[derive(Clone, Debug)]
+pub struct GenerationPipeline {
+    data: Vec<u8>,
+    mapping: Arc<dyn GenerationMapping>,
+    params: Params,
+    generation_count: usize,
+    max_in_flight: usize,
+    /// In-flight generations, in order of admission, along with their encoders.
+    in_flight: VecDeque<(usize, Encoder)>,
+    /// Index of the next generation to be admitted into the window.
+    next_generation: usize,
+    /// Position of the next in-flight generation to be coded, in round-robin fashion.
+    cursor: usize,
+}
+
+impl GenerationPipeline {
+    /// Sets up a new pipeline, mapping consecutive blocks of original data to consecutive generations.
+    ///
+    /// # Arguments
+    /// * `data` - Original data, to be split into generations of `generation_byte_len` bytes, except the last one, which can be shorter.
+    /// * `generation_byte_len` - Byte length of original data in each generation.
+    /// * `piece_count` - Number of pieces each generation gets split into.
+    /// * `max_in_flight` - Maximum number of unacknowledged generations, being sent at once.
+    ///
+    /// # Returns
+    /// * Returns `Ok(GenerationPipeline)` on success.
+    /// * Returns `Err(RLNCError::DataLengthZero)` if `data` is empty or `generation_byte_len` is zero.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `piece_count` is zero.
+    /// * Returns `Err(RLNCError::GenerationCountZero)` if `max_in_flight` is zero.
+    pub fn new(data: Vec<u8>, generation_byte_len: usize, piece_count: usize, max_in_flight: usize) -> Result<GenerationPipeline, RLNCError> {
+        Self::new_with_mapping(data, Arc::new(ContiguousMapping::new(generation_byte_len)), piece_count, max_in_flight)
+    }
+
+    /// Sets up a new pipeline, mapping original data to generations, using given strategy.
+    ///
+    /// # Arguments
+    /// * `data` - Original data, to be split into generations.
+    /// * `mapping` - Strategy deciding which bytes of original data belong to which generation.
+    /// * `piece_count` - Number of pieces each generation gets split into.
+    /// * `max_in_flight` - Maximum number of unacknowledged generations, being sent at once.
+    ///
+    /// # Returns
+    /// * Returns `Ok(GenerationPipeline)` on success.
+    /// * Returns `Err(RLNCError::DataLengthZero)` if `data` is empty or `mapping` yields an empty generation.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `piece_count` is zero.
+    /// * Returns `Err(RLNCError::GenerationCountZero)` if `max_in_flight` is zero.
+    pub fn new_with_mapping(
+        data: Vec<u8>,
+        mapping: Arc<dyn GenerationMapping>,
+        piece_count: usize,
+        max_in_flight: usize,
+    ) -> Result<GenerationPipeline, RLNCError> {
+        if data.is_empty() {
+            return Err(RLNCError::DataLengthZero);
+        }
+        if max_in_flight == 0 {
+            return Err(RLNCError::GenerationCountZero);
+        }
+
+        let params = Params::from_data_len(mapping.max_generation_byte_len(data.len()), piece_count)?;
+        let generation_count = mapping.generation_count(data.len());
+
+        let mut pipeline = GenerationPipeline {
+            data,
+            mapping,
+            params,
+            generation_count,
+            max_in_flight,
+            in_flight: VecDeque::with_capacity(max_in_flight),
+            next_generation: 0,
+            cursor: 0,
+        };
+        pipeline.admit_generations()?;
+
+        Ok(pipeline)
+    }
+
+    /// RLNC configuration shared by all generations.
+    pub fn get_params(&self) -> Params {
+        self.params
+    }
+
+    /// Number of generations, original data got split into.
+    pub fn get_generation_count(&self) -> usize {
+        self.generation_count
+    }
+
+    /// Indices of generations, which are currently in flight i.e. admitted, but not yet acknowledged, in order of admission.
+    pub fn in_flight_generations(&self) -> impl Iterator<Item = usize> + '_ {
+        self.in_flight.iter().map(|&(generation, _)| generation)
+    }
+
+    /// Returns `true` if all generations are acknowledged.
+    pub fn is_complete(&self) -> bool {
+        self.in_flight.is_empty()
+    }
+
+    /// Produces a coded piece of the next in-flight generation, in round-robin fashion.
+    ///
+    /// # Returns
+    /// * Returns `Some((generation, full_coded_piece))` if there is any generation in flight.
+    /// * Returns `None` if all generations are acknowledged.
+    pub fn next_piece<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Option<(usize, Vec<u8>)> {
+        if self.in_flight.is_empty() {
+            return None;
+        }
+
+        self.cursor %= self.in_flight.len();
+        let (generation, encoder) = &self.in_flight[self.cursor];
+        self.cursor += 1;
+
+        Some((*generation, encoder.code(rng)))
+    }
+
+    /// Marks a generation as acknowledged i.e. decoded by the receiver, removing it from the window and admitting the next one.
+    /// Acknowledging a generation, which is already acknowledged, is a no-op.
+    ///
+    /// # Returns
+    /// * Returns `Ok(())` on success.
+    /// * Returns `Err(RLNCError::InvalidGenerationIndex)` if `generation` is not less than `self.get_generation_count()`.
+    /// * Returns `Err(RLNCError::DataLengthZero)` if generation mapping yields an empty generation.
+    pub fn acknowledge(&mut self, generation: usize) -> Result<(), RLNCError> {
+        if generation >= self.generation_count {
+            return Err(RLNCError::InvalidGenerationIndex);
+        }
+
+        if let Some(position) = self.in_flight.iter().position(|&(g, _)| g == generation) {
+            self.in_flight.remove(position);
+            if position < self.cursor {
+                self.cursor -= 1;
+            }
+
+            self.admit_generations()?;
+        }
+
+        Ok(())
+    }
+
+    /// Reacts on feedback from the receiver. Only `ControlMessage::Ack` changes pipeline state, rest are ignored.
+    ///
+    /// # Returns
+    /// * Returns `Ok(())` on success.
+    /// * Returns `Err(RLNCError::InvalidGenerationIndex)` if acknowledged generation doesn't exist.
+    /// * Returns `Err(RLNCError::DataLengthZero)` if generation mapping yields an empty generation.
+    pub fn on_control_message(&mut self, message: &ControlMessage) -> Result<(), RLNCError> {
+        match message {
+            ControlMessage::Ack { generation } => self.acknowledge(*generation as usize),
+            _ => Ok(()),
+        }
+    }
+
+    /// Admits generations into the window, till it's full or no generation is left. Encoders are set up on admission,
+    /// so that memory is spent on padded copies of in-flight generations only.
+    fn admit_generations(&mut self) -> Result<(), RLNCError> {
+        while self.in_flight.len() < self.max_in_flight && self.next_generation < self.generation_count {
+            let generation_data = self.mapping.gather(&self.data, self.next_generation);
+            let encoder = Encoder::new_with(generation_data, self.params)?;
+
+            self.in_flight.push_back((self.next_generation, encoder));
+            self.next_generation += 1;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GenerationPipeline;
+    use crate::{
+        RLNCError,
+        full::{ControlMessage, Decoder, GenerationMapping, InterleavedMapping},
+    };
+    use rand::Rng;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_generation_pipeline_invalid_inputs() {
+        assert!(matches!(GenerationPipeline::new(vec![], 1024, 16, 2), Err(RLNCError::DataLengthZero)));
+        assert!(matches!(GenerationPipeline::new(vec![1], 0, 16, 2), Err(RLNCError::DataLengthZero)));
+        assert!(matches!(GenerationPipeline::new(vec![1], 1024, 0, 2), Err(RLNCError::PieceCountZero)));
+        assert!(matches!(GenerationPipeline::new(vec![1], 1024, 16, 0), Err(RLNCError::GenerationCountZero)));
+
+        let mut pipeline = GenerationPipeline::new(vec![1; 100], 10, 4, 2).expect("Pipeline must be valid");
+        assert_eq!(pipeline.acknowledge(10), Err(RLNCError::InvalidGenerationIndex));
+    }
+
+    #[test]
+    fn test_generation_pipeline_bounded_window() {
+        let mut rng = rand::rng();
+
+        let generation_byte_len = 1000usize;
+        let data = (0..generation_byte_len * 5 + 123).map(|_| rng.random()).collect::<Vec<u8>>();
+
+        let max_in_flight = 2;
+        let mut pipeline = GenerationPipeline::new(data.clone(), generation_byte_len, 16, max_in_flight).expect("Pipeline must be valid");
+        assert_eq!(pipeline.get_generation_count(), 6);
+
+        let mut decoders = (0..pipeline.get_generation_count())
+            .map(|_| Some(Decoder::new_with(pipeline.get_params())))
+            .collect::<Vec<_>>();
+        let mut decoded = vec![Vec::new(); pipeline.get_generation_count()];
+
+        while let Some((generation, piece)) = pipeline.next_piece(&mut rng) {
+            let in_flight = pipeline.in_flight_generations().collect::<Vec<_>>();
+            assert!(in_flight.len() <= max_in_flight);
+            assert!(in_flight.contains(&generation));
+
+            // Receiver decodes, acknowledging each generation, as soon as it's decoded.
+            let decoder = decoders[generation].as_mut().expect("Acknowledged generation must not be sent");
+            let _ = decoder.decode(&piece);
+
+            if decoder.is_already_decoded() {
+                let ack = ControlMessage::from_decoder(generation as u32, decoder);
+                decoded[generation] = decoders[generation]
+                    .take()
+                    .and_then(|decoder| decoder.get_decoded_data().ok())
+                    .expect("Decoding must succeed");
+
+                pipeline.on_control_message(&ack).expect("Acknowledgement must be valid");
+            }
+        }
+
+        assert!(pipeline.is_complete());
+        assert_eq!(decoded.concat(), data);
+    }
+
+    #[test]
+    fn test_generation_pipeline_with_interleaved_mapping() {
+        let mut rng = rand::rng();
+
+        let data = (0..10_000).map(|_| rng.random()).collect::<Vec<u8>>();
+        let mapping = Arc::new(InterleavedMapping::new(4, 256));
+
+        let mut pipeline = GenerationPipeline::new_with_mapping(data.clone(), mapping.clone(), 8, 2).expect("Pipeline must be valid");
+        assert_eq!(pipeline.get_generation_count(), 4);
+
+        let mut decoders = (0..pipeline.get_generation_count())
+            .map(|_| Decoder::new_with(pipeline.get_params()))
+            .collect::<Vec<_>>();
+        let mut reassembled = vec![0u8; data.len()];
+
+        while let Some((generation, piece)) = pipeline.next_piece(&mut rng) {
+            let decoder = &mut decoders[generation];
+            let _ = decoder.decode(&piece);
+
+            if decoder.is_already_decoded() {
+                let generation_data = decoder.clone().get_decoded_data().expect("Decoding must succeed");
+                mapping.scatter(&generation_data, generation, &mut reassembled);
+
+                pipeline.acknowledge(generation).expect("Acknowledgement must be valid");
+            }
+        }
+
+        assert_eq!(reassembled, data);
+    }
+}
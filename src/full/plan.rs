@@ -0,0 +1,257 @@
+use crate::RLNCError;
+
+/// Kind of piece to be sent, as prescribed by a send plan.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PieceKind {
+    /// Original i.e. uncoded source piece, at given index, sent verbatim.
+    Systematic(usize),
+    /// A freshly coded piece, with random sampled coding vector.
+    Coded,
+}
+
+/// Describes a single piece to be sent, which the application can execute against any transport.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PieceDescriptor {
+    /// Index of the generation, this piece belongs to.
+    pub generation: usize,
+    /// What to send.
+    pub kind: PieceKind,
+}
+
+/// Order in which pieces of different generations are to be sent.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PlanOrdering {
+    /// All pieces of a generation, then all pieces of the next one.
+    Sequential,
+    /// Pieces of all generations, sent in round-robin fashion, spreading burst losses across generations.
+    #[default]
+    Interleaved,
+}
+
+/// A concrete transmission plan, computed from RLNC configuration, estimated loss rate and an explicit overhead factor.
+///
+/// To deliver `piece_count` useful pieces over a channel losing fraction `p` of pieces, sender needs to send
+/// `piece_count / (1 - p)` pieces, on average. This plan sends `ceil(piece_count * overhead_factor / (1 - p))` many pieces
+/// per generation, where `overhead_factor >= 1` accounts for variance of loss and linearly dependent pieces.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SendPlan {
+    piece_count: usize,
+    generation_count: usize,
+    systematic_piece_count: usize,
+    coded_piece_count: usize,
+    ordering: PlanOrdering,
+}
+
+impl SendPlan {
+    /// Computes a new send plan.
+    ///
+    /// # Arguments
+    /// * `piece_count` - Number of pieces coded together, in each generation.
+    /// * `generation_count` - Number of generations to be sent.
+    /// * `loss_estimate` - Estimated fraction of pieces lost in transit, must be in `[0, 1)`.
+    /// * `overhead_factor` - Multiplicative overhead on top of the expected number of pieces, must be `>= 1`.
+    /// * `systematic` - If `true`, each generation starts with all of its source pieces sent verbatim.
+    /// * `ordering` - Order in which pieces of different generations are to be sent.
+    ///
+    /// # Returns
+    /// * Returns `Ok(SendPlan)` on success.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `piece_count` is zero.
+    /// * Returns `Err(RLNCError::GenerationCountZero)` if `generation_count` is zero.
+    /// * Returns `Err(RLNCError::InvalidSendPlanParameter)` if `loss_estimate` or `overhead_factor` is out of range.
+    pub fn new(
+        piece_count: usize,
+        generation_count: usize,
+        loss_estimate: f64,
+        overhead_factor: f64,
+        systematic: bool,
+        ordering: PlanOrdering,
+    ) -> Result<SendPlan, RLNCError> {
+        if piece_count == 0 {
+            return Err(RLNCError::PieceCountZero);
+        }
+        if generation_count == 0 {
+            return Err(RLNCError::GenerationCountZero);
+        }
+        if !(0.0..1.0).contains(&loss_estimate) {
+            return Err(RLNCError::InvalidSendPlanParameter);
+        }
+        if !overhead_factor.is_finite() || overhead_factor < 1.0 {
+            return Err(RLNCError::InvalidSendPlanParameter);
+        }
+
+        let total_piece_count = ((piece_count as f64 * overhead_factor) / (1.0 - loss_estimate)).ceil() as usize;
+        let total_piece_count = total_piece_count.max(piece_count);
+
+        let systematic_piece_count = if systematic { piece_count } else { 0 };
+        let coded_piece_count = total_piece_count - systematic_piece_count;
+
+        Ok(SendPlan {
+            piece_count,
+            generation_count,
+            systematic_piece_count,
+            coded_piece_count,
+            ordering,
+        })
+    }
+
+    /// Number of pieces coded together, in each generation.
+    pub fn get_piece_count(&self) -> usize {
+        self.piece_count
+    }
+
+    /// Number of generations to be sent.
+    pub fn get_generation_count(&self) -> usize {
+        self.generation_count
+    }
+
+    /// Number of source pieces to be sent verbatim, per generation.
+    pub fn get_systematic_piece_count(&self) -> usize {
+        self.systematic_piece_count
+    }
+
+    /// Number of coded pieces to be sent, per generation.
+    pub fn get_coded_piece_count(&self) -> usize {
+        self.coded_piece_count
+    }
+
+    /// Number of pieces to be sent, across all generations.
+    pub fn get_total_piece_count(&self) -> usize {
+        (self.systematic_piece_count + self.coded_piece_count) * self.generation_count
+    }
+
+    /// Returns an iterator over descriptors of all pieces to be sent, in the order they are to be sent.
+    pub fn iter(&self) -> impl Iterator<Item = PieceDescriptor> + '_ {
+        let per_generation_piece_count = self.systematic_piece_count + self.coded_piece_count;
+
+        (0..self.get_total_piece_count()).map(move |idx| {
+            let (generation, slot) = match self.ordering {
+                PlanOrdering::Sequential => (idx / per_generation_piece_count, idx % per_generation_piece_count),
+                PlanOrdering::Interleaved => (idx % self.generation_count, idx / self.generation_count),
+            };
+
+            let kind = if slot < self.systematic_piece_count {
+                PieceKind::Systematic(slot)
+            } else {
+                PieceKind::Coded
+            };
+
+            PieceDescriptor { generation, kind }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PieceDescriptor, PieceKind, PlanOrdering, SendPlan};
+    use crate::RLNCError;
+
+    #[test]
+    fn test_send_plan_invalid_inputs() {
+        assert_eq!(SendPlan::new(0, 1, 0.1, 1.1, false, PlanOrdering::Sequential), Err(RLNCError::PieceCountZero));
+        assert_eq!(
+            SendPlan::new(16, 0, 0.1, 1.1, false, PlanOrdering::Sequential),
+            Err(RLNCError::GenerationCountZero)
+        );
+        assert_eq!(
+            SendPlan::new(16, 1, 1.0, 1.1, false, PlanOrdering::Sequential),
+            Err(RLNCError::InvalidSendPlanParameter)
+        );
+        assert_eq!(
+            SendPlan::new(16, 1, -0.1, 1.1, false, PlanOrdering::Sequential),
+            Err(RLNCError::InvalidSendPlanParameter)
+        );
+        assert_eq!(
+            SendPlan::new(16, 1, 0.1, 0.9, false, PlanOrdering::Sequential),
+            Err(RLNCError::InvalidSendPlanParameter)
+        );
+        assert_eq!(
+            SendPlan::new(16, 1, 0.1, f64::NAN, false, PlanOrdering::Sequential),
+            Err(RLNCError::InvalidSendPlanParameter)
+        );
+    }
+
+    #[test]
+    fn test_send_plan_piece_counts() {
+        // No loss, no overhead: exactly piece_count many pieces.
+        let plan = SendPlan::new(16, 3, 0.0, 1.0, false, PlanOrdering::Sequential).expect("Plan must be valid");
+        assert_eq!(plan.get_systematic_piece_count(), 0);
+        assert_eq!(plan.get_coded_piece_count(), 16);
+        assert_eq!(plan.get_total_piece_count(), 48);
+
+        // 20% loss, 10% overhead: ceil(16 * 1.1 / 0.8) = 22 pieces per generation.
+        let plan = SendPlan::new(16, 3, 0.2, 1.1, true, PlanOrdering::Sequential).expect("Plan must be valid");
+        assert_eq!(plan.get_systematic_piece_count(), 16);
+        assert_eq!(plan.get_coded_piece_count(), 6);
+        assert_eq!(plan.get_total_piece_count(), 66);
+        assert_eq!(plan.iter().count(), plan.get_total_piece_count());
+    }
+
+    #[test]
+    fn test_send_plan_ordering() {
+        let plan = SendPlan::new(2, 2, 0.0, 1.5, true, PlanOrdering::Sequential).expect("Plan must be valid");
+        let sequential = plan.iter().collect::<Vec<_>>();
+
+        assert_eq!(
+            sequential,
+            vec![
+                PieceDescriptor {
+                    generation: 0,
+                    kind: PieceKind::Systematic(0)
+                },
+                PieceDescriptor {
+                    generation: 0,
+                    kind: PieceKind::Systematic(1)
+                },
+                PieceDescriptor {
+                    generation: 0,
+                    kind: PieceKind::Coded
+                },
+                PieceDescriptor {
+                    generation: 1,
+                    kind: PieceKind::Systematic(0)
+                },
+                PieceDescriptor {
+                    generation: 1,
+                    kind: PieceKind::Systematic(1)
+                },
+                PieceDescriptor {
+                    generation: 1,
+                    kind: PieceKind::Coded
+                },
+            ]
+        );
+
+        let plan = SendPlan::new(2, 2, 0.0, 1.5, true, PlanOrdering::Interleaved).expect("Plan must be valid");
+        let interleaved = plan.iter().collect::<Vec<_>>();
+
+        assert_eq!(
+            interleaved,
+            vec![
+                PieceDescriptor {
+                    generation: 0,
+                    kind: PieceKind::Systematic(0)
+                },
+                PieceDescriptor {
+                    generation: 1,
+                    kind: PieceKind::Systematic(0)
+                },
+                PieceDescriptor {
+                    generation: 0,
+                    kind: PieceKind::Systematic(1)
+                },
+                PieceDescriptor {
+                    generation: 1,
+                    kind: PieceKind::Systematic(1)
+                },
+                PieceDescriptor {
+                    generation: 0,
+                    kind: PieceKind::Coded
+                },
+                PieceDescriptor {
+                    generation: 1,
+                    kind: PieceKind::Coded
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,187 @@
+use crate::common::{gf256::Gf256, simd::gf256_mul_vec_by_scalar_then_add_into_vec};
+use rand::Rng;
+
+/// Number of random projections kept by `DependencySketch`, each a byte, together making a 64-bit sketch.
+const PROJECTION_COUNT: usize = 8;
+
+/// Cheap pre-filter for linearly dependent coding vectors, see `Decoder::enable_dependency_prefilter`.
+///
+/// Keeps a few random vectors, uniformly sampled from the null space of coding vectors of useful pieces received so far. A coding
+/// vector in their span is orthogonal to all of them, while any other one is orthogonal to each of them with probability 1/256 only.
+/// So a non-zero projection proves a coding vector is independent, without elimination. Only when all projections are zero i.e. on
+/// sketch collision, the coding vector is reduced exactly, against the basis kept here, which never touches payloads.
+#[derive(Clone, Debug)]
+pub(crate) struct DependencySketch {
+    piece_count: usize,
+    /// Basis of coding vectors inserted so far, in reduced row echelon form, with pivots normalized to one, concatenated.
+    basis: Vec<u8>,
+    /// Pivot column of each row of the basis.
+    pivots: Vec<usize>,
+    /// Random vectors of the null space of the basis, concatenated.
+    projections: Vec<u8>,
+    /// Number of coding vectors found dependent, on sketch collision.
+    rejected_count: usize,
+}
+
+impl DependencySketch {
+    /// Creates a sketch of an empty basis, whose null space is the whole space, so that projections are uniform random vectors.
+    pub(crate) fn new<R: Rng + ?Sized>(piece_count: usize, rng: &mut R) -> DependencySketch {
+        let mut projections = vec![0u8; PROJECTION_COUNT * piece_count];
+        rng.fill_bytes(&mut projections);
+
+        DependencySketch {
+            piece_count,
+            basis: Vec::new(),
+            pivots: Vec::new(),
+            projections,
+            rejected_count: 0,
+        }
+    }
+
+    /// Number of coding vectors found dependent so far.
+    pub(crate) fn get_rejected_count(&self) -> usize {
+        self.rejected_count
+    }
+
+    /// Empties the basis, resampling projections, while keeping count of coding vectors found dependent so far.
+    pub(crate) fn clear<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.basis.clear();
+        self.pivots.clear();
+        rng.fill_bytes(&mut self.projections);
+    }
+
+    /// Returns `true` if the coding vector is in the span of the basis. Sketch is consulted first, falling back to exact
+    /// reduction of the coding vector, only if it's orthogonal to all projections.
+    pub(crate) fn rejects(&mut self, coding_vector: &[u8]) -> bool {
+        let is_orthogonal = self
+            .projections
+            .chunks_exact(self.piece_count)
+            .all(|projection| dot(projection, coding_vector) == 0);
+        if !is_orthogonal {
+            return false;
+        }
+
+        let is_dependent = self.reduce(coding_vector).iter().all(|&coeff| coeff == 0);
+        self.rejected_count += usize::from(is_dependent);
+        is_dependent
+    }
+
+    /// Adds a coding vector to the basis, keeping projections in the null space of the grown basis. Coding vectors already in
+    /// the span of the basis are ignored.
+    pub(crate) fn insert(&mut self, coding_vector: &[u8]) {
+        let mut row = self.reduce(coding_vector);
+        let Some(pivot_cidx) = row.iter().position(|&coeff| coeff != 0) else {
+            return;
+        };
+
+        let inv = unsafe { Gf256::new(row[pivot_cidx]).inv().unwrap_unchecked().get() };
+        row.iter_mut().for_each(|coeff| *coeff = Gf256::mul_const(*coeff, inv));
+
+        // Null space vector of the current basis, with one at the new pivot column and zero at other non-pivot columns. Its dot
+        // product with the reduced row is one, so subtracting a multiple of it from a projection makes the projection orthogonal
+        // to the reduced row, while staying orthogonal to the current basis. Being independent of the projections, it keeps them
+        // uniformly distributed over the null space of the grown basis.
+        let mut null_vector = vec![0u8; self.piece_count];
+        null_vector[pivot_cidx] = 1;
+        for (basis_row, &cidx) in self.basis.chunks_exact(self.piece_count).zip(&self.pivots) {
+            null_vector[cidx] = basis_row[pivot_cidx];
+        }
+
+        for projection in self.projections.chunks_exact_mut(self.piece_count) {
+            let coeff = dot(projection, &row);
+            gf256_mul_vec_by_scalar_then_add_into_vec(projection, &null_vector, coeff);
+        }
+
+        // Clear the new pivot column, off existing rows of the basis.
+        for basis_row in self.basis.chunks_exact_mut(self.piece_count) {
+            let coeff = basis_row[pivot_cidx];
+            gf256_mul_vec_by_scalar_then_add_into_vec(basis_row, &row, coeff);
+        }
+
+        self.basis.extend_from_slice(&row);
+        self.pivots.push(pivot_cidx);
+    }
+
+    /// Reduces a copy of the coding vector, by rows of the basis. Result is zero iff the coding vector is in their span.
+    fn reduce(&self, coding_vector: &[u8]) -> Vec<u8> {
+        let mut row = coding_vector.to_vec();
+
+        for (basis_row, &cidx) in self.basis.chunks_exact(self.piece_count).zip(&self.pivots) {
+            let coeff = row[cidx];
+            gf256_mul_vec_by_scalar_then_add_into_vec(&mut row, basis_row, coeff);
+        }
+
+        row
+    }
+}
+
+/// Dot product of two equal length vectors, over GF(2^8).
+fn dot(lhs: &[u8], rhs: &[u8]) -> u8 {
+    lhs.iter().zip(rhs).fold(0, |acc, (&a, &b)| acc ^ Gf256::mul_const(a, b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DependencySketch, dot};
+    use crate::{
+        RLNCError,
+        full::{Decoder, Encoder},
+    };
+    use rand::Rng;
+
+    #[test]
+    fn test_dependency_sketch_projections_stay_in_null_space() {
+        const PIECE_COUNT: usize = 16;
+        let mut rng = rand::rng();
+
+        let mut sketch = DependencySketch::new(PIECE_COUNT, &mut rng);
+        let mut inserted = Vec::new();
+
+        for _ in 0..PIECE_COUNT - 1 {
+            let coding_vector = (0..PIECE_COUNT).map(|_| rng.random()).collect::<Vec<u8>>();
+            sketch.insert(&coding_vector);
+            inserted.push(coding_vector);
+
+            for projection in sketch.projections.chunks_exact(PIECE_COUNT) {
+                assert!(inserted.iter().all(|coding_vector| dot(projection, coding_vector) == 0));
+            }
+        }
+
+        // Any combination of inserted coding vectors is rejected, all-zero one included.
+        let combination = inserted.iter().fold(vec![0u8; PIECE_COUNT], |acc, coding_vector| {
+            acc.iter().zip(coding_vector).map(|(&a, &b)| a ^ b).collect()
+        });
+        assert!(sketch.rejects(&combination));
+        assert!(sketch.rejects(&[0u8; PIECE_COUNT]));
+        assert_eq!(sketch.get_rejected_count(), 2);
+    }
+
+    #[test]
+    fn test_decoder_with_dependency_prefilter() {
+        let mut rng = rand::rng();
+
+        let data = (0..4096).map(|i| (i % 251) as u8).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 32).expect("Failed to create Encoder for pre-filter test");
+
+        let mut decoder = Decoder::new_with(encoder.get_params());
+        let pieces = (0..8).map(|_| encoder.code(&mut rng)).collect::<Vec<_>>();
+        pieces.iter().for_each(|piece| decoder.decode(piece).expect("Fresh piece must be useful"));
+
+        // Pre-filter, enabled midway, knows about pieces received before.
+        decoder.enable_dependency_prefilter();
+        assert!(decoder.is_dependency_prefilter_enabled());
+        assert_eq!(decoder.decode(&pieces[3]), Err(RLNCError::PieceNotUseful));
+        assert_eq!(decoder.get_prefiltered_piece_count(), 1);
+
+        while !decoder.is_already_decoded() {
+            let piece = encoder.code(&mut rng);
+            let _ = decoder.decode(&piece);
+            if !decoder.is_already_decoded() {
+                assert_eq!(decoder.decode(&piece), Err(RLNCError::PieceNotUseful));
+            }
+        }
+
+        assert_eq!(decoder.get_prefiltered_piece_count(), decoder.get_received_piece_count() - 32);
+        assert_eq!(decoder.get_decoded_data(), Ok(data));
+    }
+}
@@ -0,0 +1,389 @@
+use super::{decoder::Decoder, encoder::Encoder, params::Params};
+use crate::{
+    RLNCError,
+    common::{gf256::Gf256, simd::gf256_mul_vec_by_scalar_then_add_into_vec},
+};
+use rand::Rng;
+
+/// Maximum number of row generations, so that column coefficients of distinct rows, which are powers of the primitive element, stay distinct.
+const MAX_ROW_GENERATION_COUNT: usize = u8::MAX as usize;
+
+/// Weight of row generation `row` in parity generation `parity`, which is `3^(parity x row)`. Weights form a Vandermonde matrix over distinct
+/// powers of the primitive element, so that any `P` row generations can be solved for, using any `P` parity generations.
+fn column_coefficient(parity: usize, row: usize) -> u8 {
+    let node = (0..row).fold(1, |acc, _| Gf256::mul_const(acc, Gf256::primitive_element().get()));
+    (0..parity).fold(1, |acc, _| Gf256::mul_const(acc, node))
+}
+
+/// Sender side of a two-dimensional product code, say for archival, where losing a whole generation is not an option.
+///
+/// Padded original data is split into `R` row generations, of `k` pieces each, which are RLNC coded as usual. On top of that, an
+/// outer code forms `P` parity generations across the rows: `i`-th source piece of parity generation `p` is a fixed linear combination
+/// of `i`-th source pieces of all row generations, i.e. the column generation made of them. Parity generations are RLNC coded too, so
+/// like 2D RAID layouts, but with RLNC on both axes. A row generation, which falls short of rank, can be recovered by `ProductDecoder`,
+/// using pieces of parity generations, once enough other rows are decoded.
+#[derive(Clone, Debug)]
+pub struct ProductEncoder {
+    rows: Vec<Encoder>,
+    parities: Vec<Encoder>,
+    params: Params,
+}
+
+impl ProductEncoder {
+    /// Splits data into row generations and computes parity generations.
+    ///
+    /// # Arguments
+    /// * `data` - Original data, padded the way `Encoder::new` does, before being split into row generations.
+    /// * `row_generation_count` - Number of row generations i.e. `R`, at max 255.
+    /// * `piece_count` - Number of pieces each generation gets split into i.e. `k`.
+    /// * `parity_generation_count` - Number of parity generations i.e. `P`.
+    ///
+    /// # Returns
+    /// * Returns `Ok(ProductEncoder)` on success.
+    /// * Returns `Err(RLNCError::DataLengthZero)` if `data` is empty.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `piece_count` is zero.
+    /// * Returns `Err(RLNCError::GenerationCountZero)` if `row_generation_count` is zero.
+    /// * Returns `Err(RLNCError::TooManyGenerations)` if `row_generation_count` is more than 255.
+    pub fn new(data: Vec<u8>, row_generation_count: usize, piece_count: usize, parity_generation_count: usize) -> Result<ProductEncoder, RLNCError> {
+        if row_generation_count == 0 {
+            return Err(RLNCError::GenerationCountZero);
+        }
+        if row_generation_count > MAX_ROW_GENERATION_COUNT {
+            return Err(RLNCError::TooManyGenerations);
+        }
+        if piece_count == 0 {
+            return Err(RLNCError::PieceCountZero);
+        }
+
+        let total_params = Params::from_data_len(data.len(), row_generation_count * piece_count)?;
+        let params = Params::new(piece_count, total_params.get_piece_byte_len())?;
+        let generation_byte_len = piece_count * params.get_piece_byte_len();
+
+        let encoder = Encoder::new_with(data, total_params)?;
+        let padded_data = (0..row_generation_count * piece_count)
+            .flat_map(|index| unsafe { encoder.source_piece(index).unwrap_unchecked() }.iter().copied())
+            .collect::<Vec<u8>>();
+
+        let rows = padded_data
+            .chunks_exact(generation_byte_len)
+            .map(|row| unsafe { Encoder::without_padding(Box::new(row.to_vec()), piece_count).unwrap_unchecked() })
+            .collect::<Vec<_>>();
+
+        let parities = (0..parity_generation_count)
+            .map(|parity| {
+                let mut parity_data = vec![0u8; generation_byte_len];
+                padded_data
+                    .chunks_exact(generation_byte_len)
+                    .enumerate()
+                    .for_each(|(row, row_data)| gf256_mul_vec_by_scalar_then_add_into_vec(&mut parity_data, row_data, column_coefficient(parity, row)));
+
+                unsafe { Encoder::without_padding(Box::new(parity_data), piece_count).unwrap_unchecked() }
+            })
+            .collect::<Vec<_>>();
+
+        Ok(ProductEncoder { rows, parities, params })
+    }
+
+    /// RLNC configuration shared by all row and parity generations.
+    pub fn get_params(&self) -> Params {
+        self.params
+    }
+
+    /// Number of row generations.
+    pub fn get_row_generation_count(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Number of parity generations.
+    pub fn get_parity_generation_count(&self) -> usize {
+        self.parities.len()
+    }
+
+    /// Produces a new coded piece of given row generation.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Vec<u8>)` holding the full coded piece.
+    /// * Returns `Err(RLNCError::InvalidGenerationIndex)` if `row` is not less than `self.get_row_generation_count()`.
+    pub fn code_row<R: Rng + ?Sized>(&self, row: usize, rng: &mut R) -> Result<Vec<u8>, RLNCError> {
+        self.rows.get(row).map(|encoder| encoder.code(rng)).ok_or(RLNCError::InvalidGenerationIndex)
+    }
+
+    /// Produces a new coded piece of given parity generation.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Vec<u8>)` holding the full coded piece.
+    /// * Returns `Err(RLNCError::InvalidGenerationIndex)` if `parity` is not less than `self.get_parity_generation_count()`.
+    pub fn code_parity<R: Rng + ?Sized>(&self, parity: usize, rng: &mut R) -> Result<Vec<u8>, RLNCError> {
+        self.parities
+            .get(parity)
+            .map(|encoder| encoder.code(rng))
+            .ok_or(RLNCError::InvalidGenerationIndex)
+    }
+}
+
+/// State of a row generation, at the receiver.
+#[derive(Clone, Debug)]
+enum RowState {
+    /// Still being decoded, along with useful pieces received so far, concatenated, for joint recovery.
+    Decoding(Box<Decoder>, Vec<u8>),
+    /// Decoded, holding its source pieces, concatenated.
+    Decoded(Vec<u8>),
+}
+
+/// Receiver side of a two-dimensional product code, see `ProductEncoder`.
+///
+/// Each row generation is decoded on its own, as its pieces arrive. Pieces of parity generations are kept aside, till
+/// `Self::recover` jointly solves for all row generations, which are not yet decoded, by subtracting contributions of
+/// decoded rows from parity pieces, so that each parity piece becomes one more equation over the remaining rows.
+#[derive(Clone, Debug)]
+pub struct ProductDecoder {
+    params: Params,
+    rows: Vec<RowState>,
+    /// Received pieces of each parity generation, concatenated.
+    parity_pieces: Vec<Vec<u8>>,
+    received_piece_count: usize,
+}
+
+impl ProductDecoder {
+    /// Sets up a decoder for the product code, with given configuration of each generation.
+    ///
+    /// # Returns
+    /// * Returns `Ok(ProductDecoder)` on success.
+    /// * Returns `Err(RLNCError::GenerationCountZero)` if `row_generation_count` is zero.
+    /// * Returns `Err(RLNCError::TooManyGenerations)` if `row_generation_count` is more than 255.
+    pub fn new(params: Params, row_generation_count: usize, parity_generation_count: usize) -> Result<ProductDecoder, RLNCError> {
+        if row_generation_count == 0 {
+            return Err(RLNCError::GenerationCountZero);
+        }
+        if row_generation_count > MAX_ROW_GENERATION_COUNT {
+            return Err(RLNCError::TooManyGenerations);
+        }
+
+        Ok(ProductDecoder {
+            params,
+            rows: vec![RowState::Decoding(Box::new(Decoder::new_with(params)), Vec::new()); row_generation_count],
+            parity_pieces: vec![Vec::new(); parity_generation_count],
+            received_piece_count: 0,
+        })
+    }
+
+    /// Returns `true` if given row generation is decoded.
+    pub fn is_row_decoded(&self, row: usize) -> bool {
+        matches!(self.rows.get(row), Some(RowState::Decoded(_)))
+    }
+
+    /// Number of decoded row generations.
+    pub fn get_decoded_row_count(&self) -> usize {
+        self.rows.iter().filter(|state| matches!(state, RowState::Decoded(_))).count()
+    }
+
+    /// Returns `true` if all row generations are decoded.
+    pub fn is_already_decoded(&self) -> bool {
+        self.get_decoded_row_count() == self.rows.len()
+    }
+
+    /// Feeds a coded piece of given row generation to its decoder.
+    ///
+    /// # Returns
+    /// * Returns `Err(RLNCError::InvalidGenerationIndex)` if `row` doesn't refer to any row generation.
+    /// * Otherwise, returns same as `Decoder::decode`.
+    pub fn decode_row(&mut self, row: usize, full_coded_piece: &[u8]) -> Result<(), RLNCError> {
+        let state = self.rows.get_mut(row).ok_or(RLNCError::InvalidGenerationIndex)?;
+        let RowState::Decoding(decoder, useful_pieces) = state else {
+            return Err(RLNCError::ReceivedAllPieces);
+        };
+
+        self.received_piece_count += 1;
+        decoder.decode(full_coded_piece)?;
+        useful_pieces.extend_from_slice(full_coded_piece);
+
+        if decoder.is_already_decoded() {
+            let decoder = std::mem::replace(decoder.as_mut(), Decoder::new_with(self.params));
+            *state = RowState::Decoded(decoder.extract_padded_data());
+        }
+
+        Ok(())
+    }
+
+    /// Keeps a coded piece of given parity generation aside, for `Self::recover`.
+    ///
+    /// # Returns
+    /// * Returns `Ok(())` on success.
+    /// * Returns `Err(RLNCError::InvalidGenerationIndex)` if `parity` doesn't refer to any parity generation.
+    /// * Returns `Err(RLNCError::InvalidPieceLength)` if the piece doesn't match RLNC configuration.
+    pub fn decode_parity(&mut self, parity: usize, full_coded_piece: &[u8]) -> Result<(), RLNCError> {
+        let parity_pieces = self.parity_pieces.get_mut(parity).ok_or(RLNCError::InvalidGenerationIndex)?;
+        if full_coded_piece.len() != self.params.get_full_coded_piece_byte_len() {
+            return Err(RLNCError::InvalidPieceLength);
+        }
+
+        parity_pieces.extend_from_slice(full_coded_piece);
+        self.received_piece_count += 1;
+
+        Ok(())
+    }
+
+    /// Jointly solves for all row generations, which are not decoded yet, using their own useful pieces, along with parity pieces,
+    /// after subtracting contributions of decoded rows from them. It's all or nothing.
+    ///
+    /// # Returns
+    /// * Returns `Ok(usize)` holding the number of row generations, which got recovered, zero if all were already decoded.
+    /// * Returns `Err(RLNCError::NotAllPiecesReceivedYet)` if received pieces don't suffice for recovering all remaining rows.
+    pub fn recover(&mut self) -> Result<usize, RLNCError> {
+        let piece_count = self.params.get_piece_count();
+        let piece_byte_len = self.params.get_piece_byte_len();
+        let full_coded_piece_byte_len = self.params.get_full_coded_piece_byte_len();
+
+        let remaining_rows = (0..self.rows.len()).filter(|&row| !self.is_row_decoded(row)).collect::<Vec<_>>();
+        if remaining_rows.is_empty() {
+            return Ok(0);
+        }
+
+        // Unknowns are source pieces of remaining rows, block by block, so that a piece of remaining row `u` spans only block `u`.
+        let mut joint_decoder = Decoder::new(piece_byte_len, remaining_rows.len() * piece_count)?;
+        let mut joint_piece = vec![0u8; joint_decoder.get_full_coded_piece_byte_len()];
+
+        for (block, &row) in remaining_rows.iter().enumerate() {
+            let RowState::Decoding(_, useful_pieces) = &self.rows[row] else {
+                continue;
+            };
+
+            for full_coded_piece in useful_pieces.chunks_exact(full_coded_piece_byte_len) {
+                let (coding_vector, coded_data) = full_coded_piece.split_at(piece_count);
+
+                joint_piece.fill(0);
+                joint_piece[block * piece_count..(block + 1) * piece_count].copy_from_slice(coding_vector);
+                joint_piece[remaining_rows.len() * piece_count..].copy_from_slice(coded_data);
+
+                let _ = joint_decoder.decode(&joint_piece);
+            }
+        }
+
+        for (parity, parity_pieces) in self.parity_pieces.iter().enumerate() {
+            for full_coded_piece in parity_pieces.chunks_exact(full_coded_piece_byte_len) {
+                if joint_decoder.is_already_decoded() {
+                    break;
+                }
+
+                let (coding_vector, coded_data) = full_coded_piece.split_at(piece_count);
+                joint_piece.fill(0);
+
+                let (joint_coding_vector, joint_coded_data) = joint_piece.split_at_mut(remaining_rows.len() * piece_count);
+                joint_coded_data.copy_from_slice(coded_data);
+
+                for (row, state) in self.rows.iter().enumerate() {
+                    let weight = column_coefficient(parity, row);
+
+                    match state {
+                        RowState::Decoded(source_pieces) => {
+                            source_pieces
+                                .chunks_exact(piece_byte_len)
+                                .zip(coding_vector)
+                                .for_each(|(source_piece, &coeff)| {
+                                    gf256_mul_vec_by_scalar_then_add_into_vec(joint_coded_data, source_piece, Gf256::mul_const(weight, coeff))
+                                });
+                        }
+                        RowState::Decoding(..) => {
+                            let block = unsafe { remaining_rows.binary_search(&row).unwrap_unchecked() };
+                            gf256_mul_vec_by_scalar_then_add_into_vec(
+                                &mut joint_coding_vector[block * piece_count..(block + 1) * piece_count],
+                                coding_vector,
+                                weight,
+                            );
+                        }
+                    }
+                }
+
+                let _ = joint_decoder.decode(&joint_piece);
+            }
+        }
+
+        if !joint_decoder.is_already_decoded() {
+            return Err(RLNCError::NotAllPiecesReceivedYet);
+        }
+
+        let generation_byte_len = piece_count * piece_byte_len;
+        for (&row, source_pieces) in remaining_rows.iter().zip(joint_decoder.extract_padded_data().chunks_exact(generation_byte_len)) {
+            self.rows[row] = RowState::Decoded(source_pieces.to_vec());
+        }
+
+        Ok(remaining_rows.len())
+    }
+
+    /// Recovers remaining row generations, if needed, and returns original data.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Vec<u8>)` holding the original data.
+    /// * Returns `Err(RLNCError::NotAllPiecesReceivedYet)` if received pieces don't suffice for recovering all rows.
+    /// * Returns `Err(RLNCError::InvalidDecodedDataFormat(_))` if decoded data doesn't end with the boundary marker, followed by zero padding.
+    pub fn get_decoded_data(mut self) -> Result<Vec<u8>, RLNCError> {
+        self.recover()?;
+
+        let mut padded_data = self
+            .rows
+            .into_iter()
+            .flat_map(|state| match state {
+                RowState::Decoded(source_pieces) => source_pieces,
+                RowState::Decoding(..) => Vec::new(),
+            })
+            .collect::<Vec<u8>>();
+
+        let piece_count = padded_data.len() / self.params.get_piece_byte_len();
+        let data_len = Decoder::get_final_data_len(&padded_data, piece_count, self.received_piece_count)?;
+
+        padded_data.truncate(data_len);
+        Ok(padded_data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ProductDecoder, ProductEncoder};
+    use crate::RLNCError;
+    use rand::Rng;
+
+    #[test]
+    fn test_product_code_recovers_short_rows() {
+        let mut rng = rand::rng();
+
+        let data = (0..10_000).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = ProductEncoder::new(data.clone(), 6, 8, 2).expect("Failed to create ProductEncoder");
+        let params = encoder.get_params();
+
+        let mut decoder = ProductDecoder::new(params, 6, 2).expect("Failed to create ProductDecoder");
+
+        // Rows 1 and 4 fall three and five pieces short of rank respectively, others are decoded.
+        for (row, useful_piece_count) in [8, 5, 8, 8, 3, 8].into_iter().enumerate() {
+            let mut fed_piece_count = 0;
+            while fed_piece_count < useful_piece_count {
+                if decoder.decode_row(row, &encoder.code_row(row, &mut rng).unwrap()).is_ok() {
+                    fed_piece_count += 1;
+                }
+            }
+        }
+        assert_eq!(decoder.get_decoded_row_count(), 4);
+
+        // Seven parity pieces are one short of the eight missing dimensions.
+        for parity in [0, 1, 0, 1, 0, 1, 0] {
+            decoder.decode_parity(parity, &encoder.code_parity(parity, &mut rng).unwrap()).unwrap();
+        }
+        assert_eq!(decoder.clone().recover(), Err(RLNCError::NotAllPiecesReceivedYet));
+
+        decoder.decode_parity(1, &encoder.code_parity(1, &mut rng).unwrap()).unwrap();
+        assert_eq!(decoder.clone().recover(), Ok(2));
+        assert_eq!(decoder.get_decoded_data(), Ok(data));
+    }
+
+    #[test]
+    fn test_product_code_invalid_inputs() {
+        assert!(matches!(ProductEncoder::new(vec![1; 100], 0, 4, 1), Err(RLNCError::GenerationCountZero)));
+        assert!(matches!(ProductEncoder::new(vec![1; 100], 256, 4, 1), Err(RLNCError::TooManyGenerations)));
+
+        let encoder = ProductEncoder::new(vec![1; 100], 2, 4, 1).unwrap();
+        assert_eq!(encoder.code_parity(1, &mut rand::rng()), Err(RLNCError::InvalidGenerationIndex));
+
+        let mut decoder = ProductDecoder::new(encoder.get_params(), 2, 1).unwrap();
+        assert_eq!(decoder.decode_parity(0, &[0; 3]), Err(RLNCError::InvalidPieceLength));
+        assert_eq!(decoder.decode_row(2, &[0; 3]), Err(RLNCError::InvalidGenerationIndex));
+    }
+}
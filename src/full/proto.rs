@@ -0,0 +1,266 @@
+//! Protobuf types for coded pieces and control messages, as described in `proto/rlnc.proto`, along with
+//! conversions from and to their native counterparts. Encode and decode them using `prost::Message`.
+//!
+//! Types are written by hand, to avoid a build-time dependency on `protoc`. Tag and wire type of each field is checked against
+//! the schema by a test.
+
+use crate::{RLNCError, full};
+
+/// A coded piece: coding vector, followed by coded data.
+#[derive(Clone, PartialEq, Eq, Hash, prost::Message)]
+pub struct CodedPiece {
+    /// One coding coefficient per piece coded together.
+    #[prost(bytes = "vec", tag = "1")]
+    pub coding_vector: Vec<u8>,
+    /// Linear combination of source pieces, as prescribed by coding vector.
+    #[prost(bytes = "vec", tag = "2")]
+    pub coded_data: Vec<u8>,
+}
+
+/// Receiver has collected `rank` many useful pieces of given generation.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, prost::Message)]
+pub struct Progress {
+    #[prost(uint32, tag = "1")]
+    pub generation: u32,
+    #[prost(uint32, tag = "2")]
+    pub rank: u32,
+}
+
+/// Receiver still lacks these dimensions of given generation.
+#[derive(Clone, PartialEq, Eq, Hash, prost::Message)]
+pub struct Missing {
+    #[prost(uint32, tag = "1")]
+    pub generation: u32,
+    #[prost(uint32, repeated, tag = "2")]
+    pub dimensions: Vec<u32>,
+}
+
+/// Receiver has decoded given generation, sender can stop sending its pieces.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, prost::Message)]
+pub struct Ack {
+    #[prost(uint32, tag = "1")]
+    pub generation: u32,
+}
+
+/// Feedback sent by a receiver back to the sender.
+#[derive(Clone, PartialEq, Eq, Hash, prost::Message)]
+pub struct ControlMessage {
+    #[prost(oneof = "Kind", tags = "1, 2, 3")]
+    pub kind: Option<Kind>,
+}
+
+/// Kind of control message.
+#[derive(Clone, PartialEq, Eq, Hash, prost::Oneof)]
+pub enum Kind {
+    #[prost(message, tag = "1")]
+    Progress(Progress),
+    #[prost(message, tag = "2")]
+    Missing(Missing),
+    #[prost(message, tag = "3")]
+    Ack(Ack),
+}
+
+impl From<&full::CodedPiece> for CodedPiece {
+    fn from(piece: &full::CodedPiece) -> Self {
+        CodedPiece {
+            coding_vector: piece.get_coding_vector().to_vec(),
+            coded_data: piece.get_coded_data().to_vec(),
+        }
+    }
+}
+
+impl TryFrom<CodedPiece> for full::CodedPiece {
+    type Error = RLNCError;
+
+    /// Fails with `RLNCError::PieceCountZero` if coding vector is empty, or with `RLNCError::PieceLengthZero` if coded data is empty.
+    fn try_from(piece: CodedPiece) -> Result<Self, Self::Error> {
+        if piece.coding_vector.is_empty() {
+            return Err(RLNCError::PieceCountZero);
+        }
+        if piece.coded_data.is_empty() {
+            return Err(RLNCError::PieceLengthZero);
+        }
+
+        Ok(full::CodedPiece::from_parts(piece.coding_vector, piece.coded_data))
+    }
+}
+
+impl From<&full::ControlMessage> for ControlMessage {
+    fn from(message: &full::ControlMessage) -> Self {
+        let kind = match message {
+            full::ControlMessage::Progress { generation, rank } => Kind::Progress(Progress {
+                generation: *generation,
+                rank: *rank,
+            }),
+            full::ControlMessage::Missing { generation, dimensions } => Kind::Missing(Missing {
+                generation: *generation,
+                dimensions: dimensions.clone(),
+            }),
+            full::ControlMessage::Ack { generation } => Kind::Ack(Ack { generation: *generation }),
+        };
+
+        ControlMessage { kind: Some(kind) }
+    }
+}
+
+impl TryFrom<ControlMessage> for full::ControlMessage {
+    type Error = RLNCError;
+
+    /// Fails with `RLNCError::SerializationFailure` if kind of control message is not set.
+    fn try_from(message: ControlMessage) -> Result<Self, Self::Error> {
+        match message.kind.ok_or(RLNCError::SerializationFailure)? {
+            Kind::Progress(Progress { generation, rank }) => Ok(full::ControlMessage::Progress { generation, rank }),
+            Kind::Missing(Missing { generation, dimensions }) => Ok(full::ControlMessage::Missing { generation, dimensions }),
+            Kind::Ack(Ack { generation }) => Ok(full::ControlMessage::Ack { generation }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        RLNCError,
+        full::{self, Encoder, proto},
+    };
+    use prost::Message;
+
+    #[test]
+    fn test_protobuf_coded_piece_roundtrip() {
+        let mut rng = rand::rng();
+
+        let encoder = Encoder::new(vec![42u8; 1024], 16).expect("Failed to create Encoder");
+        let piece = full::CodedPiece::from_bytes(&encoder.code(&mut rng), encoder.get_piece_count()).expect("Piece must be valid");
+
+        let encoded = proto::CodedPiece::from(&piece).encode_to_vec();
+        let decoded = proto::CodedPiece::decode(encoded.as_slice()).expect("Decoding protobuf must not fail");
+
+        assert_eq!(full::CodedPiece::try_from(decoded), Ok(piece));
+        assert_eq!(full::CodedPiece::try_from(proto::CodedPiece::default()), Err(RLNCError::PieceCountZero));
+    }
+
+    #[test]
+    fn test_protobuf_control_message_roundtrip() {
+        let messages = [
+            full::ControlMessage::Progress { generation: 1, rank: 7 },
+            full::ControlMessage::Missing {
+                generation: 2,
+                dimensions: vec![0, 3, 5],
+            },
+            full::ControlMessage::Ack { generation: 3 },
+        ];
+
+        for message in messages {
+            let encoded = proto::ControlMessage::from(&message).encode_to_vec();
+            let decoded = proto::ControlMessage::decode(encoded.as_slice()).expect("Decoding protobuf must not fail");
+
+            assert_eq!(full::ControlMessage::try_from(decoded), Ok(message));
+        }
+
+        assert_eq!(
+            full::ControlMessage::try_from(proto::ControlMessage::default()),
+            Err(RLNCError::SerializationFailure)
+        );
+    }
+
+    #[test]
+    fn test_protobuf_types_match_schema() {
+        use std::collections::HashMap;
+
+        // Fields of each message in the schema, along with their type and tag. Fields of a oneof belong to the enclosing message.
+        let mut schema = HashMap::new();
+        let mut message = None;
+        let mut in_oneof = false;
+
+        for line in include_str!("../../proto/rlnc.proto").lines().map(str::trim) {
+            if let Some(name) = line.strip_prefix("message ").and_then(|rest| rest.strip_suffix(" {")) {
+                message = Some(name);
+            } else if line.starts_with("oneof ") {
+                in_oneof = true;
+            } else if line == "}" {
+                if in_oneof {
+                    in_oneof = false;
+                } else {
+                    message = None;
+                }
+            } else if let Some(message) = message
+                && let Some((decl, tag)) = line.strip_suffix(';').and_then(|field| field.split_once(" = "))
+            {
+                let (field_type, name) = decl.rsplit_once(' ').expect("Field must have a type and a name");
+                schema.insert((message, name), (field_type, tag.parse::<u8>().expect("Tag must be a number")));
+            }
+        }
+
+        // Each field is set alone, so that the first byte of the encoding is its key i.e. tag and wire type.
+        let encodings = [
+            (
+                ("CodedPiece", "coding_vector"),
+                proto::CodedPiece {
+                    coding_vector: vec![1],
+                    ..Default::default()
+                }
+                .encode_to_vec(),
+            ),
+            (
+                ("CodedPiece", "coded_data"),
+                proto::CodedPiece {
+                    coded_data: vec![1],
+                    ..Default::default()
+                }
+                .encode_to_vec(),
+            ),
+            (("Progress", "generation"), proto::Progress { generation: 1, rank: 0 }.encode_to_vec()),
+            (("Progress", "rank"), proto::Progress { generation: 0, rank: 1 }.encode_to_vec()),
+            (
+                ("Missing", "generation"),
+                proto::Missing {
+                    generation: 1,
+                    dimensions: vec![],
+                }
+                .encode_to_vec(),
+            ),
+            (
+                ("Missing", "dimensions"),
+                proto::Missing {
+                    generation: 0,
+                    dimensions: vec![1],
+                }
+                .encode_to_vec(),
+            ),
+            (("Ack", "generation"), proto::Ack { generation: 1 }.encode_to_vec()),
+            (
+                ("ControlMessage", "progress"),
+                proto::ControlMessage {
+                    kind: Some(proto::Kind::Progress(Default::default())),
+                }
+                .encode_to_vec(),
+            ),
+            (
+                ("ControlMessage", "missing"),
+                proto::ControlMessage {
+                    kind: Some(proto::Kind::Missing(Default::default())),
+                }
+                .encode_to_vec(),
+            ),
+            (
+                ("ControlMessage", "ack"),
+                proto::ControlMessage {
+                    kind: Some(proto::Kind::Ack(Default::default())),
+                }
+                .encode_to_vec(),
+            ),
+        ];
+        assert_eq!(encodings.len(), schema.len());
+
+        for (field, encoded) in encodings {
+            let (field_type, tag) = schema.get(&field).unwrap_or_else(|| panic!("{field:?} must be in the schema"));
+
+            // Varint for scalars, length-delimited for bytes, messages and packed repeated scalars.
+            let wire_type = match *field_type {
+                "uint32" => 0,
+                "bytes" | "repeated uint32" | "Progress" | "Missing" | "Ack" => 2,
+                _ => panic!("Unexpected type of {field:?}: {field_type}"),
+            };
+            assert_eq!(encoded[0], (tag << 3) | wire_type, "Key of {field:?} must match the schema");
+        }
+    }
+}
@@ -1,6 +1,7 @@
-use super::encoder::Encoder;
-use crate::{RLNCError, common::gf256::Gf256};
+use super::{decoder::Decoder, decoder_matrix::DecoderMatrix, encoder::Encoder, nibble, params::Params, piece::CodedPiece, storage::PieceStorage};
+use crate::{RLNCError, common::simd::gf256_mul_vec_by_scalar_then_add_into_vec};
 use rand::Rng;
+use std::collections::VecDeque;
 
 /// Random Linear Network Coding (RLNC) Recoder
 ///
@@ -11,7 +12,8 @@ use rand::Rng;
 /// A recoder essentially acts as a new encoder, but it operates on the *encoded* source pieces.
 #[derive(Clone, Debug)]
 pub struct Recoder {
-    coding_vectors: Vec<Gf256>,
+    /// Coding vectors of received pieces, as a row-major matrix of bytes, one row per received piece.
+    coding_vectors: Vec<u8>,
     encoder: Encoder,
     num_pieces_received: usize,
     full_coded_piece_byte_len: usize,
@@ -19,6 +21,12 @@ pub struct Recoder {
     /// A temporary buffer to hold the random recoding vector during the recoding process.
     /// This avoids repeated allocations on each recoding operation.
     random_recoding_vector: Vec<u8>,
+    /// Whether received pieces got decoded by `Self::try_upgrade`, so that source pieces are held instead.
+    upgraded: bool,
+    /// Indices of received pieces, in order of arrival, oldest first, so that `Self::remove_oldest` knows which one to evict.
+    arrival_order: VecDeque<usize>,
+    /// Whether received pieces are strictly validated, see `Self::new_with_strict_validation`.
+    strict: bool,
 }
 
 impl Recoder {
@@ -66,6 +74,25 @@ impl Recoder {
     /// * Returns `Err(RLNCError::PieceCountZero)` if `num_pieces_coded_together` is zero.
     /// * Returns `Err(RLNCError::PieceLengthTooShort)` if `full_coded_piece_byte_len` is not greater than `num_pieces_coded_together`.
     pub fn new(data: Vec<u8>, full_coded_piece_byte_len: usize, num_pieces_coded_together: usize) -> Result<Recoder, RLNCError> {
+        Self::new_with_storage(data, full_coded_piece_byte_len, num_pieces_coded_together, Box::new(Vec::new()))
+    }
+
+    /// Same as `Self::new`, but received coded pieces, which are being recoded together, are stored in given storage,
+    /// which is emptied first. See `PieceStorage`.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Recoder)` on successful creation.
+    /// * Returns `Err(RLNCError::NotEnoughPiecesToRecode)` if the input `data` is empty or does not contain at least one full coded piece.
+    /// * Returns `Err(RLNCError::PieceLengthZero)` if `full_coded_piece_byte_len` is zero.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `num_pieces_coded_together` is zero.
+    /// * Returns `Err(RLNCError::PieceLengthTooShort)` if `full_coded_piece_byte_len` is not greater than `num_pieces_coded_together`.
+    /// * Returns `Err(RLNCError::PieceStorageFailure)` if `storage` can't grow to hold all received pieces.
+    pub fn new_with_storage(
+        data: Vec<u8>,
+        full_coded_piece_byte_len: usize,
+        num_pieces_coded_together: usize,
+        mut storage: Box<dyn PieceStorage>,
+    ) -> Result<Recoder, RLNCError> {
         if data.is_empty() {
             return Err(RLNCError::NotEnoughPiecesToRecode);
         }
@@ -81,20 +108,25 @@ impl Recoder {
 
         let piece_byte_len = full_coded_piece_byte_len - num_pieces_coded_together;
         let num_pieces_received = data.len() / full_coded_piece_byte_len;
+        if num_pieces_received == 0 {
+            return Err(RLNCError::NotEnoughPiecesToRecode);
+        }
 
         let mut coding_vectors = Vec::with_capacity(num_pieces_received * num_pieces_coded_together);
-        let mut coded_pieces = Vec::with_capacity(num_pieces_received * piece_byte_len);
 
-        data.chunks_exact(full_coded_piece_byte_len).for_each(|full_coded_piece| {
+        storage.truncate(0);
+        storage.reserve(num_pieces_received * piece_byte_len);
+
+        for full_coded_piece in data.chunks_exact(full_coded_piece_byte_len) {
             let coding_vector = &full_coded_piece[..num_pieces_coded_together];
             let coded_piece = &full_coded_piece[num_pieces_coded_together..];
 
-            coding_vectors.extend(coding_vector.iter().map(|&symbol| Gf256::new(symbol)));
-            coded_pieces.extend_from_slice(coded_piece);
-        });
+            coding_vectors.extend_from_slice(coding_vector);
+            storage.try_extend_from_slice(coded_piece)?;
+        }
 
         // Pre-allocate internal workspace buffers to avoid repeated allocations during recoding.
-        let encoder = unsafe { Encoder::without_padding(coded_pieces, num_pieces_received).unwrap_unchecked() };
+        let encoder = unsafe { Encoder::without_padding(storage, num_pieces_received).unwrap_unchecked() };
         let random_recoding_vector = vec![0u8; num_pieces_received];
 
         Ok(Recoder {
@@ -104,9 +136,91 @@ impl Recoder {
             full_coded_piece_byte_len,
             num_pieces_coded_together,
             random_recoding_vector,
+            upgraded: false,
+            arrival_order: (0..num_pieces_received).collect(),
+            strict: false,
         })
     }
 
+    /// Creates a new `Recoder` instance from a vector of received coded pieces, using already validated RLNC configuration.
+    ///
+    /// # Arguments
+    /// * `data`: A vector of bytes containing the concatenated full coded pieces, each of
+    ///   `params.get_full_coded_piece_byte_len()` bytes length.
+    /// * `params`: RLNC configuration, shared with encoder and decoders.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Recoder)` on successful creation.
+    /// * Returns `Err(RLNCError::NotEnoughPiecesToRecode)` if the input `data` is empty or does not contain at least one full coded piece.
+    pub fn new_with(data: Vec<u8>, params: Params) -> Result<Recoder, RLNCError> {
+        Self::new(data, params.get_full_coded_piece_byte_len(), params.get_piece_count())
+    }
+
+    /// Creates a new `Recoder` instance from received coded pieces, each validated against RLNC configuration.
+    ///
+    /// # Arguments
+    /// * `pieces`: Received coded pieces.
+    /// * `params`: RLNC configuration, shared with encoder and decoders.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Recoder)` on successful creation.
+    /// * Returns `Err(RLNCError::NotEnoughPiecesToRecode)` if `pieces` is empty.
+    /// * Returns `Err(RLNCError::InvalidPieceLength)` if coding vector or coded data of any piece is not of expected length.
+    pub fn from_pieces(pieces: &[CodedPiece], params: Params) -> Result<Recoder, RLNCError> {
+        if pieces.iter().any(|piece| !piece.matches(params)) {
+            return Err(RLNCError::InvalidPieceLength);
+        }
+
+        let data = pieces
+            .iter()
+            .flat_map(|piece| piece.get_coding_vector().iter().chain(piece.get_coded_data()).copied())
+            .collect::<Vec<u8>>();
+        Self::new_with(data, params)
+    }
+
+    /// Same as `Self::new`, but received pieces are strictly validated, rejecting structurally suspicious ones, instead of silently
+    /// ignoring trailing bytes or recoding pieces, which can never contribute anything. Pieces added later, using
+    /// `Self::add_coded_piece`, are validated alike.
+    ///
+    /// # Returns
+    /// * Returns `Err(RLNCError::InvalidPieceLength)` if length of `data` is not a multiple of `full_coded_piece_byte_len`.
+    /// * Returns `Err(RLNCError::ZeroCodingVector)` if any received piece carries an all-zero coding vector.
+    /// * Otherwise, returns same as `Self::new`.
+    pub fn new_with_strict_validation(data: Vec<u8>, full_coded_piece_byte_len: usize, num_pieces_coded_together: usize) -> Result<Recoder, RLNCError> {
+        if full_coded_piece_byte_len > num_pieces_coded_together && num_pieces_coded_together > 0 {
+            if !data.len().is_multiple_of(full_coded_piece_byte_len) {
+                return Err(RLNCError::InvalidPieceLength);
+            }
+            if data
+                .chunks_exact(full_coded_piece_byte_len)
+                .any(|full_coded_piece| full_coded_piece[..num_pieces_coded_together].iter().all(|&coeff| coeff == 0))
+            {
+                return Err(RLNCError::ZeroCodingVector);
+            }
+        }
+
+        let mut recoder = Self::new(data, full_coded_piece_byte_len, num_pieces_coded_together)?;
+        recoder.strict = true;
+
+        Ok(recoder)
+    }
+
+    /// Makes data-parallel recoding run on given `rayon` thread pool, instead of the global one.
+    /// See `Encoder::with_thread_pool`.
+    #[cfg(feature = "parallel")]
+    pub fn with_thread_pool(mut self, thread_pool: std::sync::Arc<rayon::ThreadPool>) -> Recoder {
+        self.encoder = self.encoder.with_thread_pool(thread_pool);
+        self
+    }
+
+    /// RLNC configuration of this recoder, to be shared with decoders.
+    pub fn get_params(&self) -> Params {
+        Params {
+            piece_count: self.num_pieces_coded_together,
+            piece_byte_len: self.get_piece_byte_len(),
+        }
+    }
+
     /// Produces a new coded piece by recoding the source pieces, random sampling coding coefficients
     /// and writing full coded piece into the provided buffer. The output buffer contains the
     /// computed source coding vector followed by the coded data. The length of `full_recoded_piece`
@@ -124,32 +238,48 @@ impl Recoder {
             return Err(RLNCError::InvalidOutputBuffer);
         }
 
-        let (computed_coding_vector, recoded_data) = full_recoded_piece.split_at_mut(self.num_pieces_coded_together);
-
-        // Compute the resulting coding vector for the original source pieces by multiplying
-        // the random sampled recoding vector by the matrix of received coding vectors.
         rng.fill_bytes(&mut self.random_recoding_vector);
 
-        for (coeff_idx, coeff_val) in computed_coding_vector.iter_mut().enumerate().take(self.num_pieces_coded_together) {
-            let computed_coeff = self
-                .random_recoding_vector
-                .iter()
-                .enumerate()
-                .fold(Gf256::default(), |acc, (recoding_vec_idx, &cur)| {
-                    let row_begins_at = recoding_vec_idx * self.num_pieces_coded_together;
-                    acc + Gf256::new(cur) * self.coding_vectors[row_begins_at + coeff_idx]
-                });
-
-            *coeff_val = computed_coeff.get();
+        let random_recoding_vector = std::mem::take(&mut self.random_recoding_vector);
+        let result = self.recode_with_coding_vector(&random_recoding_vector, full_recoded_piece);
+        self.random_recoding_vector = random_recoding_vector;
+
+        result
+    }
+
+    /// Produces a new coded piece by recoding the source pieces, using caller supplied recoding coefficients,
+    /// instead of random sampled ones. This gives deterministic relays, tests and custom schedulers full control
+    /// over the choice of linear combination. The output buffer contains the computed source coding vector i.e.
+    /// projection of the recoding vector on the coding vectors of received pieces, followed by the coded data.
+    ///
+    /// # Arguments
+    /// * `recoding_vector`: One coefficient per received piece, of length `self.get_num_pieces_recoded_together()`.
+    /// * `full_recoded_piece`: A mutable slice of bytes where the new coded piece will be written, of length `self.get_full_coded_piece_byte_len()`.
+    ///
+    /// # Returns
+    /// * Returns a `Ok(())` when successful.
+    /// * Returns `Err(RLNCError::CodingVectorLengthMismatch)` if the length of `recoding_vector` is incorrect.
+    /// * Returns `Err(RLNCError::InvalidOutputBuffer)` if the length of `full_recoded_piece` is incorrect.
+    pub fn recode_with_coding_vector(&self, recoding_vector: &[u8], full_recoded_piece: &mut [u8]) -> Result<(), RLNCError> {
+        if recoding_vector.len() != self.num_pieces_received {
+            return Err(RLNCError::CodingVectorLengthMismatch);
+        }
+        if full_recoded_piece.len() != self.full_coded_piece_byte_len {
+            return Err(RLNCError::InvalidOutputBuffer);
         }
 
-        unsafe {
-            self.encoder
-                .code_with_coding_vector(&self.random_recoding_vector, recoded_data)
-                .unwrap_unchecked()
-        };
+        let (computed_coding_vector, recoded_data) = full_recoded_piece.split_at_mut(self.num_pieces_coded_together);
 
-        Ok(())
+        // Compute the resulting coding vector for the original source pieces by multiplying
+        // the recoding vector by the matrix of received coding vectors. It's a linear combination
+        // of received coding vectors, so each of them is scaled and accumulated, using SIMD kernel.
+        computed_coding_vector.fill(0);
+        self.coding_vectors
+            .chunks_exact(self.num_pieces_coded_together)
+            .zip(recoding_vector)
+            .for_each(|(coding_vector, &recoding_coeff)| gf256_mul_vec_by_scalar_then_add_into_vec(computed_coding_vector, coding_vector, recoding_coeff));
+
+        self.encoder.code_with_coding_vector(recoding_vector, recoded_data)
     }
 
     /// Produces a new coded piece by recoding the source pieces using a randomly sampled coding vector.
@@ -169,6 +299,299 @@ impl Recoder {
 
         full_recoded_piece
     }
+
+    /// Produces as many independent recoded pieces as there are destination buffers, in one pass over buffered pieces, random
+    /// sampling a recoding vector for each, in order of buffers. Each byte of buffered pieces is read from memory once, instead
+    /// of once per recoded piece, as memory bandwidth, not arithmetic, bounds throughput of a relay. Given same random number
+    /// generator state, output is same as that of calling `Self::recode_with_buf` on each buffer, in order.
+    ///
+    /// # Arguments
+    /// * `rng`: Used to sample the random recoding vectors.
+    /// * `full_recoded_pieces`: Destination buffers, each of length `self.get_full_coded_piece_byte_len()`.
+    ///
+    /// # Returns
+    /// * Returns `Ok(())` when successful.
+    /// * Returns `Err(RLNCError::InvalidOutputBuffer)` if length of any destination buffer is incorrect.
+    pub fn recode_many_into<R: Rng + ?Sized>(&mut self, rng: &mut R, full_recoded_pieces: &mut [&mut [u8]]) -> Result<(), RLNCError> {
+        /// Byte length of the column block of buffered pieces, processed at a time, so that the same block of all destination buffers stays in cache.
+        const COLUMN_BLOCK_BYTE_LEN: usize = 4096;
+
+        if full_recoded_pieces.iter().any(|piece| piece.len() != self.full_coded_piece_byte_len) {
+            return Err(RLNCError::InvalidOutputBuffer);
+        }
+
+        let num_pieces_received = self.num_pieces_received;
+        let num_pieces_coded_together = self.num_pieces_coded_together;
+        let piece_byte_len = self.get_piece_byte_len();
+
+        let mut recoding_vectors = vec![0u8; full_recoded_pieces.len() * num_pieces_received];
+        for (recoding_vector, full_recoded_piece) in recoding_vectors.chunks_exact_mut(num_pieces_received).zip(full_recoded_pieces.iter_mut()) {
+            rng.fill_bytes(recoding_vector);
+
+            let computed_coding_vector = &mut full_recoded_piece[..num_pieces_coded_together];
+            computed_coding_vector.fill(0);
+            self.coding_vectors
+                .chunks_exact(num_pieces_coded_together)
+                .zip(recoding_vector.iter())
+                .for_each(|(coding_vector, &recoding_coeff)| gf256_mul_vec_by_scalar_then_add_into_vec(computed_coding_vector, coding_vector, recoding_coeff));
+
+            full_recoded_piece[num_pieces_coded_together..].fill(0);
+        }
+
+        for block_begin in (0..piece_byte_len).step_by(COLUMN_BLOCK_BYTE_LEN) {
+            let block_end = (block_begin + COLUMN_BLOCK_BYTE_LEN).min(piece_byte_len);
+
+            for piece_idx in 0..num_pieces_received {
+                let source_block = &unsafe { self.encoder.source_piece(piece_idx).unwrap_unchecked() }[block_begin..block_end];
+
+                for (recoding_vector, full_recoded_piece) in recoding_vectors.chunks_exact(num_pieces_received).zip(full_recoded_pieces.iter_mut()) {
+                    let recoded_block = &mut full_recoded_piece[num_pieces_coded_together + block_begin..num_pieces_coded_together + block_end];
+                    gf256_mul_vec_by_scalar_then_add_into_vec(recoded_block, source_block, recoding_vector[piece_idx]);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if received pieces got decoded by `Self::try_upgrade`, so that the recoder holds source pieces.
+    pub fn is_upgraded(&self) -> bool {
+        self.upgraded
+    }
+
+    /// Once coding vectors of received pieces span all `k` dimensions, solves for the original data and upgrades the recoder
+    /// to hold source pieces, in place of received ones, so that it can serve systematic pieces, see `Self::systematic_piece`,
+    /// which cost nothing to decode downstream. Recoding keeps working as before, now mixing source pieces. It's a no-op, if
+    /// the recoder is already upgraded. Source pieces are kept in contiguous RAM, irrespective of storage, the recoder was created with.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Vec<u8>)` holding the decoded original data.
+    /// * Returns `Err(RLNCError::NotAllPiecesReceivedYet)` if received pieces don't span all `k` dimensions, so the recoder is left as is.
+    /// * Returns `Err(RLNCError::InvalidDecodedDataFormat(_))` if decoded data lacks the boundary marker, though the recoder is still upgraded.
+    pub fn try_upgrade(&mut self) -> Result<Vec<u8>, RLNCError> {
+        let piece_count = self.num_pieces_coded_together;
+        let piece_byte_len = self.get_piece_byte_len();
+
+        let padded_data = if self.upgraded {
+            (0..piece_count)
+                .flat_map(|index| unsafe { self.encoder.source_piece(index).unwrap_unchecked() }.iter().copied())
+                .collect::<Vec<u8>>()
+        } else {
+            let mut decoder = Decoder::new(piece_byte_len, piece_count)?;
+            let mut full_coded_piece = Vec::with_capacity(self.full_coded_piece_byte_len);
+
+            for (index, coding_vector) in self.coding_vectors.chunks_exact(piece_count).enumerate() {
+                if decoder.is_already_decoded() {
+                    break;
+                }
+
+                full_coded_piece.clear();
+                full_coded_piece.extend_from_slice(coding_vector);
+                full_coded_piece.extend_from_slice(unsafe { self.encoder.source_piece(index).unwrap_unchecked() });
+
+                let _ = decoder.decode(&full_coded_piece);
+            }
+
+            if !decoder.is_already_decoded() {
+                return Err(RLNCError::NotAllPiecesReceivedYet);
+            }
+
+            let padded_data = decoder.extract_padded_data();
+
+            let mut coding_vectors = vec![0u8; piece_count * piece_count];
+            coding_vectors.iter_mut().step_by(piece_count + 1).for_each(|coeff| *coeff = 1);
+
+            self.replace_pieces(coding_vectors, padded_data.clone());
+            self.upgraded = true;
+
+            padded_data
+        };
+
+        let data_len = Decoder::get_final_data_len(&padded_data, piece_count, self.num_pieces_received)?;
+        Ok(padded_data[..data_len].to_vec())
+    }
+
+    /// Replaces buffered pieces with given ones, held in contiguous RAM, keeping the thread pool, recoding runs on.
+    fn replace_pieces(&mut self, coding_vectors: Vec<u8>, coded_pieces: Vec<u8>) {
+        let num_pieces_received = coding_vectors.len() / self.num_pieces_coded_together;
+
+        #[cfg(feature = "parallel")]
+        let thread_pool = self.encoder.get_thread_pool();
+
+        self.encoder = unsafe { Encoder::without_padding(Box::new(coded_pieces), num_pieces_received).unwrap_unchecked() };
+        #[cfg(feature = "parallel")]
+        if let Some(thread_pool) = thread_pool {
+            self.encoder = self.encoder.clone().with_thread_pool(thread_pool);
+        }
+
+        self.coding_vectors = coding_vectors;
+        self.num_pieces_received = num_pieces_received;
+        self.random_recoding_vector = vec![0u8; num_pieces_received];
+        self.arrival_order = (0..num_pieces_received).collect();
+    }
+
+    /// Adds a full coded piece, received after the recoder got created, to the pieces being recoded together, so that a relay's
+    /// recoding pool grows as pieces arrive, without creating a new recoder and copying all buffered pieces. It's a no-op, if the
+    /// recoder is upgraded, see `Self::try_upgrade`, as it can't gain anything more.
+    ///
+    /// # Arguments
+    /// * `full_coded_piece`: Coding vector followed by coded data, of `self.get_full_coded_piece_byte_len()` bytes.
+    ///
+    /// # Returns
+    /// * Returns `Ok(())` when successful.
+    /// * Returns `Err(RLNCError::InvalidPieceLength)` if length of `full_coded_piece` is incorrect.
+    /// * Returns `Err(RLNCError::ZeroCodingVector)` if strict validation is enabled and the coding vector is all-zero.
+    /// * Returns `Err(RLNCError::PieceStorageFailure)` if storage, the recoder was created with, can't grow to hold the piece.
+    pub fn add_coded_piece(&mut self, full_coded_piece: &[u8]) -> Result<(), RLNCError> {
+        if full_coded_piece.len() != self.full_coded_piece_byte_len {
+            return Err(RLNCError::InvalidPieceLength);
+        }
+        if self.strict && full_coded_piece[..self.num_pieces_coded_together].iter().all(|&coeff| coeff == 0) {
+            return Err(RLNCError::ZeroCodingVector);
+        }
+        if self.upgraded {
+            return Ok(());
+        }
+
+        let (coding_vector, coded_piece) = full_coded_piece.split_at(self.num_pieces_coded_together);
+        self.encoder.push_source_piece(coded_piece)?;
+        self.coding_vectors.extend_from_slice(coding_vector);
+
+        self.arrival_order.push_back(self.num_pieces_received);
+        self.num_pieces_received += 1;
+        self.random_recoding_vector.push(0);
+
+        Ok(())
+    }
+
+    /// Removes the oldest received piece, which is still being recoded together, say to keep a bounded recoding pool rotating over
+    /// the latest pieces. Only the last received piece is moved in its place, so that order of pieces, as expected by
+    /// `Self::recode_with_coding_vector`, changes. It's a no-op, if the recoder is upgraded, see `Self::try_upgrade`.
+    ///
+    /// # Returns
+    /// * Returns `Some(Vec<u8>)` holding the removed full coded piece.
+    /// * Returns `None` if the recoder is upgraded or holds only one piece, as there's nothing to recode without it.
+    pub fn remove_oldest(&mut self) -> Option<Vec<u8>> {
+        if self.upgraded || self.num_pieces_received == 1 {
+            return None;
+        }
+
+        let piece_count = self.num_pieces_coded_together;
+        let index = self.arrival_order.pop_front()?;
+        let last_index = self.num_pieces_received - 1;
+
+        let mut full_coded_piece = self.coding_vectors[index * piece_count..(index + 1) * piece_count].to_vec();
+        full_coded_piece.extend_from_slice(unsafe { self.encoder.source_piece(index).unwrap_unchecked() });
+
+        self.coding_vectors
+            .copy_within(last_index * piece_count..(last_index + 1) * piece_count, index * piece_count);
+        self.coding_vectors.truncate(last_index * piece_count);
+        self.encoder.swap_remove_source_piece(index);
+
+        if let Some(moved) = self.arrival_order.iter_mut().find(|moved| **moved == last_index) {
+            *moved = index;
+        }
+
+        self.num_pieces_received -= 1;
+        self.random_recoding_vector.pop();
+
+        Some(full_coded_piece)
+    }
+
+    /// Merges pieces buffered by another recoder of the same generation into this one, say after two receive threads buffered
+    /// pieces independently, so that a multi-socket relay doesn't have to funnel all receive paths through one recoder. Combined
+    /// pieces are deduplicated, keeping only the ones, whose coding vectors are linearly independent of the ones kept before them,
+    /// this recoder's pieces first, so that at max `k` pieces are recoded together afterwards. Recoding power is unchanged, as kept
+    /// pieces span the same space as all combined pieces. If either recoder is upgraded, see `Self::try_upgrade`, the result is upgraded
+    /// too, as it can't gain anything more. Pieces are kept in contiguous RAM, irrespective of storage, the recoder was created with.
+    ///
+    /// # Returns
+    /// * Returns `Ok(usize)` holding the number of pieces recoded together, after merge.
+    /// * Returns `Err(RLNCError::ParamsMismatch)` if `other` doesn't share RLNC configuration with this recoder, which is left as is.
+    /// * Returns `Err(RLNCError::NotEnoughPiecesToRecode)` if all combined pieces carry all-zero coding vectors, so the recoder is left as is.
+    pub fn merge(&mut self, other: Recoder) -> Result<usize, RLNCError> {
+        if other.get_params() != self.get_params() {
+            return Err(RLNCError::ParamsMismatch);
+        }
+        if self.upgraded {
+            return Ok(self.num_pieces_received);
+        }
+        if other.upgraded {
+            #[cfg(feature = "parallel")]
+            let other = match self.encoder.get_thread_pool() {
+                Some(thread_pool) => other.with_thread_pool(thread_pool),
+                None => other,
+            };
+
+            *self = other;
+            return Ok(self.num_pieces_received);
+        }
+
+        let piece_count = self.num_pieces_coded_together;
+        let piece_byte_len = self.get_piece_byte_len();
+
+        let mut span = DecoderMatrix::new(piece_count, 0);
+        let mut coding_vectors = Vec::with_capacity(piece_count * piece_count);
+        let mut coded_pieces = Vec::with_capacity(piece_count * piece_byte_len);
+
+        for recoder in [&*self, &other] {
+            for (index, coding_vector) in recoder.coding_vectors.chunks_exact(piece_count).enumerate() {
+                if span.rank() == piece_count {
+                    break;
+                }
+
+                let rank = span.rank();
+                if span.add_row(coding_vector)?.rref().rank() > rank {
+                    coding_vectors.extend_from_slice(coding_vector);
+                    coded_pieces.extend_from_slice(unsafe { recoder.encoder.source_piece(index).unwrap_unchecked() });
+                }
+            }
+        }
+
+        if coding_vectors.is_empty() {
+            return Err(RLNCError::NotEnoughPiecesToRecode);
+        }
+
+        self.replace_pieces(coding_vectors, coded_pieces);
+        Ok(self.num_pieces_received)
+    }
+
+    /// Returns a systematic piece i.e. unit coding vector, with one at `index`, followed by the source piece at `index`,
+    /// once the recoder is upgraded, see `Self::try_upgrade`.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Vec<u8>)` holding the full coded piece.
+    /// * Returns `Err(RLNCError::NotAllPiecesReceivedYet)` if the recoder is not upgraded yet.
+    /// * Returns `Err(RLNCError::InvalidPieceIndex)` if `index` is not less than `self.get_original_num_pieces_coded_together()`.
+    pub fn systematic_piece(&self, index: usize) -> Result<Vec<u8>, RLNCError> {
+        if !self.upgraded {
+            return Err(RLNCError::NotAllPiecesReceivedYet);
+        }
+
+        let source_piece = self.encoder.source_piece(index)?;
+
+        let mut full_coded_piece = vec![0u8; self.num_pieces_coded_together];
+        full_coded_piece[index] = 1;
+        full_coded_piece.extend_from_slice(source_piece);
+
+        Ok(full_coded_piece)
+    }
+
+    /// Produces a new nibble-packed coded piece, see `Encoder::code_nibble_packed`, by recoding the source pieces, using recoding
+    /// coefficients random sampled from the GF(2^4) subfield. Source pieces must be coded with subfield coefficients too, say
+    /// nibble-packed ones, unpacked using `full::nibble_unpack_piece`, so that the recoded coding vector stays in the subfield.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Vec<u8>)` holding packed coding vector followed by recoded data.
+    /// * Returns `Err(RLNCError::CoefficientNotInSubfield)` if some source piece isn't coded with subfield coefficients.
+    pub fn recode_nibble_packed<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Result<Vec<u8>, RLNCError> {
+        nibble::sample_subfield_coding_vector(rng, &mut self.random_recoding_vector);
+
+        let mut full_recoded_piece = vec![0u8; self.get_full_coded_piece_byte_len()];
+        self.recode_with_coding_vector(&self.random_recoding_vector, &mut full_recoded_piece)?;
+
+        nibble::nibble_pack_piece(&full_recoded_piece, self.num_pieces_coded_together)
+    }
 }
 
 #[cfg(test)]
@@ -302,6 +725,53 @@ mod tests {
         assert!(result_valid.is_ok());
     }
 
+    #[test]
+    fn test_recoder_recode_with_coding_vector() {
+        let mut rng = rand::rng();
+
+        let data = (0..1024).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data, 16).expect("Failed to create Encoder for recode with coding vector test");
+
+        let coded_pieces = (0..4).map(|_| encoder.code(&mut rng)).collect::<Vec<_>>();
+        let recoder = Recoder::new(coded_pieces.concat(), encoder.get_full_coded_piece_byte_len(), encoder.get_piece_count())
+            .expect("Failed to create Recoder for recode with coding vector test");
+
+        let mut recoded_piece = vec![0u8; recoder.get_full_coded_piece_byte_len()];
+
+        // Unit recoding vector selects a single received piece, as is.
+        for (piece_idx, coded_piece) in coded_pieces.iter().enumerate() {
+            let mut recoding_vector = vec![0u8; recoder.get_num_pieces_recoded_together()];
+            recoding_vector[piece_idx] = 1;
+
+            recoder
+                .recode_with_coding_vector(&recoding_vector, &mut recoded_piece)
+                .expect("Recoding must not fail");
+            assert_eq!(&recoded_piece, coded_piece);
+        }
+
+        // Recoded piece, with caller supplied coefficients, is consistent with the projected source coding vector.
+        let recoding_vector = (0..recoder.get_num_pieces_recoded_together()).map(|_| rng.random()).collect::<Vec<u8>>();
+        recoder
+            .recode_with_coding_vector(&recoding_vector, &mut recoded_piece)
+            .expect("Recoding must not fail");
+
+        let (source_coding_vector, recoded_data) = recoded_piece.split_at(encoder.get_piece_count());
+        let mut coded_data = vec![0u8; encoder.get_piece_byte_len()];
+        encoder
+            .code_with_coding_vector(source_coding_vector, &mut coded_data)
+            .expect("Coding must not fail");
+        assert_eq!(recoded_data, coded_data);
+
+        assert_eq!(
+            recoder.recode_with_coding_vector(&recoding_vector[1..], &mut recoded_piece),
+            Err(RLNCError::CodingVectorLengthMismatch)
+        );
+        assert_eq!(
+            recoder.recode_with_coding_vector(&recoding_vector, &mut recoded_piece[1..]),
+            Err(RLNCError::InvalidOutputBuffer)
+        );
+    }
+
     #[test]
     fn test_recoder_getters() {
         let mut rng = rand::rng();
@@ -329,4 +799,165 @@ mod tests {
         assert_eq!(recoder.get_piece_byte_len(), original_piece_byte_len);
         assert_eq!(recoder.get_full_coded_piece_byte_len(), full_coded_piece_byte_len);
     }
+
+    #[test]
+    fn test_recoder_strict_validation() {
+        let mut rng = rand::rng();
+
+        let encoder = Encoder::new(vec![7u8; 1024], 8).expect("Failed to create Encoder for strict validation test");
+        let full_coded_piece_byte_len = encoder.get_full_coded_piece_byte_len();
+
+        let mut data = (0..4).flat_map(|_| encoder.code(&mut rng)).collect::<Vec<u8>>();
+        assert!(Recoder::new_with_strict_validation(data.clone(), full_coded_piece_byte_len, 8).is_ok());
+
+        // Trailing bytes are silently ignored by a lenient recoder, but not by a strict one.
+        data.push(0);
+        assert!(Recoder::new(data.clone(), full_coded_piece_byte_len, 8).is_ok());
+        assert!(matches!(
+            Recoder::new_with_strict_validation(data.clone(), full_coded_piece_byte_len, 8),
+            Err(RLNCError::InvalidPieceLength)
+        ));
+
+        data.pop();
+        let mut zero_piece = encoder.code(&mut rng);
+        zero_piece[..8].fill(0);
+
+        // Pieces added later, are validated alike.
+        let mut recoder = Recoder::new(data.clone(), full_coded_piece_byte_len, 8).expect("Recoder must be valid");
+        assert_eq!(recoder.add_coded_piece(&zero_piece), Ok(()));
+
+        let mut recoder = Recoder::new_with_strict_validation(data.clone(), full_coded_piece_byte_len, 8).expect("Recoder must be valid");
+        assert_eq!(recoder.add_coded_piece(&zero_piece), Err(RLNCError::ZeroCodingVector));
+        assert_eq!(recoder.add_coded_piece(&encoder.code(&mut rng)), Ok(()));
+        assert_eq!(recoder.get_num_pieces_recoded_together(), 5);
+
+        data[full_coded_piece_byte_len..full_coded_piece_byte_len + 8].fill(0);
+        assert!(matches!(
+            Recoder::new_with_strict_validation(data, full_coded_piece_byte_len, 8),
+            Err(RLNCError::ZeroCodingVector)
+        ));
+    }
+
+    #[test]
+    fn test_recoder_try_upgrade() {
+        let mut rng = rand::rng();
+
+        let data = (0..1000).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 8).expect("Failed to create Encoder for recoder upgrade test");
+        let params = encoder.get_params();
+
+        let mut recoder = Recoder::new_with((0..5).flat_map(|_| encoder.code(&mut rng)).collect(), params).unwrap();
+        assert_eq!(recoder.try_upgrade(), Err(RLNCError::NotAllPiecesReceivedYet));
+        assert_eq!(recoder.systematic_piece(0), Err(RLNCError::NotAllPiecesReceivedYet));
+        assert_eq!(recoder.get_num_pieces_recoded_together(), 5);
+
+        let mut recoder = Recoder::new_with((0..10).flat_map(|_| encoder.code(&mut rng)).collect(), params).unwrap();
+        assert_eq!(recoder.try_upgrade(), Ok(data.clone()));
+        assert!(recoder.is_upgraded());
+        assert_eq!(recoder.try_upgrade(), Ok(data.clone()));
+        assert_eq!(recoder.systematic_piece(8), Err(RLNCError::InvalidPieceIndex));
+
+        // Systematic pieces are exactly the source pieces, while recoded ones still decode.
+        let mut decoder = crate::full::Decoder::new_with(params);
+        for index in 0..4 {
+            let piece = recoder.systematic_piece(index).unwrap();
+            assert_eq!(&piece[params.get_piece_count()..], encoder.source_piece(index).unwrap());
+            decoder.decode(&piece).expect("Systematic piece must be useful");
+        }
+        while !decoder.is_already_decoded() {
+            let _ = decoder.decode(&recoder.recode(&mut rng));
+        }
+        assert_eq!(decoder.get_decoded_data(), Ok(data));
+    }
+
+    #[test]
+    fn test_recoder_recode_many_into() {
+        use crate::full::SessionRng;
+
+        let mut rng = rand::rng();
+
+        let data = (0..20_000).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data, 4).expect("Failed to create Encoder for scatter recoding test");
+        let mut recoder = Recoder::new_with((0..6).flat_map(|_| encoder.code(&mut rng)).collect(), encoder.get_params()).unwrap();
+
+        let full_coded_piece_byte_len = recoder.get_full_coded_piece_byte_len();
+        let mut scattered = vec![vec![0xffu8; full_coded_piece_byte_len]; 5];
+        let mut destinations = scattered.iter_mut().map(|piece| piece.as_mut_slice()).collect::<Vec<_>>();
+        recoder.recode_many_into(&mut SessionRng::new(3), &mut destinations).unwrap();
+
+        let mut session_rng = SessionRng::new(3);
+        for piece in &scattered {
+            let mut expected = vec![0u8; full_coded_piece_byte_len];
+            recoder.recode_with_buf(&mut session_rng, &mut expected).unwrap();
+            assert_eq!(piece, &expected);
+        }
+
+        let mut short_piece = vec![0u8; full_coded_piece_byte_len - 1];
+        assert_eq!(
+            recoder.recode_many_into(&mut rng, &mut [short_piece.as_mut_slice()]),
+            Err(RLNCError::InvalidOutputBuffer)
+        );
+    }
+
+    #[test]
+    fn test_recoder_add_coded_piece_and_remove_oldest() {
+        let mut rng = rand::rng();
+
+        let data = (0..1000).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 8).expect("Failed to create Encoder for recoder add coded piece test");
+        let params = encoder.get_params();
+
+        // Systematic pieces are linearly independent, so that the recoder upgrades, only if it holds all of them.
+        let pieces = encoder.systematic_pieces().collect::<Vec<_>>();
+        let mut recoder = Recoder::new_with(pieces[..2].concat(), params).unwrap();
+        assert_eq!(recoder.add_coded_piece(&[0; 3]), Err(RLNCError::InvalidPieceLength));
+
+        pieces[2..].iter().for_each(|piece| recoder.add_coded_piece(piece).unwrap());
+        assert_eq!(recoder.get_num_pieces_recoded_together(), 8);
+        assert_eq!(recoder.recode(&mut rng).len(), params.get_full_coded_piece_byte_len());
+
+        // Oldest pieces get evicted first, while the rest still pair up with their own coding vectors.
+        assert_eq!(recoder.remove_oldest(), Some(pieces[0].clone()));
+        assert_eq!(recoder.remove_oldest(), Some(pieces[1].clone()));
+        assert_eq!(recoder.get_num_pieces_recoded_together(), 6);
+        assert_eq!(recoder.try_upgrade(), Err(RLNCError::NotAllPiecesReceivedYet));
+
+        recoder.add_coded_piece(&pieces[0]).unwrap();
+        assert_eq!(recoder.remove_oldest(), Some(pieces[2].clone()));
+        recoder.add_coded_piece(&pieces[1]).unwrap();
+        recoder.add_coded_piece(&pieces[2]).unwrap();
+        assert_eq!(recoder.try_upgrade(), Ok(data));
+
+        recoder.add_coded_piece(&pieces[0]).unwrap();
+        assert_eq!(recoder.get_num_pieces_recoded_together(), 8);
+        assert_eq!(recoder.remove_oldest(), None);
+        assert_eq!(Recoder::new_with(pieces[0].clone(), params).unwrap().remove_oldest(), None);
+    }
+
+    #[test]
+    fn test_recoder_merge() {
+        let mut rng = rand::rng();
+
+        let data = (0..1000).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 8).expect("Failed to create Encoder for recoder merge test");
+        let params = encoder.get_params();
+
+        // Both receive threads buffered two pieces in common, and the first one a duplicate too.
+        let pieces = (0..8).map(|_| encoder.code(&mut rng)).collect::<Vec<_>>();
+        let first = [0, 1, 2, 3, 4, 0].iter().flat_map(|&index| pieces[index].clone()).collect::<Vec<u8>>();
+        let second = [3, 4, 5, 6, 7].iter().flat_map(|&index| pieces[index].clone()).collect::<Vec<u8>>();
+
+        let mut recoder = Recoder::new_with(first, params).unwrap();
+        let other = Recoder::new_with(second, params).unwrap();
+        assert_eq!(recoder.merge(other), Ok(8));
+        assert_eq!(recoder.try_upgrade(), Ok(data.clone()));
+
+        let mut partial = Recoder::new_with(pieces[0].clone(), params).unwrap();
+        assert_eq!(partial.merge(recoder.clone()), Ok(8));
+        assert!(partial.is_upgraded());
+
+        let mismatched = Encoder::new(data, 4).unwrap();
+        let mismatched = Recoder::new_with(mismatched.code(&mut rng), mismatched.get_params()).unwrap();
+        assert_eq!(recoder.merge(mismatched), Err(RLNCError::ParamsMismatch));
+    }
 }
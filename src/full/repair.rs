@@ -0,0 +1,280 @@
+use super::{decoder_matrix::DecoderMatrix, recoder::Recoder};
+use crate::{
+    RLNCError,
+    common::simd::{gf256_inplace_add_vectors, gf256_mul_vec_by_scalar_then_add_into_vec},
+};
+use rand::Rng;
+
+/// A coded piece of a generation, which survives on some storage node, described by its coding vector only.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SurvivingPiece {
+    /// Identifier of the storage node, holding the piece.
+    pub node: usize,
+    /// Coding vector of the piece, of length `piece_count`.
+    pub coding_vector: Vec<u8>,
+}
+
+/// What a surviving node has to do for a functional repair: recode its local pieces together, using given recoding
+/// vector, and send the single resulting piece to the newcomer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecodeRequest {
+    /// Identifier of the storage node, which recodes.
+    pub node: usize,
+    /// Indices of pieces to be recoded together, into the slice of surviving pieces, the plan was computed over.
+    pub pieces: Vec<usize>,
+    /// One non-zero recoding coefficient per piece, in the order of `pieces`.
+    pub recoding_vector: Vec<u8>,
+    piece_count: usize,
+}
+
+impl RecodeRequest {
+    /// Executes the request on the surviving node, recoding its local pieces together.
+    ///
+    /// # Arguments
+    /// * `full_coded_pieces` - Full coded pieces, in the order of `self.pieces`.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Vec<u8>)` holding the recoded full coded piece, to be sent to the newcomer.
+    /// * Returns `Err(RLNCError::CodingVectorLengthMismatch)` if number of pieces doesn't match `self.pieces`.
+    /// * Returns `Err(RLNCError::PieceLengthTooShort)` if pieces are not longer than their coding vector.
+    /// * Returns `Err(RLNCError::InvalidPieceLength)` if pieces are of different length.
+    pub fn execute(&self, full_coded_pieces: &[&[u8]]) -> Result<Vec<u8>, RLNCError> {
+        if full_coded_pieces.len() != self.pieces.len() {
+            return Err(RLNCError::CodingVectorLengthMismatch);
+        }
+
+        let full_coded_piece_byte_len = full_coded_pieces[0].len();
+        if full_coded_pieces.iter().any(|piece| piece.len() != full_coded_piece_byte_len) {
+            return Err(RLNCError::InvalidPieceLength);
+        }
+
+        let recoder = Recoder::new(full_coded_pieces.concat(), full_coded_piece_byte_len, self.piece_count)?;
+
+        let mut full_recoded_piece = vec![0u8; full_coded_piece_byte_len];
+        recoder.recode_with_coding_vector(&self.recoding_vector, &mut full_recoded_piece)?;
+
+        Ok(full_recoded_piece)
+    }
+}
+
+/// Bandwidth-efficient functional repair plan, for regenerating a lost coded piece of a generation.
+///
+/// Instead of pulling `k` full pieces to the newcomer, each contacted node recodes its local pieces into a single piece,
+/// and the newcomer sums these partial recombinations up. Only nodes, which grow the span of already contacted ones, are
+/// contacted, preferring the ones holding more pieces. The regenerated piece is a random combination over the span of all
+/// surviving pieces, not a copy of the lost one, so it's as useful as a freshly coded piece, given the survivors span all `k` dimensions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RepairPlan {
+    requests: Vec<RecodeRequest>,
+    coding_vector: Vec<u8>,
+    surviving_rank: usize,
+}
+
+impl RepairPlan {
+    /// Computes a functional repair plan, given the census of surviving pieces of a generation.
+    ///
+    /// # Arguments
+    /// * `piece_count` - Number of pieces coded together.
+    /// * `survivors` - Surviving pieces of the generation, along with the node holding each.
+    /// * `rng` - Randomness source for sampling recoding vectors.
+    ///
+    /// # Returns
+    /// * Returns `Ok(RepairPlan)` on success.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `piece_count` is zero.
+    /// * Returns `Err(RLNCError::CodingVectorLengthMismatch)` if any coding vector is not of length `piece_count`.
+    /// * Returns `Err(RLNCError::NotEnoughPiecesToRecode)` if surviving pieces don't span any dimension.
+    pub fn new<R: Rng + ?Sized>(piece_count: usize, survivors: &[SurvivingPiece], rng: &mut R) -> Result<RepairPlan, RLNCError> {
+        if piece_count == 0 {
+            return Err(RLNCError::PieceCountZero);
+        }
+        if survivors.iter().any(|piece| piece.coding_vector.len() != piece_count) {
+            return Err(RLNCError::CodingVectorLengthMismatch);
+        }
+
+        // Group pieces by node, in order of first appearance, then prefer nodes holding more pieces, as each node sends
+        // a single piece, no matter how many it recodes together.
+        let mut nodes: Vec<(usize, Vec<usize>)> = Vec::new();
+        for (pidx, piece) in survivors.iter().enumerate() {
+            match nodes.iter_mut().find(|(node, _)| *node == piece.node) {
+                Some((_, pieces)) => pieces.push(pidx),
+                None => nodes.push((piece.node, vec![pidx])),
+            }
+        }
+        nodes.sort_by_key(|(_, pieces)| std::cmp::Reverse(pieces.len()));
+
+        let mut span = DecoderMatrix::new(piece_count, 0);
+        for piece in survivors {
+            span.add_row(&piece.coding_vector)?.rref();
+        }
+
+        let surviving_rank = span.rank();
+        if surviving_rank == 0 {
+            return Err(RLNCError::NotEnoughPiecesToRecode);
+        }
+
+        span.clear();
+
+        let mut requests = Vec::new();
+        let mut coding_vector = vec![0u8; piece_count];
+
+        for (node, pieces) in nodes {
+            if span.rank() == surviving_rank {
+                break;
+            }
+
+            let mut grown_span = span.clone();
+            for &pidx in &pieces {
+                grown_span.add_row(&survivors[pidx].coding_vector)?.rref();
+            }
+            if grown_span.rank() == span.rank() {
+                continue;
+            }
+            span = grown_span;
+
+            let recoding_vector = (0..pieces.len()).map(|_| rng.random_range(1..=u8::MAX)).collect::<Vec<u8>>();
+            let request = RecodeRequest {
+                node,
+                pieces,
+                recoding_vector,
+                piece_count,
+            };
+
+            // Coding vector of the regenerated piece is the sum of the coding vectors of all partial recombinations.
+            for (&pidx, &coeff) in request.pieces.iter().zip(&request.recoding_vector) {
+                gf256_mul_vec_by_scalar_then_add_into_vec(&mut coding_vector, &survivors[pidx].coding_vector, coeff);
+            }
+
+            requests.push(request);
+        }
+
+        Ok(RepairPlan {
+            requests,
+            coding_vector,
+            surviving_rank,
+        })
+    }
+
+    /// Per-node recode requests, one for each contacted node.
+    pub fn get_requests(&self) -> &[RecodeRequest] {
+        &self.requests
+    }
+
+    /// Coding vector of the regenerated piece, which can be used to validate the result of `Self::combine`.
+    pub fn get_coding_vector(&self) -> &[u8] {
+        &self.coding_vector
+    }
+
+    /// Number of pieces the newcomer downloads, following this plan i.e. one per contacted node.
+    pub fn get_download_piece_count(&self) -> usize {
+        self.requests.len()
+    }
+
+    /// Number of full pieces the newcomer would download, if it pulled linearly independent surviving pieces, instead of
+    /// partial recombinations. It's the rank of surviving pieces, which is `k`, if the generation is still recoverable.
+    pub fn get_full_download_piece_count(&self) -> usize {
+        self.surviving_rank
+    }
+
+    /// Final combination step, run by the newcomer. Sums up partial recombinations, sent by contacted nodes, into the
+    /// regenerated full coded piece.
+    ///
+    /// # Arguments
+    /// * `recoded_pieces` - Full coded pieces, produced by `RecodeRequest::execute`, in the order of `Self::get_requests`.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Vec<u8>)` holding the regenerated full coded piece.
+    /// * Returns `Err(RLNCError::NotEnoughPiecesToRecode)` if number of recoded pieces doesn't match number of requests.
+    /// * Returns `Err(RLNCError::InvalidPieceLength)` if recoded pieces are of different length or not longer than their coding vector.
+    pub fn combine(&self, recoded_pieces: &[&[u8]]) -> Result<Vec<u8>, RLNCError> {
+        if recoded_pieces.len() != self.requests.len() {
+            return Err(RLNCError::NotEnoughPiecesToRecode);
+        }
+
+        let full_coded_piece_byte_len = recoded_pieces[0].len();
+        if full_coded_piece_byte_len <= self.coding_vector.len() || recoded_pieces.iter().any(|piece| piece.len() != full_coded_piece_byte_len) {
+            return Err(RLNCError::InvalidPieceLength);
+        }
+
+        let mut regenerated_piece = vec![0u8; full_coded_piece_byte_len];
+        recoded_pieces.iter().for_each(|piece| gf256_inplace_add_vectors(&mut regenerated_piece, piece));
+
+        Ok(regenerated_piece)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RepairPlan, SurvivingPiece};
+    use crate::{
+        RLNCError,
+        full::{Decoder, Encoder},
+    };
+    use rand::Rng;
+
+    #[test]
+    fn test_functional_repair() {
+        let mut rng = rand::rng();
+
+        let data = (0..1024).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 8).expect("Failed to create Encoder for functional repair test");
+        let piece_count = encoder.get_piece_count();
+
+        // Six nodes, holding three coded pieces each.
+        let pieces = (0..18).map(|_| encoder.code(&mut rng)).collect::<Vec<_>>();
+        let survivors = pieces
+            .iter()
+            .enumerate()
+            .map(|(pidx, piece)| SurvivingPiece {
+                node: pidx / 3,
+                coding_vector: piece[..piece_count].to_vec(),
+            })
+            .collect::<Vec<_>>();
+
+        let plan = RepairPlan::new(piece_count, &survivors, &mut rng).expect("Survivors must be repairable");
+        assert_eq!(plan.get_full_download_piece_count(), piece_count);
+        assert_eq!(plan.get_download_piece_count(), 3);
+
+        let recoded_pieces = plan
+            .get_requests()
+            .iter()
+            .map(|request| {
+                let local_pieces = request.pieces.iter().map(|&pidx| pieces[pidx].as_slice()).collect::<Vec<_>>();
+                request.execute(&local_pieces).expect("Recoding local pieces must not fail")
+            })
+            .collect::<Vec<_>>();
+
+        let regenerated_piece = plan
+            .combine(&recoded_pieces.iter().map(|piece| piece.as_slice()).collect::<Vec<_>>())
+            .expect("Combination must not fail");
+        assert_eq!(&regenerated_piece[..piece_count], plan.get_coding_vector());
+
+        // Regenerated piece is as good as a freshly coded one, so it decodes along with other pieces.
+        let mut decoder = Decoder::new_with(encoder.get_params());
+        decoder.decode(&regenerated_piece).expect("Regenerated piece must be useful");
+        for piece in &pieces {
+            if decoder.is_already_decoded() {
+                break;
+            }
+            let _ = decoder.decode(piece);
+        }
+
+        assert_eq!(decoder.get_decoded_data(), Ok(data));
+    }
+
+    #[test]
+    fn test_functional_repair_invalid_inputs() {
+        let mut rng = rand::rng();
+
+        let survivor = SurvivingPiece {
+            node: 0,
+            coding_vector: vec![0; 4],
+        };
+
+        assert_eq!(RepairPlan::new(0, &[], &mut rng), Err(RLNCError::PieceCountZero));
+        assert_eq!(
+            RepairPlan::new(5, std::slice::from_ref(&survivor), &mut rng),
+            Err(RLNCError::CodingVectorLengthMismatch)
+        );
+        assert_eq!(RepairPlan::new(4, &[survivor], &mut rng), Err(RLNCError::NotEnoughPiecesToRecode));
+    }
+}
@@ -0,0 +1,173 @@
+use super::control::ControlMessage;
+use rand::{Rng, seq::index};
+
+/// Samples coding vectors, adapting their density to the receiver's rank deficit, as reported back via `ControlMessage`s.
+///
+/// While the receiver is far from full rank, almost any piece is useful, so sparse coding vectors with a few non-zero
+/// coefficients are sampled, which makes coding cheaper. Once the rank deficit drops to a threshold, dense coding vectors
+/// with all coefficients non-zero are sampled, minimizing the probability of linearly dependent last pieces, which dominate
+/// tail latency. If the receiver reports its missing dimensions, coding vectors are targeted at them i.e. they are non-zero
+/// only over the missing dimensions, which makes each of them useful for sure.
+///
+/// Use sampled coding vectors with `Encoder::code_with_coding_vector`, and recoding vectors with `Recoder::recode_with_coding_vector`.
+#[derive(Clone, Debug)]
+pub struct AdaptiveSampler {
+    piece_count: usize,
+    sparse_nonzero_count: usize,
+    dense_rank_deficit: usize,
+    /// Rank of the receiver, as last reported.
+    rank: usize,
+    /// Dimensions the receiver lacks, as last reported. It's empty, unless reported.
+    missing_dimensions: Vec<usize>,
+}
+
+impl AdaptiveSampler {
+    /// Creates a new sampler, which assumes the receiver has nothing yet.
+    ///
+    /// # Arguments
+    /// * `piece_count` - Number of pieces coded together.
+    /// * `sparse_nonzero_count` - Number of non-zero coefficients in sparse coding vectors, clamped to `1..=piece_count`.
+    /// * `dense_rank_deficit` - Dense coding vectors are sampled, once the receiver lacks at max these many useful pieces.
+    pub fn new(piece_count: usize, sparse_nonzero_count: usize, dense_rank_deficit: usize) -> AdaptiveSampler {
+        AdaptiveSampler {
+            piece_count,
+            sparse_nonzero_count: sparse_nonzero_count.clamp(1, piece_count.max(1)),
+            dense_rank_deficit,
+            rank: 0,
+            missing_dimensions: Vec::new(),
+        }
+    }
+
+    /// Number of useful pieces the receiver lacks, as last reported.
+    pub fn get_rank_deficit(&self) -> usize {
+        self.piece_count.saturating_sub(self.rank)
+    }
+
+    /// Returns `true` if dense coding vectors are being sampled, as the receiver is close to full rank.
+    pub fn is_dense(&self) -> bool {
+        self.get_rank_deficit() <= self.dense_rank_deficit
+    }
+
+    /// Updates the receiver's state, based on its feedback. Dimensions outside of `0..piece_count` are ignored.
+    pub fn on_control_message(&mut self, message: &ControlMessage) {
+        match message {
+            ControlMessage::Progress { rank, .. } => {
+                self.rank = (*rank as usize).min(self.piece_count);
+                self.missing_dimensions.clear();
+            }
+            ControlMessage::Missing { dimensions, .. } => {
+                self.missing_dimensions = dimensions
+                    .iter()
+                    .map(|&dimension| dimension as usize)
+                    .filter(|&dimension| dimension < self.piece_count)
+                    .collect();
+                self.missing_dimensions.sort_unstable();
+                self.missing_dimensions.dedup();
+
+                self.rank = self.piece_count - self.missing_dimensions.len();
+            }
+            ControlMessage::Ack { .. } => {
+                self.rank = self.piece_count;
+                self.missing_dimensions.clear();
+            }
+        }
+    }
+
+    /// Fills a coding vector of `piece_count` coefficients, over source pieces, for the encoder. It's targeted at missing
+    /// dimensions, if they were reported, otherwise dense or sparse, depending on the receiver's rank deficit.
+    pub fn fill_coding_vector<R: Rng + ?Sized>(&self, rng: &mut R, coding_vector: &mut [u8]) {
+        if !self.missing_dimensions.is_empty() && coding_vector.len() == self.piece_count {
+            coding_vector.fill(0);
+            self.missing_dimensions
+                .iter()
+                .for_each(|&dimension| coding_vector[dimension] = rng.random_range(1..=u8::MAX));
+
+            return;
+        }
+
+        self.fill_recoding_vector(rng, coding_vector);
+    }
+
+    /// Fills a recoding vector, of any length, over received pieces, for the recoder. A recoder doesn't know which source
+    /// dimensions its pieces span, so it's either dense or sparse, depending on the receiver's rank deficit.
+    pub fn fill_recoding_vector<R: Rng + ?Sized>(&self, rng: &mut R, recoding_vector: &mut [u8]) {
+        if self.is_dense() || self.sparse_nonzero_count >= recoding_vector.len() {
+            recoding_vector.iter_mut().for_each(|coeff| *coeff = rng.random_range(1..=u8::MAX));
+            return;
+        }
+
+        recoding_vector.fill(0);
+        index::sample(rng, recoding_vector.len(), self.sparse_nonzero_count)
+            .into_iter()
+            .for_each(|idx| recoding_vector[idx] = rng.random_range(1..=u8::MAX));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AdaptiveSampler;
+    use crate::full::{ControlMessage, Decoder, Encoder};
+    use rand::Rng;
+
+    #[test]
+    fn test_adaptive_sampler_density() {
+        let mut rng = rand::rng();
+
+        let piece_count = 32;
+        let mut sampler = AdaptiveSampler::new(piece_count, 4, 2);
+        let mut coding_vector = vec![0u8; piece_count];
+
+        assert_eq!(sampler.get_rank_deficit(), piece_count);
+        sampler.fill_coding_vector(&mut rng, &mut coding_vector);
+        assert_eq!(coding_vector.iter().filter(|&&coeff| coeff != 0).count(), 4);
+
+        sampler.on_control_message(&ControlMessage::Progress { generation: 0, rank: 30 });
+        assert!(sampler.is_dense());
+        sampler.fill_coding_vector(&mut rng, &mut coding_vector);
+        assert!(coding_vector.iter().all(|&coeff| coeff != 0));
+
+        sampler.on_control_message(&ControlMessage::Missing {
+            generation: 0,
+            dimensions: vec![3, 17, 17, 100],
+        });
+        assert_eq!(sampler.get_rank_deficit(), 2);
+        sampler.fill_coding_vector(&mut rng, &mut coding_vector);
+        assert!(coding_vector.iter().enumerate().all(|(idx, &coeff)| (coeff != 0) == (idx == 3 || idx == 17)));
+
+        sampler.on_control_message(&ControlMessage::Ack { generation: 0 });
+        assert_eq!(sampler.get_rank_deficit(), 0);
+    }
+
+    #[test]
+    fn test_adaptive_sampler_targets_missing_dimensions() {
+        let mut rng = rand::rng();
+
+        let data = (0..4096).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 16).expect("Failed to create Encoder");
+        let mut decoder = Decoder::new_with(encoder.get_params());
+
+        let mut sampler = AdaptiveSampler::new(encoder.get_piece_count(), 2, 0);
+        let mut full_coded_piece = vec![0u8; encoder.get_full_coded_piece_byte_len()];
+
+        // Sparse pieces, till receiver reports, what it's missing. From then on, every targeted piece must be useful.
+        for _ in 0..8 {
+            let (coding_vector, coded_data) = full_coded_piece.split_at_mut(encoder.get_piece_count());
+            sampler.fill_coding_vector(&mut rng, coding_vector);
+            encoder.code_with_coding_vector(coding_vector, coded_data).expect("Coding must not fail");
+
+            let _ = decoder.decode(&full_coded_piece);
+        }
+
+        while !decoder.is_already_decoded() {
+            sampler.on_control_message(&ControlMessage::from_decoder(0, &decoder));
+
+            let (coding_vector, coded_data) = full_coded_piece.split_at_mut(encoder.get_piece_count());
+            sampler.fill_coding_vector(&mut rng, coding_vector);
+            encoder.code_with_coding_vector(coding_vector, coded_data).expect("Coding must not fail");
+
+            assert_eq!(decoder.decode(&full_coded_piece), Ok(()));
+        }
+
+        assert_eq!(decoder.get_decoded_data(), Ok(data));
+    }
+}
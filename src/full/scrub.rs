@@ -0,0 +1,241 @@
+use super::{decoder_matrix::DecoderMatrix, repair::SurvivingPiece};
+use crate::RLNCError;
+use std::collections::HashMap;
+
+/// Health of a generation, judged by how many more pieces it can lose, before it can't be reconstructed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GenerationHealth {
+    /// Surviving pieces don't span all `k` dimensions, so the generation can't be reconstructed or repaired anymore.
+    Unrecoverable,
+    /// Generation can be reconstructed, but its rank margin is not more than the at-risk threshold.
+    AtRisk,
+    /// Generation can be reconstructed and its rank margin is more than the at-risk threshold.
+    Healthy,
+}
+
+/// Outcome of scrubbing a single generation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GenerationCensus {
+    /// Index of the generation.
+    pub generation: usize,
+    /// Number of surviving pieces.
+    pub surviving_piece_count: usize,
+    /// Rank of surviving pieces, which is `k`, if the generation can be reconstructed.
+    pub rank: usize,
+    /// Number of surviving pieces, which can be lost, while still keeping `k` of them. Zero for unrecoverable generations.
+    pub margin: usize,
+    /// Health of the generation.
+    pub health: GenerationHealth,
+}
+
+/// New pieces to be created for a generation, say with `RepairPlan`, and the nodes they should be placed on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PieceRepair {
+    /// Index of the generation.
+    pub generation: usize,
+    /// Rank margin of the generation, before the repair.
+    pub margin: usize,
+    /// Nodes, each of which receives one new piece, none of them already holding a piece of the generation.
+    pub targets: Vec<usize>,
+}
+
+/// Scrub-and-repair plan for a population of coded pieces, spread over generations and nodes.
+///
+/// Each recoverable generation, with fewer than the target number of surviving pieces, gets a repair, bringing it back to
+/// the target, placing new pieces on distinct nodes, which don't hold a piece of the generation yet, least loaded ones first.
+/// Repairs are prioritized by rank margin, generations closest to being lost coming first.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScrubPlan {
+    census: Vec<GenerationCensus>,
+    repairs: Vec<PieceRepair>,
+}
+
+impl ScrubPlan {
+    /// Computes a scrub-and-repair plan, given the census of surviving pieces.
+    ///
+    /// # Arguments
+    /// * `piece_count` - Number of pieces coded together, in each generation i.e. `k`.
+    /// * `target_piece_count` - Number of pieces each generation should have, after repair i.e. `n`.
+    /// * `at_risk_margin` - Generations with rank margin not more than this, are at risk.
+    /// * `survivors` - Surviving pieces of each generation, along with the node holding each.
+    /// * `nodes` - Nodes, which can receive new pieces.
+    ///
+    /// # Returns
+    /// * Returns `Ok(ScrubPlan)` on success.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `piece_count` is zero.
+    /// * Returns `Err(RLNCError::InvalidPlacement)` if `target_piece_count` is less than `piece_count`.
+    /// * Returns `Err(RLNCError::CodingVectorLengthMismatch)` if any coding vector is not of length `piece_count`.
+    pub fn new(
+        piece_count: usize,
+        target_piece_count: usize,
+        at_risk_margin: usize,
+        survivors: &[Vec<SurvivingPiece>],
+        nodes: &[usize],
+    ) -> Result<ScrubPlan, RLNCError> {
+        if piece_count == 0 {
+            return Err(RLNCError::PieceCountZero);
+        }
+        if target_piece_count < piece_count {
+            return Err(RLNCError::InvalidPlacement);
+        }
+
+        let mut load: HashMap<usize, usize> = nodes.iter().map(|&node| (node, 0)).collect();
+        let mut census = Vec::with_capacity(survivors.len());
+
+        for (generation, pieces) in survivors.iter().enumerate() {
+            let mut span = DecoderMatrix::new(piece_count, 0);
+            for piece in pieces {
+                if piece.coding_vector.len() != piece_count {
+                    return Err(RLNCError::CodingVectorLengthMismatch);
+                }
+                if span.rank() < piece_count {
+                    span.add_row(&piece.coding_vector)?.rref();
+                }
+                if let Some(node_load) = load.get_mut(&piece.node) {
+                    *node_load += 1;
+                }
+            }
+
+            let rank = span.rank();
+            let (margin, health) = if rank < piece_count {
+                (0, GenerationHealth::Unrecoverable)
+            } else {
+                let margin = pieces.len() - piece_count;
+                (
+                    margin,
+                    if margin <= at_risk_margin {
+                        GenerationHealth::AtRisk
+                    } else {
+                        GenerationHealth::Healthy
+                    },
+                )
+            };
+
+            census.push(GenerationCensus {
+                generation,
+                surviving_piece_count: pieces.len(),
+                rank,
+                margin,
+                health,
+            });
+        }
+
+        let mut prioritized = census
+            .iter()
+            .filter(|generation| generation.health != GenerationHealth::Unrecoverable && generation.surviving_piece_count < target_piece_count)
+            .collect::<Vec<_>>();
+        prioritized.sort_by_key(|generation| (generation.margin, generation.generation));
+
+        let mut repairs = Vec::with_capacity(prioritized.len());
+        for generation in prioritized {
+            let holders = &survivors[generation.generation];
+
+            let mut candidates = nodes
+                .iter()
+                .copied()
+                .filter(|&node| holders.iter().all(|piece| piece.node != node))
+                .collect::<Vec<_>>();
+            candidates.sort_by_key(|node| load[node]);
+            candidates.truncate(target_piece_count - generation.surviving_piece_count);
+
+            for node in &candidates {
+                if let Some(node_load) = load.get_mut(node) {
+                    *node_load += 1;
+                }
+            }
+
+            if !candidates.is_empty() {
+                repairs.push(PieceRepair {
+                    generation: generation.generation,
+                    margin: generation.margin,
+                    targets: candidates,
+                });
+            }
+        }
+
+        Ok(ScrubPlan { census, repairs })
+    }
+
+    /// Census of each generation, in order of generations.
+    pub fn get_census(&self) -> &[GenerationCensus] {
+        &self.census
+    }
+
+    /// Generations at risk or unrecoverable, in order of generations.
+    pub fn get_at_risk_generations(&self) -> impl Iterator<Item = &GenerationCensus> + '_ {
+        self.census.iter().filter(|generation| generation.health != GenerationHealth::Healthy)
+    }
+
+    /// Repairs, in order of priority.
+    pub fn get_repairs(&self) -> &[PieceRepair] {
+        &self.repairs
+    }
+
+    /// Total number of new pieces to be created, over all repairs.
+    pub fn get_new_piece_count(&self) -> usize {
+        self.repairs.iter().map(|repair| repair.targets.len()).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GenerationHealth, ScrubPlan};
+    use crate::{RLNCError, full::SurvivingPiece};
+
+    fn unit_piece(node: usize, index: usize, piece_count: usize) -> SurvivingPiece {
+        let mut coding_vector = vec![0u8; piece_count];
+        coding_vector[index % piece_count] = 1 + (index / piece_count) as u8;
+
+        SurvivingPiece { node, coding_vector }
+    }
+
+    #[test]
+    fn test_scrub_plan() {
+        const PIECE_COUNT: usize = 3;
+        let nodes = (0..6).collect::<Vec<usize>>();
+
+        // Generation 0 is intact, 1 has lost two pieces, 2 has lost one, 3 can't be reconstructed anymore.
+        let survivors = vec![
+            (0..6).map(|index| unit_piece(index, index, PIECE_COUNT)).collect::<Vec<_>>(),
+            (0..4).map(|index| unit_piece(index + 1, index, PIECE_COUNT)).collect(),
+            (0..5).map(|index| unit_piece(index, index, PIECE_COUNT)).collect(),
+            vec![unit_piece(0, 0, PIECE_COUNT), unit_piece(1, 3, PIECE_COUNT)],
+        ];
+
+        let plan = ScrubPlan::new(PIECE_COUNT, 6, 1, &survivors, &nodes).expect("Scrub plan must be computed");
+
+        let census = plan.get_census();
+        assert_eq!(
+            census.iter().map(|generation| generation.health).collect::<Vec<_>>(),
+            [
+                GenerationHealth::Healthy,
+                GenerationHealth::AtRisk,
+                GenerationHealth::Healthy,
+                GenerationHealth::Unrecoverable
+            ]
+        );
+        assert_eq!(census.iter().map(|generation| generation.margin).collect::<Vec<_>>(), [3, 1, 2, 0]);
+        assert_eq!(census[3].rank, 1);
+        assert_eq!(plan.get_at_risk_generations().count(), 2);
+
+        let repairs = plan.get_repairs();
+        assert_eq!(repairs.iter().map(|repair| repair.generation).collect::<Vec<_>>(), [1, 2]);
+        assert_eq!(plan.get_new_piece_count(), 3);
+
+        // New pieces go to nodes not holding the generation yet.
+        let mut targets = repairs[0].targets.clone();
+        targets.sort();
+        assert_eq!(targets, [0, 5]);
+        assert_eq!(repairs[1].targets, [5]);
+    }
+
+    #[test]
+    fn test_scrub_plan_invalid_inputs() {
+        assert_eq!(ScrubPlan::new(0, 4, 1, &[], &[]), Err(RLNCError::PieceCountZero));
+        assert_eq!(ScrubPlan::new(4, 3, 1, &[], &[]), Err(RLNCError::InvalidPlacement));
+        assert_eq!(
+            ScrubPlan::new(4, 6, 1, &[vec![unit_piece(0, 0, 3)]], &[0]),
+            Err(RLNCError::CodingVectorLengthMismatch)
+        );
+    }
+}
@@ -0,0 +1,145 @@
+use super::session::{NonZeroRng, SessionRng};
+use hkdf::Hkdf;
+use sha2::Sha256;
+
+/// Label, used as HKDF info prefix, for deriving a generation seed out of the session key. It's followed by big-endian generation index.
+pub const GENERATION_SEED_LABEL: &[u8] = b"rlnc/v1/generation-seed";
+/// Label, used as HKDF info prefix, for deriving a piece seed out of a generation seed. It's followed by big-endian piece index.
+pub const PIECE_SEED_LABEL: &[u8] = b"rlnc/v1/piece-seed";
+
+/// Byte length of SHA-256 digest, which is also the byte length of session keys and generation seeds.
+const DIGEST_BYTE_LEN: usize = 32;
+
+/// HKDF-Expand with HMAC-SHA-256, filling `okm` with output keying material, keyed by `prk`, which is never shorter than a digest.
+fn hkdf_sha256_expand(prk: &[u8; DIGEST_BYTE_LEN], info: &[&[u8]], okm: &mut [u8]) {
+    let hkdf = Hkdf::<Sha256>::from_prk(prk).expect("PRK is as long as SHA-256 digest");
+    hkdf.expand_multi_info(info, okm).expect("Output keying material is shorter than 8160 bytes");
+}
+
+/// Root of the seed hierarchy of a session, out of which per-generation and per-piece seeds are derived, so that a receiver can
+/// regenerate every coding vector of a transfer out of one small value, exchanged during handshake, and an auditor can reproduce it.
+///
+/// Derivation uses HKDF with HMAC-SHA-256, as specified in RFC 5869:
+///
+/// * Session key is HKDF-Extract of the master secret, salted with the session salt.
+/// * Seed of generation `g` is 32 bytes of HKDF-Expand of the session key, with info `GENERATION_SEED_LABEL || be_u64(g)`.
+/// * Seed of piece `i` of a generation is 8 bytes of HKDF-Expand of the generation seed, with info `PIECE_SEED_LABEL || be_u64(i)`,
+///   read as a little-endian `u64`, which seeds a `SessionRng`, wrapped in a `NonZeroRng`, so that coding coefficients are non-zero.
+///
+/// A generation seed can be handed over on its own, say to a relay serving only that generation, without revealing others.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SessionSeed {
+    session_key: [u8; DIGEST_BYTE_LEN],
+}
+
+impl SessionSeed {
+    /// Derives the session key out of given master secret and salt, which can be empty.
+    pub fn new(master_secret: &[u8], salt: &[u8]) -> SessionSeed {
+        SessionSeed {
+            session_key: Hkdf::<Sha256>::extract(Some(salt), master_secret).0.into(),
+        }
+    }
+
+    /// Derives seed of given generation.
+    pub fn generation_seed(&self, generation: u64) -> GenerationSeed {
+        let mut seed = [0u8; DIGEST_BYTE_LEN];
+        hkdf_sha256_expand(&self.session_key, &[GENERATION_SEED_LABEL, &generation.to_be_bytes()], &mut seed);
+
+        GenerationSeed { seed }
+    }
+}
+
+/// Keys must not end up in logs, so only the type is printed.
+impl std::fmt::Debug for SessionSeed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SessionSeed").finish_non_exhaustive()
+    }
+}
+
+/// Seed of a single generation, out of which seeds of its pieces are derived. See `SessionSeed`.
+#[derive(Clone, PartialEq, Eq)]
+pub struct GenerationSeed {
+    seed: [u8; DIGEST_BYTE_LEN],
+}
+
+impl GenerationSeed {
+    /// Wraps a generation seed, say received from a peer, which got it from `Self::to_bytes`.
+    pub fn from_bytes(seed: [u8; 32]) -> GenerationSeed {
+        GenerationSeed { seed }
+    }
+
+    /// Raw bytes of the generation seed, for handing it over to a peer.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.seed
+    }
+
+    /// Derives seed of given piece of the generation.
+    pub fn piece_seed(&self, piece: u64) -> u64 {
+        let mut seed = [0u8; size_of::<u64>()];
+        hkdf_sha256_expand(&self.seed, &[PIECE_SEED_LABEL, &piece.to_be_bytes()], &mut seed);
+
+        u64::from_le_bytes(seed)
+    }
+
+    /// Random number generator, for sampling coding vector of given piece of the generation, say passed to `Encoder::code`.
+    /// It samples coefficients out of `1..=255`, see `NonZeroRng`.
+    pub fn piece_rng(&self, piece: u64) -> NonZeroRng<SessionRng> {
+        NonZeroRng::new(SessionRng::new(self.piece_seed(piece)))
+    }
+}
+
+/// Keys must not end up in logs, so only the type is printed.
+impl std::fmt::Debug for GenerationSeed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GenerationSeed").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GenerationSeed, SessionSeed, hkdf_sha256_expand};
+    use crate::full::Encoder;
+
+    fn from_hex(hex: &str) -> Vec<u8> {
+        (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_hkdf_known_answers() {
+        // Test case 1 of RFC 5869.
+        let session_seed = SessionSeed::new(&[0x0b; 22], &from_hex("000102030405060708090a0b0c"));
+        assert_eq!(
+            session_seed.session_key.to_vec(),
+            from_hex("077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5")
+        );
+
+        let mut okm = [0u8; 42];
+        hkdf_sha256_expand(&session_seed.session_key, &[&from_hex("f0f1f2f3f4f5f6f7f8f9")], &mut okm);
+        assert_eq!(
+            okm.to_vec(),
+            from_hex("3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865")
+        );
+    }
+
+    #[test]
+    fn test_seed_hierarchy_reproduces_coding_vectors() {
+        let session_seed = SessionSeed::new(b"handshake secret", b"session 7");
+        let generation_seed = GenerationSeed::from_bytes(session_seed.generation_seed(3).to_bytes());
+
+        assert_eq!(generation_seed, session_seed.generation_seed(3));
+        assert_ne!(generation_seed, session_seed.generation_seed(4));
+        assert_ne!(generation_seed.piece_seed(0), generation_seed.piece_seed(1));
+        assert_ne!(SessionSeed::new(b"handshake secret", b"session 8"), session_seed);
+        assert_eq!(format!("{generation_seed:?}"), "GenerationSeed { .. }");
+
+        let data = (0..=255).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data, 8).expect("Failed to create Encoder for seed hierarchy test");
+
+        let sent = (0..8).map(|piece| encoder.code(&mut generation_seed.piece_rng(piece))).collect::<Vec<_>>();
+        let regenerated = (0..8)
+            .map(|piece| encoder.code(&mut session_seed.generation_seed(3).piece_rng(piece)))
+            .collect::<Vec<_>>();
+        assert_eq!(sent, regenerated);
+        assert!(sent.iter().all(|piece| piece[..8].iter().all(|&coeff| coeff != 0)));
+    }
+}
@@ -0,0 +1,190 @@
+use super::session::{NonZeroRng, SessionRng};
+use crate::RLNCError;
+use rand::RngCore;
+
+/// Tag of a serialized `SeededPiece`, carrying a seed, in place of the coding vector.
+const SEEDED_PIECE_TAG: u8 = 0;
+
+/// Tag of a serialized `SeededPiece`, carrying an explicit coding vector.
+const EXPLICIT_PIECE_TAG: u8 = 1;
+
+/// Coded piece, whose coding vector is either compressed into a 64-bit seed, or carried explicitly.
+///
+/// With large piece counts and small pieces, coding vector dominates the piece. A seeded piece carries 8 bytes instead, which the
+/// receiver expands into the coding vector i.e. first `piece_count` bytes written by `NonZeroRng::fill_bytes`, wrapping
+/// `SessionRng` seeded with the seed, so that coefficients are never zero. It's specified, so both ends agree on every
+/// architecture. See `Encoder::code_with_seed`. Recoded pieces' coding vectors are combinations of received ones, which no seed
+/// can reproduce, so they fall back to explicit coding vectors.
+///
+/// Wire representation, see `Self::to_bytes`, is a one-byte tag, followed by
+///
+/// * `0x00`: seed, as 8 little-endian bytes, and the coded data.
+/// * `0x01`: coding vector of `piece_count` bytes, and the coded data.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SeededPiece {
+    /// Piece, whose coding vector is expanded from the seed.
+    Seeded { seed: u64, coded_data: Vec<u8> },
+    /// Piece, carrying its coding vector, say a recoded one.
+    Explicit { coding_vector: Vec<u8>, coded_data: Vec<u8> },
+}
+
+impl SeededPiece {
+    /// Wraps a full coded piece, say produced by `Recoder::recode`, carrying its coding vector explicitly.
+    ///
+    /// # Returns
+    /// * Returns `Ok(SeededPiece)` on success.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `piece_count` is zero.
+    /// * Returns `Err(RLNCError::PieceLengthTooShort)` if `full_coded_piece` is not longer than `piece_count`.
+    pub fn explicit(full_coded_piece: &[u8], piece_count: usize) -> Result<SeededPiece, RLNCError> {
+        if piece_count == 0 {
+            return Err(RLNCError::PieceCountZero);
+        }
+        if full_coded_piece.len() <= piece_count {
+            return Err(RLNCError::PieceLengthTooShort);
+        }
+
+        let (coding_vector, coded_data) = full_coded_piece.split_at(piece_count);
+
+        Ok(SeededPiece::Explicit {
+            coding_vector: coding_vector.to_vec(),
+            coded_data: coded_data.to_vec(),
+        })
+    }
+
+    /// Expands the seed into a coding vector of given length, same as the encoder does.
+    pub fn expand_seed(seed: u64, piece_count: usize) -> Vec<u8> {
+        let mut coding_vector = vec![0u8; piece_count];
+        NonZeroRng::new(SessionRng::new(seed)).fill_bytes(&mut coding_vector);
+
+        coding_vector
+    }
+
+    /// Coded data of the piece.
+    pub fn get_coded_data(&self) -> &[u8] {
+        match self {
+            SeededPiece::Seeded { coded_data, .. } | SeededPiece::Explicit { coded_data, .. } => coded_data,
+        }
+    }
+
+    /// Coding vector followed by coded data, expanding the seed, if needed, ready to be passed to `Decoder::decode`.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Vec<u8>)` on success.
+    /// * Returns `Err(RLNCError::CodingVectorLengthMismatch)` if an explicit coding vector is not of length `piece_count`.
+    pub fn to_full_coded_piece(&self, piece_count: usize) -> Result<Vec<u8>, RLNCError> {
+        match self {
+            SeededPiece::Seeded { seed, coded_data } => Ok([Self::expand_seed(*seed, piece_count).as_slice(), coded_data].concat()),
+            SeededPiece::Explicit { coding_vector, coded_data } => {
+                if coding_vector.len() != piece_count {
+                    return Err(RLNCError::CodingVectorLengthMismatch);
+                }
+
+                Ok([coding_vector.as_slice(), coded_data].concat())
+            }
+        }
+    }
+
+    /// Serializes the piece, as documented on the type.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            SeededPiece::Seeded { seed, coded_data } => [&[SEEDED_PIECE_TAG], seed.to_le_bytes().as_slice(), coded_data].concat(),
+            SeededPiece::Explicit { coding_vector, coded_data } => [&[EXPLICIT_PIECE_TAG], coding_vector.as_slice(), coded_data].concat(),
+        }
+    }
+
+    /// Deserializes a piece, serialized using `Self::to_bytes`. Piece count tells the length of an explicit coding vector.
+    ///
+    /// # Returns
+    /// * Returns `Ok(SeededPiece)` on success.
+    /// * Returns `Err(RLNCError::SerializationFailure)` if the tag is unknown or `bytes` carry no coded data.
+    pub fn from_bytes(bytes: &[u8], piece_count: usize) -> Result<SeededPiece, RLNCError> {
+        let Some((&tag, rest)) = bytes.split_first() else {
+            return Err(RLNCError::SerializationFailure);
+        };
+
+        let coding_part_byte_len = match tag {
+            SEEDED_PIECE_TAG => size_of::<u64>(),
+            EXPLICIT_PIECE_TAG => piece_count,
+            _ => return Err(RLNCError::SerializationFailure),
+        };
+        if rest.len() <= coding_part_byte_len {
+            return Err(RLNCError::SerializationFailure);
+        }
+
+        let (coding_part, coded_data) = rest.split_at(coding_part_byte_len);
+        let coded_data = coded_data.to_vec();
+
+        Ok(match tag {
+            SEEDED_PIECE_TAG => SeededPiece::Seeded {
+                seed: u64::from_le_bytes(unsafe { coding_part.try_into().unwrap_unchecked() }),
+                coded_data,
+            },
+            _ => SeededPiece::Explicit {
+                coding_vector: coding_part.to_vec(),
+                coded_data,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SeededPiece;
+    use crate::{
+        RLNCError,
+        full::{Decoder, Encoder, Recoder},
+    };
+
+    #[test]
+    fn test_seeded_pieces() {
+        let mut rng = rand::rng();
+
+        let data = (0..2048).map(|i| (i % 251) as u8).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 128).expect("Failed to create Encoder for seeded piece test");
+        let params = encoder.get_params();
+
+        // Relay recodes first pieces, which it forwards with explicit coding vectors.
+        let mut relay_pieces = Vec::new();
+        for seed in 0..64 {
+            let piece = encoder.code_with_seed(seed);
+            relay_pieces.extend(piece.to_full_coded_piece(128).unwrap());
+        }
+        let mut recoder = Recoder::new_with(relay_pieces, params).expect("Failed to create Recoder for seeded piece test");
+
+        let mut decoder = Decoder::new_with(params);
+        for _ in 0..64 {
+            let piece = SeededPiece::explicit(&recoder.recode(&mut rng), 128).unwrap();
+            let piece = SeededPiece::from_bytes(&piece.to_bytes(), 128).expect("Explicit piece must deserialize");
+            let _ = decoder.decode_seeded(&piece);
+        }
+
+        let mut seed = 1000;
+        while !decoder.is_already_decoded() {
+            let piece = encoder.code_with_seed(seed);
+            let bytes = piece.to_bytes();
+            assert_eq!(bytes.len(), 1 + 8 + params.get_piece_byte_len());
+
+            let _ = decoder.decode_seeded(&SeededPiece::from_bytes(&bytes, 128).expect("Seeded piece must deserialize"));
+            seed += 1;
+        }
+
+        assert_eq!(decoder.get_decoded_data(), Ok(data));
+        assert_eq!(SeededPiece::from_bytes(&[2, 0, 0], 1), Err(RLNCError::SerializationFailure));
+        assert_eq!(SeededPiece::from_bytes(&[0; 9], 1), Err(RLNCError::SerializationFailure));
+        assert_eq!(
+            SeededPiece::explicit(&[1, 2, 3], 2).unwrap().to_full_coded_piece(3),
+            Err(RLNCError::CodingVectorLengthMismatch)
+        );
+    }
+
+    #[test]
+    fn test_expanded_seeds_have_no_zero_coefficients() {
+        for seed in 0..1024 {
+            let coding_vector = SeededPiece::expand_seed(seed, 64);
+
+            assert_eq!(coding_vector.len(), 64);
+            assert!(coding_vector.iter().all(|&coeff| coeff != 0));
+            assert_eq!(coding_vector, SeededPiece::expand_seed(seed, 64));
+        }
+    }
+}
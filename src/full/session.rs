@@ -0,0 +1,206 @@
+use rand::{RngCore, SeedableRng};
+
+/// Specified pseudo-random number generator, for sampling coding vectors from a session seed.
+///
+/// Unlike `rand::rngs::StdRng`, whose algorithm may change between `rand` releases, its output is fixed by this specification,
+/// which doesn't change within a major release of this crate:
+///
+/// * State is four `u64` words, each being the next output of SplitMix64, itself started at the 64-bit seed.
+/// * Each step outputs a `u64` word, as xoshiro256** does.
+/// * `fill_bytes` writes successive output words, in little-endian byte order, discarding unused bytes of the last word.
+///
+/// As `Encoder` and `Recoder` sample coding vectors using `fill_bytes` only, coding vectors produced from the same seed are
+/// bit-identical on every architecture, which is what record/replay debugging and verifiable transfers need. Pass it wherever
+/// a random number generator is expected, say `Encoder::code`. It's not cryptographically secure.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SessionRng {
+    state: [u64; 4],
+}
+
+impl SessionRng {
+    /// Creates a generator from a 64-bit session seed.
+    pub fn new(seed: u64) -> SessionRng {
+        let mut splitmix_state = seed;
+        let mut splitmix64 = || {
+            splitmix_state = splitmix_state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+            let mut z = splitmix_state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            z ^ (z >> 31)
+        };
+
+        SessionRng {
+            state: [splitmix64(), splitmix64(), splitmix64(), splitmix64()],
+        }
+    }
+}
+
+impl RngCore for SessionRng {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let result = self.state[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = self.state[1] << 17;
+
+        self.state[2] ^= self.state[0];
+        self.state[3] ^= self.state[1];
+        self.state[1] ^= self.state[2];
+        self.state[0] ^= self.state[3];
+        self.state[2] ^= t;
+        self.state[3] = self.state[3].rotate_left(45);
+
+        result
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        for chunk in dst.chunks_mut(size_of::<u64>()) {
+            let word = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&word[..chunk.len()]);
+        }
+    }
+}
+
+/// Seeding with 32 bytes uses the first 8 of them, in little-endian byte order, as the session seed, so that it agrees with `Self::new`.
+impl SeedableRng for SessionRng {
+    type Seed = [u8; 32];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        let mut session_seed = [0u8; size_of::<u64>()];
+        session_seed.copy_from_slice(&seed[..size_of::<u64>()]);
+
+        SessionRng::new(u64::from_le_bytes(session_seed))
+    }
+
+    fn seed_from_u64(seed: u64) -> Self {
+        SessionRng::new(seed)
+    }
+}
+
+/// Wraps a random number generator, so that every byte it produces is uniformly distributed over `1..=255`, never zero.
+///
+/// Pass it wherever a random number generator is expected, say `Encoder::code`, and every coding coefficient is non-zero, so
+/// coding vectors are never all-zero and each piece mixes all source pieces, which measurably lowers the probability of linearly
+/// dependent pieces, for small generations. Recoding coefficients are non-zero too, though the recoded coding vector, being a
+/// combination of received ones, may still have zero coefficients. It's the default for seeded schemes, see
+/// `SeededPiece::expand_seed` and `GenerationSeed::piece_rng`.
+///
+/// Output is specified in terms of the wrapped generator, so that it's reproducible: `fill_bytes` fills all bytes using wrapped
+/// generator's `fill_bytes`, then replaces each zero byte, in order, by drawing one byte at a time, using wrapped generator's
+/// `fill_bytes`, till a non-zero one is drawn. Words are made of little-endian bytes, filled that way.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NonZeroRng<R: RngCore> {
+    inner: R,
+}
+
+impl<R: RngCore> NonZeroRng<R> {
+    /// Wraps given random number generator.
+    pub fn new(inner: R) -> NonZeroRng<R> {
+        NonZeroRng { inner }
+    }
+
+    /// Unwraps the random number generator.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: RngCore> RngCore for NonZeroRng<R> {
+    fn next_u32(&mut self) -> u32 {
+        let mut word = [0u8; size_of::<u32>()];
+        self.fill_bytes(&mut word);
+        u32::from_le_bytes(word)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut word = [0u8; size_of::<u64>()];
+        self.fill_bytes(&mut word);
+        u64::from_le_bytes(word)
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        self.inner.fill_bytes(dst);
+
+        for byte in dst.iter_mut().filter(|byte| **byte == 0) {
+            while *byte == 0 {
+                self.inner.fill_bytes(std::slice::from_mut(byte));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NonZeroRng, SessionRng};
+    use crate::full::{Decoder, Encoder, Recoder};
+    use rand::{RngCore, SeedableRng};
+
+    #[test]
+    fn test_session_rng_known_answers() {
+        let mut rng = SessionRng::new(42);
+        assert_eq!(
+            [rng.next_u64(), rng.next_u64(), rng.next_u64()],
+            [0x1578_0b2e_0c2e_c716, 0x6104_d986_6d11_3a7e, 0xae17_5332_39e4_99a1]
+        );
+
+        // A coding vector of 20 coefficients consumes three words, leaving the last 4 bytes unused.
+        let encoder = Encoder::new((0..=255).collect(), 20).expect("Failed to create Encoder for session seed test");
+        let coded_piece = encoder.code(&mut SessionRng::seed_from_u64(42));
+        assert_eq!(
+            coded_piece[..20],
+            [22, 199, 46, 12, 46, 11, 120, 21, 126, 58, 17, 109, 134, 217, 4, 97, 161, 153, 228, 57]
+        );
+
+        let mut seed = [0u8; 32];
+        seed[0] = 42;
+        assert_eq!(SessionRng::from_seed(seed), SessionRng::new(42));
+    }
+
+    #[test]
+    fn test_session_rng_replays_transfer() {
+        let data = (0..4096).map(|i| (i * 7 % 251) as u8).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 16).expect("Failed to create Encoder for session seed test");
+
+        let transfer = |seed| {
+            let mut rng = SessionRng::new(seed);
+            let pieces = (0..16).flat_map(|_| encoder.code(&mut rng)).collect::<Vec<u8>>();
+
+            let mut recoder = Recoder::new_with(pieces, encoder.get_params()).expect("Failed to create Recoder for session seed test");
+            (0..20).map(|_| recoder.recode(&mut rng)).collect::<Vec<_>>()
+        };
+
+        let recorded = transfer(7);
+        assert_eq!(recorded, transfer(7));
+        assert_ne!(recorded, transfer(8));
+
+        let mut decoder = Decoder::new_with(encoder.get_params());
+        for piece in &recorded {
+            let _ = decoder.decode(piece);
+        }
+        assert_eq!(decoder.get_decoded_data(), Ok(data));
+    }
+
+    #[test]
+    fn test_nonzero_rng() {
+        let mut rng = NonZeroRng::new(SessionRng::new(42));
+
+        let mut coefficients = vec![0u8; 1 << 16];
+        rng.fill_bytes(&mut coefficients);
+        assert!(coefficients.iter().all(|&coeff| coeff != 0));
+
+        // Each non-zero value shows up, roughly equally often.
+        let mut counts = [0usize; 256];
+        coefficients.iter().for_each(|&coeff| counts[coeff as usize] += 1);
+        assert!(counts[1..].iter().all(|&count| (128..=384).contains(&count)));
+
+        // Non-zero bytes of the wrapped generator's output are kept as is.
+        let mut expected = vec![0u8; 64];
+        SessionRng::new(7).fill_bytes(&mut expected);
+
+        let mut sampled = vec![0u8; 64];
+        NonZeroRng::new(SessionRng::new(7)).fill_bytes(&mut sampled);
+        assert!(sampled.iter().zip(&expected).all(|(&sampled, &expected)| expected == 0 || sampled == expected));
+    }
+}
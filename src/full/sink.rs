@@ -0,0 +1,176 @@
+use super::{decoder::Decoder, piece::CodedPiece};
+use crate::RLNCError;
+use futures::{
+    Sink, StreamExt,
+    channel::{mpsc, oneshot},
+};
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// A `futures::Sink` of coded pieces, feeding them to a decoder, which runs Gaussian elimination on a blocking thread,
+/// off the async executor.
+///
+/// Pieces are handed over to the blocking thread through a bounded queue, so a fast producer is made to wait, while
+/// elimination catches up. Once the decoder has received enough useful pieces, decoded data is delivered through the
+/// paired `DecodeCompletion` future and any further piece is rejected with `RLNCError::ReceivedAllPieces`. This makes
+/// decoding composable with `StreamExt::forward`.
+///
+/// Malformed and linearly dependent pieces are silently dropped by the blocking thread.
+pub struct DecoderSink {
+    sender: mpsc::Sender<CodedPiece>,
+}
+
+/// Future, resolving to decoded data, once the paired `DecoderSink` has received enough useful pieces.
+/// It resolves to `Err(RLNCError::NotAllPiecesReceivedYet)`, if the sink is closed before that.
+pub struct DecodeCompletion {
+    receiver: oneshot::Receiver<Result<Vec<u8>, RLNCError>>,
+}
+
+impl DecoderSink {
+    /// Wraps a decoder into a sink, which runs elimination on a newly spawned thread.
+    ///
+    /// # Arguments
+    /// * `decoder` - Decoder to be fed, it may already hold some pieces.
+    /// * `capacity` - Number of pieces, which can be queued up, before the sink applies backpressure.
+    ///
+    /// # Returns
+    /// Returns the sink, along with a future resolving to decoded data.
+    pub fn new(decoder: Decoder, capacity: usize) -> (DecoderSink, DecodeCompletion) {
+        Self::new_with_spawner(decoder, capacity, |job| {
+            std::thread::spawn(job);
+        })
+    }
+
+    /// Same as `Self::new`, but elimination runs wherever `spawn` runs the job, say `tokio::task::spawn_blocking`
+    /// or a thread pool owned by the application. The job blocks until the sink is closed or decoding completes.
+    pub fn new_with_spawner<S>(decoder: Decoder, capacity: usize, spawn: S) -> (DecoderSink, DecodeCompletion)
+    where
+        S: FnOnce(Box<dyn FnOnce() + Send>),
+    {
+        let (sender, receiver) = mpsc::channel(capacity);
+        let (completion_sender, completion_receiver) = oneshot::channel();
+
+        spawn(Box::new(move || {
+            let result = futures::executor::block_on(Self::run(decoder, receiver));
+            let _ = completion_sender.send(result);
+        }));
+
+        (DecoderSink { sender }, DecodeCompletion { receiver: completion_receiver })
+    }
+
+    /// Drives the decoder with pieces arriving over the queue, till decoding completes or the queue gets closed.
+    async fn run(mut decoder: Decoder, mut receiver: mpsc::Receiver<CodedPiece>) -> Result<Vec<u8>, RLNCError> {
+        let mut full_coded_piece = Vec::with_capacity(decoder.get_full_coded_piece_byte_len());
+
+        while !decoder.is_already_decoded() {
+            let piece = receiver.next().await.ok_or(RLNCError::NotAllPiecesReceivedYet)?;
+
+            full_coded_piece.clear();
+            full_coded_piece.extend_from_slice(piece.get_coding_vector());
+            full_coded_piece.extend_from_slice(piece.get_coded_data());
+
+            let _ = decoder.decode(&full_coded_piece);
+        }
+
+        // Reject further pieces, so that the producer learns decoding is complete.
+        receiver.close();
+        decoder.get_decoded_data()
+    }
+}
+
+impl Sink<CodedPiece> for DecoderSink {
+    type Error = RLNCError;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.sender.poll_ready(cx).map_err(|_| RLNCError::ReceivedAllPieces)
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, item: CodedPiece) -> Result<(), Self::Error> {
+        self.sender.start_send(item).map_err(|_| RLNCError::ReceivedAllPieces)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.sender).poll_flush(cx).map_err(|_| RLNCError::ReceivedAllPieces)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.sender).poll_close(cx).map_err(|_| RLNCError::ReceivedAllPieces)
+    }
+}
+
+impl Future for DecodeCompletion {
+    type Output = Result<Vec<u8>, RLNCError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.receiver)
+            .poll(cx)
+            .map(|result| result.unwrap_or(Err(RLNCError::NotAllPiecesReceivedYet)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DecoderSink;
+    use crate::{
+        RLNCError,
+        full::{CodedPiece, Decoder, Encoder},
+    };
+    use futures::{SinkExt, StreamExt, executor::block_on, stream};
+    use rand::Rng;
+
+    #[test]
+    fn test_decoder_sink_with_stream_forward() {
+        let mut rng = rand::rng();
+
+        let data = (0..10_000).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 32).expect("Failed to create Encoder");
+        let (sink, completion) = DecoderSink::new(Decoder::new_with(encoder.get_params()), 4);
+
+        // Endless stream of coded pieces, which gets cut off, once the sink reports decoding is complete.
+        let pieces = stream::repeat_with(move || CodedPiece::from_bytes(&encoder.code(&mut rand::rng()), 32));
+
+        assert_eq!(block_on(pieces.forward(sink)), Err(RLNCError::ReceivedAllPieces));
+        assert_eq!(block_on(completion), Ok(data));
+    }
+
+    #[test]
+    fn test_decoder_sink_closed_early() {
+        let mut rng = rand::rng();
+
+        let encoder = Encoder::new(vec![1u8; 1024], 32).expect("Failed to create Encoder");
+        let (mut sink, completion) = DecoderSink::new(Decoder::new_with(encoder.get_params()), 4);
+
+        block_on(async {
+            for _ in 0..8 {
+                let piece = CodedPiece::from_bytes(&encoder.code(&mut rng), 32).expect("Piece must be valid");
+                sink.send(piece).await.expect("Sending must not fail");
+            }
+            sink.close().await.expect("Closing must not fail");
+        });
+
+        assert_eq!(block_on(completion), Err(RLNCError::NotAllPiecesReceivedYet));
+    }
+
+    #[test]
+    fn test_decoder_sink_with_custom_spawner() {
+        let mut rng = rand::rng();
+
+        let data = (0..1024).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 8).expect("Failed to create Encoder");
+
+        let (sink, completion) = DecoderSink::new_with_spawner(Decoder::new_with(encoder.get_params()), 1, |job| {
+            std::thread::Builder::new()
+                .name("rlnc-decoder".into())
+                .spawn(job)
+                .expect("Spawning must not fail");
+        });
+
+        let pieces = stream::iter((0..64).map(|_| CodedPiece::from_bytes(&encoder.code(&mut rng), 8)));
+        let _ = block_on(pieces.forward(sink));
+
+        assert_eq!(block_on(completion), Ok(data));
+    }
+}
@@ -0,0 +1,39 @@
+use super::decoder::PayloadLayout;
+
+/// Point-in-time copy of decoder state, which can be persisted or shipped elsewhere and later turned back into
+/// a `Decoder`, using `Decoder::from_snapshot`, to resume decoding.
+///
+/// Piece retention and audit log are not part of the snapshot. With `rkyv` feature enabled, snapshots can be archived
+/// and validated without copying, say before handing over to another process.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct DecoderSnapshot {
+    pub(crate) layout: PayloadLayout,
+    pub(crate) piece_byte_len: usize,
+    pub(crate) required_piece_count: usize,
+    pub(crate) received_piece_count: usize,
+    pub(crate) matrix_rows: Vec<u8>,
+    pub(crate) payloads: Vec<u8>,
+}
+
+impl DecoderSnapshot {
+    /// Memory layout of payloads, held by the snapshotted decoder.
+    pub fn get_payload_layout(&self) -> PayloadLayout {
+        self.layout
+    }
+
+    /// Byte length of each original data piece.
+    pub fn get_piece_byte_len(&self) -> usize {
+        self.piece_byte_len
+    }
+
+    /// Number of pieces original data got split into and coded together.
+    pub fn get_num_pieces_coded_together(&self) -> usize {
+        self.required_piece_count
+    }
+
+    /// Total number of pieces received by the snapshotted decoder.
+    pub fn get_received_piece_count(&self) -> usize {
+        self.received_piece_count
+    }
+}
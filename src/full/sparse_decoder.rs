@@ -0,0 +1,288 @@
+use super::{decoder::Decoder, params::Params};
+use crate::{
+    RLNCError,
+    common::{
+        gf256::Gf256,
+        simd::{gf256_inplace_mul_vec_by_scalar, gf256_mul_vec_by_scalar_then_add_into_vec},
+    },
+};
+
+/// Row of a sparse decoder, holding only non-zero coefficients, sorted by column, along with the payload.
+#[derive(Clone, Debug)]
+struct SparseRow {
+    coefficients: Vec<(usize, u8)>,
+    payload: Vec<u8>,
+}
+
+/// Decoder, which exploits sparsity of coding vectors, say when sparse or banded coding vectors are used.
+///
+/// Coding vectors are stored as lists of non-zero coefficients. Each useful piece is reduced only by pivot rows of the columns,
+/// it actually has non-zero coefficients in, till its leading column lacks a pivot row, becoming the pivot row of that column.
+/// Back-substitution is deferred till extraction of decoded data. So decoding cost is proportional to the number of non-zero
+/// coefficients in pivot rows, instead of `piece_count` per row, which keeps large sparse generations decoding in near-linear
+/// time, as long as fill-in stays low, as it does with banded coding vectors. With dense coding vectors, use `Decoder` instead.
+#[derive(Clone, Debug)]
+pub struct SparseDecoder {
+    piece_byte_len: usize,
+    required_piece_count: usize,
+    received_piece_count: usize,
+    useful_piece_count: usize,
+    /// Pivot row of each column, if any. Its leading coefficient is in that column and is normalized to one.
+    pivot_rows: Vec<Option<SparseRow>>,
+}
+
+impl SparseDecoder {
+    /// Creates a new `SparseDecoder` instance.
+    ///
+    /// # Arguments
+    /// * `piece_byte_len` - The byte length of each original data piece.
+    /// * `required_piece_count` - The minimum number of useful coded pieces
+    ///   needed for decoding (equivalent to the number of original pieces).
+    ///
+    /// # Returns
+    /// * Returns `Ok(SparseDecoder)` on successful creation.
+    /// * Returns `Err(RLNCError::PieceLengthZero)` if `piece_byte_len` is zero.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `required_piece_count` is zero.
+    pub fn new(piece_byte_len: usize, required_piece_count: usize) -> Result<SparseDecoder, RLNCError> {
+        if piece_byte_len == 0 {
+            return Err(RLNCError::PieceLengthZero);
+        }
+        if required_piece_count == 0 {
+            return Err(RLNCError::PieceCountZero);
+        }
+
+        Ok(SparseDecoder {
+            piece_byte_len,
+            required_piece_count,
+            received_piece_count: 0,
+            useful_piece_count: 0,
+            pivot_rows: vec![None; required_piece_count],
+        })
+    }
+
+    /// Creates a new `SparseDecoder` instance from already validated RLNC configuration.
+    pub fn new_with(params: Params) -> SparseDecoder {
+        unsafe { Self::new(params.get_piece_byte_len(), params.get_piece_count()).unwrap_unchecked() }
+    }
+
+    /// Number of pieces original data got split into and coded together.
+    pub fn get_num_pieces_coded_together(&self) -> usize {
+        self.required_piece_count
+    }
+
+    /// Byte length of each piece, original data got split into, after padding.
+    pub fn get_piece_byte_len(&self) -> usize {
+        self.piece_byte_len
+    }
+
+    /// Each full coded piece consists of `self.get_num_pieces_coded_together()` coefficients, appended by corresponding encoded piece of `self.get_piece_byte_len()` bytes.
+    pub fn get_full_coded_piece_byte_len(&self) -> usize {
+        self.get_num_pieces_coded_together() + self.get_piece_byte_len()
+    }
+
+    /// Total number of pieces received by the decoder so far.
+    pub fn get_received_piece_count(&self) -> usize {
+        self.received_piece_count
+    }
+
+    /// Number of useful pieces received by the decoder so far.
+    pub fn get_useful_piece_count(&self) -> usize {
+        self.useful_piece_count
+    }
+
+    /// Number of pieces remaining to be received by the decoder for successful decoding.
+    pub fn get_remaining_piece_count(&self) -> usize {
+        self.get_num_pieces_coded_together() - self.get_useful_piece_count()
+    }
+
+    /// Total number of non-zero coefficients held by pivot rows, which decides the cost of back-substitution.
+    pub fn get_nonzero_coefficient_count(&self) -> usize {
+        self.pivot_rows.iter().flatten().map(|row| row.coefficients.len()).sum()
+    }
+
+    /// Checks if the decoder has received enough useful pieces to decode.
+    pub fn is_already_decoded(&self) -> bool {
+        self.useful_piece_count == self.required_piece_count
+    }
+
+    /// Decodes a full coded piece, reducing it by pivot rows of the columns it has non-zero coefficients in.
+    ///
+    /// # Arguments
+    /// * `full_coded_piece` - A slice containing the coefficients followed by
+    ///   the coded data for one piece. Its length must be `required_piece_count + piece_byte_len`.
+    ///
+    /// # Returns
+    /// * Returns `Ok(())` if the piece was useful and added successfully.
+    /// * Returns `Err(RLNCError::ReceivedAllPieces)` if decoding is already complete.
+    /// * Returns `Err(RLNCError::PieceNotUseful)` if the piece was linearly dependent on the already received useful pieces.
+    /// * Returns `Err(RLNCError::InvalidPieceLength)` if the `full_coded_piece` has an unexpected length.
+    pub fn decode(&mut self, full_coded_piece: &[u8]) -> Result<(), RLNCError> {
+        if self.is_already_decoded() {
+            return Err(RLNCError::ReceivedAllPieces);
+        }
+        if full_coded_piece.len() != self.get_full_coded_piece_byte_len() {
+            return Err(RLNCError::InvalidPieceLength);
+        }
+
+        self.received_piece_count += 1;
+
+        let (coding_vector, payload) = full_coded_piece.split_at(self.required_piece_count);
+        let mut row = SparseRow {
+            coefficients: coding_vector
+                .iter()
+                .enumerate()
+                .filter(|&(_, &coeff)| coeff != 0)
+                .map(|(cidx, &coeff)| (cidx, coeff))
+                .collect(),
+            payload: payload.to_vec(),
+        };
+        let mut scratch = Vec::with_capacity(row.coefficients.len());
+
+        while let Some(&(leading_cidx, leading_coeff)) = row.coefficients.first() {
+            let Some(pivot_row) = self.pivot_rows[leading_cidx].as_ref() else {
+                // Normalize leading coefficient to one, so that back-substitution needs no division.
+                if leading_coeff != 1 {
+                    let inv = unsafe { Gf256::new(leading_coeff).inv().unwrap_unchecked().get() };
+
+                    row.coefficients.iter_mut().for_each(|(_, coeff)| *coeff = Gf256::mul_const(*coeff, inv));
+                    gf256_inplace_mul_vec_by_scalar(&mut row.payload, inv);
+                }
+
+                self.pivot_rows[leading_cidx] = Some(row);
+                self.useful_piece_count += 1;
+
+                return Ok(());
+            };
+
+            // Pivot row's leading coefficient is one, so the leading coefficient itself is the quotient.
+            Self::add_scaled_coefficients(&row.coefficients, &pivot_row.coefficients, leading_coeff, &mut scratch);
+            std::mem::swap(&mut row.coefficients, &mut scratch);
+            gf256_mul_vec_by_scalar_then_add_into_vec(&mut row.payload, &pivot_row.payload, leading_coeff);
+        }
+
+        Err(RLNCError::PieceNotUseful)
+    }
+
+    /// Recovers and returns the original data byte vector if decoding is complete, by back-substitution, starting
+    /// from the pivot row of the last column.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Vec<u8>)` containing the decoded data if successful.
+    /// * Returns `Err(RLNCError::NotAllPiecesReceivedYet)` if not enough useful pieces have been received.
+    /// * Returns `Err(RLNCError::InvalidDecodedDataFormat(_))` if the extracted data does not follow the expected format (e.g., boundary marker issues).
+    pub fn get_decoded_data(self) -> Result<Vec<u8>, RLNCError> {
+        if !self.is_already_decoded() {
+            return Err(RLNCError::NotAllPiecesReceivedYet);
+        }
+
+        let mut decoded_pieces = vec![Vec::new(); self.required_piece_count];
+
+        for (cidx, pivot_row) in self.pivot_rows.into_iter().enumerate().rev() {
+            let SparseRow { coefficients, mut payload } = unsafe { pivot_row.unwrap_unchecked() };
+
+            // All other columns, this row has non-zero coefficients in, come later, so they are already decoded.
+            coefficients
+                .iter()
+                .skip(1)
+                .for_each(|&(other_cidx, coeff)| gf256_mul_vec_by_scalar_then_add_into_vec(&mut payload, &decoded_pieces[other_cidx], coeff));
+
+            decoded_pieces[cidx] = payload;
+        }
+
+        let mut buf = decoded_pieces.concat();
+        let final_len = Decoder::get_final_data_len(&buf, self.required_piece_count, self.received_piece_count)?;
+
+        buf.truncate(final_len);
+        Ok(buf)
+    }
+
+    /// Computes `row + scalar x pivot_row`, over sparse coefficient lists sorted by column, into `result`, dropping zeros.
+    fn add_scaled_coefficients(row: &[(usize, u8)], pivot_row: &[(usize, u8)], scalar: u8, result: &mut Vec<(usize, u8)>) {
+        result.clear();
+
+        let (mut i, mut j) = (0, 0);
+        while i < row.len() || j < pivot_row.len() {
+            let row_cidx = row.get(i).map_or(usize::MAX, |&(cidx, _)| cidx);
+            let pivot_row_cidx = pivot_row.get(j).map_or(usize::MAX, |&(cidx, _)| cidx);
+
+            let (cidx, coeff) = if row_cidx < pivot_row_cidx {
+                i += 1;
+                row[i - 1]
+            } else if pivot_row_cidx < row_cidx {
+                j += 1;
+                (pivot_row_cidx, Gf256::mul_const(pivot_row[j - 1].1, scalar))
+            } else {
+                i += 1;
+                j += 1;
+                (row_cidx, row[i - 1].1 ^ Gf256::mul_const(pivot_row[j - 1].1, scalar))
+            };
+
+            if coeff != 0 {
+                result.push((cidx, coeff));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SparseDecoder;
+    use crate::{
+        RLNCError,
+        full::{Decoder, Encoder},
+    };
+    use rand::Rng;
+
+    #[test]
+    fn test_sparse_decoder_banded_coding_vectors() {
+        let mut rng = rand::rng();
+
+        let data = (0..100_000).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 256).expect("Failed to create Encoder for sparse decoder test");
+
+        let piece_count = encoder.get_piece_count();
+        let band_width = 16;
+
+        let mut decoder = SparseDecoder::new_with(encoder.get_params());
+        let mut full_coded_piece = vec![0u8; encoder.get_full_coded_piece_byte_len()];
+
+        while !decoder.is_already_decoded() {
+            let (coding_vector, coded_data) = full_coded_piece.split_at_mut(piece_count);
+            let band_begin = rng.random_range(0..=piece_count - band_width);
+
+            coding_vector.fill(0);
+            rng.fill(&mut coding_vector[band_begin..band_begin + band_width]);
+            encoder.code_with_coding_vector(coding_vector, coded_data).expect("Coding must not fail");
+
+            let _ = decoder.decode(&full_coded_piece);
+        }
+
+        // Fill-in stays far below that of a dense matrix.
+        assert!(decoder.get_nonzero_coefficient_count() < piece_count * piece_count / 4);
+        assert_eq!(decoder.decode(&full_coded_piece), Err(RLNCError::ReceivedAllPieces));
+        assert_eq!(decoder.get_decoded_data(), Ok(data));
+    }
+
+    #[test]
+    fn test_sparse_decoder_matches_dense_decoder() {
+        let mut rng = rand::rng();
+
+        let data = (0..10_000).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 32).expect("Failed to create Encoder for sparse decoder test");
+
+        let mut sparse_decoder = SparseDecoder::new_with(encoder.get_params());
+        let mut dense_decoder = Decoder::new_with(encoder.get_params());
+
+        assert_eq!(sparse_decoder.clone().get_decoded_data(), Err(RLNCError::NotAllPiecesReceivedYet));
+        assert_eq!(sparse_decoder.decode(&[0u8; 10]), Err(RLNCError::InvalidPieceLength));
+
+        while !dense_decoder.is_already_decoded() {
+            let full_coded_piece = encoder.code(&mut rng);
+            assert_eq!(sparse_decoder.decode(&full_coded_piece), dense_decoder.decode(&full_coded_piece));
+        }
+
+        assert!(sparse_decoder.is_already_decoded());
+        assert_eq!(sparse_decoder.get_decoded_data(), Ok(data.clone()));
+        assert_eq!(dense_decoder.get_decoded_data(), Ok(data));
+    }
+}
@@ -0,0 +1,595 @@
+use crate::RLNCError;
+use std::fmt::Debug;
+
+/// Backing memory, through which decoder matrix rows and recoder's received pieces are stored.
+///
+/// Storage is a growable byte buffer, holding fixed length rows back to back. By default it's contiguous RAM i.e. `Vec<u8>`,
+/// but environments with special memory requirements, say huge pages, pinned DMA buffers or persistent memory,
+/// can supply their own implementation, using `Decoder::new_with_storage` and `Recoder::new_with_storage`.
+///
+/// This trait is object-safe, so that storage can be chosen at runtime, without making decoder and recoder generic.
+pub trait PieceStorage: Debug + Send + Sync {
+    /// Number of bytes currently held in storage.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if storage holds no bytes.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Reserves room for at least `additional` more bytes. It's only a hint, default implementation does nothing.
+    fn reserve(&mut self, _additional: usize) {}
+
+    /// Appends given bytes at the end of storage.
+    ///
+    /// # Returns
+    /// * Returns `Ok(())` on success.
+    /// * Returns `Err(RLNCError::PieceStorageFailure)` if storage can't grow.
+    fn try_extend_from_slice(&mut self, bytes: &[u8]) -> Result<(), RLNCError>;
+
+    /// Shortens storage, keeping first `byte_len` bytes. It has no effect, if `byte_len` is not less than current length.
+    fn truncate(&mut self, byte_len: usize);
+
+    /// All bytes currently held in storage.
+    fn as_slice(&self) -> &[u8];
+
+    /// Mutable access to all bytes currently held in storage.
+    fn as_mut_slice(&mut self) -> &mut [u8];
+
+    /// Returns a deep copy of this storage.
+    fn clone_boxed(&self) -> Box<dyn PieceStorage>;
+
+    /// Consumes storage, returning all bytes held in it, as a vector.
+    fn into_vec(self: Box<Self>) -> Vec<u8> {
+        self.as_slice().to_vec()
+    }
+}
+
+/// Contiguous RAM, the default storage.
+impl PieceStorage for Vec<u8> {
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        Vec::reserve(self, additional);
+    }
+
+    fn try_extend_from_slice(&mut self, bytes: &[u8]) -> Result<(), RLNCError> {
+        self.extend_from_slice(bytes);
+        Ok(())
+    }
+
+    fn truncate(&mut self, byte_len: usize) {
+        Vec::truncate(self, byte_len);
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        self
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [u8] {
+        self
+    }
+
+    fn clone_boxed(&self) -> Box<dyn PieceStorage> {
+        Box::new(self.clone())
+    }
+
+    fn into_vec(self: Box<Self>) -> Vec<u8> {
+        *self
+    }
+}
+
+impl Clone for Box<dyn PieceStorage + '_> {
+    fn clone(&self) -> Self {
+        self.clone_boxed()
+    }
+}
+
+/// Fixed capacity storage, over a caller provided buffer, say a statically allocated one, a stack array or a slice of a
+/// preallocated arena. It never grows, appending past the end of the buffer fails. Copies can't share the buffer, so they're
+/// backed by contiguous RAM. Note, `Decoder` and `Recoder` box their storage, so they take `BufferStorage<'static>` only, see
+/// `DecoderRef` for decoding allocation-free, over a buffer of any lifetime.
+#[derive(Debug)]
+pub struct BufferStorage<'a> {
+    buffer: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> BufferStorage<'a> {
+    /// Creates empty storage, over given buffer, whose current content is ignored.
+    pub fn new(buffer: &'a mut [u8]) -> BufferStorage<'a> {
+        BufferStorage { buffer, len: 0 }
+    }
+
+    /// Number of bytes, storage can hold, which is the byte length of the buffer.
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+}
+
+impl PieceStorage for BufferStorage<'_> {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn try_extend_from_slice(&mut self, bytes: &[u8]) -> Result<(), RLNCError> {
+        let new_len = self.len + bytes.len();
+        if new_len > self.capacity() {
+            return Err(RLNCError::PieceStorageFailure);
+        }
+
+        self.buffer[self.len..new_len].copy_from_slice(bytes);
+        self.len = new_len;
+
+        Ok(())
+    }
+
+    fn truncate(&mut self, byte_len: usize) {
+        self.len = self.len.min(byte_len);
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        &self.buffer[..self.len]
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [u8] {
+        &mut self.buffer[..self.len]
+    }
+
+    fn clone_boxed(&self) -> Box<dyn PieceStorage> {
+        Box::new(self.as_slice().to_vec())
+    }
+}
+
+#[cfg(feature = "mmap")]
+pub use mmap::MmapStorage;
+
+#[cfg(feature = "mmap")]
+mod mmap {
+    use super::PieceStorage;
+    use crate::RLNCError;
+    use memmap2::{MmapMut, MmapOptions};
+    use std::{fs::File, io};
+
+    /// Memory mapped storage, either anonymous or backed by a file, in which case pieces spill to disk
+    /// instead of occupying RAM. File backed storage grows the file and remaps it, as rows are added.
+    #[derive(Debug)]
+    pub struct MmapStorage {
+        file: Option<File>,
+        map: MmapMut,
+        len: usize,
+    }
+
+    impl MmapStorage {
+        /// Creates anonymous memory mapped storage, with room for `capacity` bytes, before it needs to remap.
+        pub fn anonymous(capacity: usize) -> io::Result<MmapStorage> {
+            Ok(MmapStorage {
+                file: None,
+                map: MmapMut::map_anon(capacity.max(1))?,
+                len: 0,
+            })
+        }
+
+        /// Creates storage, which spills to given file. The file is truncated and resized to `capacity` bytes.
+        ///
+        /// File must be opened for both reading and writing, and it must not be modified by anyone else, while it's mapped.
+        pub fn file_backed(file: File, capacity: usize) -> io::Result<MmapStorage> {
+            let capacity = capacity.max(1) as u64;
+
+            file.set_len(0)?;
+            file.set_len(capacity)?;
+            let map = unsafe { MmapMut::map_mut(&file)? };
+
+            Ok(MmapStorage { file: Some(file), map, len: 0 })
+        }
+
+        /// Creates storage over a private, copy-on-write mapping of given file, holding its first `len` bytes, so that file content
+        /// is paged in on demand, instead of being read upfront. Writes never reach the file, and growing the storage moves it to
+        /// an anonymous mapping. File must not be modified by anyone else, while it's mapped.
+        pub(crate) fn file_copy_on_write(file: &File, len: usize) -> io::Result<MmapStorage> {
+            let map = unsafe { MmapOptions::new().map_copy(file)? };
+
+            Ok(MmapStorage {
+                file: None,
+                len: len.min(map.len()),
+                map,
+            })
+        }
+
+        /// Number of bytes, storage can hold, before it needs to remap.
+        pub fn capacity(&self) -> usize {
+            self.map.len()
+        }
+
+        fn grow(&mut self, min_capacity: usize) -> io::Result<()> {
+            let new_capacity = min_capacity.max(self.capacity() * 2);
+
+            match self.file.as_ref() {
+                Some(file) => {
+                    self.map.flush()?;
+                    file.set_len(new_capacity as u64)?;
+                    self.map = unsafe { MmapMut::map_mut(file)? };
+                }
+                None => {
+                    let mut map = MmapMut::map_anon(new_capacity)?;
+                    map[..self.len].copy_from_slice(&self.map[..self.len]);
+                    self.map = map;
+                }
+            }
+
+            Ok(())
+        }
+    }
+
+    impl PieceStorage for MmapStorage {
+        fn len(&self) -> usize {
+            self.len
+        }
+
+        fn reserve(&mut self, additional: usize) {
+            let min_capacity = self.len + additional;
+            if min_capacity > self.capacity() {
+                // It's only a hint, failure surfaces once bytes are actually appended.
+                let _ = self.grow(min_capacity);
+            }
+        }
+
+        fn try_extend_from_slice(&mut self, bytes: &[u8]) -> Result<(), RLNCError> {
+            let new_len = self.len + bytes.len();
+            if new_len > self.capacity() {
+                self.grow(new_len).map_err(|_| RLNCError::PieceStorageFailure)?;
+            }
+
+            self.map[self.len..new_len].copy_from_slice(bytes);
+            self.len = new_len;
+
+            Ok(())
+        }
+
+        fn truncate(&mut self, byte_len: usize) {
+            self.len = self.len.min(byte_len);
+        }
+
+        fn as_slice(&self) -> &[u8] {
+            &self.map[..self.len]
+        }
+
+        fn as_mut_slice(&mut self) -> &mut [u8] {
+            &mut self.map[..self.len]
+        }
+
+        /// Copy is always anonymous, so that two storages never share a file.
+        fn clone_boxed(&self) -> Box<dyn PieceStorage> {
+            let mut copy = match MmapStorage::anonymous(self.capacity()) {
+                Ok(copy) => copy,
+                Err(_) => return Box::new(self.as_slice().to_vec()),
+            };
+
+            copy.map[..self.len].copy_from_slice(self.as_slice());
+            copy.len = self.len;
+
+            Box::new(copy)
+        }
+    }
+}
+
+#[cfg(all(feature = "numa", target_os = "linux"))]
+pub use huge_page::HugePageStorage;
+
+#[cfg(all(feature = "numa", target_os = "linux"))]
+mod huge_page {
+    use super::PieceStorage;
+    use crate::RLNCError;
+    use memmap2::{Advice, MmapMut};
+    use std::io;
+
+    /// Size of a transparent huge page on x86_64 and aarch64 with 4KB base pages.
+    const HUGE_PAGE_BYTE_LEN: usize = 2 << 20;
+
+    /// Anonymous memory mapped storage, advised to be backed by transparent huge pages, reducing TLB misses, while sweeping
+    /// through multi-GB encoder source copies or decoder matrices. Optionally, its pages are bound to a NUMA node, so that
+    /// threads pinned to that node, see `full::numa_thread_pool`, never cross the interconnect. Capacity is rounded up to
+    /// huge page size. Linux only.
+    #[derive(Debug)]
+    pub struct HugePageStorage {
+        map: MmapMut,
+        len: usize,
+        node: Option<usize>,
+    }
+
+    impl HugePageStorage {
+        /// Creates storage with room for `capacity` bytes, before it needs to remap, placing pages on any NUMA node.
+        pub fn new(capacity: usize) -> io::Result<HugePageStorage> {
+            Ok(HugePageStorage {
+                map: Self::map(capacity, None)?,
+                len: 0,
+                node: None,
+            })
+        }
+
+        /// Creates storage with room for `capacity` bytes, before it needs to remap, binding pages to given NUMA node.
+        pub fn on_node(capacity: usize, node: usize) -> io::Result<HugePageStorage> {
+            Ok(HugePageStorage {
+                map: Self::map(capacity, Some(node))?,
+                len: 0,
+                node: Some(node),
+            })
+        }
+
+        /// Number of bytes, storage can hold, before it needs to remap.
+        pub fn capacity(&self) -> usize {
+            self.map.len()
+        }
+
+        /// NUMA node, pages are bound to, if any.
+        pub fn get_node(&self) -> Option<usize> {
+            self.node
+        }
+
+        fn map(capacity: usize, node: Option<usize>) -> io::Result<MmapMut> {
+            let capacity = capacity.max(1).next_multiple_of(HUGE_PAGE_BYTE_LEN);
+
+            let map = MmapMut::map_anon(capacity)?;
+            map.advise(Advice::HugePage)?;
+
+            if let Some(node) = node {
+                Self::bind_to_node(&map, node)?;
+            }
+
+            Ok(map)
+        }
+
+        /// Sets memory policy of the whole mapping to allocate pages only on given NUMA node, using `mbind(2)`. Pages are not
+        /// touched yet, so they get allocated on that node, on first write.
+        fn bind_to_node(map: &MmapMut, node: usize) -> io::Result<()> {
+            const NODE_MASK_WORD_BIT_LEN: usize = libc::c_ulong::BITS as usize;
+
+            let mut node_mask = vec![0 as libc::c_ulong; node / NODE_MASK_WORD_BIT_LEN + 1];
+            node_mask[node / NODE_MASK_WORD_BIT_LEN] |= 1 << (node % NODE_MASK_WORD_BIT_LEN);
+
+            // Kernel reads `maxnode - 1` bits of the node mask.
+            let max_node = node_mask.len() * NODE_MASK_WORD_BIT_LEN + 1;
+            let result = unsafe { libc::syscall(libc::SYS_mbind, map.as_ptr(), map.len(), libc::MPOL_BIND, node_mask.as_ptr(), max_node, 0) };
+
+            if result != 0 { Err(io::Error::last_os_error()) } else { Ok(()) }
+        }
+
+        fn grow(&mut self, min_capacity: usize) -> io::Result<()> {
+            let mut map = Self::map(min_capacity.max(self.capacity() * 2), self.node)?;
+            map[..self.len].copy_from_slice(&self.map[..self.len]);
+            self.map = map;
+
+            Ok(())
+        }
+    }
+
+    impl PieceStorage for HugePageStorage {
+        fn len(&self) -> usize {
+            self.len
+        }
+
+        fn reserve(&mut self, additional: usize) {
+            let min_capacity = self.len + additional;
+            if min_capacity > self.capacity() {
+                // It's only a hint, failure surfaces once bytes are actually appended.
+                let _ = self.grow(min_capacity);
+            }
+        }
+
+        fn try_extend_from_slice(&mut self, bytes: &[u8]) -> Result<(), RLNCError> {
+            let new_len = self.len + bytes.len();
+            if new_len > self.capacity() {
+                self.grow(new_len).map_err(|_| RLNCError::PieceStorageFailure)?;
+            }
+
+            self.map[self.len..new_len].copy_from_slice(bytes);
+            self.len = new_len;
+
+            Ok(())
+        }
+
+        fn truncate(&mut self, byte_len: usize) {
+            self.len = self.len.min(byte_len);
+        }
+
+        fn as_slice(&self) -> &[u8] {
+            &self.map[..self.len]
+        }
+
+        fn as_mut_slice(&mut self) -> &mut [u8] {
+            &mut self.map[..self.len]
+        }
+
+        /// Copy is bound to the same NUMA node, falling back to contiguous RAM, if mapping fails.
+        fn clone_boxed(&self) -> Box<dyn PieceStorage> {
+            let Ok(map) = Self::map(self.capacity(), self.node) else {
+                return Box::new(self.as_slice().to_vec());
+            };
+
+            let mut copy = HugePageStorage {
+                map,
+                len: self.len,
+                node: self.node,
+            };
+            copy.map[..self.len].copy_from_slice(self.as_slice());
+
+            Box::new(copy)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BufferStorage, PieceStorage};
+    use crate::{
+        RLNCError,
+        full::{Decoder, Encoder, Recoder},
+    };
+
+    /// Contiguous RAM, which refuses to grow beyond a fixed number of bytes.
+    #[derive(Clone, Debug)]
+    struct CappedStorage {
+        bytes: Vec<u8>,
+        max_byte_len: usize,
+    }
+
+    impl PieceStorage for CappedStorage {
+        fn len(&self) -> usize {
+            self.bytes.len()
+        }
+
+        fn try_extend_from_slice(&mut self, bytes: &[u8]) -> Result<(), RLNCError> {
+            if self.bytes.len() + bytes.len() > self.max_byte_len {
+                return Err(RLNCError::PieceStorageFailure);
+            }
+
+            self.bytes.extend_from_slice(bytes);
+            Ok(())
+        }
+
+        fn truncate(&mut self, byte_len: usize) {
+            self.bytes.truncate(byte_len);
+        }
+
+        fn as_slice(&self) -> &[u8] {
+            &self.bytes
+        }
+
+        fn as_mut_slice(&mut self) -> &mut [u8] {
+            &mut self.bytes
+        }
+
+        fn clone_boxed(&self) -> Box<dyn PieceStorage> {
+            Box::new(self.clone())
+        }
+    }
+
+    fn exercise_storage(mut storage: Box<dyn PieceStorage + '_>) {
+        assert!(storage.is_empty());
+
+        storage.reserve(8);
+        storage.try_extend_from_slice(&[1, 2, 3, 4]).expect("Appending must not fail");
+        storage.try_extend_from_slice(&[5, 6, 7, 8, 9, 10]).expect("Appending must not fail");
+        assert_eq!(storage.as_slice(), &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+
+        storage.as_mut_slice()[0] = 11;
+        storage.truncate(12);
+        assert_eq!(storage.len(), 10);
+        storage.truncate(3);
+        assert_eq!(storage.as_slice(), &[11, 2, 3]);
+
+        let copy = storage.clone();
+        storage.as_mut_slice()[1] = 0;
+        assert_eq!(copy.into_vec(), vec![11, 2, 3]);
+        assert_eq!(storage.into_vec(), vec![11, 0, 3]);
+    }
+
+    #[test]
+    fn test_vec_piece_storage() {
+        exercise_storage(Box::new(Vec::new()));
+    }
+
+    #[test]
+    fn test_buffer_piece_storage() {
+        let mut buffer = [0xff; 12];
+        let mut storage = BufferStorage::new(&mut buffer);
+        storage.try_extend_from_slice(&[0; 10]).expect("Appending must not fail");
+        assert_eq!(storage.try_extend_from_slice(&[0; 3]), Err(RLNCError::PieceStorageFailure));
+        storage.truncate(0);
+
+        exercise_storage(Box::new(storage));
+        assert_eq!(&buffer[..4], &[11, 0, 3, 4]);
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_mmap_piece_storage() {
+        use super::MmapStorage;
+
+        exercise_storage(Box::new(MmapStorage::anonymous(2).expect("Anonymous mapping must not fail")));
+
+        let path = std::env::temp_dir().join(format!("rlnc-mmap-storage-test-{}", std::process::id()));
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)
+            .expect("Temporary file must be creatable");
+
+        exercise_storage(Box::new(MmapStorage::file_backed(file, 2).expect("File backed mapping must not fail")));
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_custom_piece_storage_for_decoder_and_recoder() {
+        let mut rng = rand::rng();
+
+        let data = (0..1024).map(|_| rand::Rng::random(&mut rng)).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 16).expect("Failed to create Encoder");
+        let params = encoder.get_params();
+
+        let coded_pieces = (0..8).flat_map(|_| encoder.code(&mut rng)).collect::<Vec<u8>>();
+        let recoder_storage = CappedStorage {
+            bytes: Vec::new(),
+            max_byte_len: 8 * params.get_piece_byte_len(),
+        };
+        let mut recoder = Recoder::new_with_storage(
+            coded_pieces.clone(),
+            params.get_full_coded_piece_byte_len(),
+            params.get_piece_count(),
+            Box::new(recoder_storage.clone()),
+        )
+        .expect("Failed to create Recoder");
+
+        // Storage is one piece short of holding all received pieces.
+        let small_storage = CappedStorage {
+            max_byte_len: 7 * params.get_piece_byte_len(),
+            ..recoder_storage
+        };
+        assert!(matches!(
+            Recoder::new_with_storage(
+                coded_pieces,
+                params.get_full_coded_piece_byte_len(),
+                params.get_piece_count(),
+                Box::new(small_storage)
+            ),
+            Err(RLNCError::PieceStorageFailure)
+        ));
+
+        // Decoder matrix can hold only half of the pieces required for decoding.
+        let decoder_storage = CappedStorage {
+            bytes: Vec::new(),
+            max_byte_len: (params.get_piece_count() / 2) * params.get_full_coded_piece_byte_len(),
+        };
+        let mut decoder = Decoder::new_with_storage(params.get_piece_byte_len(), params.get_piece_count(), Box::new(decoder_storage.clone()))
+            .expect("Failed to create Decoder");
+
+        loop {
+            match decoder.decode(&recoder.recode(&mut rng)) {
+                Ok(_) | Err(RLNCError::PieceNotUseful) => {}
+                Err(RLNCError::PieceStorageFailure) => break,
+                Err(e) => panic!("Unexpected error during decoding: {e:?}"),
+            }
+        }
+        assert_eq!(decoder.get_useful_piece_count(), params.get_piece_count() / 2);
+
+        let decoder_storage = CappedStorage {
+            max_byte_len: params.get_piece_count() * params.get_full_coded_piece_byte_len(),
+            ..decoder_storage
+        };
+        let mut decoder =
+            Decoder::new_with_storage(params.get_piece_byte_len(), params.get_piece_count(), Box::new(decoder_storage)).expect("Failed to create Decoder");
+
+        while !decoder.is_already_decoded() {
+            match decoder.decode(&encoder.code(&mut rng)) {
+                Ok(_) | Err(RLNCError::PieceNotUseful) => {}
+                Err(e) => panic!("Unexpected error during decoding: {e:?}"),
+            }
+        }
+
+        assert_eq!(decoder.get_decoded_data(), Ok(data));
+    }
+}
@@ -1,6 +1,10 @@
 #![cfg(test)]
 
-use super::{decoder::Decoder, encoder::Encoder, recoder::Recoder};
+use super::{
+    decoder::{Decoder, PayloadLayout},
+    encoder::Encoder,
+    recoder::Recoder,
+};
 use crate::RLNCError;
 use rand::Rng;
 
@@ -202,3 +206,46 @@ fn prop_test_rlnc_decoding_with_useless_pieces() {
         assert_eq!(data_copy, decoded_data);
     });
 }
+
+#[test]
+fn prop_test_rlnc_encoder_decoder_with_column_major_payload_layout() {
+    const NUM_TEST_ITERATIONS: usize = 10;
+
+    const MIN_DATA_BYTE_LEN: usize = 1usize << 10;
+    const MAX_DATA_BYTE_LEN: usize = 1usize << 16;
+
+    const MIN_PIECE_COUNT: usize = 1usize << 1;
+    const MAX_PIECE_COUNT: usize = 1usize << 8;
+
+    let mut rng = rand::rng();
+
+    (0..NUM_TEST_ITERATIONS).for_each(|_| {
+        let data_byte_len = rng.random_range(MIN_DATA_BYTE_LEN..=MAX_DATA_BYTE_LEN);
+        let piece_count = rng.random_range(MIN_PIECE_COUNT..=MAX_PIECE_COUNT);
+
+        let data = (0..data_byte_len).map(|_| rng.random()).collect::<Vec<u8>>();
+        let data_copy = data.clone();
+
+        let encoder = Encoder::new(data, piece_count).expect("Failed to create Encoder");
+        let mut decoder =
+            Decoder::new_with_layout(encoder.get_piece_byte_len(), encoder.get_piece_count(), PayloadLayout::ColumnMajor).expect("Failed to create Decoder");
+
+        loop {
+            let coded_piece = encoder.code(&mut rng);
+
+            match decoder.decode(&coded_piece) {
+                Ok(_) => {}
+                Err(e) => match e {
+                    RLNCError::ReceivedAllPieces => break,
+                    RLNCError::PieceNotUseful => continue,
+                    _ => panic!("Did not expect this error during decoding: {e}"),
+                },
+            };
+        }
+
+        assert!(decoder.is_already_decoded());
+        let decoded_data = decoder.get_decoded_data().expect("Extracting decoded data must not fail!");
+
+        assert_eq!(data_copy, decoded_data);
+    });
+}
@@ -0,0 +1,232 @@
+use super::{dtn::unix_timestamp, params::Params, recoder::Recoder};
+use crate::RLNCError;
+use rand::Rng;
+use std::time::SystemTime;
+
+/// Byte length of the header of a serialized `TtlPiece` i.e. big-endian expiry timestamp and hop limit.
+const TTL_PIECE_HEADER_BYTE_LEN: usize = size_of::<u64>() + size_of::<u8>();
+
+/// Full coded piece, carrying its expiry timestamp, in seconds since UNIX epoch, and the number of hops it can still be forwarded over.
+///
+/// Decoders stop accepting a piece once it's expired, see `Decoder::decode_ttl`. Relays stop buffering it once it's expired or has no
+/// hops left, and pieces they recode inherit the earliest expiry timestamp and one less than the smallest hop limit of their inputs,
+/// see `TtlRecoder`. So stale pieces can't circulate forever in gossip meshes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TtlPiece {
+    expires_at: u64,
+    hop_limit: u8,
+    full_coded_piece: Vec<u8>,
+}
+
+impl TtlPiece {
+    /// Stamps a full coded piece with its expiry timestamp, in seconds since UNIX epoch, and hop limit.
+    pub fn new(expires_at: u64, hop_limit: u8, full_coded_piece: Vec<u8>) -> TtlPiece {
+        TtlPiece {
+            expires_at,
+            hop_limit,
+            full_coded_piece,
+        }
+    }
+
+    /// Expiry timestamp, in seconds since UNIX epoch.
+    pub fn get_expires_at(&self) -> u64 {
+        self.expires_at
+    }
+
+    /// Number of hops, the piece can still be forwarded over.
+    pub fn get_hop_limit(&self) -> u8 {
+        self.hop_limit
+    }
+
+    /// Coding vector followed by coded data.
+    pub fn get_full_coded_piece(&self) -> &[u8] {
+        &self.full_coded_piece
+    }
+
+    /// Returns `true` if the piece is stale at given time.
+    pub fn is_expired(&self, now: SystemTime) -> bool {
+        unix_timestamp(now) >= self.expires_at
+    }
+
+    /// Returns `true` if the piece is neither expired at given time, nor out of hops, so that a relay can buffer and forward it.
+    pub fn is_forwardable(&self, now: SystemTime) -> bool {
+        self.hop_limit > 0 && !self.is_expired(now)
+    }
+
+    /// Serializes the piece as big-endian expiry timestamp (8 bytes) and hop limit (1 byte), followed by the full coded piece.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        [self.expires_at.to_be_bytes().as_slice(), &[self.hop_limit], &self.full_coded_piece].concat()
+    }
+
+    /// Deserializes a piece, serialized using `Self::to_bytes`.
+    ///
+    /// # Returns
+    /// * Returns `Ok(TtlPiece)` on success.
+    /// * Returns `Err(RLNCError::SerializationFailure)` if `bytes` is not longer than the header.
+    pub fn from_bytes(bytes: &[u8]) -> Result<TtlPiece, RLNCError> {
+        if bytes.len() <= TTL_PIECE_HEADER_BYTE_LEN {
+            return Err(RLNCError::SerializationFailure);
+        }
+
+        let (expires_at, rest) = bytes.split_at(size_of::<u64>());
+        let (hop_limit, full_coded_piece) = rest.split_at(size_of::<u8>());
+
+        Ok(TtlPiece {
+            expires_at: u64::from_be_bytes(unsafe { expires_at.try_into().unwrap_unchecked() }),
+            hop_limit: hop_limit[0],
+            full_coded_piece: full_coded_piece.to_vec(),
+        })
+    }
+}
+
+/// Relay, which buffers forwardable pieces, drops them once they expire, and recodes live ones, decrementing hop limit.
+#[derive(Clone, Debug)]
+pub struct TtlRecoder {
+    params: Params,
+    pieces: Vec<TtlPiece>,
+    /// Recoder over buffered pieces, rebuilt lazily, after the buffer changes.
+    recoder: Option<Recoder>,
+}
+
+impl TtlRecoder {
+    /// Creates a relay with an empty buffer, for pieces of given RLNC configuration.
+    pub fn new(params: Params) -> TtlRecoder {
+        TtlRecoder {
+            params,
+            pieces: Vec::new(),
+            recoder: None,
+        }
+    }
+
+    /// Number of buffered pieces, some of which might have expired since the last call to `Self::prune`.
+    pub fn get_buffered_piece_count(&self) -> usize {
+        self.pieces.len()
+    }
+
+    /// Buffers a received piece, so that it's recoded along with other buffered ones.
+    ///
+    /// # Returns
+    /// * Returns `Ok(())` on success.
+    /// * Returns `Err(RLNCError::PieceExpired)` if the piece is expired or has no hops left, so it's dropped.
+    /// * Returns `Err(RLNCError::InvalidPieceLength)` if the full coded piece doesn't match RLNC configuration.
+    pub fn add_piece(&mut self, piece: TtlPiece, now: SystemTime) -> Result<(), RLNCError> {
+        if piece.full_coded_piece.len() != self.params.get_full_coded_piece_byte_len() {
+            return Err(RLNCError::InvalidPieceLength);
+        }
+        if !piece.is_forwardable(now) {
+            return Err(RLNCError::PieceExpired);
+        }
+
+        self.pieces.push(piece);
+        self.recoder = None;
+
+        Ok(())
+    }
+
+    /// Drops buffered pieces, which are expired at given time, returning how many got dropped.
+    pub fn prune(&mut self, now: SystemTime) -> usize {
+        let buffered_piece_count = self.pieces.len();
+        self.pieces.retain(|piece| !piece.is_expired(now));
+
+        let dropped_piece_count = buffered_piece_count - self.pieces.len();
+        if dropped_piece_count > 0 {
+            self.recoder = None;
+        }
+
+        dropped_piece_count
+    }
+
+    /// Prunes expired pieces and recodes the live ones. The recoded piece expires with the earliest expiring input and can be
+    /// forwarded over one hop less than the input with the smallest hop limit.
+    ///
+    /// # Returns
+    /// * Returns `Ok(TtlPiece)` holding the recoded piece.
+    /// * Returns `Err(RLNCError::NotEnoughPiecesToRecode)` if no live piece is buffered.
+    pub fn recode<R: Rng + ?Sized>(&mut self, rng: &mut R, now: SystemTime) -> Result<TtlPiece, RLNCError> {
+        self.prune(now);
+        if self.pieces.is_empty() {
+            return Err(RLNCError::NotEnoughPiecesToRecode);
+        }
+
+        let recoder = match self.recoder.as_mut() {
+            Some(recoder) => recoder,
+            None => {
+                let data = self.pieces.iter().flat_map(|piece| piece.full_coded_piece.iter().copied()).collect::<Vec<u8>>();
+                self.recoder.insert(Recoder::new_with(data, self.params)?)
+            }
+        };
+
+        let expires_at = self.pieces.iter().map(|piece| piece.expires_at).min().unwrap_or_default();
+        let hop_limit = self.pieces.iter().map(|piece| piece.hop_limit).min().unwrap_or_default() - 1;
+
+        Ok(TtlPiece::new(expires_at, hop_limit, recoder.recode(rng)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TtlPiece, TtlRecoder};
+    use crate::{
+        RLNCError,
+        full::{Decoder, Encoder, RejectionReason},
+    };
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn test_ttl_piece_relay_and_decode() {
+        let mut rng = rand::rng();
+
+        let data = (0..=255).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 4).expect("Failed to create Encoder for TTL piece test");
+
+        let now = UNIX_EPOCH + Duration::from_secs(1_000);
+        let later = now + Duration::from_secs(100);
+
+        let mut relay = TtlRecoder::new(encoder.get_params());
+        assert_eq!(relay.recode(&mut rng, now), Err(RLNCError::NotEnoughPiecesToRecode));
+
+        assert_eq!(
+            relay.add_piece(TtlPiece::new(1_000, 4, encoder.code(&mut rng)), now),
+            Err(RLNCError::PieceExpired)
+        );
+        assert_eq!(
+            relay.add_piece(TtlPiece::new(2_000, 0, encoder.code(&mut rng)), now),
+            Err(RLNCError::PieceExpired)
+        );
+        assert_eq!(relay.add_piece(TtlPiece::new(2_000, 4, vec![0; 8]), now), Err(RLNCError::InvalidPieceLength));
+
+        // Pieces, but the short-lived one, survive till later.
+        relay.add_piece(TtlPiece::new(1_050, 1, encoder.code(&mut rng)), now).unwrap();
+        for _ in 0..4 {
+            relay.add_piece(TtlPiece::new(2_000, 3, encoder.code(&mut rng)), now).unwrap();
+        }
+
+        let recoded = relay.recode(&mut rng, now).expect("Live pieces must be recoded");
+        assert_eq!((recoded.get_expires_at(), recoded.get_hop_limit()), (1_050, 0));
+        assert!(!recoded.is_forwardable(now));
+
+        let mut decoder = Decoder::new_with(encoder.get_params());
+        decoder.enable_audit_log(4);
+        assert_eq!(decoder.decode_ttl(&recoded, later), Err(RLNCError::PieceExpired));
+        assert_eq!(decoder.get_received_piece_count(), 0);
+        assert_eq!(
+            decoder.get_audit_log().unwrap().entries().next().map(|entry| entry.reason),
+            Some(RejectionReason::Expired)
+        );
+
+        assert_eq!(relay.prune(later), 1);
+        assert_eq!(relay.get_buffered_piece_count(), 4);
+
+        while !decoder.is_already_decoded() {
+            let recoded = relay.recode(&mut rng, later).expect("Live pieces must be recoded");
+            assert_eq!((recoded.get_expires_at(), recoded.get_hop_limit()), (2_000, 2));
+
+            let recoded = TtlPiece::from_bytes(&recoded.to_bytes()).expect("Serialized piece must deserialize");
+            let _ = decoder.decode_ttl(&recoded, later);
+        }
+
+        assert_eq!(decoder.get_decoded_data(), Ok(data));
+        assert_eq!(TtlPiece::from_bytes(&[0; 9]), Err(RLNCError::SerializationFailure));
+        assert!(TtlPiece::new(2_000, 1, vec![]).is_expired(SystemTime::now()));
+    }
+}
@@ -0,0 +1,60 @@
+use std::io::IoSlice;
+
+/// A coded piece, whose header, coding vector and coded data live in separate reusable buffers.
+///
+/// It lets senders write a coded piece out using vectored I/O i.e. `Write::write_vectored` or `sendmsg`,
+/// without first concatenating header, coding vector and coded data into one buffer per piece. Header is
+/// optional and application defined, this crate never interprets it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VectoredPiece {
+    header: Vec<u8>,
+    coding_vector: Vec<u8>,
+    coded_data: Vec<u8>,
+}
+
+impl VectoredPiece {
+    /// Creates a new zeroed vectored piece, with given header length, suitable for holding a coded piece
+    /// produced by combining `piece_count` many pieces, each of `piece_byte_len` bytes.
+    pub fn new(header_byte_len: usize, piece_count: usize, piece_byte_len: usize) -> Self {
+        VectoredPiece {
+            header: vec![0u8; header_byte_len],
+            coding_vector: vec![0u8; piece_count],
+            coded_data: vec![0u8; piece_byte_len],
+        }
+    }
+
+    /// Application defined header, written before the coding vector.
+    pub fn get_header(&self) -> &[u8] {
+        &self.header
+    }
+
+    /// Mutable access to the application defined header, so that it can be filled before writing the piece out.
+    pub fn get_header_mut(&mut self) -> &mut [u8] {
+        &mut self.header
+    }
+
+    /// Coding vector of the coded piece.
+    pub fn get_coding_vector(&self) -> &[u8] {
+        &self.coding_vector
+    }
+
+    /// Coded data of the coded piece.
+    pub fn get_coded_data(&self) -> &[u8] {
+        &self.coded_data
+    }
+
+    /// Total number of bytes to be written out, when this piece is written using `Self::as_io_slices`.
+    pub fn get_byte_len(&self) -> usize {
+        self.header.len() + self.coding_vector.len() + self.coded_data.len()
+    }
+
+    /// Returns header, coding vector and coded data, in that order, ready to be passed to `Write::write_vectored`.
+    pub fn as_io_slices(&self) -> [IoSlice<'_>; 3] {
+        [IoSlice::new(&self.header), IoSlice::new(&self.coding_vector), IoSlice::new(&self.coded_data)]
+    }
+
+    /// Mutable access to both coding vector and coded data, at once.
+    pub(crate) fn get_coding_vector_and_coded_data_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+        (&mut self.coding_vector, &mut self.coded_data)
+    }
+}
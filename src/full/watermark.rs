@@ -0,0 +1,43 @@
+use std::sync::mpsc;
+
+/// Watches decoder rank, sending each registered threshold over a channel, once rank reaches it.
+#[derive(Clone, Debug)]
+pub(crate) struct RankWatcher {
+    /// Thresholds, in ascending order.
+    thresholds: Vec<usize>,
+    /// Number of thresholds, which have already been reached.
+    num_reached: usize,
+    sender: mpsc::Sender<usize>,
+}
+
+impl RankWatcher {
+    pub(crate) fn new(mut thresholds: Vec<usize>, sender: mpsc::Sender<usize>) -> Self {
+        thresholds.sort_unstable();
+        thresholds.dedup();
+
+        RankWatcher {
+            thresholds,
+            num_reached: 0,
+            sender,
+        }
+    }
+
+    /// Notifies about all thresholds reached by given rank, which were not reached before.
+    ///
+    /// Returns `false` if the receiving end is gone, so that this watcher can be dropped.
+    pub(crate) fn on_rank(&mut self, rank: usize) -> bool {
+        while self.num_reached < self.thresholds.len() && self.thresholds[self.num_reached] <= rank {
+            if self.sender.send(self.thresholds[self.num_reached]).is_err() {
+                return false;
+            }
+            self.num_reached += 1;
+        }
+
+        true
+    }
+
+    /// Re-arms all thresholds, as rank dropped back to zero.
+    pub(crate) fn reset(&mut self) {
+        self.num_reached = 0;
+    }
+}
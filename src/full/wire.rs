@@ -0,0 +1,169 @@
+use super::{params::Params, piece::CodedPiece};
+use crate::RLNCError;
+
+/// Magic bytes, each framed piece starts with.
+pub const WIRE_MAGIC: [u8; 4] = *b"RLNC";
+
+/// Version of the framed wire format, written by `FramedPiece::to_bytes`. Bumped on every incompatible change.
+pub const WIRE_FORMAT_VERSION: u8 = 1;
+
+/// Byte length of the header of a framed piece i.e. magic bytes, format version, piece count, piece length and generation.
+pub const WIRE_HEADER_BYTE_LEN: usize = WIRE_MAGIC.len() + size_of::<u8>() + 3 * size_of::<u32>();
+
+/// Self-describing coded piece, carrying RLNC configuration and the generation it belongs to, in its header.
+///
+/// Receivers, which don't learn encoder parameters out-of-band, bootstrap a decoder straight from the first received piece, see
+/// `Decoder::from_framed_piece`. Wire representation, see `Self::to_bytes`, is
+///
+/// * 4 magic bytes `RLNC`.
+/// * 1-byte format version, see `WIRE_FORMAT_VERSION`.
+/// * Piece count, piece length and generation, each as 4 big-endian bytes.
+/// * Coding vector of piece count bytes, followed by coded data of piece length bytes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FramedPiece {
+    generation: u32,
+    params: Params,
+    piece: CodedPiece,
+}
+
+impl FramedPiece {
+    /// Frames a full coded piece, say produced by `Encoder::code` or `Recoder::recode`, of given generation.
+    ///
+    /// # Returns
+    /// * Returns `Ok(FramedPiece)` on success.
+    /// * Returns `Err(RLNCError::InvalidPieceLength)` if `full_coded_piece` is not of length `params.get_full_coded_piece_byte_len()`.
+    /// * Returns `Err(RLNCError::SerializationFailure)` if piece count or piece length doesn't fit in 32 bits.
+    pub fn new(generation: u32, full_coded_piece: &[u8], params: Params) -> Result<FramedPiece, RLNCError> {
+        if u32::try_from(params.get_piece_count()).is_err() || u32::try_from(params.get_piece_byte_len()).is_err() {
+            return Err(RLNCError::SerializationFailure);
+        }
+
+        Ok(FramedPiece {
+            generation,
+            params,
+            piece: CodedPiece::from_bytes_with(full_coded_piece, params)?,
+        })
+    }
+
+    /// Generation, the piece belongs to.
+    pub fn get_generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// RLNC configuration, the piece is coded with.
+    pub fn get_params(&self) -> Params {
+        self.params
+    }
+
+    /// Coding vector and coded data.
+    pub fn get_piece(&self) -> &CodedPiece {
+        &self.piece
+    }
+
+    /// Serializes the piece, as documented on the type.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        [
+            WIRE_MAGIC.as_slice(),
+            &[WIRE_FORMAT_VERSION],
+            &(self.params.get_piece_count() as u32).to_be_bytes(),
+            &(self.params.get_piece_byte_len() as u32).to_be_bytes(),
+            &self.generation.to_be_bytes(),
+            self.piece.get_coding_vector(),
+            self.piece.get_coded_data(),
+        ]
+        .concat()
+    }
+
+    /// Deserializes a piece, serialized using `Self::to_bytes`, validating its length against RLNC configuration in the header.
+    ///
+    /// # Returns
+    /// * Returns `Ok(FramedPiece)` on success.
+    /// * Returns `Err(RLNCError::SerializationFailure)` if `bytes` are shorter than the header or don't start with magic bytes.
+    /// * Returns `Err(RLNCError::UnsupportedWireVersion)` if the format version is not `WIRE_FORMAT_VERSION`.
+    /// * Returns `Err(RLNCError::PieceCountZero)` or `Err(RLNCError::PieceLengthZero)` if the header carries invalid RLNC configuration.
+    /// * Returns `Err(RLNCError::InvalidPieceLength)` if length of the rest doesn't match RLNC configuration in the header.
+    pub fn from_bytes(bytes: &[u8]) -> Result<FramedPiece, RLNCError> {
+        if bytes.len() < WIRE_HEADER_BYTE_LEN {
+            return Err(RLNCError::SerializationFailure);
+        }
+
+        let (header, full_coded_piece) = bytes.split_at(WIRE_HEADER_BYTE_LEN);
+        let (magic, header) = header.split_at(WIRE_MAGIC.len());
+        let (version, header) = header.split_at(size_of::<u8>());
+
+        if magic != WIRE_MAGIC {
+            return Err(RLNCError::SerializationFailure);
+        }
+        if version[0] != WIRE_FORMAT_VERSION {
+            return Err(RLNCError::UnsupportedWireVersion);
+        }
+
+        let mut fields = header
+            .chunks_exact(size_of::<u32>())
+            .map(|field| u32::from_be_bytes(unsafe { field.try_into().unwrap_unchecked() }));
+        let (piece_count, piece_byte_len, generation) = unsafe {
+            (
+                fields.next().unwrap_unchecked() as usize,
+                fields.next().unwrap_unchecked() as usize,
+                fields.next().unwrap_unchecked(),
+            )
+        };
+
+        Self::new(generation, full_coded_piece, Params::new(piece_count, piece_byte_len)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FramedPiece, WIRE_FORMAT_VERSION, WIRE_HEADER_BYTE_LEN};
+    use crate::{
+        RLNCError,
+        full::{Decoder, Encoder, Params},
+    };
+
+    #[test]
+    fn test_framed_piece_bootstraps_decoder() {
+        let mut rng = rand::rng();
+
+        let data = (0..1000).map(|i| (i % 251) as u8).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 10).expect("Failed to create Encoder");
+        let params = encoder.get_params();
+
+        let frame = |generation| FramedPiece::new(generation, &encoder.code(&mut rand::rng()), params).unwrap().to_bytes();
+
+        // Receiver knows nothing but the wire format.
+        let first = FramedPiece::from_bytes(&frame(7)).expect("Framed piece must deserialize");
+        assert_eq!((first.get_generation(), first.get_params()), (7, params));
+
+        let mut decoder = Decoder::from_framed_piece(&first);
+        decoder.decode_framed(&first).expect("First piece must be useful");
+
+        let other_params = FramedPiece::new(7, &[1; 11], Params::new(10, 1).unwrap()).unwrap();
+        assert_eq!(decoder.decode_framed(&other_params), Err(RLNCError::ParamsMismatch));
+        assert_eq!(
+            decoder.decode_framed(&FramedPiece::from_bytes(&frame(8)).unwrap()),
+            Err(RLNCError::InvalidGenerationIndex)
+        );
+
+        while !decoder.is_already_decoded() {
+            let _ = decoder.decode_framed(&FramedPiece::from_bytes(&frame(7)).unwrap());
+        }
+        assert_eq!(decoder.get_decoded_data(), Ok(data));
+
+        let bytes = FramedPiece::new(7, &encoder.code(&mut rng), params).unwrap().to_bytes();
+        assert_eq!(bytes.len(), WIRE_HEADER_BYTE_LEN + params.get_full_coded_piece_byte_len());
+        assert_eq!(FramedPiece::from_bytes(&bytes[..bytes.len() - 1]), Err(RLNCError::InvalidPieceLength));
+        assert_eq!(
+            FramedPiece::from_bytes(&bytes[..WIRE_HEADER_BYTE_LEN - 1]),
+            Err(RLNCError::SerializationFailure)
+        );
+
+        let mut corrupted = bytes.clone();
+        corrupted[0] ^= 1;
+        assert_eq!(FramedPiece::from_bytes(&corrupted), Err(RLNCError::SerializationFailure));
+
+        let mut newer = bytes;
+        newer[4] = WIRE_FORMAT_VERSION + 1;
+        assert_eq!(FramedPiece::from_bytes(&newer), Err(RLNCError::UnsupportedWireVersion));
+    }
+}
@@ -0,0 +1,165 @@
+use crate::{
+    RLNCError,
+    common::gf65536::{GF65536_SYMBOL_BYTE_LEN, gf65536_mul_vec_by_scalar_then_add_into_vec},
+    full::consts::BOUNDARY_MARKER,
+};
+use rand::Rng;
+
+/// Random Linear Network Coding (RLNC) Encoder, over GF(2^16).
+///
+/// Pads and splits data same as `full::Encoder`, but piece length is rounded up to an even number of bytes, so that each piece
+/// is a vector of GF(2^16) symbols. Each coefficient of a coding vector is a GF(2^16) symbol, serialized as two little-endian
+/// bytes, so a coding vector is `2 x piece_count` bytes long.
+#[derive(Clone, Debug)]
+pub struct Encoder {
+    data: Vec<u8>,
+    piece_count: usize,
+    piece_byte_len: usize,
+}
+
+impl Encoder {
+    /// Number of pieces original data got split into, to be coded together.
+    pub fn get_piece_count(&self) -> usize {
+        self.piece_count
+    }
+
+    /// Byte length of each piece, original data got split into, after padding. It's always even.
+    pub fn get_piece_byte_len(&self) -> usize {
+        self.piece_byte_len
+    }
+
+    /// Each full coded piece consists of `self.get_piece_count()` coefficients, each of 2 bytes, appended by corresponding coded data of `self.get_piece_byte_len()` bytes.
+    pub fn get_full_coded_piece_byte_len(&self) -> usize {
+        GF65536_SYMBOL_BYTE_LEN * self.piece_count + self.piece_byte_len
+    }
+
+    /// Creates an encoder over already padded pieces, say coded pieces received by a `Recoder`.
+    pub(super) fn without_padding(data: Vec<u8>, piece_count: usize, piece_byte_len: usize) -> Encoder {
+        Encoder {
+            data,
+            piece_count,
+            piece_byte_len,
+        }
+    }
+
+    /// Creates a new `Encoder` while padding the input data.
+    ///
+    /// Original data is followed by a boundary marker and zeros, so that it fills `piece_count` pieces, each of even byte length.
+    ///
+    /// # Arguments
+    /// * `data` - Original data to be erasure-coded.
+    /// * `piece_count` - The number of pieces to split the data into.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Encoder)` on success.
+    /// * Returns `Err(RLNCError::DataLengthZero)` if `data` is empty.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `piece_count` is zero.
+    pub fn new(mut data: Vec<u8>, piece_count: usize) -> Result<Encoder, RLNCError> {
+        if data.is_empty() {
+            return Err(RLNCError::DataLengthZero);
+        }
+        if piece_count == 0 {
+            return Err(RLNCError::PieceCountZero);
+        }
+
+        let in_data_len = data.len();
+        let boundary_marker_len = 1;
+        let piece_byte_len = (in_data_len + boundary_marker_len)
+            .div_ceil(piece_count)
+            .next_multiple_of(GF65536_SYMBOL_BYTE_LEN);
+
+        data.resize(piece_count * piece_byte_len, 0);
+        data[in_data_len] = BOUNDARY_MARKER;
+
+        Ok(Self::without_padding(data, piece_count, piece_byte_len))
+    }
+
+    /// Erasure codes the data held by the encoder using a provided coding vector, without any memory allocation.
+    ///
+    /// # Arguments
+    /// * `coding_vector` - Coding vector of `self.get_piece_count()` little-endian serialized GF(2^16) symbols.
+    /// * `coded_data` - A mutable slice to write the coded data into, of `self.get_piece_byte_len()` bytes.
+    ///
+    /// # Returns
+    /// * Returns `Ok(())` on success.
+    /// * Returns `Err(RLNCError::CodingVectorLengthMismatch)` if the length of `coding_vector` is not `2 x self.get_piece_count()`.
+    /// * Returns `Err(RLNCError::InvalidOutputBuffer)` if the length of `coded_data` is not `self.get_piece_byte_len()`.
+    pub fn code_with_coding_vector(&self, coding_vector: &[u8], coded_data: &mut [u8]) -> Result<(), RLNCError> {
+        if coding_vector.len() != GF65536_SYMBOL_BYTE_LEN * self.piece_count {
+            return Err(RLNCError::CodingVectorLengthMismatch);
+        }
+        if coded_data.len() != self.piece_byte_len {
+            return Err(RLNCError::InvalidOutputBuffer);
+        }
+
+        coded_data.fill(0);
+
+        self.data
+            .chunks_exact(self.piece_byte_len)
+            .zip(coding_vector.chunks_exact(GF65536_SYMBOL_BYTE_LEN))
+            .for_each(|(piece, coeff)| gf65536_mul_vec_by_scalar_then_add_into_vec(coded_data, piece, u16::from_le_bytes([coeff[0], coeff[1]])));
+
+        Ok(())
+    }
+
+    /// Produces a new coded piece, random sampling coding coefficients and writing full coded piece into the provided buffer.
+    ///
+    /// # Arguments
+    /// * `rng` - A mutable reference to a random number generator.
+    /// * `full_coded_piece` - A mutable slice to write the full coded piece (coding vector + coded data) into.
+    ///
+    /// # Returns
+    /// * Returns `Ok(())` on success.
+    /// * Returns `Err(RLNCError::InvalidOutputBuffer)` if the length of `full_coded_piece` is incorrect.
+    pub fn code_with_buf<R: Rng + ?Sized>(&self, rng: &mut R, full_coded_piece: &mut [u8]) -> Result<(), RLNCError> {
+        if full_coded_piece.len() != self.get_full_coded_piece_byte_len() {
+            return Err(RLNCError::InvalidOutputBuffer);
+        }
+
+        let (coding_vector, coded_data) = full_coded_piece.split_at_mut(GF65536_SYMBOL_BYTE_LEN * self.piece_count);
+
+        rng.fill_bytes(coding_vector);
+        self.code_with_coding_vector(coding_vector, coded_data)
+    }
+
+    /// Produces a new coded piece, random sampling a coding vector.
+    ///
+    /// # Arguments
+    /// * `rng` - A mutable reference to a random number generator.
+    ///
+    /// # Returns
+    /// A `Vec<u8>` containing the random sampled coding vector followed by the
+    /// coded data. The length of the returned vector is `self.get_full_coded_piece_byte_len()`.
+    pub fn code<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<u8> {
+        let mut full_coded_piece = vec![0u8; self.get_full_coded_piece_byte_len()];
+        unsafe { self.code_with_buf(rng, &mut full_coded_piece).unwrap_unchecked() };
+
+        full_coded_piece
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Encoder;
+    use crate::RLNCError;
+
+    #[test]
+    fn test_encoder_pads_to_even_piece_length() {
+        let encoder = Encoder::new(vec![7u8; 10], 3).expect("Failed to create Encoder");
+
+        assert_eq!(encoder.get_piece_byte_len(), 4);
+        assert_eq!(encoder.get_full_coded_piece_byte_len(), 2 * 3 + 4);
+
+        // Unit coding vector picks a source piece.
+        let mut coded_data = vec![0u8; 4];
+        encoder.code_with_coding_vector(&[0, 0, 0, 0, 1, 0], &mut coded_data).unwrap();
+        assert_eq!(coded_data, [7, 7, 0x81, 0]);
+
+        assert_eq!(Encoder::new(vec![], 3).unwrap_err(), RLNCError::DataLengthZero);
+        assert_eq!(Encoder::new(vec![1], 0).unwrap_err(), RLNCError::PieceCountZero);
+        assert_eq!(
+            encoder.code_with_coding_vector(&[1, 0, 0], &mut coded_data),
+            Err(RLNCError::CodingVectorLengthMismatch)
+        );
+    }
+}
@@ -0,0 +1,12 @@
+//! Random Linear Network Coding over GF(2^16), for generations of hundreds to thousands of pieces. A random coding vector is linearly
+//! dependent on the ones received before with probability 1/65536 at most, so decoding completes with hardly any extra piece, however
+//! large the generation is. Each coding coefficient takes two bytes, serialized little-endian, so a full coded piece is coding vector
+//! of `2 x piece_count` bytes, followed by coded data of even byte length.
+
+mod decoder;
+mod encoder;
+mod recoder;
+
+pub use decoder::Decoder;
+pub use encoder::Encoder;
+pub use recoder::Recoder;
@@ -0,0 +1,190 @@
+use super::encoder::Encoder;
+use crate::{
+    RLNCError,
+    common::gf65536::{GF65536_SYMBOL_BYTE_LEN, gf65536_mul_vec_by_scalar_then_add_into_vec},
+};
+use rand::Rng;
+
+/// Random Linear Network Coding (RLNC) Recoder, over GF(2^16).
+///
+/// It recodes already coded pieces using a new random sampled recoding vector, without decoding them, same as `full::Recoder`.
+#[derive(Clone, Debug)]
+pub struct Recoder {
+    /// Coding vectors of received pieces, as a row-major matrix of little-endian serialized GF(2^16) symbols, one row per received piece.
+    coding_vectors: Vec<u8>,
+    encoder: Encoder,
+    num_pieces_received: usize,
+    full_coded_piece_byte_len: usize,
+    num_pieces_coded_together: usize,
+    /// A temporary buffer to hold the random recoding vector during the recoding process.
+    random_recoding_vector: Vec<u8>,
+}
+
+impl Recoder {
+    /// Number of pieces original data got split into to be coded together.
+    pub fn get_original_num_pieces_coded_together(&self) -> usize {
+        self.num_pieces_coded_together
+    }
+
+    /// Number of pieces received by Recoder, which is getting recoded together, producing new pieces.
+    pub fn get_num_pieces_recoded_together(&self) -> usize {
+        self.num_pieces_received
+    }
+
+    /// After padding the original data, it gets split into `self.get_original_num_pieces_coded_together()` many pieces, which results into these many bytes per piece.
+    pub fn get_piece_byte_len(&self) -> usize {
+        self.full_coded_piece_byte_len - GF65536_SYMBOL_BYTE_LEN * self.num_pieces_coded_together
+    }
+
+    /// Each full coded piece consists of `self.get_original_num_pieces_coded_together()` random coefficients, each of 2 bytes, appended by corresponding encoded piece of `self.get_piece_byte_len()` bytes.
+    pub fn get_full_coded_piece_byte_len(&self) -> usize {
+        self.full_coded_piece_byte_len
+    }
+
+    /// Creates a new `Recoder` instance from a vector of received coded pieces.
+    ///
+    /// # Arguments
+    /// * `data`: A vector of bytes containing the concatenated full coded pieces, each of
+    ///   `full_coded_piece_byte_len` bytes length.
+    /// * `full_coded_piece_byte_len`: The byte length of a full coded piece.
+    /// * `num_pieces_coded_together`: The number of original pieces that were
+    ///   linearly combined to create each coded piece. Coding vector prepended to each full coded piece is twice as many bytes long.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Recoder)` on successful creation.
+    /// * Returns `Err(RLNCError::NotEnoughPiecesToRecode)` if the input `data` is empty or does not contain at least one full coded piece.
+    /// * Returns `Err(RLNCError::PieceLengthZero)` if `full_coded_piece_byte_len` is zero.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `num_pieces_coded_together` is zero.
+    /// * Returns `Err(RLNCError::PieceLengthTooShort)` if `full_coded_piece_byte_len` is not greater than the coding vector byte length.
+    /// * Returns `Err(RLNCError::OddPieceLength)` if `full_coded_piece_byte_len` is odd.
+    pub fn new(data: Vec<u8>, full_coded_piece_byte_len: usize, num_pieces_coded_together: usize) -> Result<Recoder, RLNCError> {
+        if data.is_empty() {
+            return Err(RLNCError::NotEnoughPiecesToRecode);
+        }
+        if full_coded_piece_byte_len == 0 {
+            return Err(RLNCError::PieceLengthZero);
+        }
+        if num_pieces_coded_together == 0 {
+            return Err(RLNCError::PieceCountZero);
+        }
+
+        let coding_vector_byte_len = GF65536_SYMBOL_BYTE_LEN * num_pieces_coded_together;
+        if full_coded_piece_byte_len <= coding_vector_byte_len {
+            return Err(RLNCError::PieceLengthTooShort);
+        }
+        if !full_coded_piece_byte_len.is_multiple_of(GF65536_SYMBOL_BYTE_LEN) {
+            return Err(RLNCError::OddPieceLength);
+        }
+
+        let piece_byte_len = full_coded_piece_byte_len - coding_vector_byte_len;
+        let num_pieces_received = data.len() / full_coded_piece_byte_len;
+        if num_pieces_received == 0 {
+            return Err(RLNCError::NotEnoughPiecesToRecode);
+        }
+
+        let mut coding_vectors = Vec::with_capacity(num_pieces_received * coding_vector_byte_len);
+        let mut coded_pieces = Vec::with_capacity(num_pieces_received * piece_byte_len);
+
+        for full_coded_piece in data.chunks_exact(full_coded_piece_byte_len) {
+            let (coding_vector, coded_piece) = full_coded_piece.split_at(coding_vector_byte_len);
+
+            coding_vectors.extend_from_slice(coding_vector);
+            coded_pieces.extend_from_slice(coded_piece);
+        }
+
+        Ok(Recoder {
+            coding_vectors,
+            encoder: Encoder::without_padding(coded_pieces, num_pieces_received, piece_byte_len),
+            num_pieces_received,
+            full_coded_piece_byte_len,
+            num_pieces_coded_together,
+            random_recoding_vector: vec![0u8; GF65536_SYMBOL_BYTE_LEN * num_pieces_received],
+        })
+    }
+
+    /// Produces a new coded piece by recoding the source pieces, random sampling recoding coefficients and writing full coded piece
+    /// into the provided buffer. The output buffer contains the computed source coding vector followed by the coded data.
+    ///
+    /// # Arguments
+    /// * `rng`: Used to sample the random recoding vector.
+    /// * `full_recoded_piece`: A mutable slice of bytes where the new coded piece will be written, of length `self.get_full_coded_piece_byte_len()`.
+    ///
+    /// # Returns
+    /// * Returns a `Ok(())` when successful.
+    /// * Returns `Err(RLNCError::InvalidOutputBuffer)` if the length of `full_recoded_piece` is incorrect.
+    pub fn recode_with_buf<R: Rng + ?Sized>(&mut self, rng: &mut R, full_recoded_piece: &mut [u8]) -> Result<(), RLNCError> {
+        if full_recoded_piece.len() != self.full_coded_piece_byte_len {
+            return Err(RLNCError::InvalidOutputBuffer);
+        }
+
+        rng.fill_bytes(&mut self.random_recoding_vector);
+
+        let (computed_coding_vector, recoded_data) = full_recoded_piece.split_at_mut(GF65536_SYMBOL_BYTE_LEN * self.num_pieces_coded_together);
+
+        // Resulting coding vector, for the original source pieces, is the same linear combination of received coding vectors.
+        computed_coding_vector.fill(0);
+        self.coding_vectors
+            .chunks_exact(computed_coding_vector.len())
+            .zip(self.random_recoding_vector.chunks_exact(GF65536_SYMBOL_BYTE_LEN))
+            .for_each(|(coding_vector, coeff)| {
+                gf65536_mul_vec_by_scalar_then_add_into_vec(computed_coding_vector, coding_vector, u16::from_le_bytes([coeff[0], coeff[1]]))
+            });
+
+        self.encoder.code_with_coding_vector(&self.random_recoding_vector, recoded_data)
+    }
+
+    /// Produces a new coded piece by recoding the source pieces using a randomly sampled recoding vector.
+    ///
+    /// # Arguments
+    /// * `rng`: Used to sample the random recoding vector.
+    ///
+    /// # Returns
+    /// A `Vec<u8>` representing the new coded piece prepended with its source coding vector.
+    /// The length of the returned vector is `self.get_full_coded_piece_byte_len()`.
+    pub fn recode<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Vec<u8> {
+        let mut full_recoded_piece = vec![0u8; self.get_full_coded_piece_byte_len()];
+        unsafe { self.recode_with_buf(rng, &mut full_recoded_piece).unwrap_unchecked() }
+
+        full_recoded_piece
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Recoder;
+    use crate::{
+        RLNCError,
+        full16::{Decoder, Encoder},
+    };
+    use rand::Rng;
+
+    #[test]
+    fn test_full16_rlnc_with_more_than_256_pieces() {
+        const PIECE_COUNT: usize = 300;
+        let mut rng = rand::rng();
+
+        let data = (0..PIECE_COUNT * 7).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), PIECE_COUNT).expect("Failed to create Encoder");
+
+        let pieces = (0..PIECE_COUNT / 2).flat_map(|_| encoder.code(&mut rng)).collect::<Vec<u8>>();
+        let mut recoder = Recoder::new(pieces, encoder.get_full_coded_piece_byte_len(), PIECE_COUNT).expect("Failed to create Recoder");
+        assert_eq!(recoder.get_piece_byte_len(), encoder.get_piece_byte_len());
+
+        let mut decoder = Decoder::new(encoder.get_piece_byte_len(), PIECE_COUNT).expect("Failed to create Decoder");
+
+        // Half the pieces come through the recoder, which holds that many. Linear dependence, over GF(2^16), is so unlikely that
+        // not a single piece is wasted.
+        for _ in 0..PIECE_COUNT / 2 {
+            decoder.decode(&recoder.recode(&mut rng)).expect("Recoded piece must be useful");
+        }
+        for _ in PIECE_COUNT / 2..PIECE_COUNT {
+            decoder.decode(&encoder.code(&mut rng)).expect("Coded piece must be useful");
+        }
+
+        assert_eq!(decoder.get_received_piece_count(), PIECE_COUNT);
+        assert_eq!(decoder.get_decoded_data(), Ok(data));
+
+        assert_eq!(Recoder::new(vec![0; 8], 8, 4).unwrap_err(), RLNCError::PieceLengthTooShort);
+        assert_eq!(Recoder::new(vec![0; 11], 11, 4).unwrap_err(), RLNCError::OddPieceLength);
+    }
+}
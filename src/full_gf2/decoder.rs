@@ -0,0 +1,198 @@
+use super::{get_coding_vector_byte_len, get_coeff, xor_into_vec};
+use crate::{RLNCError, full};
+
+/// Random Linear Network Coding (RLNC) Decoder, over GF(2).
+///
+/// Gaussian elimination is incremental, same as `full::Decoder`, but each row operation is a plain XOR, as the only non-zero
+/// coefficient is one. Each received piece is reduced by rows received before, and kept only if it's linearly independent of them.
+/// Back-substitution is deferred till extraction of decoded data.
+#[derive(Clone, Debug)]
+pub struct Decoder {
+    /// Useful pieces received so far, reduced, concatenated in order of arrival. Each row is `[coding_vector | data_piece]`, where
+    /// each row is zero at pivot columns of rows before it.
+    rows: Vec<u8>,
+    /// Pivot column of each row.
+    pivots: Vec<usize>,
+    piece_byte_len: usize,
+    required_piece_count: usize,
+    received_piece_count: usize,
+}
+
+impl Decoder {
+    /// Number of pieces original data got split into and coded together.
+    pub fn get_num_pieces_coded_together(&self) -> usize {
+        self.required_piece_count
+    }
+
+    /// After padding the original data, it gets split into `self.get_num_pieces_coded_together()` many pieces, which results into these many bytes per piece.
+    pub fn get_piece_byte_len(&self) -> usize {
+        self.piece_byte_len
+    }
+
+    /// Each full coded piece consists of bit-packed coding vector of `ceil(self.get_num_pieces_coded_together() / 8)` bytes, appended by corresponding encoded piece of `self.get_piece_byte_len()` bytes.
+    pub fn get_full_coded_piece_byte_len(&self) -> usize {
+        get_coding_vector_byte_len(self.required_piece_count) + self.piece_byte_len
+    }
+
+    /// Total number of pieces received by the decoder so far.
+    pub fn get_received_piece_count(&self) -> usize {
+        self.received_piece_count
+    }
+
+    /// Number of useful pieces received by the decoder so far.
+    pub fn get_useful_piece_count(&self) -> usize {
+        self.pivots.len()
+    }
+
+    /// Number of pieces remaining to be received by the decoder for successful decoding.
+    pub fn get_remaining_piece_count(&self) -> usize {
+        self.get_num_pieces_coded_together() - self.get_useful_piece_count()
+    }
+
+    /// Creates a new `Decoder` instance.
+    ///
+    /// # Arguments
+    /// * `piece_byte_len` - The byte length of each original data piece.
+    /// * `required_piece_count` - The minimum number of useful coded pieces
+    ///   needed for decoding (equivalent to the number of original pieces).
+    ///
+    /// # Returns
+    /// * Returns `Ok(Decoder)` on successful creation.
+    /// * Returns `Err(RLNCError::PieceLengthZero)` if `piece_byte_len` is zero.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `required_piece_count` is zero.
+    pub fn new(piece_byte_len: usize, required_piece_count: usize) -> Result<Decoder, RLNCError> {
+        if piece_byte_len == 0 {
+            return Err(RLNCError::PieceLengthZero);
+        }
+        if required_piece_count == 0 {
+            return Err(RLNCError::PieceCountZero);
+        }
+
+        Ok(Decoder {
+            rows: Vec::new(),
+            pivots: Vec::with_capacity(required_piece_count),
+            piece_byte_len,
+            required_piece_count,
+            received_piece_count: 0,
+        })
+    }
+
+    /// Decodes a full coded piece, i.e. bit-packed coding vector followed by coded data. Unused trailing bits of the coding vector
+    /// are ignored.
+    ///
+    /// # Arguments
+    /// * `full_coded_piece` - A slice containing the coding vector followed by the coded data for one piece.
+    ///
+    /// # Returns
+    /// * Returns `Ok(())` if the piece was useful and added successfully.
+    /// * Returns `Err(RLNCError::ReceivedAllPieces)` if decoding is already complete.
+    /// * Returns `Err(RLNCError::PieceNotUseful)` if the piece was linearly dependent on the already received useful pieces.
+    /// * Returns `Err(RLNCError::InvalidPieceLength)` if the `full_coded_piece` has an unexpected length.
+    pub fn decode(&mut self, full_coded_piece: &[u8]) -> Result<(), RLNCError> {
+        if self.is_already_decoded() {
+            return Err(RLNCError::ReceivedAllPieces);
+        }
+        if full_coded_piece.len() != self.get_full_coded_piece_byte_len() {
+            return Err(RLNCError::InvalidPieceLength);
+        }
+
+        self.received_piece_count += 1;
+
+        let mut row = full_coded_piece.to_vec();
+        for (prev_row, &pivot_cidx) in self.rows.chunks_exact(row.len()).zip(&self.pivots) {
+            if get_coeff(&row, pivot_cidx) {
+                xor_into_vec(&mut row, prev_row);
+            }
+        }
+
+        let Some(pivot_cidx) = (0..self.required_piece_count).find(|&cidx| get_coeff(&row, cidx)) else {
+            return Err(RLNCError::PieceNotUseful);
+        };
+
+        self.rows.extend_from_slice(&row);
+        self.pivots.push(pivot_cidx);
+
+        Ok(())
+    }
+
+    /// Checks if the decoder has received enough linearly independent pieces to recover the original data.
+    pub fn is_already_decoded(&self) -> bool {
+        self.pivots.len() == self.required_piece_count
+    }
+
+    /// Recovers and returns the original data byte vector if decoding is complete.
+    ///
+    /// Runs the deferred back-substitution, from last received useful piece to first one, so that each row is left with its
+    /// pivot only, and extracts source pieces in order of pivot columns. Padding is trimmed, same as `full::Decoder`.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Vec<u8>)` containing the decoded data if successful.
+    /// * Returns `Err(RLNCError::NotAllPiecesReceivedYet)` if not enough useful pieces have been received.
+    /// * Returns `Err(RLNCError::InvalidDecodedDataFormat(_))` if the extracted data does not follow the expected format (e.g., boundary marker issues).
+    pub fn get_decoded_data(mut self) -> Result<Vec<u8>, RLNCError> {
+        if !self.is_already_decoded() {
+            return Err(RLNCError::NotAllPiecesReceivedYet);
+        }
+
+        let row_byte_len = self.get_full_coded_piece_byte_len();
+
+        // Each row is zero at pivot columns of rows before it, so clearing pivot column of the last row, off all rows above it,
+        // doesn't disturb pivot columns of those rows. Repeating it bottom-up leaves the coefficient matrix a permuted identity.
+        for ridx in (1..self.pivots.len()).rev() {
+            let (upper_rows, lower_rows) = self.rows.split_at_mut(ridx * row_byte_len);
+            let pivot_row = &lower_rows[..row_byte_len];
+
+            for upper_row in upper_rows.chunks_exact_mut(row_byte_len) {
+                if get_coeff(upper_row, self.pivots[ridx]) {
+                    xor_into_vec(upper_row, pivot_row);
+                }
+            }
+        }
+
+        let mut row_of_pivot = vec![0usize; self.required_piece_count];
+        self.pivots.iter().enumerate().for_each(|(ridx, &cidx)| row_of_pivot[cidx] = ridx);
+
+        let coding_vector_byte_len = get_coding_vector_byte_len(self.required_piece_count);
+        let mut buf = row_of_pivot
+            .iter()
+            .flat_map(|&ridx| &self.rows[ridx * row_byte_len + coding_vector_byte_len..(ridx + 1) * row_byte_len])
+            .copied()
+            .collect::<Vec<u8>>();
+
+        let final_len = full::Decoder::get_final_data_len(&buf, self.required_piece_count, self.received_piece_count)?;
+
+        buf.truncate(final_len);
+        Ok(buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Decoder;
+    use crate::{RLNCError, full_gf2::Encoder};
+
+    #[test]
+    fn test_decoder_new_and_decode_invalid_inputs() {
+        assert_eq!(Decoder::new(0, 4).unwrap_err(), RLNCError::PieceLengthZero);
+        assert_eq!(Decoder::new(5, 0).unwrap_err(), RLNCError::PieceCountZero);
+
+        let mut rng = rand::rng();
+        let encoder = Encoder::new(vec![1, 2, 3, 4, 5], 3).expect("Failed to create Encoder");
+
+        let mut decoder = Decoder::new(encoder.get_piece_byte_len(), encoder.get_piece_count()).expect("Failed to create Decoder");
+        assert_eq!(decoder.decode(&[0; 4]), Err(RLNCError::InvalidPieceLength));
+        // Only unused trailing bits are set, so it's an all-zero coding vector.
+        assert_eq!(decoder.decode(&[0b1111_1000, 0, 0]), Err(RLNCError::PieceNotUseful));
+
+        let piece = [0b0000_0010, 3, 4];
+        decoder.decode(&piece).expect("First non-zero piece must be useful");
+        assert_eq!(decoder.decode(&piece), Err(RLNCError::PieceNotUseful));
+        assert_eq!(decoder.clone().get_decoded_data(), Err(RLNCError::NotAllPiecesReceivedYet));
+
+        while !decoder.is_already_decoded() {
+            let _ = decoder.decode(&encoder.code(&mut rng));
+        }
+        assert_eq!(decoder.decode(&piece), Err(RLNCError::ReceivedAllPieces));
+        assert_eq!(decoder.get_decoded_data(), Ok(vec![1, 2, 3, 4, 5]));
+    }
+}
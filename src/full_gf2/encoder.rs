@@ -0,0 +1,165 @@
+use super::{clear_unused_bits, get_coding_vector_byte_len, get_coeff, xor_into_vec};
+use crate::{RLNCError, full::consts::BOUNDARY_MARKER};
+use rand::Rng;
+
+/// Random Linear Network Coding (RLNC) Encoder, over GF(2).
+///
+/// Pads and splits data same as `full::Encoder`. A coded piece is XOR of source pieces, whose bits are set in the bit-packed coding vector.
+#[derive(Clone, Debug)]
+pub struct Encoder {
+    data: Vec<u8>,
+    piece_count: usize,
+    piece_byte_len: usize,
+}
+
+impl Encoder {
+    /// Number of pieces original data got split into, to be coded together.
+    pub fn get_piece_count(&self) -> usize {
+        self.piece_count
+    }
+
+    /// Byte length of each piece, original data got split into, after padding.
+    pub fn get_piece_byte_len(&self) -> usize {
+        self.piece_byte_len
+    }
+
+    /// Each full coded piece consists of bit-packed coding vector of `ceil(self.get_piece_count() / 8)` bytes, appended by corresponding coded data of `self.get_piece_byte_len()` bytes.
+    pub fn get_full_coded_piece_byte_len(&self) -> usize {
+        get_coding_vector_byte_len(self.piece_count) + self.piece_byte_len
+    }
+
+    /// Creates an encoder over already padded pieces, say coded pieces received by a `Recoder`.
+    pub(super) fn without_padding(data: Vec<u8>, piece_count: usize, piece_byte_len: usize) -> Encoder {
+        Encoder {
+            data,
+            piece_count,
+            piece_byte_len,
+        }
+    }
+
+    /// Creates a new `Encoder` while padding the input data.
+    ///
+    /// Original data is followed by a boundary marker and zeros, so that it fills `piece_count` pieces, same as `full::Encoder::new`.
+    ///
+    /// # Arguments
+    /// * `data` - Original data to be erasure-coded.
+    /// * `piece_count` - The number of pieces to split the data into.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Encoder)` on success.
+    /// * Returns `Err(RLNCError::DataLengthZero)` if `data` is empty.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `piece_count` is zero.
+    pub fn new(mut data: Vec<u8>, piece_count: usize) -> Result<Encoder, RLNCError> {
+        if data.is_empty() {
+            return Err(RLNCError::DataLengthZero);
+        }
+        if piece_count == 0 {
+            return Err(RLNCError::PieceCountZero);
+        }
+
+        let in_data_len = data.len();
+        let boundary_marker_len = 1;
+        let piece_byte_len = (in_data_len + boundary_marker_len).div_ceil(piece_count);
+
+        data.resize(piece_count * piece_byte_len, 0);
+        data[in_data_len] = BOUNDARY_MARKER;
+
+        Ok(Self::without_padding(data, piece_count, piece_byte_len))
+    }
+
+    /// Erasure codes the data held by the encoder using a provided bit-packed coding vector, without any memory allocation.
+    /// Unused trailing bits of the coding vector are ignored.
+    ///
+    /// # Arguments
+    /// * `coding_vector` - Bit-packed coding vector of `ceil(self.get_piece_count() / 8)` bytes.
+    /// * `coded_data` - A mutable slice to write the coded data into, of `self.get_piece_byte_len()` bytes.
+    ///
+    /// # Returns
+    /// * Returns `Ok(())` on success.
+    /// * Returns `Err(RLNCError::CodingVectorLengthMismatch)` if the length of `coding_vector` is incorrect.
+    /// * Returns `Err(RLNCError::InvalidOutputBuffer)` if the length of `coded_data` is not `self.get_piece_byte_len()`.
+    pub fn code_with_coding_vector(&self, coding_vector: &[u8], coded_data: &mut [u8]) -> Result<(), RLNCError> {
+        if coding_vector.len() != get_coding_vector_byte_len(self.piece_count) {
+            return Err(RLNCError::CodingVectorLengthMismatch);
+        }
+        if coded_data.len() != self.piece_byte_len {
+            return Err(RLNCError::InvalidOutputBuffer);
+        }
+
+        coded_data.fill(0);
+
+        self.data
+            .chunks_exact(self.piece_byte_len)
+            .enumerate()
+            .filter(|&(piece_idx, _)| get_coeff(coding_vector, piece_idx))
+            .for_each(|(_, piece)| xor_into_vec(coded_data, piece));
+
+        Ok(())
+    }
+
+    /// Produces a new coded piece, random sampling coding coefficients and writing full coded piece into the provided buffer.
+    ///
+    /// # Arguments
+    /// * `rng` - A mutable reference to a random number generator.
+    /// * `full_coded_piece` - A mutable slice to write the full coded piece (coding vector + coded data) into.
+    ///
+    /// # Returns
+    /// * Returns `Ok(())` on success.
+    /// * Returns `Err(RLNCError::InvalidOutputBuffer)` if the length of `full_coded_piece` is incorrect.
+    pub fn code_with_buf<R: Rng + ?Sized>(&self, rng: &mut R, full_coded_piece: &mut [u8]) -> Result<(), RLNCError> {
+        if full_coded_piece.len() != self.get_full_coded_piece_byte_len() {
+            return Err(RLNCError::InvalidOutputBuffer);
+        }
+
+        let (coding_vector, coded_data) = full_coded_piece.split_at_mut(get_coding_vector_byte_len(self.piece_count));
+
+        rng.fill_bytes(coding_vector);
+        clear_unused_bits(coding_vector, self.piece_count);
+
+        self.code_with_coding_vector(coding_vector, coded_data)
+    }
+
+    /// Produces a new coded piece, random sampling a coding vector.
+    ///
+    /// # Arguments
+    /// * `rng` - A mutable reference to a random number generator.
+    ///
+    /// # Returns
+    /// A `Vec<u8>` containing the random sampled bit-packed coding vector followed by the
+    /// coded data. The length of the returned vector is `self.get_full_coded_piece_byte_len()`.
+    pub fn code<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<u8> {
+        let mut full_coded_piece = vec![0u8; self.get_full_coded_piece_byte_len()];
+        unsafe { self.code_with_buf(rng, &mut full_coded_piece).unwrap_unchecked() };
+
+        full_coded_piece
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Encoder;
+    use crate::RLNCError;
+
+    #[test]
+    fn test_encoder_codes_by_xor() {
+        let mut rng = rand::rng();
+        let encoder = Encoder::new((1..=17).collect(), 9).expect("Failed to create Encoder");
+
+        assert_eq!(encoder.get_piece_byte_len(), 2);
+        assert_eq!(encoder.get_full_coded_piece_byte_len(), 2 + 2);
+
+        // Pieces 0, 3 and 8 are [1, 2], [7, 8] and [17, 0x81].
+        let mut coded_data = vec![0u8; 2];
+        encoder.code_with_coding_vector(&[0b0000_1001, 0b1111_1111], &mut coded_data).unwrap();
+        assert_eq!(coded_data, [1 ^ 7 ^ 17, 2 ^ 8 ^ 0x81]);
+
+        // Unused trailing bits of random coding vectors are cleared.
+        assert!((0..100).all(|_| encoder.code(&mut rng)[1] <= 1));
+
+        assert_eq!(
+            encoder.code_with_coding_vector(&[1], &mut coded_data),
+            Err(RLNCError::CodingVectorLengthMismatch)
+        );
+        assert_eq!(Encoder::new(vec![], 3).unwrap_err(), RLNCError::DataLengthZero);
+    }
+}
@@ -0,0 +1,40 @@
+//! Random Linear Network Coding over GF(2), for CPU-constrained receivers. Each coding coefficient is a single bit, so coding vectors
+//! are bit-packed, and all row arithmetic is pure XOR, making coding and decoding much faster than over GF(2^8). In return, a random
+//! coding vector is linearly dependent on the ones received before with probability as high as 1/2, near full rank, so decoding takes
+//! about two extra pieces on average, which is a worthwhile trade-off for small generations.
+//!
+//! A full coded piece is bit-packed coding vector of `ceil(piece_count / 8)` bytes, followed by coded data. Coefficient of source piece
+//! `i` is bit `i % 8` of byte `i / 8`, counting from least significant bit. Unused trailing bits are zero.
+
+mod decoder;
+mod encoder;
+mod recoder;
+
+pub use decoder::Decoder;
+pub use encoder::Encoder;
+pub use recoder::Recoder;
+
+/// Byte length of a bit-packed coding vector, for given number of pieces coded together.
+pub fn get_coding_vector_byte_len(piece_count: usize) -> usize {
+    piece_count.div_ceil(u8::BITS as usize)
+}
+
+/// Coding coefficient of given source piece, in a bit-packed coding vector.
+fn get_coeff(coding_vector: &[u8], idx: usize) -> bool {
+    (coding_vector[idx / u8::BITS as usize] >> (idx % u8::BITS as usize)) & 1 == 1
+}
+
+/// Clears unused trailing bits of a bit-packed coding vector, so that it refers to first `piece_count` source pieces only.
+fn clear_unused_bits(coding_vector: &mut [u8], piece_count: usize) {
+    let used_bits_in_last_byte = piece_count % u8::BITS as usize;
+    if used_bits_in_last_byte != 0
+        && let Some(last_byte) = coding_vector.last_mut()
+    {
+        *last_byte &= (1u8 << used_bits_in_last_byte) - 1;
+    }
+}
+
+/// Adds `src` into `dst`, over GF(2), which is nothing but XOR.
+fn xor_into_vec(dst: &mut [u8], src: &[u8]) {
+    dst.iter_mut().zip(src).for_each(|(dst_byte, &src_byte)| *dst_byte ^= src_byte);
+}
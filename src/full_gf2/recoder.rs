@@ -0,0 +1,181 @@
+use super::{clear_unused_bits, encoder::Encoder, get_coding_vector_byte_len, get_coeff, xor_into_vec};
+use crate::RLNCError;
+use rand::Rng;
+
+/// Random Linear Network Coding (RLNC) Recoder, over GF(2).
+///
+/// It recodes already coded pieces, XOR-ing a random subset of them, without decoding them, same as `full::Recoder`.
+#[derive(Clone, Debug)]
+pub struct Recoder {
+    /// Bit-packed coding vectors of received pieces, concatenated, one per received piece.
+    coding_vectors: Vec<u8>,
+    encoder: Encoder,
+    num_pieces_received: usize,
+    full_coded_piece_byte_len: usize,
+    num_pieces_coded_together: usize,
+    /// A temporary buffer to hold the random bit-packed recoding vector during the recoding process.
+    random_recoding_vector: Vec<u8>,
+}
+
+impl Recoder {
+    /// Number of pieces original data got split into to be coded together.
+    pub fn get_original_num_pieces_coded_together(&self) -> usize {
+        self.num_pieces_coded_together
+    }
+
+    /// Number of pieces received by Recoder, which is getting recoded together, producing new pieces.
+    pub fn get_num_pieces_recoded_together(&self) -> usize {
+        self.num_pieces_received
+    }
+
+    /// After padding the original data, it gets split into `self.get_original_num_pieces_coded_together()` many pieces, which results into these many bytes per piece.
+    pub fn get_piece_byte_len(&self) -> usize {
+        self.full_coded_piece_byte_len - get_coding_vector_byte_len(self.num_pieces_coded_together)
+    }
+
+    /// Each full coded piece consists of bit-packed coding vector of `ceil(self.get_original_num_pieces_coded_together() / 8)` bytes, appended by corresponding encoded piece of `self.get_piece_byte_len()` bytes.
+    pub fn get_full_coded_piece_byte_len(&self) -> usize {
+        self.full_coded_piece_byte_len
+    }
+
+    /// Creates a new `Recoder` instance from a vector of received coded pieces.
+    ///
+    /// # Arguments
+    /// * `data`: A vector of bytes containing the concatenated full coded pieces, each of
+    ///   `full_coded_piece_byte_len` bytes length.
+    /// * `full_coded_piece_byte_len`: The byte length of a full coded piece.
+    /// * `num_pieces_coded_together`: The number of original pieces that were
+    ///   linearly combined to create each coded piece i.e. bit length of the coding vector prepended to each full coded piece.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Recoder)` on successful creation.
+    /// * Returns `Err(RLNCError::NotEnoughPiecesToRecode)` if the input `data` is empty or does not contain at least one full coded piece.
+    /// * Returns `Err(RLNCError::PieceLengthZero)` if `full_coded_piece_byte_len` is zero.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `num_pieces_coded_together` is zero.
+    /// * Returns `Err(RLNCError::PieceLengthTooShort)` if `full_coded_piece_byte_len` is not greater than the coding vector byte length.
+    pub fn new(data: Vec<u8>, full_coded_piece_byte_len: usize, num_pieces_coded_together: usize) -> Result<Recoder, RLNCError> {
+        if data.is_empty() {
+            return Err(RLNCError::NotEnoughPiecesToRecode);
+        }
+        if full_coded_piece_byte_len == 0 {
+            return Err(RLNCError::PieceLengthZero);
+        }
+        if num_pieces_coded_together == 0 {
+            return Err(RLNCError::PieceCountZero);
+        }
+
+        let coding_vector_byte_len = get_coding_vector_byte_len(num_pieces_coded_together);
+        if full_coded_piece_byte_len <= coding_vector_byte_len {
+            return Err(RLNCError::PieceLengthTooShort);
+        }
+
+        let piece_byte_len = full_coded_piece_byte_len - coding_vector_byte_len;
+        let num_pieces_received = data.len() / full_coded_piece_byte_len;
+        if num_pieces_received == 0 {
+            return Err(RLNCError::NotEnoughPiecesToRecode);
+        }
+
+        let mut coding_vectors = Vec::with_capacity(num_pieces_received * coding_vector_byte_len);
+        let mut coded_pieces = Vec::with_capacity(num_pieces_received * piece_byte_len);
+
+        for full_coded_piece in data.chunks_exact(full_coded_piece_byte_len) {
+            let (coding_vector, coded_piece) = full_coded_piece.split_at(coding_vector_byte_len);
+
+            coding_vectors.extend_from_slice(coding_vector);
+            coded_pieces.extend_from_slice(coded_piece);
+        }
+
+        Ok(Recoder {
+            coding_vectors,
+            encoder: Encoder::without_padding(coded_pieces, num_pieces_received, piece_byte_len),
+            num_pieces_received,
+            full_coded_piece_byte_len,
+            num_pieces_coded_together,
+            random_recoding_vector: vec![0u8; get_coding_vector_byte_len(num_pieces_received)],
+        })
+    }
+
+    /// Produces a new coded piece by recoding the source pieces, random sampling recoding coefficients and writing full coded piece
+    /// into the provided buffer. The output buffer contains the computed source coding vector followed by the coded data.
+    ///
+    /// # Arguments
+    /// * `rng`: Used to sample the random recoding vector.
+    /// * `full_recoded_piece`: A mutable slice of bytes where the new coded piece will be written, of length `self.get_full_coded_piece_byte_len()`.
+    ///
+    /// # Returns
+    /// * Returns a `Ok(())` when successful.
+    /// * Returns `Err(RLNCError::InvalidOutputBuffer)` if the length of `full_recoded_piece` is incorrect.
+    pub fn recode_with_buf<R: Rng + ?Sized>(&mut self, rng: &mut R, full_recoded_piece: &mut [u8]) -> Result<(), RLNCError> {
+        if full_recoded_piece.len() != self.full_coded_piece_byte_len {
+            return Err(RLNCError::InvalidOutputBuffer);
+        }
+
+        rng.fill_bytes(&mut self.random_recoding_vector);
+        clear_unused_bits(&mut self.random_recoding_vector, self.num_pieces_received);
+
+        let (computed_coding_vector, recoded_data) = full_recoded_piece.split_at_mut(get_coding_vector_byte_len(self.num_pieces_coded_together));
+
+        // Resulting coding vector, for the original source pieces, is XOR of coding vectors of the chosen received pieces.
+        computed_coding_vector.fill(0);
+        self.coding_vectors
+            .chunks_exact(computed_coding_vector.len())
+            .enumerate()
+            .filter(|&(piece_idx, _)| get_coeff(&self.random_recoding_vector, piece_idx))
+            .for_each(|(_, coding_vector)| xor_into_vec(computed_coding_vector, coding_vector));
+
+        self.encoder.code_with_coding_vector(&self.random_recoding_vector, recoded_data)
+    }
+
+    /// Produces a new coded piece by recoding the source pieces using a randomly sampled recoding vector.
+    ///
+    /// # Arguments
+    /// * `rng`: Used to sample the random recoding vector.
+    ///
+    /// # Returns
+    /// A `Vec<u8>` representing the new coded piece prepended with its source coding vector.
+    /// The length of the returned vector is `self.get_full_coded_piece_byte_len()`.
+    pub fn recode<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Vec<u8> {
+        let mut full_recoded_piece = vec![0u8; self.get_full_coded_piece_byte_len()];
+        unsafe { self.recode_with_buf(rng, &mut full_recoded_piece).unwrap_unchecked() }
+
+        full_recoded_piece
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Recoder;
+    use crate::{
+        RLNCError,
+        full_gf2::{Decoder, Encoder},
+    };
+    use rand::Rng;
+
+    #[test]
+    fn test_full_gf2_rlnc_with_recoding() {
+        const PIECE_COUNT: usize = 50;
+        let mut rng = rand::rng();
+
+        let data = (0..PIECE_COUNT * 33).map(|_| rng.random()).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), PIECE_COUNT).expect("Failed to create Encoder");
+
+        let pieces = (0..PIECE_COUNT / 2).flat_map(|_| encoder.code(&mut rng)).collect::<Vec<u8>>();
+        let mut recoder = Recoder::new(pieces, encoder.get_full_coded_piece_byte_len(), PIECE_COUNT).expect("Failed to create Recoder");
+        assert_eq!(recoder.get_piece_byte_len(), encoder.get_piece_byte_len());
+
+        let mut decoder = Decoder::new(encoder.get_piece_byte_len(), PIECE_COUNT).expect("Failed to create Decoder");
+
+        // Recoded pieces span at most as many dimensions as there are pieces held by the recoder.
+        for _ in 0..PIECE_COUNT {
+            let _ = decoder.decode(&recoder.recode(&mut rng));
+        }
+        assert!(decoder.get_useful_piece_count() <= PIECE_COUNT / 2);
+
+        while !decoder.is_already_decoded() {
+            let _ = decoder.decode(&encoder.code(&mut rng));
+        }
+        assert_eq!(decoder.get_decoded_data(), Ok(data));
+
+        assert_eq!(Recoder::new(vec![0; 7], 7, 50).unwrap_err(), RLNCError::PieceLengthTooShort);
+    }
+}
@@ -39,7 +39,7 @@
 //!     throughput and robustness in complex network topologies.
 //!
 //! -   **`Decoder`**: Receives coded pieces and attempts to reconstruct the original data.
-//!     It employs repeated Gaussian elimination to decode received pieces. As soon as enough
+//!     It employs incremental Gaussian elimination, reducing each received piece against the ones before. As soon as enough
 //!     linearly independent pieces are received, it can reconstruct the original data, regardless
 //!     of which specific pieces were lost or received, in whichever order.
 //!
@@ -47,6 +47,14 @@
 //!
 //! -   **Flexible data handling**: Supports arbitrary byte lengths for input
 //!     data, with internal padding and boundary markers for robust decoding.
+//! -   **Large generations**: `full16` module codes over $GF(2^{16})$, so that generations of thousands of pieces
+//!     hardly ever get a linearly dependent piece, at the cost of two-byte coding coefficients.
+//! -   **XOR-only coding**: `full_gf2` module codes over $GF(2)$, with bit-packed coding vectors, so that all row arithmetic
+//!     is plain XOR, for CPU-constrained receivers of small generations.
+//! -   **Large objects**: `chunked` module splits objects into many generations, coded independently, scheduling
+//!     pieces among them round-robin, sequentially or at random, and reassembles them with a `GenerationDecoder`.
+//! -   **Streaming**: `window` module codes over a sliding window of the latest source symbols, delivering each of them
+//!     as soon as it's decoded, with the window advanced by receiver acknowledgements, for live video or VoIP.
 //! -   **Error Handling**: Comprehensive `RLNCError` enum for various failure scenarios.
 //!
 //! ## Example Usage
@@ -115,9 +123,23 @@
 //!
 //! ```toml
 //! [dependencies]
-//! rlnc = "=0.8.5"                                      # On x86_64 and aarch64 targets, it offers fast encoding, recoding and decoding, using SIMD intrinsics.
+//! rlnc = "=0.8.5"                                      # On x86_64, aarch64 and 32-bit ARM (NEON) targets, it offers fast encoding, recoding and decoding, using SIMD intrinsics.
 //! # or
-//! rlnc = { version = "=0.8.5", features = "parallel" } # Uses `rayon`-based data-parallelism for fast encoding/ recoding. Decoding is not yet parallelized.
+//! rlnc = { version = "=0.8.5", features = "parallel" } # Uses `rayon`-based data-parallelism for fast encoding/ recoding, on the global or a user provided thread pool, whose worker count and core affinity can be configured. Decoding parallelizes payload part of row elimination, for pieces of 64 KiB or more.
+//! # or
+//! rlnc = { version = "=0.8.5", features = "compile-time-dispatch" } # Picks SIMD kernels only based on target features enabled at compile time, say with `-C target-cpu=native`, skipping runtime CPU feature detection. Without such target features, it falls back to scalar code.
+//! # or
+//! rlnc = { version = "=0.8.5", features = "simd-cross-check" } # Shadow executes a sample of SIMD kernel invocations by scalar reference implementation, panicking on mismatch. Meant for debugging, not for production use.
+//! # or
+//! rlnc = { version = "=0.8.5", features = "sim" } # Network topology simulator, under `rlnc::sim::topology`, for multi-hop recoding experiments, reporting delivery time and overhead.
+//! # or
+//! rlnc = { version = "=0.8.5", features = "debug-tools" } # Pretty-prints decoder's coefficient matrix, highlighting pivots, and optionally writes a step-by-step elimination trace, explaining why a piece is not useful.
+//! # or
+//! rlnc = { version = "=0.8.5", features = "testing" } # In-process lossy pipe, under `rlnc::testing`, connecting an encoder or recoder to a decoder, over a seeded channel, which drops, duplicates and reorders pieces, for deterministic integration tests.
+//! # or
+//! rlnc = { version = "=0.8.5", features = "numa" } # On Linux, offers huge page backed storage, optionally bound to a NUMA node, for encoder source copy and decoder matrix, and with `parallel`, `rayon` thread pools pinned to a NUMA node, for multi-GB workloads.
+//! # or
+//! rlnc = { version = "=0.8.5", default-features = false } # Disables `simd` feature, which is on by default, so that all GF(2^8) vector arithmetic runs the portable SWAR implementation, processing a 64-bit word at a time, on every target. Meant for deterministic cross-platform benchmarking, running under miri or sanitizers, and ruling out SIMD kernels while debugging.
 //!
 //! rand = { version = "=0.9.1" } # Required for random number generation
 //! ```
@@ -126,5 +148,15 @@
 
 mod common;
 
+pub mod chunked;
 pub mod full;
-pub use crate::common::errors::RLNCError;
+pub mod full16;
+pub mod full_gf2;
+#[cfg(feature = "sim")]
+pub mod sim;
+pub mod simd;
+pub mod sparse;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod window;
+pub use crate::common::errors::{DecodedDataDiagnostics, RLNCError};
@@ -0,0 +1,3 @@
+//! Simulation harnesses, which exercise real coding types end to end, for research and experimentation.
+
+pub mod topology;
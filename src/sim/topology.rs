@@ -0,0 +1,372 @@
+use crate::{
+    RLNCError,
+    full::{Decoder, Encoder, Params, Recoder},
+};
+use rand::Rng;
+
+/// Index of a node in a `Topology`, as returned on adding the node.
+pub type NodeId = usize;
+
+/// Role of a node in a simulated network.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NodeKind {
+    /// Holds original data, sending freshly coded pieces, using an `Encoder`.
+    Source,
+    /// Forwards recoded pieces, using a `Recoder`, over useful pieces it received so far.
+    Relay,
+    /// Collects pieces, using a `Decoder`, till original data is recovered. It doesn't forward anything.
+    Sink,
+}
+
+/// Unidirectional, lossy link between two nodes, with limited bandwidth.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Link {
+    /// Node sending pieces over this link.
+    pub from: NodeId,
+    /// Node receiving pieces over this link.
+    pub to: NodeId,
+    /// Probability of each piece getting lost, independently of others, must be in `[0, 1]`.
+    pub loss_probability: f64,
+    /// Number of pieces sent over this link, in each tick.
+    pub pieces_per_tick: usize,
+}
+
+/// Network of sources, relays and sinks, connected by lossy links, on which delivery of a single generation is simulated.
+///
+/// Simulation runs in discrete ticks. In each tick, every link sends `pieces_per_tick` pieces, coded or recoded by its
+/// sending node, each of which is lost with the link's loss probability. Pieces sent in a tick are delivered at the end
+/// of it, so that each hop costs a tick. A relay, which didn't receive anything yet, has nothing to send.
+#[derive(Clone, Debug, Default)]
+pub struct Topology {
+    nodes: Vec<NodeKind>,
+    links: Vec<Link>,
+}
+
+/// Outcome of delivering a generation to a single sink.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SinkReport {
+    /// The sink node.
+    pub node: NodeId,
+    /// Tick, at the end of which original data got recovered, if it did, starting from one.
+    pub decoded_at_tick: Option<usize>,
+    /// Number of pieces, which reached this sink, till decoding completed or simulation ended.
+    pub received_piece_count: usize,
+    /// Number of linearly independent pieces among those received.
+    pub useful_piece_count: usize,
+}
+
+impl SinkReport {
+    /// Ratio of received pieces to pieces coded together, which is `>= 1`, once decoded. Excess shows the cost of
+    /// linearly dependent pieces, reaching this sink.
+    pub fn overhead(&self, piece_count: usize) -> f64 {
+        self.received_piece_count as f64 / piece_count as f64
+    }
+}
+
+/// Outcome of a simulation run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SimulationReport {
+    /// Number of ticks simulated, till all sinks recovered original data or tick budget got exhausted.
+    pub elapsed_ticks: usize,
+    /// Total number of pieces sent over all links.
+    pub transmitted_piece_count: usize,
+    /// Number of pieces lost in transit, over all links.
+    pub lost_piece_count: usize,
+    /// Outcome for each sink, in order of their addition.
+    pub sinks: Vec<SinkReport>,
+}
+
+impl SimulationReport {
+    /// Returns `true` if all sinks recovered original data.
+    pub fn is_complete(&self) -> bool {
+        self.sinks.iter().all(|sink| sink.decoded_at_tick.is_some())
+    }
+}
+
+/// State of a node, while simulation is running.
+enum NodeState {
+    Source(Encoder),
+    Relay {
+        /// Only used to filter out linearly dependent pieces.
+        decoder: Decoder,
+        /// Concatenated useful pieces received so far.
+        pieces: Vec<u8>,
+        /// Recoder over `pieces`, rebuilt lazily, once new pieces arrive.
+        recoder: Option<Recoder>,
+    },
+    Sink {
+        decoder: Decoder,
+        report: SinkReport,
+    },
+}
+
+impl NodeState {
+    /// Produces the next piece to be sent, if the node has anything to send.
+    fn emit<R: Rng + ?Sized>(&mut self, params: Params, rng: &mut R) -> Option<Vec<u8>> {
+        match self {
+            NodeState::Source(encoder) => Some(encoder.code(rng)),
+            NodeState::Relay { pieces, recoder, .. } => {
+                if pieces.is_empty() {
+                    return None;
+                }
+                if recoder.is_none() {
+                    *recoder = Recoder::new_with(pieces.clone(), params).ok();
+                }
+
+                recoder.as_mut().map(|recoder| recoder.recode(rng))
+            }
+            NodeState::Sink { .. } => None,
+        }
+    }
+
+    /// Accepts a piece, delivered at the end of given tick.
+    fn absorb(&mut self, piece: &[u8], tick: usize) {
+        match self {
+            NodeState::Source(_) => {}
+            NodeState::Relay { decoder, pieces, recoder } => {
+                if decoder.decode(piece).is_ok() {
+                    pieces.extend_from_slice(piece);
+                    *recoder = None;
+                }
+            }
+            NodeState::Sink { decoder, report } => {
+                if report.decoded_at_tick.is_some() {
+                    return;
+                }
+
+                report.received_piece_count += 1;
+                if decoder.decode(piece).is_ok() {
+                    report.useful_piece_count += 1;
+                }
+                if decoder.is_already_decoded() {
+                    report.decoded_at_tick = Some(tick);
+                }
+            }
+        }
+    }
+}
+
+impl Topology {
+    /// Creates an empty topology.
+    pub fn new() -> Topology {
+        Topology::default()
+    }
+
+    /// Adds a source node, returning its identifier.
+    pub fn add_source(&mut self) -> NodeId {
+        self.add_node(NodeKind::Source)
+    }
+
+    /// Adds a relay node, returning its identifier.
+    pub fn add_relay(&mut self) -> NodeId {
+        self.add_node(NodeKind::Relay)
+    }
+
+    /// Adds a sink node, returning its identifier.
+    pub fn add_sink(&mut self) -> NodeId {
+        self.add_node(NodeKind::Sink)
+    }
+
+    /// Kind of given node, if it exists.
+    pub fn get_node_kind(&self, node: NodeId) -> Option<NodeKind> {
+        self.nodes.get(node).copied()
+    }
+
+    /// Connects two nodes with a unidirectional link.
+    ///
+    /// # Returns
+    /// * Returns `Ok(())` on success.
+    /// * Returns `Err(RLNCError::InvalidTopology)` if any end of the link doesn't exist, the link starts at a sink
+    ///   or ends at a source, or loss probability is not in `[0, 1]`.
+    pub fn add_link(&mut self, link: Link) -> Result<(), RLNCError> {
+        let (Some(from), Some(to)) = (self.get_node_kind(link.from), self.get_node_kind(link.to)) else {
+            return Err(RLNCError::InvalidTopology);
+        };
+        if from == NodeKind::Sink || to == NodeKind::Source {
+            return Err(RLNCError::InvalidTopology);
+        }
+        if !(0.0..=1.0).contains(&link.loss_probability) {
+            return Err(RLNCError::InvalidTopology);
+        }
+
+        self.links.push(link);
+        Ok(())
+    }
+
+    /// Simulates delivery of a single generation of given data, from all sources to all sinks.
+    ///
+    /// # Arguments
+    /// * `data` - Original data, held by all sources.
+    /// * `piece_count` - Number of pieces, original data gets split into.
+    /// * `max_ticks` - Simulation stops after these many ticks, even if some sink didn't recover original data yet.
+    /// * `rng` - Randomness source for coding and losses, seed it for reproducible runs.
+    ///
+    /// # Returns
+    /// * Returns `Ok(SimulationReport)` on success.
+    /// * Returns `Err(RLNCError::DataLengthZero)` if `data` is empty.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `piece_count` is zero.
+    /// * Returns `Err(RLNCError::InvalidTopology)` if there is no source or no sink.
+    pub fn simulate<R: Rng + ?Sized>(&self, data: &[u8], piece_count: usize, max_ticks: usize, rng: &mut R) -> Result<SimulationReport, RLNCError> {
+        if !self.nodes.contains(&NodeKind::Source) || !self.nodes.contains(&NodeKind::Sink) {
+            return Err(RLNCError::InvalidTopology);
+        }
+
+        let encoder = Encoder::new(data.to_vec(), piece_count)?;
+        let params = encoder.get_params();
+
+        let mut states = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(node, kind)| match kind {
+                NodeKind::Source => NodeState::Source(encoder.clone()),
+                NodeKind::Relay => NodeState::Relay {
+                    decoder: Decoder::new_with(params),
+                    pieces: Vec::new(),
+                    recoder: None,
+                },
+                NodeKind::Sink => NodeState::Sink {
+                    decoder: Decoder::new_with(params),
+                    report: SinkReport {
+                        node,
+                        decoded_at_tick: None,
+                        received_piece_count: 0,
+                        useful_piece_count: 0,
+                    },
+                },
+            })
+            .collect::<Vec<_>>();
+
+        let mut report = SimulationReport {
+            elapsed_ticks: 0,
+            transmitted_piece_count: 0,
+            lost_piece_count: 0,
+            sinks: Vec::new(),
+        };
+        let mut in_transit = Vec::new();
+
+        for tick in 1..=max_ticks {
+            report.elapsed_ticks = tick;
+
+            for link in &self.links {
+                for _ in 0..link.pieces_per_tick {
+                    let Some(piece) = states[link.from].emit(params, rng) else {
+                        break;
+                    };
+
+                    report.transmitted_piece_count += 1;
+                    if rng.random_bool(link.loss_probability) {
+                        report.lost_piece_count += 1;
+                    } else {
+                        in_transit.push((link.to, piece));
+                    }
+                }
+            }
+
+            in_transit.drain(..).for_each(|(to, piece)| states[to].absorb(&piece, tick));
+
+            let is_complete = states
+                .iter()
+                .all(|state| !matches!(state, NodeState::Sink { report, .. } if report.decoded_at_tick.is_none()));
+            if is_complete {
+                break;
+            }
+        }
+
+        report.sinks = states
+            .into_iter()
+            .filter_map(|state| match state {
+                NodeState::Sink { report, .. } => Some(report),
+                _ => None,
+            })
+            .collect();
+
+        Ok(report)
+    }
+
+    fn add_node(&mut self, kind: NodeKind) -> NodeId {
+        self.nodes.push(kind);
+        self.nodes.len() - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Link, Topology};
+    use crate::RLNCError;
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    #[test]
+    fn test_topology_invalid_links() {
+        let mut topology = Topology::new();
+        let source = topology.add_source();
+        let sink = topology.add_sink();
+
+        let link = |from, to, loss_probability| Link {
+            from,
+            to,
+            loss_probability,
+            pieces_per_tick: 1,
+        };
+
+        assert_eq!(topology.add_link(link(source, 10, 0.0)), Err(RLNCError::InvalidTopology));
+        assert_eq!(topology.add_link(link(sink, source, 0.0)), Err(RLNCError::InvalidTopology));
+        assert_eq!(topology.add_link(link(source, sink, 1.5)), Err(RLNCError::InvalidTopology));
+        assert_eq!(topology.add_link(link(source, sink, 0.1)), Ok(()));
+
+        assert_eq!(Topology::new().simulate(&[1, 2, 3], 2, 10, &mut rand::rng()), Err(RLNCError::InvalidTopology));
+    }
+
+    #[test]
+    fn test_topology_multi_hop_recoding() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let data = (0..4096).map(|_| rng.random()).collect::<Vec<u8>>();
+        let piece_count = 16;
+
+        // Butterfly-like topology: source reaches both relays, each of which reaches both sinks.
+        let mut topology = Topology::new();
+        let source = topology.add_source();
+        let relays = [topology.add_relay(), topology.add_relay()];
+        let sinks = [topology.add_sink(), topology.add_sink()];
+
+        for relay in relays {
+            topology
+                .add_link(Link {
+                    from: source,
+                    to: relay,
+                    loss_probability: 0.2,
+                    pieces_per_tick: 2,
+                })
+                .expect("Link must be valid");
+
+            for sink in sinks {
+                topology
+                    .add_link(Link {
+                        from: relay,
+                        to: sink,
+                        loss_probability: 0.1,
+                        pieces_per_tick: 1,
+                    })
+                    .expect("Link must be valid");
+            }
+        }
+
+        let report = topology.simulate(&data, piece_count, 1000, &mut rng).expect("Simulation must not fail");
+
+        assert!(report.is_complete());
+        assert_eq!(report.sinks.iter().map(|sink| sink.node).collect::<Vec<_>>(), sinks);
+        assert!(report.lost_piece_count < report.transmitted_piece_count);
+
+        for sink in &report.sinks {
+            // Relays need a tick to receive something, before forwarding it, so decoding takes at least two hops worth of ticks.
+            assert!(sink.decoded_at_tick.is_some_and(|tick| tick >= 2 && tick <= report.elapsed_ticks));
+            assert_eq!(sink.useful_piece_count, piece_count);
+            assert!(sink.overhead(piece_count) >= 1.0);
+        }
+
+        // Tick budget exhausted, before anything reaches the sink.
+        let report = topology.simulate(&data, piece_count, 1, &mut rng).expect("Simulation must not fail");
+        assert!(!report.is_complete());
+        assert_eq!(report.elapsed_ticks, 1);
+    }
+}
@@ -0,0 +1,30 @@
+//! Introspection of SIMD kernels, GF(2^8) vector arithmetic runs on, along with strided kernels, for column-oriented
+//! operations on row-major data, say a matrix of pieces, without materializing a transposed copy first.
+
+pub use crate::common::simd::{Backend, active_backend, gf256_strided_inplace_mul_vec_by_scalar, gf256_strided_mul_vec_by_scalar_then_add_into_vec};
+
+#[cfg(test)]
+mod tests {
+    use super::{Backend, active_backend};
+
+    #[test]
+    fn test_active_backend() {
+        let backend = active_backend();
+        assert_eq!(backend, active_backend());
+
+        #[cfg(not(feature = "simd"))]
+        assert_eq!(backend, Backend::Scalar);
+
+        #[cfg(all(feature = "simd", not(feature = "compile-time-dispatch"), target_arch = "x86_64"))]
+        assert_eq!(backend == Backend::Scalar, !is_x86_feature_detected!("ssse3"));
+
+        #[cfg(all(feature = "simd", feature = "compile-time-dispatch", target_arch = "x86_64"))]
+        assert_eq!(backend == Backend::Scalar, !cfg!(target_feature = "ssse3"));
+
+        #[cfg(all(feature = "simd", not(feature = "compile-time-dispatch"), target_arch = "aarch64"))]
+        assert_eq!(backend == Backend::Neon, std::arch::is_aarch64_feature_detected!("neon"));
+
+        #[cfg(all(feature = "simd", target_arch = "arm"))]
+        assert!(matches!(backend, Backend::Scalar | Backend::Neon));
+    }
+}
@@ -0,0 +1,166 @@
+use crate::{
+    RLNCError,
+    common::simd::gf256_mul_vec_by_scalar_then_add_into_vec,
+    full::{self, Params},
+};
+use rand::Rng;
+
+/// Sparse Random Linear Network Coding (RLNC) Encoder.
+///
+/// Each coefficient of a coding vector is non-zero, with probability given by density, in which case it's uniformly sampled out of
+/// non-zero elements of GF(2^8). So a coded piece is a combination of about `density x piece_count` source pieces, and only those
+/// are touched while coding. Coding vectors are never all-zero. Lower density makes pieces cheaper to code and decode, at the cost
+/// of more linearly dependent pieces, especially near full rank. Decode them using `sparse::Decoder`.
+#[derive(Clone, Debug)]
+pub struct Encoder {
+    encoder: full::Encoder,
+    density: f64,
+}
+
+impl Encoder {
+    /// Creates a new sparse encoder, padding and splitting data same as `full::Encoder::new`.
+    ///
+    /// # Arguments
+    /// * `data` - The original data to be encoded.
+    /// * `piece_count` - The number of pieces to split the data into.
+    /// * `density` - Probability of each coding coefficient being non-zero, in `(0, 1]`.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Encoder)` on successful creation.
+    /// * Returns `Err(RLNCError::InvalidDensity)` if `density` is not in `(0, 1]`.
+    /// * Otherwise, returns same errors as `full::Encoder::new`.
+    pub fn new(data: Vec<u8>, piece_count: usize, density: f64) -> Result<Encoder, RLNCError> {
+        if !(density > 0.0 && density <= 1.0) {
+            return Err(RLNCError::InvalidDensity);
+        }
+
+        Ok(Encoder {
+            encoder: full::Encoder::new(data, piece_count)?,
+            density,
+        })
+    }
+
+    /// Number of pieces original data got split into, to be coded together.
+    pub fn get_piece_count(&self) -> usize {
+        self.encoder.get_piece_count()
+    }
+
+    /// Byte length of each piece, original data got split into, after padding.
+    pub fn get_piece_byte_len(&self) -> usize {
+        self.encoder.get_piece_byte_len()
+    }
+
+    /// Each full coded piece consists of `self.get_piece_count()` coefficients, appended by corresponding coded data of `self.get_piece_byte_len()` bytes.
+    pub fn get_full_coded_piece_byte_len(&self) -> usize {
+        self.encoder.get_full_coded_piece_byte_len()
+    }
+
+    /// RLNC configuration of this encoder, to be shared with decoders.
+    pub fn get_params(&self) -> Params {
+        self.encoder.get_params()
+    }
+
+    /// Probability of each coding coefficient being non-zero.
+    pub fn get_density(&self) -> f64 {
+        self.density
+    }
+
+    /// Produces a new coded piece, sampling a sparse coding vector and writing full coded piece into the provided buffer.
+    ///
+    /// # Arguments
+    /// * `rng` - A mutable reference to a random number generator.
+    /// * `full_coded_piece` - A mutable slice to write the full coded piece (coding vector + coded data) into.
+    ///
+    /// # Returns
+    /// * Returns `Ok(())` on success.
+    /// * Returns `Err(RLNCError::InvalidOutputBuffer)` if the length of `full_coded_piece` is incorrect.
+    pub fn code_with_buf<R: Rng + ?Sized>(&self, rng: &mut R, full_coded_piece: &mut [u8]) -> Result<(), RLNCError> {
+        if full_coded_piece.len() != self.get_full_coded_piece_byte_len() {
+            return Err(RLNCError::InvalidOutputBuffer);
+        }
+
+        let (coding_vector, coded_data) = full_coded_piece.split_at_mut(self.get_piece_count());
+
+        // Redraw all-zero coding vectors, as they can never be useful.
+        loop {
+            coding_vector
+                .iter_mut()
+                .for_each(|coeff| *coeff = if rng.random_bool(self.density) { rng.random_range(1..=u8::MAX) } else { 0 });
+
+            if coding_vector.iter().any(|&coeff| coeff != 0) {
+                break;
+            }
+        }
+
+        // Only source pieces with non-zero coefficients are touched.
+        coded_data.fill(0);
+        for (index, &coeff) in coding_vector.iter().enumerate().filter(|&(_, &coeff)| coeff != 0) {
+            let source_piece = unsafe { self.encoder.source_piece(index).unwrap_unchecked() };
+            gf256_mul_vec_by_scalar_then_add_into_vec(coded_data, source_piece, coeff);
+        }
+
+        Ok(())
+    }
+
+    /// Produces a new coded piece, sampling a sparse coding vector. It allocates a new `Vec<u8>`, see `Self::code_with_buf`.
+    ///
+    /// # Returns
+    /// A `Vec<u8>` containing the coding vector followed by the coded data, of length `self.get_full_coded_piece_byte_len()`.
+    pub fn code<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<u8> {
+        let mut full_coded_piece = vec![0u8; self.get_full_coded_piece_byte_len()];
+        unsafe { self.code_with_buf(rng, &mut full_coded_piece).unwrap_unchecked() };
+
+        full_coded_piece
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Encoder;
+    use crate::{RLNCError, full, sparse};
+    use rand::Rng;
+
+    #[test]
+    fn test_sparse_encoder_and_decoder() {
+        let mut rng = rand::rng();
+
+        let data = (0..64 * 1024).map(|_| rng.random()).collect::<Vec<u8>>();
+        let piece_count = 128;
+
+        let encoder = Encoder::new(data.clone(), piece_count, 0.1).expect("Failed to create sparse Encoder");
+        let mut sparse_decoder = sparse::Decoder::new_with(encoder.get_params());
+        let mut full_decoder = full::Decoder::new_with(encoder.get_params());
+
+        let mut nonzero_coeff_count = 0;
+        let mut coded_piece_count = 0;
+
+        while !sparse_decoder.is_already_decoded() {
+            let full_coded_piece = encoder.code(&mut rng);
+            let coding_vector = &full_coded_piece[..piece_count];
+
+            assert!(coding_vector.iter().any(|&coeff| coeff != 0));
+            nonzero_coeff_count += coding_vector.iter().filter(|&&coeff| coeff != 0).count();
+            coded_piece_count += 1;
+
+            assert_eq!(sparse_decoder.decode(&full_coded_piece), full_decoder.decode(&full_coded_piece));
+        }
+
+        // Roughly a tenth of coefficients are non-zero.
+        let density = nonzero_coeff_count as f64 / (coded_piece_count * piece_count) as f64;
+        assert!((0.05..0.15).contains(&density));
+
+        assert_eq!(sparse_decoder.get_decoded_data(), Ok(data.clone()));
+        assert_eq!(full_decoder.get_decoded_data(), Ok(data));
+    }
+
+    #[test]
+    fn test_sparse_encoder_invalid_density() {
+        for density in [0.0, -0.5, 1.5, f64::NAN] {
+            assert!(matches!(Encoder::new(vec![1, 2, 3], 2, density), Err(RLNCError::InvalidDensity)));
+        }
+        assert!(Encoder::new(vec![1, 2, 3], 2, 1.0).is_ok());
+
+        let encoder = Encoder::new(vec![1, 2, 3], 2, 0.5).unwrap();
+        assert_eq!(encoder.code_with_buf(&mut rand::rng(), &mut [0u8; 3]), Err(RLNCError::InvalidOutputBuffer));
+    }
+}
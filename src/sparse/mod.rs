@@ -0,0 +1,8 @@
+//! Sparse Random Linear Network Coding, where only a tunable share of coding coefficients are non-zero. Encoding cost shrinks in
+//! proportion to density, and decoding, which exploits sparsity during elimination, stays fast for large piece counts. Pieces are
+//! laid out exactly like those of `full` RLNC i.e. coding vector followed by coded data, so a `full::Decoder` can decode them too.
+
+mod encoder;
+
+pub use crate::full::SparseDecoder as Decoder;
+pub use encoder::Encoder;
@@ -0,0 +1,5 @@
+//! In-process test harnesses, letting downstream crates write deterministic integration tests of their RLNC handling, without sockets.
+
+mod pipe;
+
+pub use pipe::{Channel, PieceSource, Pipe};
@@ -0,0 +1,250 @@
+use crate::{
+    RLNCError,
+    full::{Decoder, Encoder, Recoder},
+};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+/// Anything producing full coded pieces, which can feed a `Pipe`.
+pub trait PieceSource {
+    /// Produces the next full coded piece i.e. coding vector followed by coded data.
+    fn next_piece(&mut self, rng: &mut StdRng) -> Vec<u8>;
+}
+
+impl PieceSource for Encoder {
+    fn next_piece(&mut self, rng: &mut StdRng) -> Vec<u8> {
+        self.code(rng)
+    }
+}
+
+impl PieceSource for Recoder {
+    fn next_piece(&mut self, rng: &mut StdRng) -> Vec<u8> {
+        self.recode(rng)
+    }
+}
+
+/// Misbehaviour of a simulated channel, applied independently to each piece.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Channel {
+    loss_probability: f64,
+    duplication_probability: f64,
+    reorder_window: usize,
+}
+
+impl Channel {
+    /// Configures a channel.
+    ///
+    /// # Arguments
+    /// * `loss_probability` - Probability of a piece getting lost.
+    /// * `duplication_probability` - Probability of a piece, which isn't lost, getting delivered twice.
+    /// * `reorder_window` - Number of pieces held in flight, out of which a random one is delivered next. Zero keeps pieces in order.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Channel)` on success.
+    /// * Returns `Err(RLNCError::InvalidChannel)` if any probability is not in `[0, 1]`.
+    pub fn new(loss_probability: f64, duplication_probability: f64, reorder_window: usize) -> Result<Channel, RLNCError> {
+        if !(0.0..=1.0).contains(&loss_probability) || !(0.0..=1.0).contains(&duplication_probability) {
+            return Err(RLNCError::InvalidChannel);
+        }
+
+        Ok(Channel {
+            loss_probability,
+            duplication_probability,
+            reorder_window,
+        })
+    }
+
+    /// Lossless channel, delivering each piece once, in order.
+    pub fn perfect() -> Channel {
+        Channel {
+            loss_probability: 0.0,
+            duplication_probability: 0.0,
+            reorder_window: 0,
+        }
+    }
+}
+
+/// In-process pipe, connecting a piece source, say an `Encoder` or a `Recoder`, to a decoder, through a lossy channel.
+///
+/// All randomness, both of coding and of the channel, comes from a generator seeded on creation, so that the same seed
+/// yields the same sequence of delivered pieces, keeping integration tests deterministic.
+#[derive(Clone, Debug)]
+pub struct Pipe<S: PieceSource> {
+    source: S,
+    channel: Channel,
+    rng: StdRng,
+    /// Pieces in flight, delivered in random order, once the reorder window is full.
+    in_flight: Vec<Vec<u8>>,
+    sent_piece_count: usize,
+    dropped_piece_count: usize,
+    duplicated_piece_count: usize,
+    delivered_piece_count: usize,
+}
+
+impl<S: PieceSource> Pipe<S> {
+    /// Connects a piece source to the channel, seeding the random number generator.
+    pub fn new(source: S, channel: Channel, seed: u64) -> Pipe<S> {
+        Pipe {
+            source,
+            channel,
+            rng: StdRng::seed_from_u64(seed),
+            in_flight: Vec::new(),
+            sent_piece_count: 0,
+            dropped_piece_count: 0,
+            duplicated_piece_count: 0,
+            delivered_piece_count: 0,
+        }
+    }
+
+    /// The piece source.
+    pub fn get_source(&self) -> &S {
+        &self.source
+    }
+
+    /// Number of pieces sent into the channel so far.
+    pub fn get_sent_piece_count(&self) -> usize {
+        self.sent_piece_count
+    }
+
+    /// Number of pieces lost by the channel so far.
+    pub fn get_dropped_piece_count(&self) -> usize {
+        self.dropped_piece_count
+    }
+
+    /// Number of pieces duplicated by the channel so far.
+    pub fn get_duplicated_piece_count(&self) -> usize {
+        self.duplicated_piece_count
+    }
+
+    /// Number of pieces delivered out of the channel so far, duplicates included.
+    pub fn get_delivered_piece_count(&self) -> usize {
+        self.delivered_piece_count
+    }
+
+    /// Number of pieces in the channel, yet to be delivered.
+    pub fn get_in_flight_piece_count(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    /// Sends the next piece of the source into the channel, returning pieces delivered out of it, in the meantime.
+    pub fn transmit(&mut self) -> Vec<Vec<u8>> {
+        let piece = self.source.next_piece(&mut self.rng);
+        self.sent_piece_count += 1;
+
+        if self.rng.random_bool(self.channel.loss_probability) {
+            self.dropped_piece_count += 1;
+            return Vec::new();
+        }
+        if self.rng.random_bool(self.channel.duplication_probability) {
+            self.duplicated_piece_count += 1;
+            self.in_flight.push(piece.clone());
+        }
+        self.in_flight.push(piece);
+
+        let mut delivered = Vec::new();
+        while self.in_flight.len() > self.channel.reorder_window {
+            let index = if self.channel.reorder_window == 0 {
+                0
+            } else {
+                self.rng.random_range(..self.in_flight.len())
+            };
+            delivered.push(self.in_flight.remove(index));
+        }
+
+        self.delivered_piece_count += delivered.len();
+        delivered
+    }
+
+    /// Delivers all pieces still in flight, in random order, unless the channel keeps order.
+    pub fn flush(&mut self) -> Vec<Vec<u8>> {
+        let mut delivered = std::mem::take(&mut self.in_flight);
+        if self.channel.reorder_window > 0 {
+            for index in (1..delivered.len()).rev() {
+                delivered.swap(index, self.rng.random_range(..=index));
+            }
+        }
+
+        self.delivered_piece_count += delivered.len();
+        delivered
+    }
+
+    /// Keeps transmitting pieces, feeding delivered ones to the decoder, till it decodes or given number of pieces is sent.
+    /// Pieces in flight are flushed before giving up.
+    ///
+    /// # Returns
+    /// * Returns `Ok(usize)` holding the number of pieces sent, once the decoder has decoded.
+    /// * Returns `Err(RLNCError::NotAllPiecesReceivedYet)` if the decoder couldn't decode, after `max_sent_piece_count` many pieces.
+    pub fn run_until_decoded(&mut self, decoder: &mut Decoder, max_sent_piece_count: usize) -> Result<usize, RLNCError> {
+        while !decoder.is_already_decoded() && self.sent_piece_count < max_sent_piece_count {
+            for piece in self.transmit() {
+                let _ = decoder.decode(&piece);
+            }
+        }
+        if !decoder.is_already_decoded() {
+            for piece in self.flush() {
+                let _ = decoder.decode(&piece);
+            }
+        }
+
+        if decoder.is_already_decoded() {
+            Ok(self.sent_piece_count)
+        } else {
+            Err(RLNCError::NotAllPiecesReceivedYet)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Channel, Pipe};
+    use crate::{
+        RLNCError,
+        full::{Decoder, Encoder, Recoder},
+    };
+
+    #[test]
+    fn test_pipe_is_deterministic() {
+        let data = (0..2048).map(|i| (i % 251) as u8).collect::<Vec<u8>>();
+        let channel = Channel::new(0.3, 0.2, 4).expect("Channel must be valid");
+
+        let run = |seed| {
+            let encoder = Encoder::new(data.clone(), 16).expect("Failed to create Encoder for pipe test");
+            let mut pipe = Pipe::new(encoder, channel, seed);
+
+            let mut delivered = (0..64).flat_map(|_| pipe.transmit()).collect::<Vec<_>>();
+            delivered.extend(pipe.flush());
+            delivered
+        };
+
+        assert_eq!(run(7), run(7));
+        assert_ne!(run(7), run(8));
+    }
+
+    #[test]
+    fn test_pipe_encoder_to_recoder_to_decoder() {
+        let data = (0..2048).map(|i| (i % 251) as u8).collect::<Vec<u8>>();
+        let encoder = Encoder::new(data.clone(), 16).expect("Failed to create Encoder for pipe test");
+        let params = encoder.get_params();
+
+        let mut first_hop = Pipe::new(encoder, Channel::new(0.25, 0.1, 3).unwrap(), 42);
+        let mut relay = Decoder::new_with(params);
+        assert_eq!(first_hop.run_until_decoded(&mut relay, 8), Err(RLNCError::NotAllPiecesReceivedYet));
+        let sent_piece_count = first_hop.run_until_decoded(&mut relay, 1000).expect("Relay must decode over a lossy channel");
+
+        assert!(sent_piece_count >= 16);
+        assert_eq!(
+            first_hop.get_delivered_piece_count() + first_hop.get_in_flight_piece_count(),
+            sent_piece_count - first_hop.get_dropped_piece_count() + first_hop.get_duplicated_piece_count()
+        );
+
+        let pieces = (0..16).flat_map(|_| first_hop.get_source().code(&mut rand::rng())).collect::<Vec<u8>>();
+        let recoder = Recoder::new_with(pieces, params).expect("Failed to create Recoder for pipe test");
+
+        let mut second_hop = Pipe::new(recoder, Channel::perfect(), 42);
+        let mut sink = Decoder::new_with(params);
+        second_hop.run_until_decoded(&mut sink, 1000).expect("Sink must decode over a perfect channel");
+
+        assert_eq!(second_hop.get_dropped_piece_count(), 0);
+        assert_eq!(sink.get_decoded_data(), Ok(data));
+        assert!(matches!(Channel::new(1.5, 0.0, 0), Err(RLNCError::InvalidChannel)));
+    }
+}
@@ -0,0 +1,411 @@
+use super::parse_coded_piece;
+use crate::{
+    RLNCError,
+    common::{
+        gf256::Gf256,
+        simd::{gf256_inplace_mul_vec_by_scalar, gf256_mul_vec_by_scalar_then_add_into_vec},
+    },
+};
+use std::{collections::VecDeque, iter};
+
+/// Useful coded piece, reduced, over source symbols not yet delivered.
+#[derive(Clone, Debug)]
+struct Row {
+    /// Coefficients of source symbols, starting from the first one not yet delivered.
+    coeffs: Vec<u8>,
+    data: Vec<u8>,
+    /// Column of the first non-zero coefficient, which is one.
+    pivot: usize,
+}
+
+impl Row {
+    /// Adds `scalar` times `other` into this row. Other row may span fewer columns, if this row grew the span, which are zero.
+    fn add_scaled(&mut self, other: &Row, scalar: u8) {
+        gf256_mul_vec_by_scalar_then_add_into_vec(&mut self.coeffs[..other.coeffs.len()], &other.coeffs, scalar);
+        gf256_mul_vec_by_scalar_then_add_into_vec(&mut self.data, &other.data, scalar);
+    }
+}
+
+/// Sliding-window Random Linear Network Coding (RLNC) Decoder.
+///
+/// Received pieces are kept in reduced row echelon form, over source symbols not yet delivered, so that a source symbol is decoded
+/// as soon as its row is left with its pivot only. Decoded source symbols are delivered in order of their ids, see
+/// `Self::pop_decoded_symbol`. Latest delivered source symbols are kept around, so that their contribution is subtracted off pieces
+/// still coding them. If the window of a received piece starts past the first source symbol not yet delivered, the encoder has
+/// evicted the symbols in between, so they're skipped as lost.
+#[derive(Clone, Debug)]
+pub struct Decoder {
+    rows: Vec<Row>,
+    /// Id of the first source symbol, not yet delivered, which is the first column of each row.
+    next_symbol_id: u64,
+    /// Number of columns of each row.
+    span: usize,
+    /// Latest `window_size` delivered source symbols, oldest first, lost ones being `None`.
+    history: VecDeque<Option<Vec<u8>>>,
+    /// Decoded source symbols, not yet popped by the application.
+    decoded: VecDeque<(u64, Vec<u8>)>,
+    symbol_byte_len: usize,
+    window_size: usize,
+    received_piece_count: usize,
+    lost_symbol_count: u64,
+}
+
+impl Decoder {
+    /// Creates a new sliding-window `Decoder`, expecting source symbols from id zero.
+    ///
+    /// # Arguments
+    /// * `symbol_byte_len` - Byte length of each source symbol, same as of the encoder.
+    /// * `window_size` - Maximum number of source symbols coded together, same as of the encoder.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Decoder)` on success.
+    /// * Returns `Err(RLNCError::PieceLengthZero)` if `symbol_byte_len` is zero.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `window_size` is zero.
+    pub fn new(symbol_byte_len: usize, window_size: usize) -> Result<Decoder, RLNCError> {
+        if symbol_byte_len == 0 {
+            return Err(RLNCError::PieceLengthZero);
+        }
+        if window_size == 0 {
+            return Err(RLNCError::PieceCountZero);
+        }
+
+        Ok(Decoder {
+            rows: Vec::with_capacity(window_size),
+            next_symbol_id: 0,
+            span: 0,
+            history: VecDeque::with_capacity(window_size),
+            decoded: VecDeque::new(),
+            symbol_byte_len,
+            window_size,
+            received_piece_count: 0,
+            lost_symbol_count: 0,
+        })
+    }
+
+    /// Byte length of each source symbol.
+    pub fn get_symbol_byte_len(&self) -> usize {
+        self.symbol_byte_len
+    }
+
+    /// Maximum number of source symbols coded together.
+    pub fn get_window_size(&self) -> usize {
+        self.window_size
+    }
+
+    /// Id of the first source symbol, not yet decoded, to be fed back to `window::Encoder::acknowledge`.
+    pub fn get_next_symbol_id(&self) -> u64 {
+        self.next_symbol_id
+    }
+
+    /// Total number of pieces received by the decoder so far.
+    pub fn get_received_piece_count(&self) -> usize {
+        self.received_piece_count
+    }
+
+    /// Number of source symbols, seen in received pieces, but not yet decoded.
+    pub fn get_pending_symbol_count(&self) -> usize {
+        self.span
+    }
+
+    /// Number of source symbols skipped, because the encoder evicted them before they could be decoded.
+    pub fn get_lost_symbol_count(&self) -> u64 {
+        self.lost_symbol_count
+    }
+
+    /// Decodes a coded piece, produced by `window::Encoder::code`, delivering all source symbols it makes decodable.
+    ///
+    /// # Arguments
+    /// * `coded_piece` - Header, coding vector and coded data, laid out as documented on `window`.
+    ///
+    /// # Returns
+    /// * Returns `Ok(())` if the piece was useful.
+    /// * Returns `Err(RLNCError::InvalidPieceLength)` if the piece is malformed, see `window`, or its window runs past the
+    ///   largest source symbol id.
+    /// * Returns `Err(RLNCError::PieceNotUseful)` if the piece is linearly dependent on the ones received before, or codes only
+    ///   source symbols already delivered or lost.
+    pub fn decode(&mut self, coded_piece: &[u8]) -> Result<(), RLNCError> {
+        let (first_symbol_id, coding_vector, coded_data) = parse_coded_piece(coded_piece, self.symbol_byte_len, self.window_size)?;
+        let end_symbol_id = first_symbol_id.checked_add(coding_vector.len() as u64).ok_or(RLNCError::InvalidPieceLength)?;
+        self.received_piece_count += 1;
+
+        if end_symbol_id <= self.next_symbol_id {
+            return Err(RLNCError::PieceNotUseful);
+        }
+        if first_symbol_id > self.next_symbol_id {
+            self.skip_lost_symbols(first_symbol_id - self.next_symbol_id);
+        }
+
+        // Rows grow to the span of this piece, only once it's found useful.
+        let span = self.span.max((end_symbol_id - self.next_symbol_id) as usize);
+        let mut row = Row {
+            coeffs: vec![0u8; span],
+            data: coded_data.to_vec(),
+            pivot: 0,
+        };
+
+        // Delivered source symbols are known, so their contribution is subtracted off, undelivered ones become columns.
+        for (symbol_id, &coeff) in (first_symbol_id..end_symbol_id).zip(coding_vector) {
+            if symbol_id >= self.next_symbol_id {
+                row.coeffs[(symbol_id - self.next_symbol_id) as usize] = coeff;
+            } else if coeff != 0 {
+                let hidx = self.history.len().checked_sub((self.next_symbol_id - symbol_id) as usize);
+                let Some(symbol) = hidx.and_then(|hidx| self.history[hidx].as_ref()) else {
+                    return Err(RLNCError::PieceNotUseful);
+                };
+
+                gf256_mul_vec_by_scalar_then_add_into_vec(&mut row.data, symbol, coeff);
+            }
+        }
+
+        for prev_row in &self.rows {
+            let coeff = row.coeffs[prev_row.pivot];
+            if coeff != 0 {
+                row.add_scaled(prev_row, coeff);
+            }
+        }
+
+        let Some(pivot) = row.coeffs.iter().position(|&coeff| coeff != 0) else {
+            return Err(RLNCError::PieceNotUseful);
+        };
+
+        let pivot_inv = unsafe { Gf256::new(row.coeffs[pivot]).inv().unwrap_unchecked() }.get();
+        gf256_inplace_mul_vec_by_scalar(&mut row.coeffs, pivot_inv);
+        gf256_inplace_mul_vec_by_scalar(&mut row.data, pivot_inv);
+        row.pivot = pivot;
+
+        if span > self.span {
+            self.rows.iter_mut().for_each(|row| row.coeffs.resize(span, 0));
+            self.span = span;
+        }
+
+        for prev_row in &mut self.rows {
+            let coeff = prev_row.coeffs[pivot];
+            if coeff != 0 {
+                prev_row.add_scaled(&row, coeff);
+            }
+        }
+
+        self.rows.push(row);
+        self.deliver_decoded_symbols();
+
+        Ok(())
+    }
+
+    /// Pops the oldest decoded source symbol, not yet popped, along with its id. Ids are increasing, but not necessarily
+    /// consecutive, if some source symbols were lost.
+    pub fn pop_decoded_symbol(&mut self) -> Option<(u64, Vec<u8>)> {
+        self.decoded.pop_front()
+    }
+
+    /// Delivers decoded source symbols, in order, till the first one, which is not yet decoded.
+    fn deliver_decoded_symbols(&mut self) {
+        while let Some(ridx) = self.rows.iter().position(|row| row.pivot == 0) {
+            if self.rows[ridx].coeffs[1..].iter().any(|&coeff| coeff != 0) {
+                break;
+            }
+
+            let row = self.rows.swap_remove(ridx);
+            self.decoded.push_back((self.next_symbol_id, row.data.clone()));
+            self.advance(Some(row.data));
+        }
+    }
+
+    /// Skips given number of source symbols, not yet delivered, as lost, dropping rows pivoted on any of them.
+    ///
+    /// Skipping at least as many source symbols as rows span drops all rows, and only the latest `window_size` lost ones are kept
+    /// in history, so that it costs `O(window_size)`, however far ahead the encoder has moved.
+    fn skip_lost_symbols(&mut self, count: u64) {
+        self.lost_symbol_count += count;
+
+        if count >= self.span as u64 {
+            self.rows.clear();
+            self.span = 0;
+            self.next_symbol_id += count;
+
+            let lost_in_history = count.min(self.window_size as u64) as usize;
+            self.history.drain(..(self.history.len() + lost_in_history).saturating_sub(self.window_size));
+            self.history.extend(iter::repeat_n(None, lost_in_history));
+            return;
+        }
+
+        let count = count as usize;
+        self.rows.retain(|row| row.pivot >= count);
+        (0..count).for_each(|_| self.advance(None));
+    }
+
+    /// Advances past the first source symbol not yet delivered, dropping its column, which is zero in all remaining rows.
+    fn advance(&mut self, symbol: Option<Vec<u8>>) {
+        for row in &mut self.rows {
+            row.coeffs.remove(0);
+            row.pivot -= 1;
+        }
+
+        if self.history.len() == self.window_size {
+            self.history.pop_front();
+        }
+        self.history.push_back(symbol);
+
+        self.next_symbol_id += 1;
+        self.span = self.span.saturating_sub(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Decoder;
+    use crate::{RLNCError, window::Encoder};
+    use rand::Rng;
+    use std::iter;
+
+    #[test]
+    fn test_window_decoder_delivers_in_order_with_feedback() {
+        let mut rng = rand::rng();
+
+        let (symbol_byte_len, window_size) = (16, 8);
+        let mut encoder = Encoder::new(symbol_byte_len, window_size).expect("Failed to create Encoder");
+        let mut decoder = Decoder::new(symbol_byte_len, window_size).expect("Failed to create Decoder");
+        assert_eq!(decoder.decode(&[0; 12]), Err(RLNCError::InvalidPieceLength));
+
+        let symbols = (0..100)
+            .map(|_| (0..symbol_byte_len).map(|_| rng.random()).collect::<Vec<u8>>())
+            .collect::<Vec<_>>();
+        let mut delivered = Vec::new();
+
+        // Push one symbol, then send two coded pieces, losing every third one on the way.
+        for (i, symbol) in symbols.iter().enumerate() {
+            encoder.push(symbol).unwrap();
+
+            for j in 0..2 {
+                let piece = encoder.code(&mut rng).unwrap();
+                if (2 * i + j) % 3 != 2 {
+                    let _ = decoder.decode(&piece);
+                }
+            }
+
+            encoder.acknowledge(decoder.get_next_symbol_id());
+            while let Some(symbol) = decoder.pop_decoded_symbol() {
+                delivered.push(symbol);
+            }
+        }
+
+        while decoder.get_next_symbol_id() < symbols.len() as u64 {
+            let _ = decoder.decode(&encoder.code(&mut rng).unwrap());
+            encoder.acknowledge(decoder.get_next_symbol_id());
+        }
+        while let Some(symbol) = decoder.pop_decoded_symbol() {
+            delivered.push(symbol);
+        }
+
+        assert_eq!(decoder.get_lost_symbol_count(), 0);
+        assert_eq!(
+            delivered,
+            symbols.iter().cloned().enumerate().map(|(i, symbol)| (i as u64, symbol)).collect::<Vec<_>>()
+        );
+        assert_eq!(encoder.code(&mut rng), Err(RLNCError::EmptyWindow));
+
+        // Without feedback, pushing past the window evicts symbols, the receiver never got, so they're skipped as lost.
+        for symbol in &symbols[..window_size + 2] {
+            encoder.push(symbol).unwrap();
+        }
+        while decoder.pop_decoded_symbol().is_none() {
+            let _ = decoder.decode(&encoder.code(&mut rng).unwrap());
+        }
+
+        assert_eq!(decoder.get_lost_symbol_count(), 2);
+        assert_eq!(decoder.get_next_symbol_id(), 102 + window_size as u64);
+    }
+
+    /// Coded piece, laid out as documented on `window`, with given window, coding vector and coded data.
+    fn raw_piece(first_symbol_id: u64, coding_vector: &[u8], coded_data: &[u8]) -> Vec<u8> {
+        [
+            first_symbol_id.to_be_bytes().as_slice(),
+            &(coding_vector.len() as u32).to_be_bytes(),
+            coding_vector,
+            coded_data,
+        ]
+        .concat()
+    }
+
+    #[test]
+    fn test_window_decoder_rejects_pieces_without_side_effects() {
+        let (symbol_byte_len, window_size) = (4, 4);
+        let mut decoder = Decoder::new(symbol_byte_len, window_size).expect("Failed to create Decoder");
+
+        assert_eq!(
+            decoder.decode(&raw_piece(u64::MAX - 1, &[1, 1, 1], &[0; 4])),
+            Err(RLNCError::InvalidPieceLength)
+        );
+        assert_eq!(decoder.get_received_piece_count(), 0);
+        assert_eq!(decoder.get_next_symbol_id(), 0);
+
+        // A useful piece over first two symbols, then a dependent one over first four, which must not grow pending symbols.
+        assert_eq!(decoder.decode(&raw_piece(0, &[1, 2], &[1; 4])), Ok(()));
+        assert_eq!(decoder.get_pending_symbol_count(), 2);
+        assert_eq!(decoder.decode(&raw_piece(0, &[2, 4, 0, 0], &[2; 4])), Err(RLNCError::PieceNotUseful));
+        assert_eq!(decoder.get_pending_symbol_count(), 2);
+
+        // Encoder moved far ahead, without feedback, so all symbols in between are lost, at once.
+        assert_eq!(decoder.decode(&raw_piece(u64::MAX - 4, &[1, 1, 1, 1], &[3; 4])), Ok(()));
+        assert_eq!(decoder.get_next_symbol_id(), u64::MAX - 4);
+        assert_eq!(decoder.get_lost_symbol_count(), u64::MAX - 4);
+        assert_eq!(decoder.get_pending_symbol_count(), 4);
+
+        // Source symbol, just before the window, is lost, so a piece coding it can't be reduced.
+        assert_eq!(decoder.decode(&raw_piece(u64::MAX - 5, &[1, 0, 0, 0], &[0; 4])), Err(RLNCError::PieceNotUseful));
+        assert_eq!(decoder.get_pending_symbol_count(), 4);
+
+        for (idx, coding_vector) in [[0, 1, 0, 0], [0, 0, 1, 0], [0, 0, 0, 1]].iter().enumerate() {
+            assert_eq!(decoder.decode(&raw_piece(u64::MAX - 4, coding_vector, &[idx as u8; 4])), Ok(()));
+        }
+        assert_eq!(decoder.get_next_symbol_id(), u64::MAX);
+
+        // First source symbol is the sum of all four pieces, as its coefficient is one, in the first piece only.
+        let decoded = iter::from_fn(|| decoder.pop_decoded_symbol()).collect::<Vec<_>>();
+        assert_eq!(
+            decoded,
+            vec![
+                (u64::MAX - 4, vec![0; 4]),
+                (u64::MAX - 3, vec![0; 4]),
+                (u64::MAX - 2, vec![1; 4]),
+                (u64::MAX - 1, vec![2; 4])
+            ]
+        );
+    }
+
+    #[test]
+    fn test_window_decoder_span_grows_mid_stream() {
+        let mut rng = rand::rng();
+
+        // Wide window, so that span grows past SIMD chunk boundaries, between pieces.
+        for window_size in [8, 12, 20, 100] {
+            let symbol_byte_len = 16;
+            let mut encoder = Encoder::new(symbol_byte_len, window_size).expect("Failed to create Encoder");
+            let mut decoder = Decoder::new(symbol_byte_len, window_size).expect("Failed to create Decoder");
+
+            let symbols = (0..window_size)
+                .map(|_| (0..symbol_byte_len).map(|_| rng.random()).collect::<Vec<u8>>())
+                .collect::<Vec<_>>();
+
+            // A piece over first half of the window, before the rest of the symbols are pushed.
+            let half_window_size = window_size / 2;
+            for symbol in &symbols[..half_window_size] {
+                encoder.push(symbol).unwrap();
+            }
+            assert_eq!(decoder.decode(&encoder.code(&mut rng).unwrap()), Ok(()));
+            assert_eq!(decoder.get_pending_symbol_count(), half_window_size);
+
+            for symbol in &symbols[half_window_size..] {
+                encoder.push(symbol).unwrap();
+            }
+            while decoder.get_next_symbol_id() < window_size as u64 {
+                let _ = decoder.decode(&encoder.code(&mut rng).unwrap());
+            }
+
+            let decoded = iter::from_fn(|| decoder.pop_decoded_symbol()).collect::<Vec<_>>();
+            assert_eq!(
+                decoded,
+                symbols.into_iter().enumerate().map(|(i, symbol)| (i as u64, symbol)).collect::<Vec<_>>()
+            );
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use super::WINDOW_HEADER_BYTE_LEN;
+use crate::{RLNCError, common::simd::gf256_mul_vec_by_scalar_then_add_into_vec};
+use rand::Rng;
+use std::collections::VecDeque;
+
+/// Sliding-window Random Linear Network Coding (RLNC) Encoder.
+///
+/// Source symbols, all of the same byte length, are pushed by the application and get consecutive ids, starting from zero. Each
+/// coded piece is a random linear combination of source symbols currently in the window, which holds at most `window_size` of
+/// the latest ones, not yet acknowledged by the receiver.
+#[derive(Clone, Debug)]
+pub struct Encoder {
+    /// Source symbols in the window, oldest first.
+    symbols: VecDeque<Vec<u8>>,
+    /// Id of the oldest source symbol in the window.
+    first_symbol_id: u64,
+    symbol_byte_len: usize,
+    window_size: usize,
+}
+
+impl Encoder {
+    /// Creates a new sliding-window `Encoder`, with an empty window.
+    ///
+    /// # Arguments
+    /// * `symbol_byte_len` - Byte length of each source symbol.
+    /// * `window_size` - Maximum number of source symbols coded together.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Encoder)` on success.
+    /// * Returns `Err(RLNCError::PieceLengthZero)` if `symbol_byte_len` is zero.
+    /// * Returns `Err(RLNCError::PieceCountZero)` if `window_size` is zero.
+    pub fn new(symbol_byte_len: usize, window_size: usize) -> Result<Encoder, RLNCError> {
+        if symbol_byte_len == 0 {
+            return Err(RLNCError::PieceLengthZero);
+        }
+        if window_size == 0 {
+            return Err(RLNCError::PieceCountZero);
+        }
+
+        Ok(Encoder {
+            symbols: VecDeque::with_capacity(window_size),
+            first_symbol_id: 0,
+            symbol_byte_len,
+            window_size,
+        })
+    }
+
+    /// Byte length of each source symbol.
+    pub fn get_symbol_byte_len(&self) -> usize {
+        self.symbol_byte_len
+    }
+
+    /// Maximum number of source symbols coded together.
+    pub fn get_window_size(&self) -> usize {
+        self.window_size
+    }
+
+    /// Number of source symbols currently in the window.
+    pub fn get_window_len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    /// Id of the oldest source symbol in the window, which is same as `Self::get_next_symbol_id`, if the window is empty.
+    pub fn get_first_symbol_id(&self) -> u64 {
+        self.first_symbol_id
+    }
+
+    /// Id, the next pushed source symbol gets.
+    pub fn get_next_symbol_id(&self) -> u64 {
+        self.first_symbol_id + self.symbols.len() as u64
+    }
+
+    /// Byte length of a coded piece over the current window, i.e. header, coding vector and coded data.
+    pub fn get_coded_piece_byte_len(&self) -> usize {
+        WINDOW_HEADER_BYTE_LEN + self.symbols.len() + self.symbol_byte_len
+    }
+
+    /// Pushes a new source symbol into the window, evicting the oldest one, if the window is full. Evicted symbols are never coded
+    /// again, so the receiver skips them, if it couldn't decode them by then.
+    ///
+    /// # Returns
+    /// * Returns `Ok(u64)` holding the id of the pushed source symbol.
+    /// * Returns `Err(RLNCError::InvalidPieceLength)` if `symbol` is not of `self.get_symbol_byte_len()` bytes.
+    pub fn push(&mut self, symbol: &[u8]) -> Result<u64, RLNCError> {
+        if symbol.len() != self.symbol_byte_len {
+            return Err(RLNCError::InvalidPieceLength);
+        }
+
+        if self.symbols.len() == self.window_size {
+            self.symbols.pop_front();
+            self.first_symbol_id += 1;
+        }
+
+        self.symbols.push_back(symbol.to_vec());
+        Ok(self.get_next_symbol_id() - 1)
+    }
+
+    /// Advances the window past source symbols the receiver has decoded, as reported by `window::Decoder::get_next_symbol_id`.
+    /// Stale acknowledgements, not going past the oldest source symbol in the window, are ignored.
+    ///
+    /// # Arguments
+    /// * `next_symbol_id` - Id of the first source symbol, the receiver hasn't decoded yet.
+    pub fn acknowledge(&mut self, next_symbol_id: u64) {
+        let acknowledged = next_symbol_id.saturating_sub(self.first_symbol_id).min(self.symbols.len() as u64);
+
+        self.symbols.drain(..acknowledged as usize);
+        self.first_symbol_id += acknowledged;
+    }
+
+    /// Produces a new coded piece over the current window, random sampling a coding vector.
+    ///
+    /// # Arguments
+    /// * `rng` - A mutable reference to a random number generator.
+    ///
+    /// # Returns
+    /// * Returns `Ok(Vec<u8>)` holding the coded piece, of `self.get_coded_piece_byte_len()` bytes, laid out as documented on `window`.
+    /// * Returns `Err(RLNCError::EmptyWindow)` if the window holds no source symbol.
+    pub fn code<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Vec<u8>, RLNCError> {
+        if self.symbols.is_empty() {
+            return Err(RLNCError::EmptyWindow);
+        }
+
+        let mut coded_piece = vec![0u8; self.get_coded_piece_byte_len()];
+
+        let (header, rest) = coded_piece.split_at_mut(WINDOW_HEADER_BYTE_LEN);
+        header[..size_of::<u64>()].copy_from_slice(&self.first_symbol_id.to_be_bytes());
+        header[size_of::<u64>()..].copy_from_slice(&(self.symbols.len() as u32).to_be_bytes());
+
+        let (coding_vector, coded_data) = rest.split_at_mut(self.symbols.len());
+        rng.fill_bytes(coding_vector);
+
+        self.symbols
+            .iter()
+            .zip(coding_vector.iter())
+            .for_each(|(symbol, &coeff)| gf256_mul_vec_by_scalar_then_add_into_vec(coded_data, symbol, coeff));
+
+        Ok(coded_piece)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Encoder;
+    use crate::RLNCError;
+
+    #[test]
+    fn test_window_encoder_slides() {
+        let mut rng = rand::rng();
+
+        assert_eq!(Encoder::new(0, 4).unwrap_err(), RLNCError::PieceLengthZero);
+        assert_eq!(Encoder::new(4, 0).unwrap_err(), RLNCError::PieceCountZero);
+
+        let mut encoder = Encoder::new(4, 3).expect("Failed to create Encoder");
+        assert_eq!(encoder.code(&mut rng), Err(RLNCError::EmptyWindow));
+        assert_eq!(encoder.push(&[1; 5]), Err(RLNCError::InvalidPieceLength));
+
+        assert_eq!((0..5).map(|i| encoder.push(&[i; 4]).unwrap()).collect::<Vec<_>>(), [0, 1, 2, 3, 4]);
+        assert_eq!((encoder.get_first_symbol_id(), encoder.get_window_len()), (2, 3));
+
+        // Stale acknowledgement doesn't move the window back.
+        encoder.acknowledge(1);
+        assert_eq!(encoder.get_first_symbol_id(), 2);
+
+        encoder.acknowledge(4);
+        assert_eq!((encoder.get_first_symbol_id(), encoder.get_window_len()), (4, 1));
+
+        let piece = encoder.code(&mut rng).expect("Window is not empty");
+        assert_eq!(piece.len(), encoder.get_coded_piece_byte_len());
+        assert_eq!(&piece[..12], [0, 0, 0, 0, 0, 0, 0, 4, 0, 0, 0, 1]);
+
+        encoder.acknowledge(10);
+        assert_eq!((encoder.get_first_symbol_id(), encoder.get_next_symbol_id()), (5, 5));
+    }
+}
@@ -0,0 +1,45 @@
+//! Sliding-window Random Linear Network Coding, for low-latency streaming, say live video or VoIP. Unlike block coding, which
+//! can't decode anything before `k` pieces of a generation arrive, the encoder codes over the last few source symbols, pushed by
+//! the application, and the decoder delivers each source symbol, in order, as soon as it's decoded. The window advances as new
+//! symbols are pushed, evicting the oldest ones once it's full, and as receiver feedback acknowledges decoded prefixes.
+//!
+//! A coded piece is
+//!
+//! * Id of the first source symbol in the window, as 8 big-endian bytes.
+//! * Number of source symbols in the window, as 4 big-endian bytes.
+//! * Coding vector of as many bytes, followed by coded data of symbol length bytes.
+
+mod decoder;
+mod encoder;
+
+pub use decoder::Decoder;
+pub use encoder::Encoder;
+
+use crate::RLNCError;
+
+/// Byte length of the header of a coded piece i.e. id of the first source symbol and number of source symbols in the window.
+pub const WINDOW_HEADER_BYTE_LEN: usize = size_of::<u64>() + size_of::<u32>();
+
+/// Splits a coded piece into id of the first source symbol, coding vector and coded data, validating its length.
+///
+/// # Returns
+/// * Returns `Ok((first_symbol_id, coding_vector, coded_data))` on success.
+/// * Returns `Err(RLNCError::InvalidPieceLength)` if the window is empty or larger than `window_size`, or if length of the piece
+///   doesn't match the window and `symbol_byte_len`.
+fn parse_coded_piece(coded_piece: &[u8], symbol_byte_len: usize, window_size: usize) -> Result<(u64, &[u8], &[u8]), RLNCError> {
+    if coded_piece.len() < WINDOW_HEADER_BYTE_LEN {
+        return Err(RLNCError::InvalidPieceLength);
+    }
+
+    let (header, rest) = coded_piece.split_at(WINDOW_HEADER_BYTE_LEN);
+    let (first_symbol_id, symbol_count) = header.split_at(size_of::<u64>());
+    let first_symbol_id = u64::from_be_bytes(unsafe { first_symbol_id.try_into().unwrap_unchecked() });
+    let symbol_count = u32::from_be_bytes(unsafe { symbol_count.try_into().unwrap_unchecked() }) as usize;
+
+    if symbol_count == 0 || symbol_count > window_size || rest.len() != symbol_count + symbol_byte_len {
+        return Err(RLNCError::InvalidPieceLength);
+    }
+
+    let (coding_vector, coded_data) = rest.split_at(symbol_count);
+    Ok((first_symbol_id, coding_vector, coded_data))
+}